@@ -5,6 +5,7 @@ use config::config::ConfigEngine;
 use editor::editor::{Editor, EditorInterface, EditorResult};
 use egui::FontDefinitions;
 use graphics::graphics::graphics::Graphics;
+use i18n::translations::Translations;
 use sound::sound::SoundManager;
 
 pub struct ApiEditor {
@@ -63,6 +64,17 @@ pub fn editor_file_hovered(file: Option<PathBuf>) {
     API_EDITOR.with(|g| g.state.borrow_mut().as_mut().unwrap().file_hovered(file))
 }
 
+#[unsafe(no_mangle)]
+pub fn editor_set_translations(translations: Translations) {
+    API_EDITOR.with(|g| {
+        g.state
+            .borrow_mut()
+            .as_mut()
+            .unwrap()
+            .set_translations(translations)
+    })
+}
+
 #[unsafe(no_mangle)]
 pub fn editor_destroy() {
     API_EDITOR.with(|g| *g.state.borrow_mut() = None);
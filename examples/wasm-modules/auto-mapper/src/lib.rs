@@ -4,6 +4,11 @@ use std::hash::Hasher;
 
 use editor_interface::auto_mapper::{
     AutoMapperInputModes, AutoMapperInterface, AutoMapperModes, AutoMapperOutputModes,
+    AutoMapperPhysicsLayer,
+};
+use map::{
+    map::groups::layers::tiles::{MapTileLayerPhysicsTiles, Tile},
+    tiles::DdraceTileNum,
 };
 
 pub use api::{DB, IO_RUNTIME};
@@ -20,9 +25,16 @@ struct AutoMapperGrassMain {}
 
 impl AutoMapperInterface for AutoMapperGrassMain {
     fn supported_modes(&self) -> Vec<AutoMapperModes> {
-        vec![AutoMapperModes::DesignTileLayer {
-            neighbouring_tiles: Some(2.try_into().unwrap()),
-        }]
+        vec![
+            AutoMapperModes::DesignTileLayer {
+                neighbouring_tiles: Some(2.try_into().unwrap()),
+            },
+            AutoMapperModes::PhysicsTileLayer {
+                layer: AutoMapperPhysicsLayer::Game,
+                neighbouring_tiles: Some(1.try_into().unwrap()),
+                design_layer_input: true,
+            },
+        ]
     }
 
     fn run(
@@ -31,14 +43,35 @@ impl AutoMapperInterface for AutoMapperGrassMain {
         input: AutoMapperInputModes,
     ) -> Result<AutoMapperOutputModes, String> {
         // Very simple edge detection, but programatically.
-        let AutoMapperInputModes::DesignTileLayer {
-            mut tiles,
-            width,
-            height,
-            off_x,
-            off_y,
-            ..
-        } = input;
+        let (mut tiles, width, height, off_x, off_y) = match input {
+            AutoMapperInputModes::DesignTileLayer {
+                tiles,
+                width,
+                height,
+                off_x,
+                off_y,
+                ..
+            } => (tiles, width, height, off_x, off_y),
+            AutoMapperInputModes::PhysicsTileLayer {
+                tiles,
+                design_tiles,
+                width,
+                height,
+                ..
+            } => {
+                let MapTileLayerPhysicsTiles::Game(tiles) = tiles else {
+                    return Err("only the game layer is supported".to_string());
+                };
+                return Ok(AutoMapperOutputModes::PhysicsTileLayer {
+                    tiles: MapTileLayerPhysicsTiles::Game(game_layer(
+                        tiles,
+                        design_tiles.as_deref(),
+                        width.get() as usize,
+                        height.get() as usize,
+                    )),
+                });
+            }
+        };
 
         // skip, layers of such sizes are not supported.
         if width.get() < 2 || height.get() < 2 {
@@ -120,3 +153,87 @@ impl AutoMapperInterface for AutoMapperGrassMain {
         Ok(AutoMapperOutputModes::DesignTileLayer { tiles })
     }
 }
+
+/// Game layer auto mapping:
+/// - Every design tile without a game tile becomes unhookable.
+/// - Every freeze area is outlined with unfreeze tiles, only air is replaced.
+fn game_layer(
+    mut tiles: Vec<Tile>,
+    design_tiles: Option<&[Tile]>,
+    width: usize,
+    height: usize,
+) -> Vec<Tile> {
+    if let Some(design_tiles) = design_tiles {
+        for (tile, design_tile) in tiles.iter_mut().zip(design_tiles.iter()) {
+            if tile.index == 0 && design_tile.index != 0 {
+                tile.index = DdraceTileNum::NoHook as u8;
+                tile.flags = Default::default();
+            }
+        }
+    }
+
+    let is_freeze = |tiles: &[Tile], x: usize, y: usize| {
+        tiles[y * width + x].index == DdraceTileNum::Freeze as u8
+    };
+    let src = tiles.clone();
+    let mut changed_tiles = 0;
+    for y in 0..height {
+        for x in 0..width {
+            let tile = &mut tiles[y * width + x];
+            if tile.index != DdraceTileNum::Air as u8 {
+                continue;
+            }
+            let next_to_freeze = (y.saturating_sub(1)..(y + 2).min(height)).any(|check_y| {
+                (x.saturating_sub(1)..(x + 2).min(width))
+                    .any(|check_x| is_freeze(&src, check_x, check_y))
+            });
+            if next_to_freeze {
+                changed_tiles += 1;
+                tile.index = DdraceTileNum::Unfreeze as u8;
+                tile.flags = Default::default();
+            }
+        }
+    }
+    log::info!("Outlined freeze with {changed_tiles} unfreeze tiles");
+
+    tiles
+}
+
+#[cfg(test)]
+mod test {
+    use map::{map::groups::layers::tiles::Tile, tiles::DdraceTileNum};
+
+    use super::game_layer;
+
+    #[test]
+    fn freeze_next_to_design_wall() {
+        let tile = |index: DdraceTileNum| Tile {
+            index: index as u8,
+            flags: Default::default(),
+        };
+        let tiles = vec![
+            tile(DdraceTileNum::Air),
+            tile(DdraceTileNum::Freeze),
+            tile(DdraceTileNum::Air),
+            tile(DdraceTileNum::Air),
+        ];
+        // the third tile is only a wall in the design layer
+        let design_tiles = vec![
+            tile(DdraceTileNum::Air),
+            tile(DdraceTileNum::Air),
+            tile(DdraceTileNum::Solid),
+            tile(DdraceTileNum::Air),
+        ];
+
+        let tiles = game_layer(tiles, Some(&design_tiles), 4, 1);
+        assert_eq!(
+            tiles,
+            vec![
+                tile(DdraceTileNum::Unfreeze),
+                tile(DdraceTileNum::Freeze),
+                tile(DdraceTileNum::NoHook),
+                tile(DdraceTileNum::Air),
+            ]
+        );
+    }
+}
@@ -53,6 +53,7 @@ impl ChatPage {
                     channel: NetChatMsgPlayerChannel::GameTeam,
                 }),
                 add_time: Duration::MAX,
+                highlighted: false,
             },
             MsgInChat {
                 msg: ServerMsg::Chat(ChatMsg {
@@ -78,6 +79,7 @@ impl ChatPage {
                     }),
                 }),
                 add_time: Duration::MAX,
+                highlighted: false,
             },
         ]
         .into();
@@ -106,6 +108,7 @@ impl ChatPage {
                     channel: NetChatMsgPlayerChannel::Global,
                 }),
                 add_time: Duration::MAX,
+                highlighted: false,
             });
         }
         entries.push_back(MsgInChat {
@@ -121,6 +124,7 @@ impl ChatPage {
                 }),
             }),
             add_time: Duration::MAX,
+            highlighted: false,
         });
         entries.push_back(MsgInChat {
             msg: ServerMsg::System(MsgSystem {
@@ -129,6 +133,7 @@ impl ChatPage {
                 end_skin: None,
             }),
             add_time: Duration::MAX,
+            highlighted: false,
         });
         entries.push_back(MsgInChat {
             msg: ServerMsg::System(MsgSystem {
@@ -143,6 +148,7 @@ impl ChatPage {
                 }),
             }),
             add_time: Duration::MAX,
+            highlighted: false,
         });
         client_ui::chat::main_frame::render(
             ui,
@@ -159,6 +165,7 @@ impl ChatPage {
                     skin_container: &mut self.skin_container,
                     render_tee: &self.render_tee,
                     mode: ChatMode::Global,
+                    log_search: None,
 
                     character_infos: &Default::default(),
                     local_character_ids: &Default::default(),
@@ -46,8 +46,12 @@ impl DemoPlayerPage {
                     max_duration: &Duration::from_secs_f32(5.5),
                     events: &mut events,
                     speed: &Default::default(),
+                    camera_mode: &Default::default(),
+                    zoom: &1.0,
+                    characters: &[],
                     state: &mut self.state,
                     name: "example_demo",
+                    can_encode_to_video: &true,
                 },
             ),
             ui_state,
@@ -2,7 +2,7 @@ use api_ui_game::render::{create_ctf_container, create_skin_container};
 use base::{linked_hash_map_view::FxLinkedHashMap, network_string::PoolNetworkString};
 use client_containers::{ctf::CtfContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
-use client_ui::hud::user_data::RenderDateTime;
+use client_ui::hud::{layout::HudLayout, user_data::RenderDateTime};
 use game_interface::types::{
     character_info::{NetworkCharacterInfo, NetworkSkinInfo},
     id_gen::IdGenerator,
@@ -30,6 +30,7 @@ pub struct HudPage {
     render_tee: RenderTee,
     ctf_container: CtfContainer,
     character_infos: FxLinkedHashMap<CharacterId, CharacterInfo>,
+    layout: HudLayout,
 }
 
 impl HudPage {
@@ -59,6 +60,7 @@ impl HudPage {
             render_tee: RenderTee::new(graphics),
             ctf_container: create_ctf_container(),
             character_infos,
+            layout: Default::default(),
         }
     }
 
@@ -113,6 +115,11 @@ impl HudPage {
                         time: PoolString::new_str_without_pool("22:14:14"),
                         date: PoolString::new_str_without_pool("Saturday, 27. September 2025"),
                     }),
+                    ghost_split: &None,
+                    spectator_info: &None,
+                    layout: &self.layout,
+                    editing: false,
+                    events: &mut Vec::new(),
                 },
             ),
             ui_state,
@@ -16,6 +16,7 @@ use editor::editor::EditorInterface;
 use editor::editor::EditorResult;
 use egui::FontDefinitions;
 use graphics_types::types::WindowProps;
+use i18n::translations::Translations;
 
 unsafe extern "Rust" {
     /// returns an instance of the game state and the game tick speed
@@ -67,4 +68,7 @@ impl EditorInterface for ApiEditor {
 
     #[guest_func_call_from_host_auto(option)]
     fn file_hovered(&mut self, file: Option<PathBuf>) {}
+
+    #[guest_func_call_from_host_auto(option)]
+    fn set_translations(&mut self, translations: Translations) {}
 }
@@ -205,6 +205,9 @@ impl GameStateInterface for ApiState {
     #[guest_func_call_from_host_auto(option)]
     fn build_from_snapshot_by_hotreload(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {}
 
+    #[guest_func_call_from_host_auto(option)]
+    fn build_from_snapshot_by_savestate(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {}
+
     #[guest_func_call_from_host_auto(option)]
     fn build_from_snapshot_for_prev(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {}
 
@@ -31,10 +31,14 @@ pub enum BindActionsLocalPlayer {
     ActivateChatInput,
     ActivateSideOrStageChatInput,
     ActivateWhisperChatInput,
+    /// Search the chat logs of the current server.
+    ActivateChatSearchInput,
     ShowScoreboard,
     ShowChatHistory,
     ShowEmoteWheel,
     ShowSpectatorSelection,
+    /// Move, scale & hide the HUD elements.
+    ToggleHudEditor,
     Kill,
     FreeCam,
     PhasedFreeCam,
@@ -47,7 +51,7 @@ pub enum BindActionsLocalPlayer {
     ZoomReset,
 }
 
-const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 46] = [
+const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 48] = [
     (
         "+left",
         BindActionsLocalPlayer::Character(BindActionsCharacter::MoveLeft),
@@ -173,6 +177,10 @@ const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 46] = [
         "chat_whisper",
         BindActionsLocalPlayer::ActivateWhisperChatInput,
     ),
+    (
+        "chat_search",
+        BindActionsLocalPlayer::ActivateChatSearchInput,
+    ),
     ("+scoreboard", BindActionsLocalPlayer::ShowScoreboard),
     ("+chat_history", BindActionsLocalPlayer::ShowChatHistory),
     ("+emote_wheel", BindActionsLocalPlayer::ShowEmoteWheel),
@@ -180,6 +188,7 @@ const LOCAL_PLAYER_ACTIONS: [(&str, BindActionsLocalPlayer); 46] = [
         "+spectator_selection",
         BindActionsLocalPlayer::ShowSpectatorSelection,
     ),
+    ("toggle_hud_editor", BindActionsLocalPlayer::ToggleHudEditor),
     ("vote_yes", BindActionsLocalPlayer::VoteYes),
     ("vote_no", BindActionsLocalPlayer::VoteNo),
     ("kill", BindActionsLocalPlayer::Kill),
@@ -294,6 +303,14 @@ fn bind_keys_str_to_bind_keys(bind_keys_str: &str) -> anyhow::Result<Vec<BindKey
             bind_keys.push(BindKey::Mouse(key_code));
         } else if let Ok(key_code) = serde_json::from_str::<_>(&bind_key_str) {
             bind_keys.push(BindKey::Extra(key_code));
+        } else if let Ok(btn) =
+            serde_json::from_str::<_>(&bind_key_str.replacen("\"Gamepad", "\"", 1))
+        {
+            bind_keys.push(BindKey::GamepadButton(btn));
+        } else if let Ok(axis) =
+            serde_json::from_str::<_>(&bind_key_str.replacen("\"Gamepad", "\"", 1))
+        {
+            bind_keys.push(BindKey::GamepadAxis(axis));
         } else {
             let bind_key_str = format!("\"Key{cap_bind_key_str}\"");
             if let Ok(key_code) = serde_json::from_str::<KeyCode>(&bind_key_str) {
@@ -403,6 +420,24 @@ pub fn bind_keys_to_str(bind_keys: &[BindKey]) -> String {
                     .as_str(),
                 );
             }
+            BindKey::GamepadButton(btn) => {
+                res.push_str(&format!(
+                    "gamepad_{}",
+                    replace_inner_upper_with_underscore(
+                        &serde_json::to_string(btn).unwrap().replace('"', ""),
+                    )
+                    .to_lowercase()
+                ));
+            }
+            BindKey::GamepadAxis(axis) => {
+                res.push_str(&format!(
+                    "gamepad_{}",
+                    replace_inner_upper_with_underscore(
+                        &serde_json::to_string(axis).unwrap().replace('"', ""),
+                    )
+                    .to_lowercase()
+                ));
+            }
         }
 
         if index + 1 != key_chain_len {
@@ -481,11 +516,14 @@ pub fn str_list_to_binds_lossy(
 #[cfg(test)]
 mod test {
     use command_parser::parser::{Command, Syn};
-    use input_binds::binds::{BindKey, KeyCode, MouseButton, MouseExtra, PhysicalKey};
+    use input_binds::binds::{
+        BindKey, GamepadAxis, GamepadButton, KeyCode, MouseButton, MouseExtra, PhysicalKey,
+    };
 
     use crate::binds::{
-        BindAction, BindActionsCharacter, BindActionsLocalPlayer, bind_to_str,
-        gen_local_player_action_hash_map, gen_local_player_action_hash_map_rev, syn_to_bind,
+        BindAction, BindActionsCharacter, BindActionsLocalPlayer, bind_keys_str_to_bind_keys,
+        bind_keys_to_str, bind_to_str, gen_local_player_action_hash_map,
+        gen_local_player_action_hash_map_rev, syn_to_bind,
     };
 
     #[test]
@@ -569,4 +607,23 @@ mod test {
         );
         assert!(res.is_ok(), "{:?}", res);
     }
+
+    #[test]
+    fn gamepad_bind_keys() {
+        let keys = [
+            BindKey::GamepadButton(GamepadButton::LeftBumper),
+            BindKey::GamepadButton(GamepadButton::DpadUp),
+            BindKey::GamepadAxis(GamepadAxis::RightStickLeft),
+        ];
+        let keys_str = bind_keys_to_str(&keys);
+        assert_eq!(
+            keys_str,
+            "gamepad_left_bumper+gamepad_dpad_up+gamepad_right_stick_left"
+        );
+        assert_eq!(bind_keys_str_to_bind_keys(&keys_str).unwrap(), keys);
+        assert_eq!(
+            bind_keys_str_to_bind_keys("gamepad_south").unwrap(),
+            [BindKey::GamepadButton(GamepadButton::South)]
+        );
+    }
 }
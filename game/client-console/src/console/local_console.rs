@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     net::{SocketAddr, ToSocketAddrs},
     ops::Range,
     path::PathBuf,
@@ -16,8 +16,9 @@ use client_types::{
     console::{ConsoleEntry, ConsoleEntryCmd, ConsoleEntryVariable, entries_to_parser},
 };
 use client_ui::console::utils::{syn_vec_to_config_val, try_apply_config_val};
-use command_parser::parser::{
-    self, CommandArg, CommandArgType, CommandType, ParserCache, Syn, format_args,
+use command_parser::{
+    parser::{self, CommandArg, CommandArgType, CommandType, ParserCache, Syn, format_args},
+    vars::is_var_name,
 };
 use config::{
     config::ConfigEngine,
@@ -73,6 +74,17 @@ pub enum LocalConsoleEvent {
     ConfigVariable {
         name: String,
     },
+    /// An alias was added or removed
+    Alias,
+    /// Start a practice session on the internal server
+    Practice {
+        map: String,
+    },
+    /// Execute a command in the remote console of the current server
+    Rcon {
+        ident_text: String,
+        args: String,
+    },
     LocalPlayerAction(BindActionsLocalPlayer),
     Quit,
 }
@@ -156,6 +168,8 @@ impl Default for LocalConsoleBuilder {
         );
         let parser_cache = Rc::new(ParserCache::default());
         Self::register_commands(console_events.clone(), &mut entries, parser_cache.clone());
+        Self::register_practice_commands(console_events.clone(), &mut entries);
+        Self::register_macros(console_events.clone(), &mut entries);
 
         Self {
             console_events,
@@ -647,8 +661,10 @@ impl LocalConsoleBuilder {
         let keys_arg_cmd = keys_arg.clone();
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "bind".into(),
-            usage: "bind <keys> <commands>".into(),
-            description: "Binds commands to a single key or key chain.".into(),
+            usage: "bind <key[+key...]> <commands>".into(),
+            description:
+                "Binds commands to a single key or a combination of keys (e.g. `control_left+k`)."
+                    .into(),
             cmd: Rc::new(move |_config_engine, config_game, _, path| {
                 bind(
                     config_game.profiles.main as usize,
@@ -679,7 +695,7 @@ impl LocalConsoleBuilder {
         let keys_arg_cmd = keys_arg.clone();
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "bind_dummy".into(),
-            usage: "bind_dummy <keys> <commands>".into(),
+            usage: "bind_dummy <key[+key...]> <commands>".into(),
             description: "Binds commands to a single key or key chain for the dummy profile."
                 .into(),
             cmd: Rc::new(move |_config_engine, config_game, _, path| {
@@ -711,7 +727,7 @@ impl LocalConsoleBuilder {
         let events = console_events.clone();
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "unbind".into(),
-            usage: "unbind <keys>".into(),
+            usage: "unbind <key[+key...]>".into(),
             description: "Unbinds commands from a single key or key chain.".into(),
             cmd: Rc::new(move |_config_engine, config_game, _, path| {
                 unbind(
@@ -733,7 +749,7 @@ impl LocalConsoleBuilder {
         let events = console_events.clone();
         list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
             name: "unbind_dummy".into(),
-            usage: "unbind_dummy <keys>".into(),
+            usage: "unbind_dummy <key[+key...]>".into(),
             description: "Unbinds commands from a single key or key chain for the dummy profile."
                 .into(),
             cmd: Rc::new(move |_config_engine, config_game, _, path| {
@@ -948,6 +964,376 @@ impl LocalConsoleBuilder {
         }));
     }
 
+    fn register_practice_commands(
+        console_events: LocalConsoleEvents,
+        list: &mut Vec<ConsoleEntry>,
+    ) {
+        let console_events_cmd = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "practice".into(),
+            usage: "practice <map>".into(),
+            description: "Starts the internal server on the given map in practice mode \
+                and connects to it."
+                .into(),
+            cmd: Rc::new(move |_, _, _, path| {
+                let Syn::Text(map) = &path[0].0 else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+
+                console_events_cmd.push(LocalConsoleEvent::Practice { map: map.clone() });
+                Ok(format!("Starting practice on {map}"))
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Text,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
+        // these only forward to the rcon commands of the server,
+        // so they can be used in binds
+        let rcon_cmds = [
+            (
+                "practice_save",
+                "practice.save",
+                "practice_save <slot>",
+                "Saves the state of the game to the given slot.",
+                Some(CommandArgType::Number),
+            ),
+            (
+                "practice_load",
+                "practice.load",
+                "practice_load <slot>",
+                "Loads the state of the game from the given slot.",
+                Some(CommandArgType::Number),
+            ),
+            (
+                "practice_rewind",
+                "practice.rewind",
+                "practice_rewind <seconds>",
+                "Rewinds the game by the given amount of seconds.",
+                Some(CommandArgType::Float),
+            ),
+            (
+                "practice_speed",
+                "practice.speed",
+                "practice_speed <factor>",
+                "Sets the game speed, e.g. 0.5 for half the speed.",
+                Some(CommandArgType::Float),
+            ),
+            (
+                "practice_teleport",
+                "cheats.teleport_to_cursor",
+                "practice_teleport",
+                "Teleports the local player to the position of the cursor.",
+                None,
+            ),
+        ];
+        for (name, ident_text, usage, description, arg) in rcon_cmds {
+            let console_events = console_events.clone();
+            list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+                name: name.into(),
+                usage: usage.into(),
+                description: format!("{description} Requires the practice mode."),
+                cmd: Rc::new(move |_, _, _, path| {
+                    let args = format_args(path);
+                    console_events.push(LocalConsoleEvent::Rcon {
+                        ident_text: ident_text.to_string(),
+                        args: args.clone(),
+                    });
+                    Ok(format!("{ident_text} {args}"))
+                }),
+                args: arg
+                    .into_iter()
+                    .map(|ty| CommandArg { ty, user_ty: None })
+                    .collect(),
+                allows_partial_cmds: false,
+            }));
+        }
+    }
+
+    /// Commands that are implemented as [`ConsoleEntry::Macro`],
+    /// all other macros are user defined aliases.
+    const BUILTIN_MACROS: [&str; 3] = ["if", "ifeq", "ifneq"];
+
+    fn register_macros(console_events: LocalConsoleEvents, list: &mut Vec<ConsoleEntry>) {
+        let reserved: Rc<HashSet<String>> = Rc::new(
+            list.iter()
+                .map(|entry| match entry {
+                    ConsoleEntry::Var(var) => var.full_name.clone(),
+                    ConsoleEntry::Cmd(cmd) | ConsoleEntry::Macro(cmd) => cmd.name.clone(),
+                })
+                .chain(
+                    ["alias", "unalias", "set", "unset"]
+                        .into_iter()
+                        .chain(Self::BUILTIN_MACROS)
+                        .map(|name| name.to_string()),
+                )
+                .collect(),
+        );
+
+        fn commands_to_str(syn: &Syn) -> String {
+            match syn {
+                Syn::Commands(cmds) => cmds
+                    .iter()
+                    .map(|cmd| cmd.to_string().trim_end().to_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                Syn::Command(cmd) => cmd.to_string().trim_end().to_string(),
+                syn => format_args(&[(syn.clone(), 0..0)]),
+            }
+        }
+        fn config_val(
+            config_engine: &mut ConfigEngine,
+            config_game: &mut ConfigGame,
+            path: &[(Syn, Range<usize>)],
+        ) -> anyhow::Result<String> {
+            let path = syn_vec_to_config_val(path).unwrap_or_default();
+            let res_engine = config_engine.try_set_from_str(
+                path.clone(),
+                None,
+                None,
+                None,
+                ConfigFromStrOperation::Set,
+            );
+            let res_game = config_game.try_set_from_str(
+                path.clone(),
+                None,
+                None,
+                None,
+                ConfigFromStrOperation::Set,
+            );
+            Ok(res_engine.or(res_game)?)
+        }
+
+        let events = console_events.clone();
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "alias".into(),
+            usage: "alias <name> <commands>".into(),
+            description: "Adds a new command that executes the given commands.".into(),
+            cmd: Rc::new(move |_, config_game, _, path| {
+                let Syn::Text(name) = &path[0].0 else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+                anyhow::ensure!(
+                    Self::is_alias_name(name),
+                    "The name of an alias can only contain letters, digits, `_`, `-` & `+`."
+                );
+                anyhow::ensure!(
+                    !reserved.contains(name),
+                    "{name} is already a command or variable."
+                );
+                let cmds = commands_to_str(&path[1].0);
+                config_game
+                    .cl
+                    .console
+                    .aliases
+                    .insert(name.clone(), cmds.clone());
+                events.push(LocalConsoleEvent::Alias);
+                Ok(format!("Added alias {name}: {cmds}"))
+            }),
+            args: vec![
+                CommandArg {
+                    ty: CommandArgType::Text,
+                    user_ty: None,
+                },
+                CommandArg {
+                    ty: CommandArgType::Commands,
+                    user_ty: None,
+                },
+            ],
+            allows_partial_cmds: false,
+        }));
+        let events = console_events;
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "unalias".into(),
+            usage: "unalias <name>".into(),
+            description: "Removes an alias.".into(),
+            cmd: Rc::new(move |_, config_game, _, path| {
+                let Syn::Text(name) = &path[0].0 else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+                config_game
+                    .cl
+                    .console
+                    .aliases
+                    .remove(name)
+                    .ok_or_else(|| anyhow!("No alias with the name {name} found."))?;
+                events.push(LocalConsoleEvent::Alias);
+                Ok(format!("Removed alias {name}"))
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Text,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "set".into(),
+            usage: "set <name> <value>".into(),
+            description: "Sets a user variable, which can be used as `$name` in arguments.".into(),
+            cmd: Rc::new(move |_, config_game, _, path| {
+                let (Syn::Text(name), Syn::Text(val)) = (&path[0].0, &path[1].0) else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+                anyhow::ensure!(
+                    is_var_name(name),
+                    "The name of a variable can only contain letters, digits & `_`."
+                );
+                config_game
+                    .cl
+                    .console
+                    .vars
+                    .insert(name.clone(), val.clone());
+                Ok(format!("${name} = {val}"))
+            }),
+            args: vec![
+                CommandArg {
+                    ty: CommandArgType::Text,
+                    user_ty: None,
+                },
+                CommandArg {
+                    ty: CommandArgType::Text,
+                    user_ty: None,
+                },
+            ],
+            allows_partial_cmds: false,
+        }));
+        list.push(ConsoleEntry::Cmd(ConsoleEntryCmd {
+            name: "unset".into(),
+            usage: "unset <name>".into(),
+            description: "Removes a user variable.".into(),
+            cmd: Rc::new(move |_, config_game, _, path| {
+                let Syn::Text(name) = &path[0].0 else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+                config_game
+                    .cl
+                    .console
+                    .vars
+                    .remove(name)
+                    .ok_or_else(|| anyhow!("No variable with the name {name} found."))?;
+                Ok(format!("Removed ${name}"))
+            }),
+            args: vec![CommandArg {
+                ty: CommandArgType::Text,
+                user_ty: None,
+            }],
+            allows_partial_cmds: false,
+        }));
+
+        list.push(ConsoleEntry::Macro(ConsoleEntryCmd {
+            name: "if".into(),
+            usage: "if <var> <commands>".into(),
+            description: "Executes the commands, if the config variable is not \
+                empty, 0 or false."
+                .into(),
+            cmd: Rc::new(move |config_engine, config_game, _, path| {
+                let val = config_val(config_engine, config_game, &path[0..1])?;
+                Ok(if !matches!(val.as_str(), "" | "0" | "false") {
+                    commands_to_str(&path[1].0)
+                } else {
+                    String::default()
+                })
+            }),
+            args: vec![
+                CommandArg {
+                    ty: CommandArgType::CommandIdent,
+                    user_ty: None,
+                },
+                CommandArg {
+                    ty: CommandArgType::Commands,
+                    user_ty: None,
+                },
+            ],
+            allows_partial_cmds: false,
+        }));
+        for (name, eq) in [("ifeq", true), ("ifneq", false)] {
+            list.push(ConsoleEntry::Macro(ConsoleEntryCmd {
+                name: name.into(),
+                usage: format!("{name} <var> <value> <commands>"),
+                description: format!(
+                    "Executes the commands, if the config variable is {} to the value.",
+                    if eq { "equal" } else { "not equal" }
+                ),
+                cmd: Rc::new(move |config_engine, config_game, _, path| {
+                    let val = config_val(config_engine, config_game, &path[0..1])?;
+                    let cmp_val = format_args(&path[1..2]);
+                    Ok(if (val == cmp_val) == eq {
+                        commands_to_str(&path[2].0)
+                    } else {
+                        String::default()
+                    })
+                }),
+                args: vec![
+                    CommandArg {
+                        ty: CommandArgType::CommandIdent,
+                        user_ty: None,
+                    },
+                    CommandArg {
+                        ty: CommandArgType::Text,
+                        user_ty: None,
+                    },
+                    CommandArg {
+                        ty: CommandArgType::Commands,
+                        user_ty: None,
+                    },
+                ],
+                allows_partial_cmds: false,
+            }));
+        }
+    }
+
+    fn is_alias_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+'))
+    }
+
+    /// Makes the entries match the aliases of the config,
+    /// so the aliases can be parsed & executed.
+    pub fn sync_aliases(entries: &mut Vec<ConsoleEntry>, config_game: &ConfigGame) {
+        entries.retain(|entry| match entry {
+            ConsoleEntry::Macro(cmd) => Self::BUILTIN_MACROS.contains(&cmd.name.as_str()),
+            ConsoleEntry::Var(_) | ConsoleEntry::Cmd(_) => true,
+        });
+        let names: HashSet<String> = entries
+            .iter()
+            .map(|entry| match entry {
+                ConsoleEntry::Var(var) => var.full_name.clone(),
+                ConsoleEntry::Cmd(cmd) | ConsoleEntry::Macro(cmd) => cmd.name.clone(),
+            })
+            .collect();
+
+        let mut aliases: Vec<_> = config_game.cl.console.aliases.iter().collect();
+        aliases.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+        for (name, cmds) in aliases {
+            if names.contains(name) || !Self::is_alias_name(name) {
+                log::warn!("ignored alias {name}, because the name is invalid or already in use");
+                continue;
+            }
+            let alias_name = name.clone();
+            entries.push(ConsoleEntry::Macro(ConsoleEntryCmd {
+                name: name.clone(),
+                usage: name.clone(),
+                description: format!("Alias for: {cmds}"),
+                cmd: Rc::new(move |_, config_game, _, _| {
+                    config_game
+                        .cl
+                        .console
+                        .aliases
+                        .get(&alias_name)
+                        .cloned()
+                        .ok_or_else(|| anyhow!("The alias {alias_name} does not exist anymore."))
+                }),
+                args: vec![],
+                allows_partial_cmds: false,
+            }));
+        }
+    }
+
     pub fn build(self, creator: &UiCreator) -> LocalConsole {
         ConsoleRender::new(
             creator,
@@ -958,3 +1344,120 @@ impl LocalConsoleBuilder {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use client_types::console::entries_to_parser;
+    use client_ui::console::utils::run_commands;
+    use command_parser::parser::{self, ParserCache};
+    use config::config::ConfigEngine;
+    use game_config::config::ConfigGame;
+
+    use crate::console::console::ConsoleEvents;
+
+    use super::{LocalConsoleBuilder, LocalConsoleEvent};
+
+    /// Runs the commands & returns whether they succeeded and the echoed texts.
+    fn run(
+        builder: &mut LocalConsoleBuilder,
+        config_game: &mut ConfigGame,
+        cmds: &str,
+    ) -> (bool, Vec<String>) {
+        LocalConsoleBuilder::sync_aliases(&mut builder.entries, config_game);
+        let cmds = parser::parse(
+            cmds,
+            &entries_to_parser(&builder.entries),
+            &ParserCache::default(),
+        );
+        let mut msgs = String::new();
+        let res = run_commands(
+            &cmds,
+            &builder.entries,
+            &mut ConfigEngine::default(),
+            config_game,
+            &mut msgs,
+            true,
+        );
+        let echos = builder
+            .console_events
+            .take()
+            .into_iter()
+            .filter_map(|ev| match ev {
+                LocalConsoleEvent::Echo { text } => Some(text),
+                _ => None,
+            })
+            .collect();
+        (res, echos)
+    }
+
+    #[test]
+    fn if_macros() {
+        let mut builder = LocalConsoleBuilder::default();
+        let mut config_game = ConfigGame::default();
+
+        let cmds = "if cl.chat.logs echo if; \
+            ifeq cl.chat.logs true echo ifeq; \
+            ifneq cl.chat.logs true echo ifneq";
+        assert_eq!(
+            run(&mut builder, &mut config_game, cmds),
+            (true, vec!["ifneq".to_string()])
+        );
+
+        config_game.cl.chat.logs = true;
+        assert_eq!(
+            run(&mut builder, &mut config_game, cmds),
+            (true, vec!["if".to_string(), "ifeq".to_string()])
+        );
+
+        // multiple commands in a branch
+        assert_eq!(
+            run(
+                &mut builder,
+                &mut config_game,
+                "if cl.chat.logs \"echo a; echo b\""
+            ),
+            (true, vec!["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn aliases() {
+        let mut builder = LocalConsoleBuilder::default();
+        let mut config_game = ConfigGame::default();
+
+        assert!(run(&mut builder, &mut config_game, "alias greet echo hi").0);
+        assert_eq!(
+            run(&mut builder, &mut config_game, "greet; greet"),
+            (true, vec!["hi".to_string(), "hi".to_string()])
+        );
+
+        // aliases can use other aliases & the if macros
+        assert!(
+            run(
+                &mut builder,
+                &mut config_game,
+                "alias maybe_greet ifneq cl.chat.logs true greet"
+            )
+            .0
+        );
+        assert_eq!(
+            run(&mut builder, &mut config_game, "maybe_greet"),
+            (true, vec!["hi".to_string()])
+        );
+
+        assert!(run(&mut builder, &mut config_game, "unalias greet").0);
+        assert!(!run(&mut builder, &mut config_game, "maybe_greet").0);
+    }
+
+    #[test]
+    fn recursive_alias_stops() {
+        let mut builder = LocalConsoleBuilder::default();
+        let mut config_game = ConfigGame::default();
+
+        assert!(run(&mut builder, &mut config_game, "alias a a").0);
+        assert_eq!(
+            run(&mut builder, &mut config_game, "a; echo after"),
+            (false, vec!["after".to_string()])
+        );
+    }
+}
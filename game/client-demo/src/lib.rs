@@ -17,13 +17,20 @@ pub use av_encoder::types::EncoderSettings;
 use av_encoder::{AvEncoder, traits::AudioVideoEncoder};
 use base::steady_clock::SteadyClock;
 use base_io::{io::Io, runtime::IoRuntimeTask};
+use camera::Camera;
 use client_map::client_map::{ClientMapFile, ClientMapLoading, GameMap, RenderGameWasmManager};
 use client_render_base::map::render_pipe::GameTimeInfo;
-use client_render_game::render_game::{
-    RenderForPlayer, RenderGameCreateOptions, RenderGameForPlayer, RenderGameInput,
-    RenderGameInterface, RenderGameSettings, RenderModTy, RenderPlayerCameraMode,
+use client_render_game::{
+    auto_director::AutoDirector,
+    render_game::{
+        RenderChatMsg, RenderForPlayer, RenderGameCreateOptions, RenderGameForPlayer,
+        RenderGameInput, RenderGameInterface, RenderGameSettings, RenderModTy,
+        RenderPlayerCameraMode,
+    },
+};
+use client_ui::demo_player::user_data::{
+    DemoViewerCameraMode, DemoViewerEvent, DemoViewerUiState, UserData,
 };
-use client_ui::demo_player::user_data::{DemoViewerEvent, DemoViewerUiState, UserData};
 use config::config::ConfigEngine;
 use demo::{
     ChunkHeader, DemoEvent, DemoEvents, DemoHeader, DemoHeaderExt, DemoSnapshot, DemoTail,
@@ -33,7 +40,10 @@ use demo::{
 use egui::{FontDefinitions, Rect};
 use game_base::{assets_url::HTTP_RESOURCE_URL, game_types::intra_tick_time_to_ratio};
 use game_config::config::{ConfigGame, ConfigMap, ConfigRender, ConfigSoundRender};
-use game_interface::{interface::GameStateInterface, types::game::GameTickType};
+use game_interface::{
+    interface::GameStateInterface,
+    types::{game::GameTickType, id_types::CharacterId},
+};
 use graphics::{
     graphics::graphics::Graphics,
     handles::{
@@ -47,7 +57,7 @@ use graphics_backend::backend::GraphicsBackend;
 use graphics_types::rendering::{BlendType, ColorMaskMode, State};
 use math::math::vector::{ffixed, ubvec4, vec2};
 use pool::datatypes::{
-    PoolBTreeMap, PoolFxLinkedHashMap, PoolFxLinkedHashSet, PoolVec, PoolVecDeque,
+    PoolBTreeMap, PoolFxHashSet, PoolFxLinkedHashMap, PoolFxLinkedHashSet, PoolVec, PoolVecDeque,
 };
 use pool::mt_datatypes::PoolCow as MtPoolCow;
 use serde::de::DeserializeOwned;
@@ -71,6 +81,28 @@ pub struct DemoContainer {
     pub tail: DemoTail,
 }
 
+/// The camera the demo is watched with.
+#[derive(Debug, Clone)]
+struct DemoCamera {
+    mode: DemoViewerCameraMode,
+    /// The position (in tiles) of the camera in the last frame,
+    /// the free camera starts from here.
+    ///
+    /// `None` as long as the camera was never placed.
+    pos: Option<vec2>,
+    zoom: f32,
+}
+
+impl Default for DemoCamera {
+    fn default() -> Self {
+        Self {
+            mode: Default::default(),
+            pos: Default::default(),
+            zoom: 1.0,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct DemoViewerInner {
     demo: DemoContainer,
@@ -80,6 +112,11 @@ pub struct DemoViewerInner {
     is_closed: bool,
     is_paused: bool,
     speed: ffixed,
+
+    camera: DemoCamera,
+    director: AutoDirector,
+    /// The characters the camera can follow.
+    characters: Vec<(CharacterId, String)>,
 }
 
 impl DemoViewerInner {
@@ -99,6 +136,7 @@ impl DemoViewerInner {
         self.cur_time = time;
         self.cur_snapshots.clear();
         self.cur_events.clear();
+        self.director.reset();
     }
 
     fn read_chunks<A: DeserializeOwned>(
@@ -270,6 +308,20 @@ pub struct DemoViewerSettings {
     pub render: ConfigRender,
     pub snd: ConfigSoundRender,
     pub global_sound_volume: f64,
+    /// The camera the demo is watched with.
+    pub camera_mode: DemoViewerCameraMode,
+    /// The position (in tiles) the free camera starts at.
+    pub camera_pos: Option<vec2>,
+    pub zoom: f32,
+}
+
+/// The user asked to encode the watched demo to a video.
+#[derive(Debug, Clone)]
+pub struct DemoViewerEncodeRequest {
+    pub video_name: String,
+    pub camera_mode: DemoViewerCameraMode,
+    pub camera_pos: Option<vec2>,
+    pub zoom: f32,
 }
 
 #[derive(Debug, Clone)]
@@ -303,6 +355,7 @@ pub struct DemoViewerImpl {
 
     events: Vec<DemoViewerEvent>,
     ui_state: DemoViewerUiState,
+    encode_request: Option<DemoViewerEncodeRequest>,
 }
 
 impl DemoViewerImpl {
@@ -321,6 +374,14 @@ impl DemoViewerImpl {
         name: String,
         encoder_offair_id: Option<u128>,
     ) -> Self {
+        let camera = encode_to_video
+            .as_ref()
+            .map(|props| DemoCamera {
+                mode: props.settings.camera_mode,
+                pos: props.settings.camera_pos,
+                zoom: props.settings.zoom,
+            })
+            .unwrap_or_default();
         Self {
             data: DemoStaticData {
                 canvas_handle: graphics.canvas_handle.clone(),
@@ -380,6 +441,10 @@ impl DemoViewerImpl {
                 // Always paused
                 is_paused: true,
                 speed: ffixed::from_num(1.0),
+
+                camera: Default::default(),
+                director: Default::default(),
+                characters: Default::default(),
             },
             should_show_preview: None,
             inner: DemoViewerInner {
@@ -393,12 +458,17 @@ impl DemoViewerImpl {
                 is_closed: false,
                 is_paused: false,
                 speed: ffixed::from_num(1.0),
+
+                camera,
+                director: Default::default(),
+                characters: Default::default(),
             },
 
             demo_ui: DemoPlayerUiRender::new(graphics, ui_creator),
 
             events: Default::default(),
             ui_state: Default::default(),
+            encode_request: None,
         }
     }
 
@@ -410,6 +480,30 @@ impl DemoViewerImpl {
         self.inner.is_closed()
     }
 
+    /// Takes the last request of the user to encode this demo to a video.
+    pub fn take_encode_request(&mut self) -> Option<DemoViewerEncodeRequest> {
+        self.encode_request.take()
+    }
+
+    /// Creates a new viewer that encodes this demo to a video.
+    pub fn encode_to_video(
+        &self,
+        thread_pool: &Arc<rayon::ThreadPool>,
+        fonts: FontDefinitions,
+        encode_to_video: DemoVideoEncodeProperties,
+    ) -> DemoViewer {
+        let demo = self.inner.demo.clone();
+        DemoViewer::Loading(Box::new(DemoViewerLoading {
+            task: self.io.rt.spawn(async move { Ok(demo) }),
+            io: self.io.clone(),
+            thread_pool: thread_pool.clone(),
+            fonts,
+            encode_to_video: Some(encode_to_video),
+            sub_dir: self.sub_dir.clone(),
+            name: self.demo_name.clone(),
+        }))
+    }
+
     fn set_time_and_reset_state(
         client_map: &mut ClientMapLoading,
         inner: &mut DemoViewerInner,
@@ -426,6 +520,28 @@ impl DemoViewerImpl {
         render.clear_render_state();
     }
 
+    /// The aspect ratio the game is rendered with.
+    fn render_aspect(
+        data: &DemoStaticData,
+        for_video_encode: bool,
+        config_render: &ConfigRender,
+    ) -> f32 {
+        let encoder = for_video_encode
+            .then_some(data.av_encoder.as_ref())
+            .flatten();
+        let render = encoder.map_or(config_render, |encoder| &encoder.settings.settings.render);
+        render
+            .use_ingame_aspect_ratio
+            .then_some(render.ingame_aspect_ratio as f32)
+            .unwrap_or_else(|| match encoder {
+                Some(encoder) => {
+                    encoder.settings.encoder_settings.width as f32
+                        / encoder.settings.encoder_settings.height as f32
+                }
+                None => data.canvas_handle.window_canvas_aspect(),
+            })
+    }
+
     fn prepare_render_input<'a>(
         viewer: &mut DemoViewerInner,
         data: &mut DemoStaticData,
@@ -494,12 +610,14 @@ impl DemoViewerImpl {
         let (player_id, _) = local_players.iter().next().unwrap();
         let intra_tick_time = viewer.intra_tick_time(monotonic_tick, prev_tick, next_tick);
 
-        let render_for_player = RenderForPlayer {
+        let mut render_for_player = RenderForPlayer {
             chat_info: None,
             emote_wheel_input: None,
             spectator_selection_input: None,
+            hud_editor_input: None,
             scoreboard_active: false,
             chat_show_all: false,
+            chat_log_search: None,
 
             local_player_info: game.collect_character_local_render_info(player_id),
 
@@ -515,6 +633,13 @@ impl DemoViewerImpl {
             intra_tick_time_to_ratio(intra_tick_time, viewer.demo.header_ext.ticks_per_second);
 
         let character_infos = game.collect_characters_info();
+        viewer.characters.clear();
+        viewer.characters.extend(
+            character_infos
+                .iter()
+                .filter(|(_, c)| c.stage_id.is_some())
+                .map(|(id, c)| (*id, c.info.name.to_string())),
+        );
 
         let stages = game.all_stages(intra_tick_ratio);
 
@@ -538,7 +663,10 @@ impl DemoViewerImpl {
                         events.insert((monotonic_tick, false), evs);
                     }
                     DemoEvent::Chat(msg) => {
-                        chat_msgs.push_back(*msg);
+                        chat_msgs.push_back(RenderChatMsg {
+                            msg: *msg,
+                            highlighted: false,
+                        });
                     }
                     DemoEvent::Marker => {
                         // ignore
@@ -562,6 +690,7 @@ impl DemoViewerImpl {
             stages,
             scoreboard_info,
             date_time,
+            ghost_split: None,
             chat_msgs,
             game_time_info,
             settings: if let Some(DemoEncoder {
@@ -578,6 +707,7 @@ impl DemoViewerImpl {
                     viewer.speed.to_num::<f64>(),
                     false,
                     encoder_game_settings.settings.global_sound_volume,
+                    &Default::default(),
                 )
             } else {
                 RenderGameSettings::new(
@@ -587,11 +717,81 @@ impl DemoViewerImpl {
                     viewer.speed.to_num::<f64>(),
                     false,
                     global_sound_volume,
+                    &Default::default(),
                 )
             },
             ext: game.collect_render_ext(),
         };
 
+        let auto_director = if let Some(encoder) = for_video_encode
+            .then_some(data.av_encoder.as_ref())
+            .flatten()
+        {
+            encoder.settings.settings.render.auto_director
+        } else {
+            config_render.auto_director
+        };
+        let camera = &mut viewer.camera;
+        let character_pos = |id: &CharacterId| {
+            render_game_input
+                .character_infos
+                .get(id)
+                .and_then(|c| c.stage_id)
+                .and_then(|stage_id| render_game_input.stages.get(&stage_id))
+                .and_then(|stage| stage.world.characters.get(id))
+                .map(|c| c.lerped_pos)
+        };
+        render_for_player.zoom = camera.zoom;
+        match camera.mode {
+            DemoViewerCameraMode::Recorded if auto_director => {
+                if let Some(director_camera) = viewer.director.update(
+                    &viewer.cur_time,
+                    Self::render_aspect(data, for_video_encode, config_render),
+                    &render_game_input.events,
+                    &render_game_input.stages,
+                ) {
+                    camera.pos = Some(director_camera.pos);
+                    render_for_player.cam_mode = RenderPlayerCameraMode::AtPos {
+                        pos: director_camera.pos,
+                        locked_ingame: false,
+                    };
+                    // the user's zoom is relative to the director's
+                    render_for_player.zoom *= director_camera.zoom;
+                }
+            }
+            DemoViewerCameraMode::Recorded => {
+                if let Some(pos) = character_pos(player_id) {
+                    camera.pos = Some(pos);
+                }
+            }
+            DemoViewerCameraMode::Follow(id) => {
+                if let Some(pos) = character_pos(&id) {
+                    camera.pos = Some(pos);
+                }
+                let mut character_ids = PoolFxHashSet::new_without_pool();
+                character_ids.insert(id);
+                render_for_player.cam_mode = RenderPlayerCameraMode::OnCharacters {
+                    character_ids,
+                    fallback_pos: camera.pos.unwrap_or_default(),
+                };
+            }
+            DemoViewerCameraMode::Free => {
+                // a free camera that was never placed starts at the recorded player
+                if camera.pos.is_none() {
+                    camera.pos = character_pos(player_id);
+                }
+                if let Some(pos) = camera.pos {
+                    render_for_player.cam_mode = RenderPlayerCameraMode::AtPos {
+                        pos,
+                        locked_ingame: false,
+                    };
+                }
+            }
+        }
+        if !auto_director || camera.mode != DemoViewerCameraMode::Recorded {
+            viewer.director.reset();
+        }
+
         let render_for_player = RenderGameForPlayer {
             render_for_player,
             observed_players: PoolVec::new_without_pool(),
@@ -721,9 +921,14 @@ impl DemoViewerImpl {
                     cur_duration: &self.inner.cur_time,
                     max_duration: &self.inner.demo.header.len,
                     speed: &self.inner.speed,
+                    camera_mode: &self.inner.camera.mode,
+                    zoom: &self.inner.camera.zoom,
+                    characters: &self.inner.characters,
                     events: &mut self.events,
                     state: &mut self.ui_state,
                     name: &self.demo_name,
+                    can_encode_to_video: &(cfg!(feature = "ffmpeg")
+                        && self.data.av_encoder.is_none()),
                 },
             },
             input,
@@ -789,6 +994,24 @@ impl DemoViewerImpl {
                 DemoViewerEvent::SpeedReset => {
                     self.inner.speed = ffixed::from_num(1.0);
                 }
+                DemoViewerEvent::CameraMode(mode) => {
+                    self.inner.camera.mode = mode;
+                }
+                DemoViewerEvent::CameraPan { delta } => {
+                    // moving the camera detaches it from the characters
+                    let camera = &mut self.inner.camera;
+                    camera.mode = DemoViewerCameraMode::Free;
+
+                    let aspect = Self::render_aspect(&self.data, do_encoding, render);
+                    let (mut width, mut height) = (0.0, 0.0);
+                    Camera::calc_canvas_params(aspect, camera.zoom, &mut width, &mut height);
+                    if let Some(pos) = camera.pos.as_mut() {
+                        *pos -= vec2::new(delta.x * width, delta.y * height);
+                    }
+                }
+                DemoViewerEvent::CameraZoom { zoom } => {
+                    self.inner.camera.zoom = zoom;
+                }
                 DemoViewerEvent::Export(data) => {
                     let demo = &self.inner.demo;
                     let ext = &demo.header_ext;
@@ -849,6 +1072,7 @@ impl DemoViewerImpl {
                     Self::set_time_and_reset_state(&mut self.client_map, &mut self.inner, time);
                 }
                 DemoViewerEvent::PreviewAt { rect, time } => {
+                    self.preview.camera = self.inner.camera.clone();
                     Self::set_time_and_reset_state(
                         &mut self.preview_client_map,
                         &mut self.preview,
@@ -856,6 +1080,15 @@ impl DemoViewerImpl {
                     );
                     self.should_show_preview = Some(rect);
                 }
+                DemoViewerEvent::EncodeToVideo { video_name } => {
+                    let camera = &self.inner.camera;
+                    self.encode_request = Some(DemoViewerEncodeRequest {
+                        video_name,
+                        camera_mode: camera.mode,
+                        camera_pos: camera.pos,
+                        zoom: camera.zoom,
+                    });
+                }
                 DemoViewerEvent::Close => {
                     self.inner.is_closed = true;
                 }
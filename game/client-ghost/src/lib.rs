@@ -1,34 +1,76 @@
+pub mod split;
+
 use std::{
-    collections::{HashMap, VecDeque},
-    path::PathBuf,
+    collections::VecDeque,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
     time::Duration,
 };
 
-use base::{
-    hash::{Hash, fmt_hash},
-    reduced_ascii_str::ReducedAsciiString,
-    steady_clock::SteadyClock,
-};
+use base::{hash::Hash, reduced_ascii_str::ReducedAsciiString, steady_clock::SteadyClock};
 use base_io::{io::Io, runtime::IoRuntimeTask};
-use base_io_traits::fs_traits::FileSystemEntryTy;
 use client_demo::DemoViewer;
 use client_render_game::render_game::RenderGameInput;
+use client_ui::hud::user_data::RenderGhostSplit;
 use config::config::ConfigEngine;
 use game_config::config::ConfigGame;
 use game_interface::types::{
+    character_info::NetworkSkinInfo,
     game::GameEntityId,
     id_gen::IdGenerator,
     render::{game::GameRenderInfo, stage::StageRenderInfo, world::WorldRenderInfo},
 };
+use ghost::storage::{GhostEntry, GhostIndex, ghost_dir, load_ghost_index};
 use graphics::graphics::graphics::Graphics;
 use graphics_backend::backend::GraphicsBackend;
+use math::math::vector::{ubvec4, vec2};
 use pool::datatypes::PoolFxLinkedHashMap;
 use sound::sound::SoundManager;
 use sound_backend::sound_backend::SoundBackend;
+use split::GhostSplit;
 use ui_base::{font_data::FontDefinitions, ui::UiCreator};
 
+/// Colors to tint the shown ghosts with, in order of their rank.
+const GHOST_TINTS: [ubvec4; 6] = [
+    ubvec4 {
+        x: 255,
+        y: 215,
+        z: 0,
+        w: 255,
+    },
+    ubvec4 {
+        x: 0,
+        y: 191,
+        z: 255,
+        w: 255,
+    },
+    ubvec4 {
+        x: 255,
+        y: 105,
+        z: 180,
+        w: 255,
+    },
+    ubvec4 {
+        x: 50,
+        y: 205,
+        z: 50,
+        w: 255,
+    },
+    ubvec4 {
+        x: 255,
+        y: 140,
+        z: 0,
+        w: 255,
+    },
+    ubvec4 {
+        x: 148,
+        y: 0,
+        z: 211,
+        w: 255,
+    },
+];
+
 struct GhostIds {
     usable_ids: VecDeque<GameEntityId>,
     next_ids: VecDeque<GameEntityId>,
@@ -58,8 +100,17 @@ impl GhostIds {
     }
 }
 
+struct LoadedGhost {
+    entry: GhostEntry,
+    viewer: DemoViewer,
+}
+
 pub struct GhostViewer {
-    ghosts: HashMap<String, DemoViewer>,
+    /// Shown ghosts, best run first.
+    ghosts: Vec<LoadedGhost>,
+    split: GhostSplit,
+    /// Position of the ghost selected for the split comparison.
+    split_ghost_pos: Option<vec2>,
 
     sound: SoundManager,
     graphics: Graphics,
@@ -72,7 +123,7 @@ pub struct GhostViewer {
     base_path: PathBuf,
     fonts: FontDefinitions,
 
-    task: Option<IoRuntimeTask<Vec<String>>>,
+    task: Option<IoRuntimeTask<GhostIndex>>,
     io: Io,
     tp: Arc<rayon::ThreadPool>,
 }
@@ -94,23 +145,13 @@ impl GhostViewer {
         let id_generator = IdGenerator::new();
         id_generator.reverse();
 
-        let fs = io.fs.clone();
-        let map_name = map_name.to_string();
-        let base_path = format!("ghosts/{}_{}", map_name, fmt_hash(&map_hash));
-        let base_path_task = base_path.clone();
-        let task = io.rt.spawn(async move {
-            let entries = fs.entries_in_dir(base_path_task.as_ref()).await?;
-            Ok(entries
-                .into_iter()
-                .filter_map(|(name, ty)| match ty {
-                    FileSystemEntryTy::File { .. } => Some(name),
-                    FileSystemEntryTy::Directory => None,
-                })
-                .collect())
-        });
+        let base_path = ghost_dir(map_name.as_str(), &map_hash);
+        let task = Self::load_index(io, &base_path);
 
         Self {
             ghosts: Default::default(),
+            split: Default::default(),
+            split_ghost_pos: None,
             sound: sound.clone(),
             graphics: graphics.clone(),
             backend: backend.clone(),
@@ -123,7 +164,7 @@ impl GhostViewer {
                 id_generator,
             },
 
-            base_path: base_path.into(),
+            base_path,
             fonts,
 
             task: Some(task),
@@ -133,6 +174,22 @@ impl GhostViewer {
         }
     }
 
+    fn load_index(io: &Io, dir: &Path) -> IoRuntimeTask<GhostIndex> {
+        let fs = io.fs.clone();
+        let dir = dir.to_path_buf();
+        // ghosts that are not indexed yet are still listed,
+        // the recorder takes care of storing them in the index
+        io.rt
+            .spawn(async move { Ok(load_ghost_index(fs.as_ref(), &dir).await?.0) })
+    }
+
+    /// Loads the ghosts again, e.g. because a new run was stored.
+    ///
+    /// The current ghosts are shown until the new ones are loaded.
+    pub fn reload(&mut self) {
+        self.task = Some(Self::load_index(&self.io, &self.base_path));
+    }
+
     pub fn update(
         &mut self,
         config: &ConfigEngine,
@@ -144,20 +201,29 @@ impl GhostViewer {
         if self.task.as_ref().is_some_and(|task| task.is_finished()) {
             let task = self.task.take().unwrap();
             match task.get() {
-                Ok(ghosts) => {
-                    self.ghosts.extend(ghosts.into_iter().map(|ghost| {
-                        let demo_path = self.base_path.join(&ghost);
-                        (
-                            ghost,
-                            DemoViewer::new(
-                                &self.io,
-                                &self.tp,
-                                demo_path.as_ref(),
-                                self.fonts.clone(),
-                                None,
-                            ),
-                        )
-                    }));
+                Ok(index) => {
+                    self.ghosts.clear();
+                    self.split = Default::default();
+                    self.ghosts.extend(
+                        index
+                            .entries
+                            .into_iter()
+                            .take(config_game.cl.ghost.max_shown as usize)
+                            .map(|entry| {
+                                let demo_path =
+                                    self.base_path.join(format!("{}.twdemo", entry.file_name));
+                                LoadedGhost {
+                                    viewer: DemoViewer::new(
+                                        &self.io,
+                                        &self.tp,
+                                        demo_path.as_ref(),
+                                        self.fonts.clone(),
+                                        None,
+                                    ),
+                                    entry,
+                                }
+                            }),
+                    );
                 }
                 Err(err) => {
                     log::error!("failed to fetch ghosts: {err}")
@@ -165,8 +231,14 @@ impl GhostViewer {
             }
         }
 
-        self.ghosts.retain(|_, ghost| {
-            if let Err(err) = ghost.continue_loading(
+        let split_ghost = config_game.cl.ghost.split_ghost as usize;
+        let tint = config_game.cl.ghost.tint;
+        self.split_ghost_pos = None;
+        let mut rank = 0;
+        self.ghosts.retain_mut(|LoadedGhost { entry, viewer }| {
+            let index = rank;
+            rank += 1;
+            if let Err(err) = viewer.continue_loading(
                 &self.sound,
                 &self.graphics,
                 &self.backend,
@@ -176,10 +248,10 @@ impl GhostViewer {
                 &self.time,
                 ui_creator,
             ) {
-                log::warn!("failed to render ghost: {err}");
+                log::warn!("failed to render ghost of {}: {err}", entry.player);
                 return false;
             }
-            if let Some(demo_viewer) = ghost.try_get_mut() {
+            if let Some(demo_viewer) = viewer.try_get_mut() {
                 match demo_viewer.get_render_input_for_time(race_time) {
                     Ok(mut render_input) => {
                         let stage_id = self
@@ -200,7 +272,7 @@ impl GhostViewer {
                                 game_ticks_passed: 0,
                             });
 
-                        for (real_id, char_info) in render_input.character_infos.drain() {
+                        for (real_id, mut char_info) in render_input.character_infos.drain() {
                             let id = self
                                 .ids
                                 .next_id(|id| !input.character_infos.contains_key(&id.into()));
@@ -209,8 +281,18 @@ impl GhostViewer {
                                 .and_then(|stage_id| render_input.stages.get_mut(&stage_id))
                                 && let Some(render_char) = stage.world.characters.remove(&real_id)
                             {
+                                if index == split_ghost {
+                                    self.split_ghost_pos = Some(render_char.lerped_pos);
+                                }
                                 render_stage.world.characters.insert(id.into(), render_char);
                             }
+                            if tint {
+                                let color = GHOST_TINTS[index % GHOST_TINTS.len()];
+                                char_info.skin_info = NetworkSkinInfo::Custom {
+                                    body_color: color,
+                                    feet_color: color,
+                                };
+                            }
                             input.character_infos.insert(id.into(), char_info);
                         }
                     }
@@ -224,4 +306,24 @@ impl GhostViewer {
         });
         self.ids.swap();
     }
+
+    /// The time difference of the own run at `player_pos` to the selected ghost.
+    ///
+    /// Should be called after [`GhostViewer::update`] for the same `race_time`.
+    pub fn split(
+        &mut self,
+        config_game: &ConfigGame,
+        race_time: Duration,
+        player_pos: Option<vec2>,
+    ) -> Option<RenderGhostSplit> {
+        let split = self
+            .split
+            .update(race_time, player_pos, self.split_ghost_pos);
+        split.filter(|_| config_game.cl.ghost.show_split)
+    }
+
+    /// The metadata of all shown ghosts, best run first.
+    pub fn ghosts(&self) -> impl Iterator<Item = &GhostEntry> {
+        self.ghosts.iter().map(|ghost| &ghost.entry)
+    }
 }
@@ -0,0 +1,106 @@
+use std::time::Duration;
+
+use client_ui::hud::user_data::RenderGhostSplit;
+use math::math::{distance_squared, vector::vec2};
+
+/// Minimum time between two samples of a path.
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(20);
+/// How close (in world units) a position must be to a sample
+/// to be considered the same position.
+const MAX_MATCH_DISTANCE: f32 = 64.0;
+
+/// Sampled positions of a race run over time.
+#[derive(Debug, Default)]
+pub struct RacePath {
+    samples: Vec<(Duration, vec2)>,
+}
+
+impl RacePath {
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn last_time(&self) -> Option<Duration> {
+        self.samples.last().map(|(time, _)| *time)
+    }
+
+    pub fn push(&mut self, time: Duration, pos: vec2) {
+        if self
+            .last_time()
+            .is_none_or(|last_time| last_time + SAMPLE_INTERVAL <= time)
+        {
+            self.samples.push((time, pos));
+        }
+    }
+
+    /// The time at which the path most recently passed `pos`.
+    ///
+    /// Only the latest visit near `pos` is considered, so paths
+    /// that cross themselves still match the right part.
+    pub fn time_at(&self, pos: vec2) -> Option<Duration> {
+        let max_dist = MAX_MATCH_DISTANCE * MAX_MATCH_DISTANCE;
+        let mut best: Option<(Duration, f32)> = None;
+        for (time, sample) in self.samples.iter().rev() {
+            let dist = distance_squared(sample, &pos);
+            if dist <= max_dist {
+                if best.is_none_or(|(_, best_dist)| dist < best_dist) {
+                    best = Some((*time, dist));
+                }
+            } else if best.is_some() {
+                break;
+            }
+        }
+        best.map(|(time, _)| time)
+    }
+}
+
+/// Compares the own run against a ghost at matching positions.
+#[derive(Debug, Default)]
+pub struct GhostSplit {
+    player: RacePath,
+    ghost: RacePath,
+}
+
+impl GhostSplit {
+    /// Adds the current positions and returns the time difference,
+    /// if the positions of both runs could be matched.
+    pub fn update(
+        &mut self,
+        race_time: Duration,
+        player_pos: Option<vec2>,
+        ghost_pos: Option<vec2>,
+    ) -> Option<RenderGhostSplit> {
+        // a new run started
+        if self
+            .player
+            .last_time()
+            .or(self.ghost.last_time())
+            .is_some_and(|last_time| race_time < last_time)
+        {
+            self.player.clear();
+            self.ghost.clear();
+        }
+
+        if let Some(pos) = player_pos {
+            self.player.push(race_time, pos);
+        }
+        if let Some(pos) = ghost_pos {
+            self.ghost.push(race_time, pos);
+        }
+
+        // the ghost was already here, so the player is behind
+        if let Some(time) = player_pos.and_then(|pos| self.ghost.time_at(pos)) {
+            return Some(RenderGhostSplit {
+                diff: race_time.saturating_sub(time),
+                ahead: false,
+            });
+        }
+        // the player was already where the ghost is now
+        ghost_pos
+            .and_then(|pos| self.player.time_at(pos))
+            .map(|time| RenderGhostSplit {
+                diff: race_time.saturating_sub(time),
+                ahead: true,
+            })
+    }
+}
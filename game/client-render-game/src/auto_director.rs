@@ -0,0 +1,492 @@
+use std::time::Duration;
+
+use camera::Camera;
+use game_interface::{
+    events::{
+        GameCharacterEffectEvent, GameCharacterEventEffect, GameEvents, GameWorldAction,
+        GameWorldEntityEffectEvent, GameWorldEvent, GameWorldNotificationEvent,
+    },
+    types::{
+        game::GameTickType,
+        id_types::{CharacterId, StageId},
+        render::stage::StageRenderInfo,
+    },
+};
+use math::math::{distance, length, vector::vec2};
+use pool::datatypes::{PoolBTreeMap, PoolFxLinkedHashMap};
+
+/// Characters closer than this (in tiles) are considered to be in view
+/// of each other.
+const VIEW_RADIUS: f32 = 16.0;
+/// Characters closer than this (in tiles) are considered to be in
+/// a fight or a close race.
+const CLOSE_RADIUS: f32 = 4.0;
+/// Space (in tiles) that is kept around the framed characters.
+const VIEW_MARGIN: f32 = 6.0;
+const MAX_ZOOM: f32 = 1.75;
+
+/// Minimum time a target is shown before the camera switches
+/// to another target.
+const MIN_SHOT_DURATION: Duration = Duration::from_secs(3);
+/// How much better (relatively) another target must be,
+/// so that the camera switches to it.
+const SWITCH_FACTOR: f32 = 1.3;
+/// Time in seconds after which the weight of an action spot
+/// dropped to about a third.
+const ACTION_DECAY_SECS: f32 = 2.0;
+
+/// A spot where something interesting happened recently.
+#[derive(Debug, Clone, Copy)]
+struct ActionSpot {
+    stage_id: StageId,
+    pos: vec2,
+    weight: f32,
+}
+
+/// The camera that the [`AutoDirector`] decided on.
+#[derive(Debug, Clone, Copy)]
+pub struct AutoDirectorCamera {
+    pub pos: vec2,
+    pub zoom: f32,
+}
+
+/// Controls the camera automatically, e.g. for spectators
+/// of tournaments or when rendering demos.
+///
+/// It scores every character by the action around it and
+/// follows the best one. To prevent a restless camera it only
+/// switches to another character if that one is clearly better,
+/// and eases between the targets.
+#[derive(Debug, Default)]
+pub struct AutoDirector {
+    actions: Vec<ActionSpot>,
+    last_event_tick: Option<GameTickType>,
+
+    target: Option<CharacterId>,
+    last_switch: Duration,
+
+    camera: Option<AutoDirectorCamera>,
+    last_update: Option<Duration>,
+}
+
+impl AutoDirector {
+    /// Forgets the current target & all recent actions,
+    /// e.g. after seeking in a demo.
+    pub fn reset(&mut self) {
+        *self = Default::default();
+    }
+
+    fn add_events(
+        &mut self,
+        events: &PoolBTreeMap<(GameTickType, bool), GameEvents>,
+        stages: &PoolFxLinkedHashMap<StageId, StageRenderInfo>,
+    ) {
+        // predicted events would be counted twice
+        let last_event_tick = self.last_event_tick;
+        for ((tick, _), events) in events.iter().filter(|&(&(tick, is_prediction), _)| {
+            !is_prediction && last_event_tick.is_none_or(|last| tick > last)
+        }) {
+            self.last_event_tick = Some(*tick);
+            for (stage_id, world) in events.worlds.iter() {
+                let character_pos = |id: &CharacterId| {
+                    stages
+                        .get(stage_id)
+                        .and_then(|stage| stage.world.characters.get(id))
+                        .map(|c| c.lerped_pos)
+                };
+                for ev in world.events.values() {
+                    let (pos, weight) = match ev {
+                        GameWorldEvent::Effect(ev) => (
+                            Some(ev.pos),
+                            match ev.ev {
+                                GameWorldEntityEffectEvent::Character(
+                                    GameCharacterEffectEvent::Effect(
+                                        GameCharacterEventEffect::DamageIndicator { .. }
+                                        | GameCharacterEventEffect::HammerHit,
+                                    ),
+                                ) => 1.0,
+                                GameWorldEntityEffectEvent::Grenade(_)
+                                | GameWorldEntityEffectEvent::Laser(_)
+                                | GameWorldEntityEffectEvent::Shotgun(_) => 0.5,
+                                _ => 0.0,
+                            },
+                        ),
+                        GameWorldEvent::Notification(GameWorldNotificationEvent::Action(
+                            GameWorldAction::Kill {
+                                killer, victims, ..
+                            },
+                        )) => (
+                            killer.as_ref().or(victims.first()).and_then(character_pos),
+                            4.0,
+                        ),
+                        GameWorldEvent::Sound(_) | GameWorldEvent::Notification(_) => (None, 0.0),
+                    };
+                    if let Some(pos) = pos.filter(|_| weight > 0.0) {
+                        self.actions.push(ActionSpot {
+                            stage_id: *stage_id,
+                            pos,
+                            weight,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    /// The score of a character, higher means more interesting.
+    fn score(
+        &self,
+        stage_id: &StageId,
+        stage: &StageRenderInfo,
+        character_id: &CharacterId,
+        pos: &vec2,
+    ) -> f32 {
+        let mut score = 1.0;
+
+        // flag carriers are always worth a look
+        if stage
+            .world
+            .ctf_flags
+            .values()
+            .any(|flag| flag.owner_id == Some(*character_id))
+        {
+            score += 4.0;
+        }
+
+        // many players in view, fights & close races
+        for (_, other) in stage
+            .world
+            .characters
+            .iter()
+            .filter(|&(id, _)| id != character_id)
+        {
+            let dist = distance(pos, &other.lerped_pos);
+            if dist < CLOSE_RADIUS {
+                score += 2.0;
+            } else if dist < VIEW_RADIUS {
+                score += 1.0;
+            }
+        }
+
+        score += self
+            .actions
+            .iter()
+            .filter(|action| {
+                action.stage_id == *stage_id && distance(pos, &action.pos) < VIEW_RADIUS
+            })
+            .map(|action| action.weight)
+            .sum::<f32>();
+
+        score
+    }
+
+    /// Calculates the camera for the current frame,
+    /// `aspect` is the aspect ratio the game is rendered with.
+    ///
+    /// Returns `None` if there is nothing to look at.
+    pub fn update(
+        &mut self,
+        cur_time: &Duration,
+        aspect: f32,
+        events: &PoolBTreeMap<(GameTickType, bool), GameEvents>,
+        stages: &PoolFxLinkedHashMap<StageId, StageRenderInfo>,
+    ) -> Option<AutoDirectorCamera> {
+        // time went backwards (e.g. demo seeking)
+        if self.last_update.is_some_and(|last| *cur_time < last) {
+            self.reset();
+        }
+        let delta = self
+            .last_update
+            .map(|last| cur_time.saturating_sub(last).as_secs_f32().min(0.25))
+            .unwrap_or_default();
+        self.last_update = Some(*cur_time);
+
+        let decay = (-delta / ACTION_DECAY_SECS).exp();
+        self.actions.retain_mut(|action| {
+            action.weight *= decay;
+            action.weight > 0.05
+        });
+        self.add_events(events, stages);
+
+        let mut best: Option<(CharacterId, StageId, f32)> = None;
+        let mut target_score = None;
+        for (stage_id, stage) in stages.iter() {
+            for (id, character) in stage.world.characters.iter() {
+                let score = self.score(stage_id, stage, id, &character.lerped_pos)
+                    // a bit of motion is better than standing around
+                    + (length(&character.lerped_vel) / 20.0).min(1.0);
+                if self.target == Some(*id) {
+                    target_score = Some((*stage_id, score));
+                }
+                if best.is_none_or(|(_, _, best_score)| score > best_score) {
+                    best = Some((*id, *stage_id, score));
+                }
+            }
+        }
+        let (best_id, best_stage_id, best_score) = best?;
+
+        // hysteresis, don't switch for small differences
+        let stage_id = match target_score {
+            Some((stage_id, score))
+                if best_score <= score * SWITCH_FACTOR
+                    || cur_time.saturating_sub(self.last_switch) < MIN_SHOT_DURATION =>
+            {
+                stage_id
+            }
+            _ => {
+                self.target = Some(best_id);
+                self.last_switch = *cur_time;
+                best_stage_id
+            }
+        };
+
+        // frame the target together with everyone close to it
+        let stage = stages.get(&stage_id)?;
+        let target_pos = stage
+            .world
+            .characters
+            .get(&self.target?)
+            .map(|c| c.lerped_pos)?;
+        let (min, max) = stage
+            .world
+            .characters
+            .values()
+            .map(|c| c.lerped_pos)
+            .filter(|pos| distance(pos, &target_pos) < VIEW_RADIUS)
+            .fold((target_pos, target_pos), |(min, max), pos| {
+                (
+                    vec2::new(min.x.min(pos.x), min.y.min(pos.y)),
+                    vec2::new(max.x.max(pos.x), max.y.max(pos.y)),
+                )
+            });
+        let pos = (min + max) / 2.0;
+        let size = max - min + vec2::new(VIEW_MARGIN, VIEW_MARGIN) * 2.0;
+        // the visible area of a camera with a zoom of `1.0`
+        let (mut view_width, mut view_height) = (0.0, 0.0);
+        Camera::calc_canvas_params(aspect, 1.0, &mut view_width, &mut view_height);
+        let zoom = (size.x / view_width)
+            .max(size.y / view_height)
+            .clamp(1.0, MAX_ZOOM);
+
+        let camera = match self.camera {
+            Some(camera) => {
+                let pos_factor = 1.0 - (-delta * 3.0).exp();
+                let zoom_factor = 1.0 - (-delta * 1.5).exp();
+                AutoDirectorCamera {
+                    pos: camera.pos + (pos - camera.pos) * pos_factor,
+                    zoom: camera.zoom + (zoom - camera.zoom) * zoom_factor,
+                }
+            }
+            None => AutoDirectorCamera { pos, zoom },
+        };
+        self.camera = Some(camera);
+        Some(camera)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use game_interface::types::{
+        id_gen::IdGenerator,
+        id_types::{CharacterId, StageId},
+        render::{
+            character::{CharacterRenderInfo, TeeEye},
+            game::GameRenderInfo,
+            stage::StageRenderInfo,
+            world::WorldRenderInfo,
+        },
+        weapons::WeaponType,
+    };
+    use math::math::vector::vec2;
+    use pool::datatypes::{PoolBTreeMap, PoolFxLinkedHashMap};
+
+    use super::{AutoDirector, MIN_SHOT_DURATION};
+
+    const ASPECT: f32 = 16.0 / 9.0;
+
+    fn character(pos: vec2) -> CharacterRenderInfo {
+        CharacterRenderInfo {
+            lerped_pos: pos,
+            lerped_vel: Default::default(),
+            lerped_hook: None,
+            hook_collision: None,
+            has_air_jump: false,
+            lerped_cursor_pos: Default::default(),
+            lerped_dyn_cam_offset: Default::default(),
+            move_dir: 0,
+            cur_weapon: WeaponType::Hammer,
+            recoil_ticks_passed: None,
+            left_eye: TeeEye::Normal,
+            right_eye: TeeEye::Normal,
+            buffs: PoolFxLinkedHashMap::new_without_pool(),
+            debuffs: PoolFxLinkedHashMap::new_without_pool(),
+            animation_ticks_passed: 0,
+            game_ticks_passed: 0,
+            emoticon: None,
+            phased: false,
+        }
+    }
+
+    fn stages(
+        stage_id: StageId,
+        characters: &[(CharacterId, vec2)],
+    ) -> PoolFxLinkedHashMap<StageId, StageRenderInfo> {
+        let mut world = WorldRenderInfo {
+            projectiles: PoolFxLinkedHashMap::new_without_pool(),
+            ctf_flags: PoolFxLinkedHashMap::new_without_pool(),
+            lasers: PoolFxLinkedHashMap::new_without_pool(),
+            pickups: PoolFxLinkedHashMap::new_without_pool(),
+            characters: PoolFxLinkedHashMap::new_without_pool(),
+        };
+        for (id, pos) in characters {
+            world.characters.insert(*id, character(*pos));
+        }
+        let mut stages = PoolFxLinkedHashMap::new_without_pool();
+        stages.insert(
+            stage_id,
+            StageRenderInfo {
+                world,
+                game: GameRenderInfo::Race {},
+                game_ticks_passed: 0,
+            },
+        );
+        stages
+    }
+
+    #[test]
+    fn scoring() {
+        let ids = IdGenerator::new();
+        let stage_id: StageId = ids.next_id();
+        let (a, b, c, d): (CharacterId, CharacterId, CharacterId, CharacterId) =
+            (ids.next_id(), ids.next_id(), ids.next_id(), ids.next_id());
+        let stages = stages(
+            stage_id,
+            &[
+                (a, vec2::new(0.0, 0.0)),
+                (b, vec2::new(100.0, 100.0)),
+                (c, vec2::new(102.0, 100.0)),
+                (d, vec2::new(110.0, 100.0)),
+            ],
+        );
+        let director = AutoDirector::default();
+        let stage = stages.get(&stage_id).unwrap();
+        let score = |id: &CharacterId| {
+            let pos = stage.world.characters.get(id).unwrap().lerped_pos;
+            director.score(&stage_id, stage, id, &pos)
+        };
+        // alone
+        assert_eq!(score(&a), 1.0);
+        // one close & one in view
+        assert_eq!(score(&b), 4.0);
+        assert_eq!(score(&c), 4.0);
+        // two in view
+        assert_eq!(score(&d), 3.0);
+
+        // the camera frames the best character & everyone around it
+        let mut director = AutoDirector::default();
+        let camera = director
+            .update(
+                &Duration::ZERO,
+                ASPECT,
+                &PoolBTreeMap::new_without_pool(),
+                &stages,
+            )
+            .unwrap();
+        assert_eq!(director.target, Some(b));
+        assert_eq!(camera.pos, vec2::new(105.0, 100.0));
+        assert!(camera.zoom >= 1.0);
+
+        // nothing to look at
+        let mut director = AutoDirector::default();
+        assert!(
+            director
+                .update(
+                    &Duration::ZERO,
+                    ASPECT,
+                    &PoolBTreeMap::new_without_pool(),
+                    &PoolFxLinkedHashMap::new_without_pool(),
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn hysteresis() {
+        let ids = IdGenerator::new();
+        let stage_id: StageId = ids.next_id();
+        let (a, b, c, d): (CharacterId, CharacterId, CharacterId, CharacterId) =
+            (ids.next_id(), ids.next_id(), ids.next_id(), ids.next_id());
+        let events = PoolBTreeMap::new_without_pool();
+        let mut director = AutoDirector::default();
+        let mut update = |secs: f32, characters: &[(CharacterId, vec2)]| {
+            director.update(
+                &Duration::from_secs_f32(secs),
+                ASPECT,
+                &events,
+                &stages(stage_id, characters),
+            );
+            director.target
+        };
+
+        let a_alone = (a, vec2::new(0.0, 0.0));
+        let b_alone = (b, vec2::new(100.0, 0.0));
+        let c_close_to_b = (c, vec2::new(102.0, 0.0));
+        let d_close_to_a = (d, vec2::new(2.0, 0.0));
+
+        assert_eq!(update(0.0, &[a_alone, b_alone]), Some(a));
+        // b is clearly better, but a was not shown long enough
+        let secs = MIN_SHOT_DURATION.as_secs_f32();
+        assert_eq!(
+            update(secs - 1.0, &[a_alone, b_alone, c_close_to_b]),
+            Some(a)
+        );
+        assert_eq!(
+            update(secs + 1.0, &[a_alone, b_alone, c_close_to_b]),
+            Some(b)
+        );
+        // a is as good as b again, which is not enough to switch back
+        assert_eq!(
+            update(secs * 3.0, &[a_alone, b_alone, c_close_to_b, d_close_to_a]),
+            Some(b)
+        );
+    }
+
+    #[test]
+    fn reset_when_time_goes_backwards() {
+        let ids = IdGenerator::new();
+        let stage_id: StageId = ids.next_id();
+        let (a, b, c): (CharacterId, CharacterId, CharacterId) =
+            (ids.next_id(), ids.next_id(), ids.next_id());
+        let events = PoolBTreeMap::new_without_pool();
+        let mut director = AutoDirector::default();
+
+        let b_with_c = stages(
+            stage_id,
+            &[
+                (a, vec2::new(0.0, 0.0)),
+                (b, vec2::new(100.0, 0.0)),
+                (c, vec2::new(102.0, 0.0)),
+            ],
+        );
+        director.update(&Duration::from_secs(10), ASPECT, &events, &b_with_c);
+        assert_eq!(director.target, Some(b));
+
+        // a is clearly better, without the reset the camera would keep the
+        // shot of b & ease towards the new position
+        let a_with_c = stages(
+            stage_id,
+            &[
+                (a, vec2::new(0.0, 0.0)),
+                (b, vec2::new(100.0, 0.0)),
+                (c, vec2::new(2.0, 0.0)),
+            ],
+        );
+        let camera = director
+            .update(&Duration::from_secs(5), ASPECT, &events, &a_with_c)
+            .unwrap();
+        assert_eq!(director.target, Some(a));
+        assert_eq!(camera.pos, vec2::new(1.0, 0.0));
+    }
+}
@@ -10,7 +10,10 @@ use client_containers::{
 };
 use client_render::hud::page::{HudRender, HudRenderPipe};
 use client_render_base::render::tee::RenderTee;
-use client_ui::hud::user_data::RenderDateTime;
+use client_ui::hud::{
+    layout::{HEALTH_AMMO_SIZE, HudLayout},
+    user_data::{HudEvent, RenderDateTime, RenderGhostSplit, RenderSpectatorInfo},
+};
 use game_interface::types::{
     emoticons::{EnumCount, IntoEnumIterator},
     game::{GameTickType, NonZeroGameTickType},
@@ -55,6 +58,11 @@ pub struct RenderHudPipe<'a> {
     pub ctf_container: &'a mut CtfContainer,
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
     pub date_time: &'a Option<RenderDateTime>,
+    pub ghost_split: &'a Option<RenderGhostSplit>,
+    pub spectator_info: &'a Option<RenderSpectatorInfo>,
+    pub layout: &'a HudLayout,
+    /// If some, the HUD editor is shown & receives this input.
+    pub editor_input: &'a mut Option<egui::RawInput>,
 }
 
 pub struct RenderOffsetsVanilla {
@@ -795,8 +803,8 @@ impl RenderHud {
         }
     }
 
-    pub fn render(&mut self, pipe: &mut RenderHudPipe) {
-        self.ui.render(&mut HudRenderPipe {
+    pub fn render(&mut self, pipe: &mut RenderHudPipe) -> Vec<HudEvent> {
+        let events = self.ui.render(&mut HudRenderPipe {
             cur_time: pipe.cur_time,
             race_timer_counter: pipe.race_timer_counter,
             ticks_per_second: pipe.ticks_per_second,
@@ -806,8 +814,17 @@ impl RenderHud {
             ctf_container: pipe.ctf_container,
             character_infos: pipe.character_infos,
             date_time: pipe.date_time,
+            ghost_split: pipe.ghost_split,
+            spectator_info: pipe.spectator_info,
+            layout: pipe.layout,
+            editor_input: pipe.editor_input,
         });
 
+        let layout = &pipe.layout.health_ammo;
+        if !layout.visible {
+            return events;
+        }
+
         let hud = pipe.hud_container.get_or_default_opt(pipe.hud_key);
         let weapon = pipe.weapon_container.get_or_default_opt(pipe.weapon_key);
         let mut state = State::default();
@@ -817,12 +834,22 @@ impl RenderHud {
             .zoom_level
             .get()
             .unwrap_or(self.canvas_handle.pixels_per_point());
-        state.map_canvas(
-            0.0,
-            0.0,
+        let (width, height) = (
             self.canvas_handle.canvas_width() as f32 / ppp,
             self.canvas_handle.canvas_height() as f32 / ppp,
         );
+        // move & scale the canvas, so the quads end up inside the layout's rect
+        let rect = layout.rect(
+            egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(width, height)),
+            HEALTH_AMMO_SIZE,
+        );
+        let scale = layout.scale;
+        state.map_canvas(
+            -rect.min.x / scale,
+            -rect.min.y / scale,
+            (width - rect.min.x) / scale,
+            (height - rect.min.y) / scale,
+        );
 
         match pipe.local_player_render_info {
             LocalCharacterRenderInfo::Vanilla(info) => {
@@ -835,5 +862,7 @@ impl RenderHud {
                 // nothing to do
             }
         }
+
+        events
     }
 }
@@ -1,4 +1,5 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod auto_director;
 pub mod components;
 pub mod render_game;
@@ -47,7 +47,10 @@ use client_types::{
 use client_ui::{
     chat::user_data::{ChatEvent, ChatMode, MsgInChat},
     emote_wheel::user_data::EmoteWheelEvent,
-    hud::user_data::RenderDateTime,
+    hud::{
+        layout::HudLayout,
+        user_data::{HudEvent, RenderDateTime, RenderGhostSplit, RenderSpectatorInfo},
+    },
     spectator_selection::user_data::SpectatorSelectionEvent,
     thumbnail_container::{
         DEFAULT_THUMBNAIL_CONTAINER_PATH, ThumbnailContainer, load_thumbnail_container,
@@ -62,7 +65,8 @@ use game_base::network::{
     types::chat::NetChatMsg,
 };
 use game_config::config::{
-    ConfigDummyScreenAnchor, ConfigGame, ConfigMap, ConfigRender, ConfigSoundRender,
+    ConfigDummyScreenAnchor, ConfigGame, ConfigHudLayout, ConfigMap, ConfigRender,
+    ConfigSoundRender,
 };
 use game_interface::{
     chat_commands::ChatCommands,
@@ -84,7 +88,7 @@ use game_interface::{
         id_types::{CharacterId, PlayerId, StageId},
         player_info::{PlayerBanReason, PlayerDropReason, PlayerKickReason},
         render::{
-            character::{CharacterBuff, CharacterInfo, LocalCharacterRenderInfo},
+            character::{CharacterBuff, CharacterInfo, LocalCharacterRenderInfo, PlayerIngameMode},
             game::game_match::MatchSide,
             scoreboard::Scoreboard,
             stage::StageRenderInfo,
@@ -120,6 +124,7 @@ pub enum PlayerFeedbackEvent {
     Chat(ChatEvent),
     EmoteWheel(EmoteWheelEvent),
     SpectatorSelection(SpectatorSelectionEvent),
+    Hud(HudEvent),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -241,8 +246,13 @@ pub struct RenderForPlayer {
     pub chat_info: Option<(ChatMode, String, Option<egui::RawInput>)>,
     pub emote_wheel_input: Option<EmoteWheelInput>,
     pub spectator_selection_input: Option<SpectatorSelectionInput>,
+    /// If some, the HUD editor is shown & receives this input.
+    pub hud_editor_input: Option<egui::RawInput>,
     pub local_player_info: LocalCharacterRenderInfo,
     pub chat_show_all: bool,
+    /// Results of the chat log search, if the search is active.
+    /// `None` if chat logs are disabled.
+    pub chat_log_search: Option<Vec<String>>,
     pub scoreboard_active: bool,
 
     pub zoom: f32,
@@ -348,6 +358,8 @@ pub struct RenderGameSettings {
     /// Whether to enable dynamic camera while spectating another
     /// character.
     pub spec_dyncam: bool,
+    /// Position, scale & visibility of the HUD elements.
+    pub hud_layout: HudLayout,
 }
 
 impl RenderGameSettings {
@@ -358,6 +370,7 @@ impl RenderGameSettings {
         sound_playback_speed: f64,
         anti_ping: bool,
         global_volume: f64,
+        hud_layout: &ConfigHudLayout,
     ) -> Self {
         Self {
             spatial_sound: snd.spatial,
@@ -380,10 +393,18 @@ impl RenderGameSettings {
                 .use_ingame_aspect_ratio
                 .then_some(render.ingame_aspect_ratio as f32),
             spec_dyncam: render.spec_dyncam,
+            hud_layout: hud_layout.into(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderChatMsg {
+    pub msg: NetChatMsg,
+    /// The message mentions a local player or a highlight word.
+    pub highlighted: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RenderGameInput {
     pub players: PoolFxLinkedHashMap<PlayerId, RenderGameForPlayer>,
@@ -395,7 +416,7 @@ pub struct RenderGameInput {
     /// The bool indicates if the events were generated on the client (`true`) or
     /// from the server.
     pub events: PoolBTreeMap<(GameTickType, bool), GameEvents>,
-    pub chat_msgs: PoolVecDeque<NetChatMsg>,
+    pub chat_msgs: PoolVecDeque<RenderChatMsg>,
     /// Vote state
     pub vote: Option<(PoolRc<VoteState>, Option<Voted>, Duration)>,
 
@@ -404,6 +425,8 @@ pub struct RenderGameInput {
     pub scoreboard_info: Option<Scoreboard>,
 
     pub date_time: Option<RenderDateTime>,
+    /// Time difference to the selected ghost, if any.
+    pub ghost_split: Option<RenderGhostSplit>,
 
     pub game_time_info: GameTimeInfo,
 
@@ -889,6 +912,7 @@ impl RenderGame {
                         tee_render: &mut self.players.tee_renderer,
                         character_infos: &render_info.character_infos,
                         local_character_ids: local_player_ids,
+                        log_search: player_render_info.chat_log_search.as_deref(),
                     })
                     .into_iter()
                     .map(PlayerFeedbackEvent::Chat),
@@ -974,6 +998,7 @@ impl RenderGame {
 
         // hud + scoreboard
         if let Some((player_id, render_for_game)) = player_info {
+            let mut hud_editor_input = render_for_game.render_for_player.hud_editor_input.take();
             let local_render_info = &render_for_game.render_for_player;
 
             let cam_player_id = match &render_for_game.render_for_player.cam_mode {
@@ -1001,7 +1026,16 @@ impl RenderGame {
                     }
                 });
             let p = stage.and_then(|s| s.world.characters.get(cam_player_id));
-            self.hud.render(&mut RenderHudPipe {
+            let is_spectator = render_info
+                .character_infos
+                .get(player_id)
+                .and_then(|c| c.player_info.as_ref())
+                .is_some_and(|p| matches!(p.ingame_mode, PlayerIngameMode::Spectator));
+            let spectator_info =
+                (is_spectator || cam_player_id != player_id).then(|| RenderSpectatorInfo {
+                    spectated: (cam_player_id != player_id).then_some(*cam_player_id),
+                });
+            let hud_evs = self.hud.render(&mut RenderHudPipe {
                 hud_container: &mut self.containers.hud_container,
                 hud_key: character_info.map(|c| c.info.hud.borrow()),
                 weapon_container: &mut self.containers.weapon_container,
@@ -1021,7 +1055,12 @@ impl RenderGame {
                 ctf_container: &mut self.containers.ctf_container,
                 character_infos: &render_info.character_infos,
                 date_time: &render_info.date_time,
+                ghost_split: &render_info.ghost_split,
+                spectator_info: &spectator_info,
+                layout: &render_info.settings.hud_layout,
+                editor_input: &mut hud_editor_input,
             });
+            res.extend(hud_evs.into_iter().map(PlayerFeedbackEvent::Hud));
             if let Some(scoreboard_info) = local_render_info
                 .scoreboard_active
                 .then_some(())
@@ -2249,6 +2288,7 @@ impl RenderGame {
                                                 end_skin,
                                             }),
                                             add_time: *cur_time,
+                                            highlighted: false,
                                         })
                                     }
                                     GameWorldNotificationEvent::Action(ev) => {
@@ -2296,8 +2336,9 @@ impl RenderGame {
 
     fn handle_chat_msgs(&mut self, cur_time: &Duration, game: &mut RenderGameInput) {
         let it = game.chat_msgs.drain(..).map(|msg| MsgInChat {
-            msg: ServerMsg::Chat(Self::from_net_msg(&game.character_infos, msg)),
+            msg: ServerMsg::Chat(Self::from_net_msg(&game.character_infos, msg.msg)),
             add_time: *cur_time,
+            highlighted: msg.highlighted,
         });
         for msg in it {
             // push_front is intentionally used over extend or similar, so msgs are
@@ -2415,8 +2456,10 @@ impl RenderGame {
                                 chat_info: None,
                                 emote_wheel_input: None,
                                 spectator_selection_input: None,
+                                hud_editor_input: None,
                                 local_player_info,
                                 chat_show_all: false,
+                                chat_log_search: None,
                                 scoreboard_active: false,
 
                                 zoom: 1.0,
@@ -2460,8 +2503,10 @@ impl RenderGame {
                                     chat_info: None,
                                     emote_wheel_input: None,
                                     spectator_selection_input: None,
+                                    hud_editor_input: None,
                                     local_player_info: LocalCharacterRenderInfo::Unavailable,
                                     chat_show_all: false,
+                                    chat_log_search: None,
                                     scoreboard_active: false,
 
                                     zoom: 0.5,
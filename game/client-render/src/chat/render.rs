@@ -43,6 +43,7 @@ pub struct ChatRenderPipe<'a> {
     pub msg: &'a mut String,
     pub input: &'a mut Option<egui::RawInput>,
     pub mode: ChatMode,
+    pub log_search: Option<&'a [String]>,
     pub skin_container: &'a mut SkinContainer,
     pub tee_render: &'a RenderTee,
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
@@ -117,6 +118,7 @@ impl ChatRender {
             skin_container: pipe.skin_container,
             render_tee: pipe.tee_render,
             mode: pipe.mode,
+            log_search: pipe.log_search,
             character_infos: pipe.character_infos,
             local_character_ids: pipe.local_character_ids,
             find_player_prompt: &mut self.find_player_prompt,
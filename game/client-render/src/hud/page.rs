@@ -4,10 +4,11 @@ use base::linked_hash_map_view::FxLinkedHashMap;
 use client_containers::{ctf::CtfContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
 use client_ui::hud::{
+    layout::HudLayout,
     page::HudUi,
-    user_data::{RenderDateTime, UserData},
+    user_data::{HudEvent, RenderDateTime, RenderGhostSplit, RenderSpectatorInfo, UserData},
 };
-use egui::Color32;
+use egui::{Color32, RawInput};
 use game_interface::types::{
     game::{GameTickType, NonZeroGameTickType},
     id_types::CharacterId,
@@ -36,6 +37,11 @@ pub struct HudRenderPipe<'a> {
     pub ctf_container: &'a mut CtfContainer,
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
     pub date_time: &'a Option<RenderDateTime>,
+    pub ghost_split: &'a Option<RenderGhostSplit>,
+    pub spectator_info: &'a Option<RenderSpectatorInfo>,
+    pub layout: &'a HudLayout,
+    /// If some, the HUD editor is shown & receives this input.
+    pub editor_input: &'a mut Option<RawInput>,
 }
 
 pub struct HudRender {
@@ -63,7 +69,8 @@ impl HudRender {
         }
     }
 
-    pub fn render(&mut self, pipe: &mut HudRenderPipe) {
+    pub fn render(&mut self, pipe: &mut HudRenderPipe) -> Vec<HudEvent> {
+        let mut events = Vec::new();
         let mut user_data = UserData {
             race_round_timer_counter: pipe.race_timer_counter,
             ticks_per_second: pipe.ticks_per_second,
@@ -75,6 +82,11 @@ impl HudRender {
             canvas_handle: &self.canvas_handle,
             stream_handle: &self.stream_handle,
             date_time: pipe.date_time,
+            ghost_split: pipe.ghost_split,
+            spectator_info: pipe.spectator_info,
+            layout: pipe.layout,
+            editing: pipe.editor_input.is_some(),
+            events: &mut events,
         };
         let mut dummy_pipe = UiRenderPipe::new(*pipe.cur_time, &mut user_data);
 
@@ -86,7 +98,9 @@ impl HudRender {
             &mut self.ui,
             &mut self.hud_ui,
             &mut dummy_pipe,
-            Default::default(),
+            pipe.editor_input.take().unwrap_or_default(),
         );
+
+        events
     }
 }
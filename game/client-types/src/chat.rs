@@ -30,3 +30,35 @@ pub enum ServerMsg {
     Chat(ChatMsg),
     System(MsgSystem),
 }
+
+/// Whether the message mentions the word, ignoring case.
+///
+/// The word has to stand on its own, so `tee` is
+/// not mentioned in `teeworlds`.
+pub fn mentions(msg: &str, word: &str) -> bool {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return false;
+    }
+    let msg = msg.to_lowercase();
+    let is_word_char = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    msg.match_indices(&word).any(|(index, _)| {
+        !is_word_char(msg[..index].chars().next_back())
+            && !is_word_char(msg[index + word.len()..].chars().next())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::mentions;
+
+    #[test]
+    fn mention_words() {
+        assert!(mentions("hi Tee, how are you?", "tee"));
+        assert!(mentions("tee", "Tee"));
+        assert!(!mentions("teeworlds is fun", "tee"));
+        assert!(!mentions("my_tee", "tee"));
+        assert!(mentions("@nameless tee: gg", "nameless tee"));
+        assert!(!mentions("anything", ""));
+    }
+}
@@ -73,13 +73,17 @@ impl Debug for ConsoleEntryCmd {
 pub enum ConsoleEntry {
     Var(ConsoleEntryVariable),
     Cmd(ConsoleEntryCmd),
+    /// Like [`ConsoleEntry::Cmd`], but the string returned by the
+    /// command are commands, which the console executes
+    /// afterwards (e.g. aliases or conditionals).
+    Macro(ConsoleEntryCmd),
 }
 
 impl ConsoleEntry {
     pub fn args(&self) -> &Vec<CommandArg> {
         match self {
             ConsoleEntry::Var(cmd) => &cmd.args,
-            ConsoleEntry::Cmd(cmd) => &cmd.args,
+            ConsoleEntry::Cmd(cmd) | ConsoleEntry::Macro(cmd) => &cmd.args,
         }
     }
 }
@@ -94,7 +98,7 @@ pub fn entries_to_parser(
                 entry.full_name.clone().try_into().unwrap(),
                 entry.args.clone(),
             ),
-            ConsoleEntry::Cmd(entry) => {
+            ConsoleEntry::Cmd(entry) | ConsoleEntry::Macro(entry) => {
                 (entry.name.clone().try_into().unwrap(), entry.args.clone())
             }
         })
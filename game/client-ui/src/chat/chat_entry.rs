@@ -22,8 +22,12 @@ pub fn render(
     pipe: &mut UiRenderPipe<UserData>,
     ui_state: &mut UiState,
     msg: &ChatMsg,
+    highlighted: bool,
 ) {
     let (stroke, to) = match &msg.channel {
+        NetChatMsgPlayerChannel::Global if highlighted => {
+            (Stroke::new(2.0, Color32::from_rgb(255, 200, 60)), None)
+        }
         NetChatMsgPlayerChannel::Global => (Stroke::NONE, None),
         NetChatMsgPlayerChannel::GameTeam => (Stroke::new(2.0, Color32::LIGHT_GREEN), None),
         NetChatMsgPlayerChannel::Whisper(to) => (Stroke::new(2.0, Color32::RED), Some(to)),
@@ -38,7 +42,11 @@ pub fn render(
             ui.with_layout(Layout::bottom_up(egui::Align::Min), |ui| {
                 ui.add_space(2.0);
                 let text_format = egui::TextFormat {
-                    color: Color32::WHITE,
+                    color: if highlighted {
+                        Color32::from_rgb(255, 220, 120)
+                    } else {
+                        Color32::WHITE
+                    },
                     ..Default::default()
                 };
                 let job = LayoutJob::single_section(msg.msg.clone(), text_format);
@@ -6,7 +6,7 @@ use egui::Layout;
 use tracing::instrument;
 use ui_base::types::{UiRenderPipe, UiState};
 
-use super::user_data::UserData;
+use super::user_data::{ChatMode, UserData};
 
 /// frame for the chat entries
 #[instrument(level = "trace", skip_all)]
@@ -15,6 +15,11 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         // active input comes first (most bottom)
         super::input::render(ui, ui_state, pipe);
 
+        if pipe.user_data.is_input_active && matches!(pipe.user_data.mode, ChatMode::Search) {
+            super::log_search::render(ui, pipe);
+            return;
+        }
+
         for msg in pipe.user_data.entries.iter() {
             let time_diff = if pipe.user_data.show_chat_history {
                 Duration::ZERO
@@ -34,8 +39,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                 };
                 ui.set_opacity(chat_fade);
                 match &msg.msg {
-                    ServerMsg::Chat(msg) => {
-                        super::chat_entry::render(ui, pipe, ui_state, msg);
+                    ServerMsg::Chat(chat_msg) => {
+                        super::chat_entry::render(ui, pipe, ui_state, chat_msg, msg.highlighted);
                     }
                     ServerMsg::System(msg) => {
                         super::system_entry::render(ui, pipe, ui_state, msg);
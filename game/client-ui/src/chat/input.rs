@@ -51,6 +51,7 @@ fn render_inner(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPi
                 let (mode_name, to) = match pipe.user_data.mode {
                     ChatMode::Global => ("All", None),
                     ChatMode::Team => ("Team", None),
+                    ChatMode::Search => ("Search", None),
                     ChatMode::Whisper(player_id) => ("To", {
                         player_id
                             .and_then(|player_id| {
@@ -103,7 +104,10 @@ fn render_inner(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPi
                             pipe.user_data.chat_events.push(ChatEvent::ChatClosed);
                         }
                         if (matches!(pipe.user_data.mode, ChatMode::Whisper(Some(_)))
-                            || !matches!(pipe.user_data.mode, ChatMode::Whisper(_)))
+                            || !matches!(
+                                pipe.user_data.mode,
+                                ChatMode::Whisper(_) | ChatMode::Search
+                            ))
                             && !pipe.user_data.msg.is_empty()
                             && !is_escape
                         {
@@ -0,0 +1,43 @@
+use egui::{Color32, Stroke};
+use tracing::instrument;
+use ui_base::types::UiRenderPipe;
+
+use super::{
+    shared::{MARGIN, entry_frame},
+    user_data::UserData,
+};
+
+/// Lines of the chat log that match the search, newest at the bottom.
+#[instrument(level = "trace", skip_all)]
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    let Some(lines) = pipe.user_data.log_search else {
+        entry_frame(ui, Stroke::NONE, |ui| {
+            ui.add_space(MARGIN);
+            ui.colored_label(
+                Color32::LIGHT_GRAY,
+                "Chat logs are disabled, enable them in the chat settings.",
+            );
+            ui.add_space(MARGIN);
+        });
+        return;
+    };
+    if lines.is_empty() {
+        entry_frame(ui, Stroke::NONE, |ui| {
+            ui.add_space(MARGIN);
+            ui.colored_label(Color32::LIGHT_GRAY, "No matching chat messages.");
+            ui.add_space(MARGIN);
+        });
+        return;
+    }
+    for line in lines {
+        entry_frame(ui, Stroke::NONE, |ui| {
+            ui.add_space(MARGIN);
+            ui.horizontal(|ui| {
+                ui.add_space(MARGIN);
+                ui.colored_label(Color32::WHITE, line);
+                ui.add_space(MARGIN);
+            });
+            ui.add_space(MARGIN);
+        });
+    }
+}
@@ -1,6 +1,7 @@
 pub mod chat_entry;
 pub mod chat_list;
 pub mod input;
+pub mod log_search;
 pub mod main_frame;
 pub mod page;
 pub mod shared;
@@ -21,6 +21,8 @@ pub enum ChatMode {
     Global,
     Team,
     Whisper(Option<PlayerId>),
+    /// Searches the chat logs instead of sending a message.
+    Search,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -35,6 +37,8 @@ pub enum ChatEvent {
 pub struct MsgInChat {
     pub msg: ServerMsg,
     pub add_time: Duration,
+    /// The message mentions a local player or a highlight word.
+    pub highlighted: bool,
 }
 
 pub struct UserData<'a> {
@@ -46,6 +50,9 @@ pub struct UserData<'a> {
     pub stream_handle: &'a GraphicsStreamHandle,
     pub canvas_handle: &'a GraphicsCanvasHandle,
     pub mode: ChatMode,
+    /// Lines of the chat log that match the current search,
+    /// `None` if the chat logs are disabled.
+    pub log_search: Option<&'a [String]>,
 
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
     pub local_character_ids: &'a HashSet<CharacterId>,
@@ -293,7 +293,10 @@ pub fn render(
                                                 full_name: name,
                                                 ..
                                             })
-                                            | ConsoleEntry::Cmd(ConsoleEntryCmd { name, .. }) => {
+                                            | ConsoleEntry::Cmd(ConsoleEntryCmd { name, .. })
+                                            | ConsoleEntry::Macro(ConsoleEntryCmd {
+                                                name, ..
+                                            }) => {
                                                 *pipe.user_data.msg = name.clone();
                                             }
                                         }
@@ -346,7 +349,8 @@ pub fn render(
                                 ConsoleEntry::Var(ConsoleEntryVariable {
                                     full_name: name, ..
                                 })
-                                | ConsoleEntry::Cmd(ConsoleEntryCmd { name, .. }) => name,
+                                | ConsoleEntry::Cmd(ConsoleEntryCmd { name, .. })
+                                | ConsoleEntry::Macro(ConsoleEntryCmd { name, .. }) => name,
                             },
                             MatchedType::ArgList(index) | MatchedType::CustomList { index, .. } => {
                                 &list_entries.as_ref().unwrap()[*index]
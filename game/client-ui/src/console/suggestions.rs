@@ -76,7 +76,9 @@ pub fn render(
                                                 .full_name
                                                 .replace("$KEY$", "[key]")
                                                 .replace("$INDEX$", "[index]"),
-                                            ConsoleEntry::Cmd(c) => c.name.clone(),
+                                            ConsoleEntry::Cmd(c) | ConsoleEntry::Macro(c) => {
+                                                c.name.clone()
+                                            }
                                         }
                                     }
                                     MatchedType::ArgList(index)
@@ -231,7 +233,7 @@ pub fn render(
                                     },
                                 );
                             }
-                            ConsoleEntry::Cmd(cmd) => {
+                            ConsoleEntry::Cmd(cmd) | ConsoleEntry::Macro(cmd) => {
                                 job.append(
                                     &format!("usage: {}", cmd.usage),
                                     0.0,
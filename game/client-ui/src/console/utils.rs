@@ -1,9 +1,12 @@
 use std::ops::Range;
 
 use client_types::console::{ConsoleEntry, entries_to_parser};
-use command_parser::parser::{
-    self, Command, CommandArgType, CommandParseResult, CommandType, CommandTypeRef, CommandsTyped,
-    Syn,
+use command_parser::{
+    parser::{
+        self, Command, CommandArgType, CommandParseResult, CommandType, CommandTypeRef,
+        CommandsTyped, ParserCache, Syn,
+    },
+    vars::substitute_vars,
 };
 use config::{
     config::ConfigEngine,
@@ -370,7 +373,7 @@ pub fn find_matches(
                             },
                         )
                     }
-                    ConsoleEntry::Cmd(c) => (
+                    ConsoleEntry::Cmd(c) | ConsoleEntry::Macro(c) => (
                         MatchedType::Entry(index),
                         c.name.len() as i64,
                         matcher.fuzzy_indices(&c.name, &console_inp_without_modifiers),
@@ -420,7 +423,7 @@ pub fn find_matches_old(entries: &[ConsoleEntry], msg: &str) -> Vec<(usize, (i64
                     },
                 )
             }
-            ConsoleEntry::Cmd(c) => (
+            ConsoleEntry::Cmd(c) | ConsoleEntry::Macro(c) => (
                 index,
                 matcher.fuzzy_indices(&c.name, &console_inp_without_modifiers),
             ),
@@ -498,6 +501,60 @@ pub fn try_apply_config_val(
         })
 }
 
+/// How often macros (e.g. aliases) can execute other macros,
+/// before the execution is stopped.
+const MAX_MACRO_DEPTH: usize = 16;
+
+/// Replaces the user variables in the text-like arguments.
+fn substitute_args_vars(
+    args: &[(Syn, Range<usize>)],
+    config_game: &ConfigGame,
+) -> Vec<(Syn, Range<usize>)> {
+    let vars = &config_game.cl.console.vars;
+    let substitute = |s: &str| substitute_vars(s, |name| vars.get(name).cloned());
+    args.iter()
+        .map(|(syn, range)| {
+            (
+                match syn {
+                    Syn::Text(s) => Syn::Text(substitute(s)),
+                    Syn::Number(s) => Syn::Number(substitute(s)),
+                    Syn::Float(s) => Syn::Float(substitute(s)),
+                    syn => syn.clone(),
+                },
+                range.clone(),
+            )
+        })
+        .collect()
+}
+
+fn run_macro(
+    cmds: &str,
+    entries: &[ConsoleEntry],
+    config_engine: &mut ConfigEngine,
+    config_game: &mut ConfigGame,
+    can_change_config: bool,
+    depth: usize,
+) -> anyhow::Result<String, String> {
+    if depth >= MAX_MACRO_DEPTH {
+        return Err(format!(
+            "Max recursion depth of {MAX_MACRO_DEPTH} reached while executing: {cmds}"
+        ));
+    }
+    let cmds = parser::parse(cmds, &entries_to_parser(entries), &ParserCache::default());
+    let mut msgs = String::new();
+    let succeeded = run_commands_impl(
+        &cmds,
+        entries,
+        config_engine,
+        config_game,
+        &mut msgs,
+        can_change_config,
+        depth + 1,
+    );
+    let msgs = msgs.trim_end().to_string();
+    if succeeded { Ok(msgs) } else { Err(msgs) }
+}
+
 /// Returns `false` if the command was considered partially or fully failed.
 pub fn run_command(
     cmd: CommandTypeRef<'_>,
@@ -506,13 +563,44 @@ pub fn run_command(
     config_game: &mut ConfigGame,
     can_change_config: bool,
 ) -> anyhow::Result<String, String> {
-    if let Some(entry_cmd) = entries
+    run_command_impl(
+        cmd,
+        entries,
+        config_engine,
+        config_game,
+        can_change_config,
+        0,
+    )
+}
+
+fn run_command_impl(
+    cmd: CommandTypeRef<'_>,
+    entries: &[ConsoleEntry],
+    config_engine: &mut ConfigEngine,
+    config_game: &mut ConfigGame,
+    can_change_config: bool,
+    depth: usize,
+) -> anyhow::Result<String, String> {
+    let substituted_cmd;
+    let cmd = match cmd {
+        CommandTypeRef::Full(full_cmd) if !config_game.cl.console.vars.is_empty() => {
+            substituted_cmd = Command {
+                args: substitute_args_vars(&full_cmd.args, config_game),
+                ..full_cmd.clone()
+            };
+            CommandTypeRef::Full(&substituted_cmd)
+        }
+        cmd => cmd,
+    };
+
+    if let Some((entry_cmd, is_macro)) = entries
         .iter()
         .filter_map(|e| match e {
             client_types::console::ConsoleEntry::Var(_) => None,
-            client_types::console::ConsoleEntry::Cmd(c) => Some(c),
+            client_types::console::ConsoleEntry::Cmd(c) => Some((c, false)),
+            client_types::console::ConsoleEntry::Macro(c) => Some((c, true)),
         })
-        .find(|c| {
+        .find(|(c, _)| {
             if c.allows_partial_cmds {
                 match cmd {
                     CommandTypeRef::Full(cmd) => Some(&cmd.ident),
@@ -528,6 +616,14 @@ pub fn run_command(
     {
         let cmd = cmd.unwrap_full_or_partial_cmd_ref();
         match (entry_cmd.cmd)(config_engine, config_game, &cmd.cmd_text, &cmd.args) {
+            Ok(cmds) if is_macro => run_macro(
+                &cmds,
+                entries,
+                config_engine,
+                config_game,
+                can_change_config,
+                depth,
+            ),
             Ok(msg) => Ok(msg),
             Err(err) => Err(format!("Parsing error: {err}\n")),
         }
@@ -585,15 +681,36 @@ pub fn run_commands(
     config_game: &mut ConfigGame,
     msgs: &mut String,
     can_change_config: bool,
+) -> bool {
+    run_commands_impl(
+        cmds,
+        entries,
+        config_engine,
+        config_game,
+        msgs,
+        can_change_config,
+        0,
+    )
+}
+
+fn run_commands_impl(
+    cmds: &CommandsTyped,
+    entries: &[ConsoleEntry],
+    config_engine: &mut ConfigEngine,
+    config_game: &mut ConfigGame,
+    msgs: &mut String,
+    can_change_config: bool,
+    depth: usize,
 ) -> bool {
     let mut res = true;
     for cmd in cmds {
-        let msg = match run_command(
+        let msg = match run_command_impl(
             cmd.as_ref(),
             entries,
             config_engine,
             config_game,
             can_change_config,
+            depth,
         ) {
             Ok(msg) => msg,
             Err(msg) => {
@@ -607,3 +724,95 @@ pub fn run_commands(
     }
     res
 }
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use client_types::console::{ConsoleEntry, ConsoleEntryCmd, entries_to_parser};
+    use command_parser::parser::{self, CommandArg, CommandArgType, ParserCache, Syn};
+    use config::config::ConfigEngine;
+    use game_config::config::ConfigGame;
+
+    use super::{MAX_MACRO_DEPTH, run_commands};
+
+    fn entry(name: &str, is_macro: bool, cmds: &'static str) -> ConsoleEntry {
+        let cmd = ConsoleEntryCmd {
+            name: name.into(),
+            usage: name.into(),
+            description: Default::default(),
+            cmd: Rc::new(move |_, _, _, _| Ok(cmds.to_string())),
+            args: Default::default(),
+            allows_partial_cmds: false,
+        };
+        if is_macro {
+            ConsoleEntry::Macro(cmd)
+        } else {
+            ConsoleEntry::Cmd(cmd)
+        }
+    }
+
+    fn entries() -> Vec<ConsoleEntry> {
+        vec![
+            ConsoleEntry::Cmd(ConsoleEntryCmd {
+                name: "echo".into(),
+                usage: "echo <text>".into(),
+                description: Default::default(),
+                cmd: Rc::new(|_, _, _, path| {
+                    let Syn::Text(text) = &path[0].0 else {
+                        panic!("Command parser returned a non requested command arg");
+                    };
+                    Ok(format!("echo: {text}"))
+                }),
+                args: vec![CommandArg {
+                    ty: CommandArgType::Text,
+                    user_ty: None,
+                }],
+                allows_partial_cmds: false,
+            }),
+            entry("noop", false, ""),
+            // aliases
+            entry("greet", true, "echo hi"),
+            entry("greet_twice", true, "greet; greet"),
+            entry("empty", true, ""),
+            // `alias a a`
+            entry("a", true, "a"),
+        ]
+    }
+
+    fn run(cmds: &str) -> (bool, String) {
+        let entries = entries();
+        let cmds = parser::parse(cmds, &entries_to_parser(&entries), &ParserCache::default());
+        let mut msgs = String::new();
+        let res = run_commands(
+            &cmds,
+            &entries,
+            &mut ConfigEngine::default(),
+            &mut ConfigGame::default(),
+            &mut msgs,
+            true,
+        );
+        (res, msgs.trim_end().to_string())
+    }
+
+    #[test]
+    fn alias_execution() {
+        assert_eq!(run("greet"), (true, "echo: hi".to_string()));
+        assert_eq!(run("greet_twice"), (true, "echo: hi\necho: hi".to_string()));
+        // a macro that expands to nothing does nothing
+        assert_eq!(run("empty"), (true, String::new()));
+        assert_eq!(run("noop; greet"), (true, "echo: hi".to_string()));
+    }
+
+    #[test]
+    fn macro_depth_limit() {
+        let (res, msgs) = run("a");
+        assert!(!res);
+        assert!(msgs.contains(&format!("Max recursion depth of {MAX_MACRO_DEPTH} reached")));
+
+        // commands after the recursive alias still run
+        let (res, msgs) = run("a; greet");
+        assert!(!res);
+        assert!(msgs.ends_with("echo: hi"));
+    }
+}
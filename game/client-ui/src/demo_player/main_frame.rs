@@ -2,9 +2,10 @@ use std::time::Duration;
 
 use base::duration_ext::DurationToRaceStr;
 use egui::{
-    Align2, Button, Color32, CornerRadius, FontId, Frame, Grid, Layout, Rect, Shadow, Stroke,
-    TopBottomPanel, Vec2, Window,
+    Align2, Button, Color32, ComboBox, CornerRadius, FontId, Frame, Grid, Layout, Rect, Sense,
+    Shadow, Stroke, TopBottomPanel, Vec2, Window,
 };
+use game_interface::types::id_types::CharacterId;
 
 use tracing::instrument;
 use ui_base::{
@@ -12,10 +13,28 @@ use ui_base::{
     utils::{add_horizontal_margins, text_sized},
 };
 
-use crate::demo_player::user_data::{DemoViewerEvent, DemoViewerEventExport};
+use crate::demo_player::user_data::{DemoViewerCameraMode, DemoViewerEvent, DemoViewerEventExport};
 
 use super::user_data::UserData;
 
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 8.0;
+const ZOOM_STEP: f32 = 1.1;
+
+fn camera_mode_name(mode: &DemoViewerCameraMode, characters: &[(CharacterId, String)]) -> String {
+    match mode {
+        DemoViewerCameraMode::Recorded => "Recorded view".to_string(),
+        DemoViewerCameraMode::Follow(id) => format!(
+            "Follow {}",
+            characters
+                .iter()
+                .find_map(|(character_id, name)| (character_id == id).then_some(name.as_str()))
+                .unwrap_or("unknown player")
+        ),
+        DemoViewerCameraMode::Free => "Free camera".to_string(),
+    }
+}
+
 /// not required
 #[instrument(level = "trace", skip_all)]
 pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
@@ -254,6 +273,53 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                             pipe.user_data.events.push(DemoViewerEvent::SpeedFaster);
                         }
 
+                        ui.add_space(15.0);
+
+                        // camera mode & zoom
+                        let camera_mode = *pipe.user_data.camera_mode;
+                        let characters = pipe.user_data.characters;
+                        ComboBox::new("demo-camera-mode", "")
+                            .selected_text(camera_mode_name(&camera_mode, characters))
+                            .show_ui(ui, |ui| {
+                                let modes =
+                                    [DemoViewerCameraMode::Recorded, DemoViewerCameraMode::Free]
+                                        .into_iter()
+                                        .chain(
+                                            characters
+                                                .iter()
+                                                .map(|(id, _)| DemoViewerCameraMode::Follow(*id)),
+                                        );
+                                for mode in modes {
+                                    if ui
+                                        .selectable_label(
+                                            mode == camera_mode,
+                                            camera_mode_name(&mode, characters),
+                                        )
+                                        .clicked()
+                                    {
+                                        pipe.user_data
+                                            .events
+                                            .push(DemoViewerEvent::CameraMode(mode));
+                                    }
+                                }
+                            });
+                        let zoom = *pipe.user_data.zoom;
+                        if ui.button(text_sized("\u{f010}", FONT_SIZE)).clicked() {
+                            pipe.user_data.events.push(DemoViewerEvent::CameraZoom {
+                                zoom: (zoom * ZOOM_STEP).clamp(MIN_ZOOM, MAX_ZOOM),
+                            });
+                        }
+                        if ui.button(format!("{:\u{2007}^7.2}", zoom)).clicked() {
+                            pipe.user_data
+                                .events
+                                .push(DemoViewerEvent::CameraZoom { zoom: 1.0 });
+                        }
+                        if ui.button(text_sized("\u{f00e}", FONT_SIZE)).clicked() {
+                            pipe.user_data.events.push(DemoViewerEvent::CameraZoom {
+                                zoom: (zoom / ZOOM_STEP).clamp(MIN_ZOOM, MAX_ZOOM),
+                            });
+                        }
+
                         ui.add_space(15.0);
                         ui.colored_label(Color32::WHITE, pipe.user_data.name);
                     });
@@ -265,8 +331,18 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
 
                         ui.add_space(15.0);
 
-                        // left bracket, right bracket, share (in reverse order)
                         let state = &mut *pipe.user_data.state;
+
+                        // encode to video
+                        if *pipe.user_data.can_encode_to_video
+                            && ui.button(text_sized("\u{f03d}", FONT_SIZE)).clicked()
+                        {
+                            state.encode_to_video = Some(pipe.user_data.name.to_string());
+                        }
+
+                        ui.add_space(15.0);
+
+                        // left bracket, right bracket, share (in reverse order)
                         if ui
                             .add_enabled(
                                 state.left.is_some() && state.right.is_some(),
@@ -338,9 +414,72 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                                     });
                                 });
                         }
+
+                        if state.encode_to_video.is_some() {
+                            Window::new("Encode demo to video")
+                                .anchor(Align2::CENTER_CENTER, Vec2::default())
+                                .show(ui.ctx(), |ui| {
+                                    Grid::new("encode-demo-grid").num_columns(2).show(ui, |ui| {
+                                        if let Some(video_name) = state.encode_to_video.as_mut() {
+                                            ui.label("Video name:");
+                                            ui.text_edit_singleline(video_name);
+                                            ui.end_row();
+
+                                            ui.label("Camera:");
+                                            ui.label(format!(
+                                                "{} ({:.2}x zoom)",
+                                                camera_mode_name(
+                                                    pipe.user_data.camera_mode,
+                                                    pipe.user_data.characters
+                                                ),
+                                                pipe.user_data.zoom
+                                            ));
+                                            ui.end_row();
+
+                                            let video_name = video_name.clone();
+                                            if ui.button("Abort").clicked() {
+                                                state.encode_to_video.take();
+                                            }
+                                            if ui.button("Encode").clicked() {
+                                                pipe.user_data.events.push(
+                                                    DemoViewerEvent::EncodeToVideo { video_name },
+                                                );
+
+                                                state.encode_to_video.take();
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                                });
+                        }
                     });
                 });
             });
         });
     ui_state.add_blur_rect(res.response.rect, 0.0);
+
+    // dragging the game moves the camera, scrolling zooms
+    let screen_rect = ui.ctx().screen_rect();
+    let mut game_rect = screen_rect;
+    game_rect.max.y = res.response.rect.min.y;
+    let game_res = ui.interact(game_rect, ui.id().with("demo-camera"), Sense::drag());
+    if game_res.dragged() && game_res.drag_delta() != Vec2::ZERO {
+        pipe.user_data.events.push(DemoViewerEvent::CameraPan {
+            delta: game_res.drag_delta() / screen_rect.size(),
+        });
+    }
+    if game_res.hovered() {
+        let scroll = ui.input(|i| i.raw_scroll_delta.y);
+        if scroll != 0.0 {
+            let zoom = *pipe.user_data.zoom;
+            pipe.user_data.events.push(DemoViewerEvent::CameraZoom {
+                zoom: if scroll > 0.0 {
+                    zoom / ZOOM_STEP
+                } else {
+                    zoom * ZOOM_STEP
+                }
+                .clamp(MIN_ZOOM, MAX_ZOOM),
+            });
+        }
+    }
 }
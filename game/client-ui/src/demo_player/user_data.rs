@@ -1,9 +1,11 @@
 use std::time::Duration;
 
-use egui::Rect;
+use egui::{Rect, Vec2};
+use game_interface::types::id_types::CharacterId;
 use graphics::handles::{
     canvas::canvas::GraphicsCanvasHandle, stream::stream::GraphicsStreamHandle,
 };
+use hiarc::Hiarc;
 use math::math::vector::ffixed;
 
 #[derive(Debug, Clone)]
@@ -14,6 +16,18 @@ pub struct DemoViewerEventExport {
     pub remove_chat: bool,
 }
 
+/// The camera that is used to watch the demo.
+#[derive(Debug, Hiarc, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DemoViewerCameraMode {
+    /// The point of view of the player that recorded the demo.
+    #[default]
+    Recorded,
+    /// Follows the given character.
+    Follow(CharacterId),
+    /// A camera that is detached from all characters.
+    Free,
+}
+
 #[derive(Debug, Clone)]
 pub enum DemoViewerEvent {
     ResumeToggle,
@@ -32,10 +46,28 @@ pub enum DemoViewerEvent {
     SpeedFaster,
     SpeedReset,
 
-    Export(DemoViewerEventExport),
+    CameraMode(DemoViewerCameraMode),
+    /// Moves the free camera, the delta is relative to the screen size.
+    CameraPan {
+        delta: Vec2,
+    },
+    CameraZoom {
+        zoom: f32,
+    },
 
-    SkipTo { time: Duration },
-    PreviewAt { rect: Rect, time: Duration },
+    Export(DemoViewerEventExport),
+    /// Encodes the demo to a video, watched with the current camera.
+    EncodeToVideo {
+        video_name: String,
+    },
+
+    SkipTo {
+        time: Duration,
+    },
+    PreviewAt {
+        rect: Rect,
+        time: Duration,
+    },
 
     Close,
 }
@@ -47,6 +79,8 @@ pub struct DemoViewerUiState {
     pub left: Option<Duration>,
     pub right: Option<Duration>,
     pub export: Option<DemoViewerEventExport>,
+    /// The name of the video, if the encode dialog is open.
+    pub encode_to_video: Option<String>,
 }
 
 pub struct UserData<'a> {
@@ -57,7 +91,13 @@ pub struct UserData<'a> {
     pub cur_duration: &'a Duration,
     pub max_duration: &'a Duration,
     pub speed: &'a ffixed,
+    pub camera_mode: &'a DemoViewerCameraMode,
+    pub zoom: &'a f32,
+    /// The characters that the camera can follow.
+    pub characters: &'a [(CharacterId, String)],
     pub name: &'a str,
+    /// Whether the client was built with video encoding support.
+    pub can_encode_to_video: &'a bool,
 
     pub events: &'a mut Vec<DemoViewerEvent>,
 
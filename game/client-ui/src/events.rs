@@ -7,6 +7,8 @@ use game_interface::votes::{
 use hiarc::{Hiarc, hiarc_safer_rc_refcell};
 use math::math::vector::ubvec4;
 
+use crate::demo_player::user_data::DemoViewerCameraMode;
+
 #[derive(Debug, Hiarc)]
 pub enum UiEvent {
     StartLocalServer,
@@ -17,6 +19,8 @@ pub enum UiEvent {
     EncodeDemoToVideo {
         name: PathBuf,
         video_name: String,
+        camera_mode: DemoViewerCameraMode,
+        zoom: f32,
     },
     RecordDemo,
     StopRecordDemo,
@@ -0,0 +1,127 @@
+use egui::{
+    Align2, Area, Color32, CornerRadius, FontId, Frame, Id, Order, Rect, RichText, Sense, Stroke,
+    StrokeKind, Vec2,
+};
+
+use super::layout::{HudElement, HudLayout};
+
+const MIN_SCALE: f32 = 0.25;
+const MAX_SCALE: f32 = 4.0;
+
+/// Renders a frame around every HUD element, that can be
+/// dragged to move, scrolled to scale and right clicked to hide
+/// the element.
+///
+/// `sizes` are the unscaled sizes of the elements.
+/// Returns the changed layout, if any.
+pub fn render(
+    ui: &mut egui::Ui,
+    layout: &HudLayout,
+    sizes: &[(HudElement, Vec2)],
+) -> Option<HudLayout> {
+    let screen = ui.ctx().screen_rect();
+    let mut new_layout = *layout;
+    let mut reset = false;
+
+    Area::new(Id::new("hud_editor"))
+        .order(Order::Foreground)
+        .fixed_pos(screen.min)
+        .show(ui.ctx(), |ui| {
+            ui.set_clip_rect(screen);
+            ui.painter()
+                .rect_filled(screen, 0.0, Color32::from_black_alpha(60));
+
+            for &(element, size) in sizes {
+                let element_layout = new_layout.get_mut(element);
+                let rect = element_layout.rect(screen, size);
+                let res = ui.interact(
+                    rect,
+                    Id::new("hud_editor_element").with(element as u8),
+                    Sense::click_and_drag(),
+                );
+
+                if res.dragged() {
+                    element_layout.offset += res.drag_delta();
+                }
+                if res.drag_stopped() {
+                    element_layout.reanchor(screen, size);
+                }
+                if res.hovered() {
+                    let scroll = ui.input(|i| i.raw_scroll_delta.y);
+                    if scroll != 0.0 {
+                        let old_rect = element_layout.rect(screen, size);
+                        element_layout.scale = (element_layout.scale
+                            * if scroll > 0.0 { 1.1 } else { 1.0 / 1.1 })
+                        .clamp(MIN_SCALE, MAX_SCALE);
+                        // scale around the center of the element
+                        let new_rect = element_layout.rect(screen, size);
+                        element_layout.offset += old_rect.center() - new_rect.center();
+                    }
+                }
+                if res.secondary_clicked() {
+                    element_layout.visible = !element_layout.visible;
+                }
+
+                let rect = element_layout.rect(screen, size);
+                let (stroke_color, fill) = if element_layout.visible {
+                    (
+                        Color32::LIGHT_BLUE,
+                        Color32::from_rgba_unmultiplied(0, 0, 255, 30),
+                    )
+                } else {
+                    (Color32::DARK_GRAY, Color32::from_black_alpha(100))
+                };
+                let stroke_color = if res.hovered() || res.dragged() {
+                    Color32::WHITE
+                } else {
+                    stroke_color
+                };
+                ui.painter().rect(
+                    rect,
+                    CornerRadius::same(3),
+                    fill,
+                    Stroke::new(1.0, stroke_color),
+                    StrokeKind::Outside,
+                );
+                ui.painter().text(
+                    rect.center(),
+                    Align2::CENTER_CENTER,
+                    if element_layout.visible {
+                        element.name().to_string()
+                    } else {
+                        format!("{} (hidden)", element.name())
+                    },
+                    FontId::proportional(12.0),
+                    stroke_color,
+                );
+            }
+
+            let help_rect = Rect::from_center_size(screen.center(), Vec2::new(320.0, 80.0));
+            ui.scope_builder(egui::UiBuilder::new().max_rect(help_rect), |ui| {
+                Frame::default()
+                    .fill(Color32::from_black_alpha(150))
+                    .corner_radius(CornerRadius::same(5))
+                    .inner_margin(5)
+                    .show(ui, |ui| {
+                        ui.vertical_centered(|ui| {
+                            ui.label(RichText::new("HUD editor").color(Color32::WHITE));
+                            ui.label(
+                                RichText::new(
+                                    "Drag to move, scroll to scale, right click to hide.",
+                                )
+                                .color(Color32::LIGHT_GRAY),
+                            );
+                            if ui.button("Reset layout").clicked() {
+                                reset = true;
+                            }
+                        });
+                    });
+            });
+        });
+
+    if reset {
+        new_layout = HudLayout::default();
+    }
+
+    (new_layout != *layout).then_some(new_layout)
+}
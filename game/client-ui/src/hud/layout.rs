@@ -0,0 +1,278 @@
+use egui::{Align2, Pos2, Rect, Vec2, emath::TSTransform};
+use game_config::config::{ConfigHudAnchor, ConfigHudElement, ConfigHudLayout};
+use serde::{Deserialize, Serialize};
+
+/// Unscaled size of the health, armor & ammo display.
+pub const HEALTH_AMMO_SIZE: Vec2 = Vec2::new(24.0 * 10.0 + 16.0, 24.0 * 3.0 + 8.0);
+/// Unscaled size of the FPS display.
+pub const FPS_SIZE: Vec2 = Vec2::new(60.0, 14.0);
+/// Unscaled size of the network statistics.
+pub const NET_GRAPH_SIZE: Vec2 = Vec2::new(100.0, 300.0);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HudAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl HudAnchor {
+    pub fn align2(self) -> Align2 {
+        match self {
+            Self::TopLeft => Align2::LEFT_TOP,
+            Self::Top => Align2::CENTER_TOP,
+            Self::TopRight => Align2::RIGHT_TOP,
+            Self::Left => Align2::LEFT_CENTER,
+            Self::Center => Align2::CENTER_CENTER,
+            Self::Right => Align2::RIGHT_CENTER,
+            Self::BottomLeft => Align2::LEFT_BOTTOM,
+            Self::Bottom => Align2::CENTER_BOTTOM,
+            Self::BottomRight => Align2::RIGHT_BOTTOM,
+        }
+    }
+
+    /// The anchor of the screen region (split in thirds)
+    /// the given point is in.
+    pub fn nearest(screen: Rect, pos: Pos2) -> Self {
+        let third = |v: f32, min: f32, size: f32| ((v - min) / size * 3.0).clamp(0.0, 2.0) as u8;
+        match (
+            third(pos.x, screen.min.x, screen.width()),
+            third(pos.y, screen.min.y, screen.height()),
+        ) {
+            (0, 0) => Self::TopLeft,
+            (1, 0) => Self::Top,
+            (_, 0) => Self::TopRight,
+            (0, 1) => Self::Left,
+            (1, 1) => Self::Center,
+            (_, 1) => Self::Right,
+            (0, _) => Self::BottomLeft,
+            (1, _) => Self::Bottom,
+            (_, _) => Self::BottomRight,
+        }
+    }
+}
+
+impl From<ConfigHudAnchor> for HudAnchor {
+    fn from(value: ConfigHudAnchor) -> Self {
+        match value {
+            ConfigHudAnchor::TopLeft => Self::TopLeft,
+            ConfigHudAnchor::Top => Self::Top,
+            ConfigHudAnchor::TopRight => Self::TopRight,
+            ConfigHudAnchor::Left => Self::Left,
+            ConfigHudAnchor::Center => Self::Center,
+            ConfigHudAnchor::Right => Self::Right,
+            ConfigHudAnchor::BottomLeft => Self::BottomLeft,
+            ConfigHudAnchor::Bottom => Self::Bottom,
+            ConfigHudAnchor::BottomRight => Self::BottomRight,
+        }
+    }
+}
+
+impl From<HudAnchor> for ConfigHudAnchor {
+    fn from(value: HudAnchor) -> Self {
+        match value {
+            HudAnchor::TopLeft => Self::TopLeft,
+            HudAnchor::Top => Self::Top,
+            HudAnchor::TopRight => Self::TopRight,
+            HudAnchor::Left => Self::Left,
+            HudAnchor::Center => Self::Center,
+            HudAnchor::Right => Self::Right,
+            HudAnchor::BottomLeft => Self::BottomLeft,
+            HudAnchor::Bottom => Self::Bottom,
+            HudAnchor::BottomRight => Self::BottomRight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct HudElementLayout {
+    pub visible: bool,
+    pub anchor: HudAnchor,
+    pub offset: Vec2,
+    pub scale: f32,
+}
+
+impl HudElementLayout {
+    /// The point on the screen the element's anchor is attached to.
+    pub fn anchor_pos(&self, screen: Rect) -> Pos2 {
+        self.anchor.align2().pos_in_rect(&screen) + self.offset
+    }
+
+    /// The rect on the screen of an element with the given unscaled size.
+    pub fn rect(&self, screen: Rect, size: Vec2) -> Rect {
+        self.anchor
+            .align2()
+            .anchor_size(self.anchor_pos(screen), size * self.scale)
+    }
+
+    /// Scales around the anchor position, to be used as layer transform
+    /// of an element that is anchored at [`Self::anchor_pos`].
+    pub fn transform(&self, screen: Rect) -> TSTransform {
+        TSTransform::new(
+            self.anchor_pos(screen).to_vec2() * (1.0 - self.scale),
+            self.scale,
+        )
+    }
+
+    /// Attaches the element to the anchor nearest to its center,
+    /// without moving it.
+    pub fn reanchor(&mut self, screen: Rect, size: Vec2) {
+        let rect = self.rect(screen, size);
+        self.anchor = HudAnchor::nearest(screen, rect.center());
+        let align = self.anchor.align2();
+        let anchor_pos_in_rect = align.pos_in_rect(&rect);
+        self.offset = anchor_pos_in_rect - align.pos_in_rect(&screen);
+    }
+}
+
+impl From<&ConfigHudElement> for HudElementLayout {
+    fn from(value: &ConfigHudElement) -> Self {
+        Self {
+            visible: value.visible,
+            anchor: value.anchor.into(),
+            offset: Vec2::new(value.offset_x as f32, value.offset_y as f32),
+            scale: value.scale.clamp(0.25, 4.0) as f32,
+        }
+    }
+}
+
+impl From<&HudElementLayout> for ConfigHudElement {
+    fn from(value: &HudElementLayout) -> Self {
+        Self {
+            visible: value.visible,
+            anchor: value.anchor.into(),
+            offset_x: value.offset.x as f64,
+            offset_y: value.offset.y as f64,
+            scale: value.scale as f64,
+        }
+    }
+}
+
+/// All elements of the HUD that can be customized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HudElement {
+    HealthAmmo,
+    Timer,
+    Scores,
+    SpectatorInfo,
+    Fps,
+    NetGraph,
+}
+
+impl HudElement {
+    pub const ALL: [Self; 6] = [
+        Self::HealthAmmo,
+        Self::Timer,
+        Self::Scores,
+        Self::SpectatorInfo,
+        Self::Fps,
+        Self::NetGraph,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::HealthAmmo => "Health & ammo",
+            Self::Timer => "Timer",
+            Self::Scores => "Scores",
+            Self::SpectatorInfo => "Spectator info",
+            Self::Fps => "FPS",
+            Self::NetGraph => "Net graph",
+        }
+    }
+}
+
+/// Position, scale & visibility of the HUD elements.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct HudLayout {
+    pub health_ammo: HudElementLayout,
+    pub timer: HudElementLayout,
+    pub scores: HudElementLayout,
+    pub spectator_info: HudElementLayout,
+    pub fps: HudElementLayout,
+    pub net_graph: HudElementLayout,
+}
+
+impl HudLayout {
+    pub fn get(&self, element: HudElement) -> &HudElementLayout {
+        match element {
+            HudElement::HealthAmmo => &self.health_ammo,
+            HudElement::Timer => &self.timer,
+            HudElement::Scores => &self.scores,
+            HudElement::SpectatorInfo => &self.spectator_info,
+            HudElement::Fps => &self.fps,
+            HudElement::NetGraph => &self.net_graph,
+        }
+    }
+
+    pub fn get_mut(&mut self, element: HudElement) -> &mut HudElementLayout {
+        match element {
+            HudElement::HealthAmmo => &mut self.health_ammo,
+            HudElement::Timer => &mut self.timer,
+            HudElement::Scores => &mut self.scores,
+            HudElement::SpectatorInfo => &mut self.spectator_info,
+            HudElement::Fps => &mut self.fps,
+            HudElement::NetGraph => &mut self.net_graph,
+        }
+    }
+}
+
+impl Default for HudLayout {
+    fn default() -> Self {
+        (&ConfigHudLayout::default()).into()
+    }
+}
+
+impl From<&ConfigHudLayout> for HudLayout {
+    fn from(value: &ConfigHudLayout) -> Self {
+        Self {
+            health_ammo: (&value.health_ammo).into(),
+            timer: (&value.timer).into(),
+            scores: (&value.scores).into(),
+            spectator_info: (&value.spectator_info).into(),
+            fps: (&value.fps).into(),
+            net_graph: (&value.net_graph).into(),
+        }
+    }
+}
+
+impl From<&HudLayout> for ConfigHudLayout {
+    fn from(value: &HudLayout) -> Self {
+        Self {
+            health_ammo: (&value.health_ammo).into(),
+            timer: (&value.timer).into(),
+            scores: (&value.scores).into(),
+            spectator_info: (&value.spectator_info).into(),
+            fps: (&value.fps).into(),
+            net_graph: (&value.net_graph).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use egui::{Pos2, Rect, Vec2};
+
+    use super::{HudAnchor, HudLayout};
+
+    #[test]
+    fn reanchor_keeps_position() {
+        let screen = Rect::from_min_size(Pos2::ZERO, Vec2::new(1200.0, 900.0));
+        let size = Vec2::new(100.0, 40.0);
+        let mut layout = HudLayout::default().timer;
+        layout.scale = 2.0;
+        layout.offset = Vec2::new(500.0, 800.0);
+        let rect = layout.rect(screen, size);
+
+        layout.reanchor(screen, size);
+        assert_eq!(layout.anchor, HudAnchor::BottomRight);
+        let new_rect = layout.rect(screen, size);
+        assert!((new_rect.min - rect.min).length() < 0.01);
+        assert!((new_rect.max - rect.max).length() < 0.01);
+    }
+}
@@ -27,7 +27,11 @@ use ui_base::{
 
 use crate::utils::{render_tee_for_ui, render_texture_for_ui};
 
-use super::user_data::UserData;
+use super::{
+    editor,
+    layout::{FPS_SIZE, HEALTH_AMMO_SIZE, HudElement, NET_GRAPH_SIZE},
+    user_data::{HudEvent, UserData},
+};
 
 /// not required
 #[instrument(level = "trace", skip_all)]
@@ -103,28 +107,10 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
 
     const ROUNDING: u8 = 5;
     const MARGIN: i8 = 3;
-    let (max_height, rounding) = if is_game_over.is_some() {
-        (40.0, CornerRadius::same(ROUNDING))
-    } else {
-        match pipe.user_data.game {
-            Some(GameRenderInfo::Match {
-                standings: MatchStandings::Solo { .. },
-                ..
-            }) => (60.0, CornerRadius::same(0)),
-            Some(GameRenderInfo::Match {
-                standings: MatchStandings::Sided { .. },
-                ..
-            }) => (
-                40.0,
-                CornerRadius {
-                    ne: ROUNDING,
-                    nw: ROUNDING,
-                    ..Default::default()
-                },
-            ),
-            Some(GameRenderInfo::Race { .. }) | None => (25.0, CornerRadius::same(ROUNDING)),
-        }
-    };
+    let rounding = CornerRadius::same(ROUNDING);
+
+    let screen = ui.ctx().screen_rect();
+    let layout = *pipe.user_data.layout;
 
     enum Side {
         Left,
@@ -297,53 +283,44 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                             if let Side::Bottom(rect) = side {
                                 // no spacing for points
                                 ui.style_mut().spacing.item_spacing = Default::default();
-                                ui.scope_builder(
-                                    UiBuilder::default().max_rect(
-                                        rect.translate(egui::vec2(
-                                            0.0,
-                                            rect.height() + 2.0 * MARGIN as f32,
-                                        ))
-                                        .expand(MARGIN as f32),
-                                    ),
-                                    |ui| {
-                                        StripBuilder::new(ui)
-                                            .size(Size::remainder())
-                                            .size(Size::remainder())
-                                            .cell_layout(Layout::top_down(egui::Align::Center))
-                                            .horizontal(|mut strip| {
-                                                strip.cell(|ui| {
-                                                    ui.style_mut().wrap_mode = None;
-                                                    Frame::NONE
-                                                        .fill(color_a(Color32::RED, 150))
-                                                        .corner_radius(CornerRadius {
-                                                            sw: ROUNDING,
-                                                            ..Default::default()
-                                                        })
-                                                        .show(ui, |ui| {
-                                                            ui.colored_label(
-                                                                Color32::WHITE,
-                                                                format!("{score_red}"),
-                                                            );
-                                                        });
-                                                });
-                                                strip.cell(|ui| {
-                                                    ui.style_mut().wrap_mode = None;
-                                                    Frame::NONE
-                                                        .fill(color_a(Color32::BLUE, 150))
-                                                        .corner_radius(CornerRadius {
-                                                            se: ROUNDING,
-                                                            ..Default::default()
-                                                        })
-                                                        .show(ui, |ui| {
-                                                            ui.colored_label(
-                                                                Color32::WHITE,
-                                                                format!("{score_blue}"),
-                                                            );
-                                                        });
-                                                });
+                                ui.scope_builder(UiBuilder::default().max_rect(rect), |ui| {
+                                    StripBuilder::new(ui)
+                                        .size(Size::remainder())
+                                        .size(Size::remainder())
+                                        .cell_layout(Layout::top_down(egui::Align::Center))
+                                        .horizontal(|mut strip| {
+                                            strip.cell(|ui| {
+                                                ui.style_mut().wrap_mode = None;
+                                                Frame::NONE
+                                                    .fill(color_a(Color32::RED, 150))
+                                                    .corner_radius(CornerRadius {
+                                                        sw: ROUNDING,
+                                                        ..Default::default()
+                                                    })
+                                                    .show(ui, |ui| {
+                                                        ui.colored_label(
+                                                            Color32::WHITE,
+                                                            format!("{score_red}"),
+                                                        );
+                                                    });
                                             });
-                                    },
-                                );
+                                            strip.cell(|ui| {
+                                                ui.style_mut().wrap_mode = None;
+                                                Frame::NONE
+                                                    .fill(color_a(Color32::BLUE, 150))
+                                                    .corner_radius(CornerRadius {
+                                                        se: ROUNDING,
+                                                        ..Default::default()
+                                                    })
+                                                    .show(ui, |ui| {
+                                                        ui.colored_label(
+                                                            Color32::WHITE,
+                                                            format!("{score_blue}"),
+                                                        );
+                                                    });
+                                            });
+                                        });
+                                });
                                 true
                             } else if matches!(side, Side::Left) && has_carrier {
                                 render_char(
@@ -430,152 +407,232 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         }
     }
 
-    let res = Window::new("")
-        .resizable(false)
-        .title_bar(false)
-        .frame(Frame::NONE)
-        .anchor(Align2::CENTER_TOP, Vec2::new(0.0, 5.0))
-        .max_height(max_height)
-        .show(ui.ctx(), |ui| {
-            ui.set_clip_rect(ui.ctx().screen_rect());
-            ui.style_mut().spacing.item_spacing.y = 0.0;
-            let rect = ui
-                .with_layout(
-                    Layout::left_to_right(egui::Align::Center)
-                        .with_main_justify(false)
-                        .with_cross_justify(true),
-                    |ui| {
-                        let rendered = render_side(pipe, ui, ui_state, Side::Left);
-
-                        let mut frame = Frame::default()
-                            .corner_radius(rounding)
-                            .inner_margin(Margin::same(MARGIN))
-                            .fill(color_a(Color32::BLACK, 50))
-                            .begin_better(ui);
-
-                        if rendered {
-                            frame.frame.corner_radius.nw = 0;
-                            frame.frame.corner_radius.sw = 0;
-                        }
+    let timer_res = if layout.timer.visible {
+        Window::new("")
+            .resizable(false)
+            .title_bar(false)
+            .frame(Frame::NONE)
+            .anchor(layout.timer.anchor.align2(), layout.timer.offset)
+            .show(ui.ctx(), |ui| {
+                ui.set_clip_rect(screen);
+                ui.style_mut().spacing.item_spacing.y = 0.0;
+                let mut frame = Frame::default()
+                    .corner_radius(rounding)
+                    .inner_margin(Margin::same(MARGIN))
+                    .fill(color_a(Color32::BLACK, 50))
+                    .begin_better(ui);
 
-                        if let Some(is_game_over) = is_game_over {
-                            match is_game_over {
-                                MatchRoundGameOverWinner::Characters(chars) => {
-                                    frame.content_ui.horizontal(|ui| {
-                                        ui.style_mut().spacing.item_spacing.x = 0.0;
-                                        let rect = ui.available_rect_before_wrap();
-                                        for (index, char) in chars.iter().enumerate() {
-                                            const SKIN_RECT_SIZE: f32 = 50.0;
-                                            ui.add_space(SKIN_RECT_SIZE);
+                if let Some(is_game_over) = is_game_over {
+                    match is_game_over {
+                        MatchRoundGameOverWinner::Characters(chars) => {
+                            frame.content_ui.horizontal(|ui| {
+                                ui.style_mut().spacing.item_spacing.x = 0.0;
+                                let rect = ui.available_rect_before_wrap();
+                                for (index, char) in chars.iter().enumerate() {
+                                    const SKIN_RECT_SIZE: f32 = 50.0;
+                                    ui.add_space(SKIN_RECT_SIZE);
 
-                                            render_tee_for_ui(
-                                                pipe.user_data.canvas_handle,
-                                                pipe.user_data.skin_container,
-                                                pipe.user_data.skin_renderer,
-                                                ui,
-                                                ui_state,
-                                                ui.ctx().screen_rect(),
-                                                Some(rect),
-                                                (*char.skin).borrow(),
-                                                Some(&char.skin_info),
-                                                vec2::new(
-                                                    ui.available_rect_before_wrap().min.x
-                                                        - SKIN_RECT_SIZE / 2.0,
-                                                    rect.center().y,
-                                                ),
-                                                SKIN_RECT_SIZE / 2.0,
-                                                TeeEye::Normal,
-                                            );
+                                    render_tee_for_ui(
+                                        pipe.user_data.canvas_handle,
+                                        pipe.user_data.skin_container,
+                                        pipe.user_data.skin_renderer,
+                                        ui,
+                                        ui_state,
+                                        ui.ctx().screen_rect(),
+                                        Some(rect),
+                                        (*char.skin).borrow(),
+                                        Some(&char.skin_info),
+                                        vec2::new(
+                                            ui.available_rect_before_wrap().min.x
+                                                - SKIN_RECT_SIZE / 2.0,
+                                            rect.center().y,
+                                        ),
+                                        SKIN_RECT_SIZE / 2.0,
+                                        TeeEye::Normal,
+                                    );
 
-                                            ui.label(
-                                                RichText::new(char.name.as_str())
-                                                    .color(Color32::WHITE),
-                                            );
+                                    ui.label(
+                                        RichText::new(char.name.as_str()).color(Color32::WHITE),
+                                    );
 
-                                            match (index + 2).cmp(&chars.len()) {
-                                                std::cmp::Ordering::Less => {
-                                                    ui.label(
-                                                        RichText::new(", ").color(Color32::WHITE),
-                                                    );
-                                                }
-                                                std::cmp::Ordering::Equal => {
-                                                    ui.label(
-                                                        RichText::new(" & ").color(Color32::WHITE),
-                                                    );
-                                                }
-                                                std::cmp::Ordering::Greater => {
-                                                    // can't happen
-                                                }
-                                            }
+                                    match (index + 2).cmp(&chars.len()) {
+                                        std::cmp::Ordering::Less => {
+                                            ui.label(RichText::new(", ").color(Color32::WHITE));
                                         }
-
-                                        match chars.len().cmp(&1) {
-                                            std::cmp::Ordering::Less => {
-                                                // ignore
-                                            }
-                                            std::cmp::Ordering::Equal => {
-                                                ui.label(
-                                                    RichText::new(" wins!").color(Color32::WHITE),
-                                                );
-                                            }
-                                            std::cmp::Ordering::Greater => {
-                                                ui.label(
-                                                    RichText::new(" win!").color(Color32::WHITE),
-                                                );
-                                            }
+                                        std::cmp::Ordering::Equal => {
+                                            ui.label(RichText::new(" & ").color(Color32::WHITE));
                                         }
-                                    });
-                                }
-                                MatchRoundGameOverWinner::Side(side) => {
-                                    frame.content_ui.label(
-                                        RichText::new(format!(
-                                            "{} wins!",
-                                            match side {
-                                                MatchSide::Red => "Red",
-                                                MatchSide::Blue => "Blue",
-                                            }
-                                        ))
-                                        .color(Color32::WHITE),
-                                    );
+                                        std::cmp::Ordering::Greater => {
+                                            // can't happen
+                                        }
+                                    }
                                 }
-                                MatchRoundGameOverWinner::SideNamed(name) => {
-                                    frame.content_ui.label(
-                                        RichText::new(format!("{} wins!", name.as_str()))
-                                            .color(Color32::WHITE),
-                                    );
+
+                                match chars.len().cmp(&1) {
+                                    std::cmp::Ordering::Less => {
+                                        // ignore
+                                    }
+                                    std::cmp::Ordering::Equal => {
+                                        ui.label(RichText::new(" wins!").color(Color32::WHITE));
+                                    }
+                                    std::cmp::Ordering::Greater => {
+                                        ui.label(RichText::new(" win!").color(Color32::WHITE));
+                                    }
                                 }
-                            }
-                        } else {
+                            });
+                        }
+                        MatchRoundGameOverWinner::Side(side) => {
                             frame.content_ui.label(
-                                RichText::new(time_str)
-                                    .font(FontId::proportional(20.0))
-                                    .color(time_str_color),
+                                RichText::new(format!(
+                                    "{} wins!",
+                                    match side {
+                                        MatchSide::Red => "Red",
+                                        MatchSide::Blue => "Blue",
+                                    }
+                                ))
+                                .color(Color32::WHITE),
+                            );
+                        }
+                        MatchRoundGameOverWinner::SideNamed(name) => {
+                            frame.content_ui.label(
+                                RichText::new(format!("{} wins!", name.as_str()))
+                                    .color(Color32::WHITE),
                             );
-                        };
+                        }
+                    }
+                } else {
+                    frame.content_ui.label(
+                        RichText::new(time_str)
+                            .font(FontId::proportional(20.0))
+                            .color(time_str_color),
+                    );
+                };
 
-                        frame.allocate_space(ui);
-                        let rendered = render_side(pipe, ui, ui_state, Side::Right);
+                frame.allocate_space(ui);
+                frame.paint(ui);
+            })
+    } else {
+        None
+    };
+    let timer_size = timer_res.as_ref().map(|r| r.response.rect.size());
+    if let Some(res) = &timer_res {
+        ui.ctx()
+            .set_transform_layer(res.response.layer_id, layout.timer.transform(screen));
+    }
+    // the rect of the timer on the screen, after scaling
+    let timer_rect = timer_size.map(|size| layout.timer.rect(screen, size));
 
-                        if rendered {
-                            frame.frame.corner_radius.ne = 0;
-                            frame.frame.corner_radius.se = 0;
-                        }
-                        frame.paint(ui)
-                    },
-                )
-                .inner;
-            render_side(pipe, ui, ui_state, Side::Bottom(rect));
-        });
+    let has_scores =
+        is_game_over.is_none() && matches!(pipe.user_data.game, Some(GameRenderInfo::Match { .. }));
+    let scores_res = if layout.scores.visible && has_scores {
+        Window::new("hud_scores")
+            .resizable(false)
+            .title_bar(false)
+            .frame(Frame::NONE)
+            .anchor(layout.scores.anchor.align2(), layout.scores.offset)
+            .show(ui.ctx(), |ui| {
+                ui.set_clip_rect(screen);
+                ui.style_mut().spacing.item_spacing = Vec2::ZERO;
+                let row = ui
+                    .horizontal(|ui| {
+                        render_side(pipe, ui, ui_state, Side::Left);
+                        render_side(pipe, ui, ui_state, Side::Right);
+                    })
+                    .response
+                    .rect;
+                let bar_rect =
+                    Rect::from_min_size(ui.cursor().min, Vec2::new(row.width().max(120.0), 20.0));
+                render_side(pipe, ui, ui_state, Side::Bottom(bar_rect));
+            })
+    } else {
+        None
+    };
+    let scores_size = scores_res.as_ref().map(|r| r.response.rect.size());
+    if let Some(res) = &scores_res {
+        ui.ctx()
+            .set_transform_layer(res.response.layer_id, layout.scores.transform(screen));
+    }
+
+    // Time difference to the selected ghost
+    if let Some(split) = pipe.user_data.ghost_split {
+        let (sign, color) = if split.ahead {
+            ("-", Color32::LIGHT_GREEN)
+        } else {
+            ("+", Color32::LIGHT_RED)
+        };
+        Window::new("ghost_split_overlay")
+            .order(egui::Order::Tooltip)
+            .interactable(false)
+            .title_bar(false)
+            .resizable(false)
+            .frame(Frame::NONE)
+            .pivot(Align2::CENTER_TOP)
+            .fixed_pos(
+                timer_rect
+                    .map(|r| r.center_bottom())
+                    .unwrap_or_else(|| layout.timer.anchor_pos(screen))
+                    + Vec2::new(0.0, 5.0),
+            )
+            .show(ui.ctx(), |ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "{sign}{}.{:0>2}",
+                        split.diff.as_secs(),
+                        split.diff.subsec_millis() / 10
+                    ))
+                    .color(color),
+                );
+            });
+    }
+
+    // Whom the player is spectating
+    let spectator_res = if let Some(info) = pipe
+        .user_data
+        .spectator_info
+        .filter(|_| layout.spectator_info.visible)
+    {
+        let text = match info
+            .spectated
+            .and_then(|id| pipe.user_data.character_infos.get(&id))
+        {
+            Some(character) => format!("Spectating: {}", character.info.name.as_str()),
+            None => "Free view".to_string(),
+        };
+        Window::new("hud_spectator_info")
+            .interactable(false)
+            .title_bar(false)
+            .resizable(false)
+            .frame(
+                Frame::new()
+                    .fill(color_a(Color32::BLACK, 50))
+                    .inner_margin(Margin::same(MARGIN))
+                    .corner_radius(rounding),
+            )
+            .anchor(
+                layout.spectator_info.anchor.align2(),
+                layout.spectator_info.offset,
+            )
+            .show(ui.ctx(), |ui| {
+                ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Extend);
+                ui.label(RichText::new(text).color(Color32::WHITE));
+            })
+    } else {
+        None
+    };
+    let spectator_size = spectator_res.as_ref().map(|r| r.response.rect.size());
+    if let Some(res) = &spectator_res {
+        ui.ctx().set_transform_layer(
+            res.response.layer_id,
+            layout.spectator_info.transform(screen),
+        );
+    }
 
     if let Some((balance_msg, color)) = balance_msg {
         ui.scope_builder(
             UiBuilder::default().max_rect(
-                res.map(|r| {
-                    ui.ctx()
-                        .screen_rect()
-                        .translate(egui::vec2(0.0, r.response.rect.height()))
-                })
-                .unwrap_or_else(|| ui.ctx().screen_rect()),
+                timer_rect
+                    .map(|r| screen.translate(egui::vec2(0.0, r.max.y)))
+                    .unwrap_or(screen),
             ),
             |ui| {
                 ui.with_layout(
@@ -589,4 +646,24 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
             },
         );
     }
+
+    if pipe.user_data.editing {
+        // hidden elements still need a size to be selectable
+        let sizes = HudElement::ALL.map(|element| {
+            let size = match element {
+                HudElement::HealthAmmo => HEALTH_AMMO_SIZE,
+                HudElement::Timer => timer_size.unwrap_or(Vec2::new(100.0, 30.0)),
+                HudElement::Scores => scores_size.unwrap_or(Vec2::new(200.0, 80.0)),
+                HudElement::SpectatorInfo => spectator_size.unwrap_or(Vec2::new(150.0, 22.0)),
+                HudElement::Fps => FPS_SIZE,
+                HudElement::NetGraph => NET_GRAPH_SIZE,
+            };
+            (element, size)
+        });
+        if let Some(new_layout) = editor::render(ui, &layout, &sizes) {
+            pipe.user_data
+                .events
+                .push(HudEvent::LayoutChanged(new_layout));
+        }
+    }
 }
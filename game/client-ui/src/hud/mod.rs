@@ -1,3 +1,5 @@
+pub mod editor;
+pub mod layout;
 pub mod main_frame;
 pub mod page;
 pub mod user_data;
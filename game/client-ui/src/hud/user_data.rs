@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use base::linked_hash_map_view::FxLinkedHashMap;
 use client_containers::{ctf::CtfContainer, skins::SkinContainer};
 use client_render_base::render::tee::RenderTee;
@@ -12,12 +14,37 @@ use graphics::handles::{
 use pool::datatypes::PoolString;
 use serde::{Deserialize, Serialize};
 
+use super::layout::HudLayout;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RenderDateTime {
     pub time: PoolString,
     pub date: PoolString,
 }
 
+/// Time difference of the local player to the selected ghost
+/// at a matching position.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RenderGhostSplit {
+    /// The absolute time difference.
+    pub diff: Duration,
+    /// Whether the local player is ahead of the ghost.
+    pub ahead: bool,
+}
+
+/// Whom the local player is currently watching as spectator.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RenderSpectatorInfo {
+    /// `None` if the player uses the free view.
+    pub spectated: Option<CharacterId>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum HudEvent {
+    /// The user changed the layout in the HUD editor.
+    LayoutChanged(HudLayout),
+}
+
 pub struct UserData<'a> {
     pub canvas_handle: &'a GraphicsCanvasHandle,
     pub stream_handle: &'a GraphicsStreamHandle,
@@ -33,4 +60,11 @@ pub struct UserData<'a> {
     pub character_infos: &'a FxLinkedHashMap<CharacterId, CharacterInfo>,
 
     pub date_time: &'a Option<RenderDateTime>,
+    pub ghost_split: &'a Option<RenderGhostSplit>,
+    pub spectator_info: &'a Option<RenderSpectatorInfo>,
+
+    pub layout: &'a HudLayout,
+    /// Whether the HUD editor is shown.
+    pub editing: bool,
+    pub events: &'a mut Vec<HudEvent>,
 }
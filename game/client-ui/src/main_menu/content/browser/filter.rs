@@ -69,6 +69,20 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                         ui.checkbox(&mut filter.no_password, "No password");
                         ui.checkbox(&mut filter.unfinished_maps, "Unfinished maps only");
                         ui.checkbox(&mut filter.hide_legacy_servers, "Hide legacy servers");
+                        ui.horizontal(|ui| {
+                            ui.label("Max ping");
+                            ui.add(
+                                egui::DragValue::new(&mut filter.max_ping)
+                                    .range(0..=999)
+                                    .custom_formatter(|v, _| {
+                                        if v == 0.0 {
+                                            "off".to_string()
+                                        } else {
+                                            format!("{v} ms")
+                                        }
+                                    }),
+                            );
+                        });
                         if filter != prev_filter {
                             config.set_storage("browser_filter", &filter);
                         }
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use egui::Button;
 use egui_extras::TableRow;
 use game_base::server_browser::ServerBrowserServer;
@@ -8,6 +10,7 @@ use game_base::server_browser::ServerBrowserServer;
 pub fn render(
     mut row: TableRow<'_, '_>,
     server: &ServerBrowserServer,
+    ping: Option<Duration>,
     local_server: bool,
 ) -> (bool, bool) {
     let mut clicked_restart = false;
@@ -61,7 +64,13 @@ pub fn render(
                     .on_hover_text("Restart local server")
                     .clicked();
             } else {
-                clicked |= ui.label(server.location.as_str()).clicked();
+                clicked |= ui
+                    .label(
+                        ping.map(|ping| ping.as_millis().to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                    )
+                    .on_hover_text(server.location.as_str())
+                    .clicked();
             }
         })
         .1
@@ -195,8 +195,9 @@ pub fn render(mut body: TableBody<'_>, pipe: &mut UiRenderPipe<UserData>, cur_pa
             let server_addr = get_addr(&server.addresses);
             let is_selected = server_addr.to_string() == cur_addr;
             row.set_selected(is_selected);
+            let ping = pipe.user_data.browser_data.ping(server);
             let (clicked, restart_clicked) =
-                super::entry::render(row, server, cur_page == MENU_LAN_NAME);
+                super::entry::render(row, server, ping, cur_page == MENU_LAN_NAME);
             let clicked = clicked
                 || (cur_page == MENU_LAN_NAME && lan_server.len() == 1)
                 || select_index
@@ -11,6 +11,7 @@ use ui_base::{
 };
 
 use crate::{
+    demo_player::user_data::DemoViewerCameraMode,
     events::UiEvent,
     main_menu::{constants::MENU_DEMO_NAME, user_data::UserData},
 };
@@ -52,6 +53,15 @@ fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                 );
                 ui.end_row();
 
+                ui.label("Zoom:");
+                ui.add(
+                    DragValue::new(&mut config.cl.recorder.zoom)
+                        .update_while_editing(false)
+                        .speed(0.01)
+                        .range(0.25..=8.0),
+                );
+                ui.end_row();
+
                 ui.label("Crf (0 = lossless, 51 = worst):");
                 ui.add(
                     DragValue::new(&mut config.cl.recorder.crf)
@@ -94,6 +104,8 @@ fn record_settings(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     pipe.user_data.events.push(UiEvent::EncodeDemoToVideo {
                         name: demo_path,
                         video_name,
+                        camera_mode: DemoViewerCameraMode::Recorded,
+                        zoom: config.game.cl.recorder.zoom as f32,
                     });
                     config.path().query.remove("recorder-clicked");
                 }
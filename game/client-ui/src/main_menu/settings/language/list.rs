@@ -1,30 +1,14 @@
-use std::collections::{BTreeMap, HashMap};
-
 use client_containers::container::ContainerItemIndexType;
-use game_interface::types::resource_key::NetworkResourceKey;
+use i18n::languages::{LANGUAGES, Language};
 use ui_base::types::{UiRenderPipe, UiState};
 
 use crate::{main_menu::user_data::UserData, utils::render_flag_for_ui};
 
-struct Language {
-    flag: String,
-    code: String,
+fn language_by_name(name: &str) -> Option<&'static Language> {
+    LANGUAGES.iter().find(|lang| lang.name == name)
 }
 
 pub fn lang_list(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &mut UiState) {
-    let languages = [(
-        "english".to_string(),
-        Language {
-            flag: "us".to_string(),
-            code: "en".to_string(),
-        },
-    )]
-    .into_iter()
-    .collect::<HashMap<String, Language>>();
-    let entries_sorted = languages
-        .keys()
-        .map(|lang| (lang.to_string(), ContainerItemIndexType::Disk))
-        .collect::<BTreeMap<_, _>>();
     let setting = &mut pipe.user_data.config.game.cl.language;
     let search_str = pipe
         .user_data
@@ -35,20 +19,21 @@ pub fn lang_list(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state:
         .query
         .entry("lang-search".to_string())
         .or_default();
-    let mut next_name = None;
+    let mut next_code = None;
     super::super::list::list::render(
         ui,
-        entries_sorted.iter().map(|(name, &ty)| (name.as_str(), ty)),
+        LANGUAGES
+            .iter()
+            .map(|lang| (lang.name, ContainerItemIndexType::Disk)),
         50.0,
-        |_, name| {
-            let valid: Result<NetworkResourceKey<32>, _> = name.try_into();
-            valid.map(|_| ()).map_err(|err| err.into())
-        },
-        |_, name| setting == name,
+        |_, _| Ok(()),
+        |_, name| language_by_name(name).is_some_and(|lang| lang.code == setting.as_str()),
         |s| s,
         |ui, _, name, pos, asset_size| {
             let flag = pipe.user_data.flags_container.default_key.clone();
-            let name = &languages.get(name).unwrap().flag;
+            let Some(lang) = language_by_name(name) else {
+                return;
+            };
             render_flag_for_ui(
                 pipe.user_data.stream_handle,
                 pipe.user_data.canvas_handle,
@@ -58,19 +43,19 @@ pub fn lang_list(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state:
                 ui.ctx().screen_rect(),
                 Some(ui.clip_rect()),
                 &flag,
-                name,
+                lang.flag,
                 pos,
                 asset_size,
             );
         },
         |_, name| {
-            next_name = Some(languages.get(name).unwrap().code.to_string());
+            next_code = language_by_name(name).map(|lang| lang.code.to_string());
         },
         |_, _| None,
         search_str,
         |_| {},
     );
-    if let Some(next_name) = next_name.take() {
-        *setting = next_name;
+    if let Some(next_code) = next_code.take() {
+        *setting = next_code;
     }
 }
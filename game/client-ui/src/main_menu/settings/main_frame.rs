@@ -4,6 +4,7 @@ use egui_extras::{Size, StripBuilder};
 use tracing::instrument;
 use ui_base::{
     style::bg_frame_color,
+    translate::UiTranslate,
     types::{UiRenderPipe, UiState},
     utils::{add_margins, add_vertical_margins},
 };
@@ -12,6 +13,25 @@ use crate::main_menu::{constants::MENU_SETTINGS_NAME, user_data::UserData};
 
 use super::constants::{SETTINGS_SUB_UI_PAGE_QUERY, SETTINGS_UI_PAGE_QUERY};
 
+/// The translated label of a settings page.
+fn nav_label(ui: &egui::Ui, page: &str) -> String {
+    match page {
+        "General" => ui.tr("settings-general"),
+        "Language" => ui.tr("settings-language"),
+        "Player" => ui.tr("settings-player"),
+        "Tee" => ui.tr("settings-tee"),
+        "Misc" => ui.tr("settings-misc"),
+        "Assets" => ui.tr("settings-assets"),
+        "Chat" => ui.tr("settings-chat"),
+        "Controls" => ui.tr("settings-controls"),
+        "Graphics" => ui.tr("settings-graphics"),
+        "Sound" => ui.tr("settings-sound"),
+        "Spatial Chat" => ui.tr("settings-spatial-chat"),
+        "\u{1f50d} Settings" => ui.tr("settings-search"),
+        page => page.to_string(),
+    }
+}
+
 fn render_nav(
     ui: &mut egui::Ui,
     pipe: &mut UiRenderPipe<UserData>,
@@ -40,7 +60,7 @@ fn render_nav(
                     if submenu.is_none() {
                         entry.size = 16.0;
                     }
-                    let btn = ui.add(Button::new(s).frame(false));
+                    let btn = ui.add(Button::new(nav_label(ui, s)).frame(false));
                     let style = ui.style_mut();
                     let entry = style
                         .text_styles
@@ -130,6 +150,7 @@ fn render_nav(
                             add_btn(ui, "Tee", Some("Player"));
                             add_btn(ui, "Misc", Some("Player"));
                             add_btn(ui, "Assets", Some("Player"));
+                            add_btn(ui, "Chat", Some("Player"));
                             add_btn(ui, "Controls", Some("Player"));
                         },
                     );
@@ -0,0 +1,63 @@
+use egui::Layout;
+use tracing::instrument;
+use ui_base::{components::clearable_edit_field::clearable_edit_field, types::UiRenderPipe};
+
+use crate::main_menu::user_data::UserData;
+
+/// An editable list of words or names,
+/// empty entries are removed when they lose focus.
+fn edit_list(ui: &mut egui::Ui, label: &str, entries: &mut Vec<String>) {
+    ui.label(label);
+    let mut remove = None;
+    for (index, entry) in entries.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            let res = clearable_edit_field(ui, entry, Some(200.0), None);
+            if ui.button("\u{f2ed}").clicked()
+                || (entry.is_empty() && res.is_some_and(|res| res.lost_focus()))
+            {
+                remove = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove {
+        entries.remove(index);
+    }
+    if ui.button("\u{f0fe}").clicked() {
+        entries.push(Default::default());
+    }
+    ui.add_space(10.0);
+}
+
+#[instrument(level = "trace", skip_all)]
+pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    ui.with_layout(Layout::top_down(egui::Align::Min), |ui| {
+        let config = &mut pipe.user_data.config.game.cl.chat;
+
+        ui.checkbox(
+            &mut config.highlight_own_name,
+            "Highlight messages that mention your name",
+        );
+        ui.checkbox(
+            &mut config.highlight_notification,
+            "Show a notification for highlighted messages",
+        );
+        ui.add_space(10.0);
+        edit_list(
+            ui,
+            "Additional highlight words:",
+            &mut config.highlight_words,
+        );
+
+        edit_list(ui, "Ignored player names:", &mut config.ignored_names);
+        edit_list(ui, "Ignored account names:", &mut config.ignored_accounts);
+
+        ui.checkbox(
+            &mut config.logs,
+            "Save the chat of every server to a log file",
+        )
+        .on_hover_text(
+            "The logs can be searched ingame with the chat search bind.\n\
+            Changes apply when connecting to the next server.",
+        );
+    });
+}
@@ -7,6 +7,7 @@ use binds::binds::{
 };
 use egui::{Button, Color32, DragValue, Grid, Layout, ScrollArea};
 use egui_extras::{Size, StripBuilder};
+use game_config::config::ConfigTouchMoveLayout;
 use game_interface::types::weapons::WeaponType;
 use serde::{Deserialize, Serialize};
 use ui_base::types::UiRenderPipe;
@@ -79,6 +80,80 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                 ui.checkbox(&mut config.inp.use_dyncam, "");
                 ui.end_row();
 
+                // Gamepad input
+                ui.label("Use gamepads");
+                ui.checkbox(&mut config.inp.gamepad.enabled, "");
+                ui.end_row();
+                if config.inp.gamepad.enabled {
+                    ui.label("Gamepad aim sensitivity");
+                    ui.add(
+                        DragValue::new(&mut config.inp.gamepad.sensitivity)
+                            .update_while_editing(false)
+                            .range(0..=1000)
+                            .suffix("%"),
+                    );
+                    ui.end_row();
+                    ui.label("Gamepad aim deadzone");
+                    ui.add(
+                        DragValue::new(&mut config.inp.gamepad.deadzone)
+                            .update_while_editing(false)
+                            .range(0..=99)
+                            .suffix("%"),
+                    );
+                    ui.end_row();
+                    ui.label("Gamepad stick threshold for binds");
+                    ui.add(
+                        DragValue::new(&mut config.inp.gamepad.axis_threshold)
+                            .update_while_editing(false)
+                            .range(1..=100)
+                            .suffix("%"),
+                    );
+                    ui.end_row();
+                    ui.label("Aim with the left stick");
+                    ui.checkbox(&mut config.inp.gamepad.aim_with_left_stick, "");
+                    ui.end_row();
+                }
+
+                // Touch controls
+                ui.label("On-screen touch controls");
+                ui.checkbox(&mut config.inp.touch.enabled, "");
+                ui.end_row();
+                if config.inp.touch.enabled {
+                    ui.label("Touch movement");
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(
+                            &mut config.inp.touch.move_layout,
+                            ConfigTouchMoveLayout::Joystick,
+                            "Joystick",
+                        );
+                        ui.selectable_value(
+                            &mut config.inp.touch.move_layout,
+                            ConfigTouchMoveLayout::Buttons,
+                            "Buttons",
+                        );
+                    });
+                    ui.end_row();
+                    ui.label("Touch controls size");
+                    ui.add(
+                        DragValue::new(&mut config.inp.touch.size)
+                            .update_while_editing(false)
+                            .range(25..=300)
+                            .suffix("%"),
+                    );
+                    ui.end_row();
+                    ui.label("Touch controls opacity");
+                    ui.add(
+                        DragValue::new(&mut config.inp.touch.opacity)
+                            .update_while_editing(false)
+                            .range(0..=100)
+                            .suffix("%"),
+                    );
+                    ui.end_row();
+                    ui.label("Movement controls on the right");
+                    ui.checkbox(&mut config.inp.touch.swap_sides, "");
+                    ui.end_row();
+                }
+
                 // Movement controls
                 let mut inp = |label: &str, bind_action: BindAction| {
                     let mut keys = binds
@@ -376,6 +451,10 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     "Show chat history:",
                     BindAction::LocalPlayer(BindActionsLocalPlayer::ShowChatHistory),
                 );
+                inp(
+                    "Search chat logs:",
+                    BindAction::LocalPlayer(BindActionsLocalPlayer::ActivateChatSearchInput),
+                );
 
                 // Dummy
                 //inp("Toggle dummy:", BindActions::LocalPlayer(BindActionsLocalPlayer::ToggleDummy));
@@ -397,6 +476,10 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
                     "Spectate list:",
                     BindAction::LocalPlayer(BindActionsLocalPlayer::ShowSpectatorSelection),
                 );
+                inp(
+                    "Edit HUD layout:",
+                    BindAction::LocalPlayer(BindActionsLocalPlayer::ToggleHudEditor),
+                );
                 /*inp("Spectate next:", BindActions::LocalPlayer(BindActionsLocalPlayer::SpectateNext));
                 inp("Spectate previous:", BindActions::LocalPlayer(BindActionsLocalPlayer::SpectatePrev));
                 inp("Client console:", BindActions::LocalPlayer(BindActionsLocalPlayer::LocalConsole));
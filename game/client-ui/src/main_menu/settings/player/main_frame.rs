@@ -34,6 +34,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         "Assets" => {
             super::assets::main_frame::render(ui, pipe, ui_state);
         }
+        "Chat" => {
+            super::chat::main_frame::render(ui, pipe);
+        }
         #[cfg(feature = "binds")]
         "Controls" => {
             super::controls::main_frame::render(ui, pipe);
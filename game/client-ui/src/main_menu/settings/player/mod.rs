@@ -1,4 +1,5 @@
 pub mod assets;
+pub mod chat;
 // too annoying with wasm support
 #[cfg(feature = "binds")]
 pub mod controls;
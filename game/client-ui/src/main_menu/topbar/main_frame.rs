@@ -9,6 +9,7 @@ use ui_base::types::{UiRenderPipe, UiState};
 use ui_base::{
     components::menu_top_button::{MenuTopButtonProps, menu_top_button_icon},
     style::topbar_buttons,
+    translate::UiTranslate,
     utils::add_horizontal_margins,
 };
 
@@ -106,39 +107,29 @@ pub fn render(
                     |ui| {
                         match current_active.as_str() {
                             MENU_INTERNET_NAME | "" => {
-                                ui.label("Internet \u{f05a}").on_hover_text_at_pointer(
-                                    "The internet tab \
-                                            shows all servers.",
-                                );
+                                ui.label(format!("{} \u{f05a}", ui.tr("menu-internet")))
+                                    .on_hover_text_at_pointer(ui.tr("menu-internet-info"));
                             }
                             MENU_LAN_NAME => {
-                                ui.label("LAN \u{f05a}").on_hover_text_at_pointer(
-                                    "The LAN tab shows servers \
-                                            in your local network.",
-                                );
+                                ui.label(format!("{} \u{f05a}", ui.tr("menu-lan")))
+                                    .on_hover_text_at_pointer(ui.tr("menu-lan-info"));
                             }
                             MENU_SETTINGS_NAME => {
-                                ui.label("Settings \u{f05a}").on_hover_text_at_pointer(
-                                    "Change the settings of your client here.",
-                                );
+                                ui.label(format!("{} \u{f05a}", ui.tr("menu-settings")))
+                                    .on_hover_text_at_pointer(ui.tr("menu-settings-info"));
                             }
                             MENU_PROFILE_NAME => {
-                                ui.label("Profiles \u{f05a}").on_hover_text_at_pointer(
-                                    "Here you can manage your accounts, \
-                                            and select the current active one.",
-                                );
+                                ui.label(format!("{} \u{f05a}", ui.tr("menu-profiles")))
+                                    .on_hover_text_at_pointer(ui.tr("menu-profiles-info"));
                             }
                             MENU_FAVORITES_NAME => {
-                                ui.label("Favorites \u{f05a}").on_hover_text_at_pointer(
-                                    "The favorite tab shows servers \
-                                            that you marked with a \u{f005}.",
-                                );
+                                ui.label(format!("{} \u{f05a}", ui.tr("menu-favorites")))
+                                    .on_hover_text_at_pointer(ui.tr("menu-favorites-info"));
                             }
                             MENU_EXPLORE_COMMUNITIES_NAME => {
-                                ui.label("Explore communities \u{f05a}")
+                                ui.label(format!("{} \u{f05a}", ui.tr("menu-explore-communities")))
                                     .on_hover_text_at_pointer(
-                                        "This tab shows an overview over \
-                                            all existing communities.",
+                                        ui.tr("menu-explore-communities-info"),
                                     );
                             }
                             x if x.starts_with(MENU_COMMUNITY_PREFIX) => {
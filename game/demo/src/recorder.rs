@@ -72,7 +72,7 @@ pub struct DemoRecorder {
     /// Must stay to not be dropped
     thread_sender: Sender<DemoRecorderEvent>,
     /// the thread that writes all demo changes to disk
-    _writer_thread: JoinHandle<()>,
+    writer_thread: Option<JoinHandle<()>>,
 }
 
 impl DemoRecorder {
@@ -150,7 +150,7 @@ impl DemoRecorder {
             events: Default::default(),
 
             thread_sender,
-            _writer_thread: writer_thread,
+            writer_thread: Some(writer_thread),
         }
     }
 
@@ -552,6 +552,15 @@ impl DemoRecorder {
         }
     }
 
+    /// Finishes the demo, the returned thread ends
+    /// once the demo is completely written.
+    pub fn finish(mut self) -> JoinHandle<()> {
+        let writer_thread = self.writer_thread.take().unwrap();
+        // dropping writes the remaining chunks & closes the channel
+        drop(self);
+        writer_thread
+    }
+
     pub fn cancel(self) {
         self.thread_sender.send(DemoRecorderEvent::Cancel).unwrap();
     }
@@ -1,8 +1,37 @@
 use std::num::NonZeroU16;
 
-use map::{map::groups::layers::tiles::Tile, types::NonZeroU16MinusOne};
+use map::{
+    map::groups::layers::tiles::{MapTileLayerPhysicsTiles, Tile},
+    types::NonZeroU16MinusOne,
+};
 use serde::{Deserialize, Serialize};
 
+/// The physics layers an auto mapper can run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoMapperPhysicsLayer {
+    Game,
+    Front,
+    Tele,
+    Speedup,
+    Switch,
+    Tune,
+}
+
+impl AutoMapperPhysicsLayer {
+    /// The layer kind of the given tiles, `None` for arbitrary layers.
+    pub fn of(tiles: &MapTileLayerPhysicsTiles) -> Option<Self> {
+        match tiles {
+            MapTileLayerPhysicsTiles::Arbitrary(_) => None,
+            MapTileLayerPhysicsTiles::Game(_) => Some(Self::Game),
+            MapTileLayerPhysicsTiles::Front(_) => Some(Self::Front),
+            MapTileLayerPhysicsTiles::Tele(_) => Some(Self::Tele),
+            MapTileLayerPhysicsTiles::Speedup(_) => Some(Self::Speedup),
+            MapTileLayerPhysicsTiles::Switch(_) => Some(Self::Switch),
+            MapTileLayerPhysicsTiles::Tune(_) => Some(Self::Tune),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum AutoMapperModes {
     DesignTileLayer {
@@ -18,6 +47,18 @@ pub enum AutoMapperModes {
         /// also useful if the whole layer is always needed anyway.
         neighbouring_tiles: Option<NonZeroU16>,
     },
+    PhysicsTileLayer {
+        /// The physics layer this mode works on.
+        /// An auto mapper that supports multiple physics layers
+        /// returns one mode per layer.
+        layer: AutoMapperPhysicsLayer,
+        /// See [`AutoMapperModes::DesignTileLayer`].
+        neighbouring_tiles: Option<NonZeroU16>,
+        /// If `true` the auto mapper wants the tiles of a design
+        /// tile layer as additional input, e.g. to derive
+        /// unhookable tiles from the design.
+        design_layer_input: bool,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,11 +80,36 @@ pub enum AutoMapperInputModes {
         /// __Should not be used for accessing tiles or calculating indices.__
         full_height: NonZeroU16MinusOne,
     },
+    PhysicsTileLayer {
+        /// The tiles of the physics layer, including the
+        /// extra tile data like tele numbers or switch delays.
+        tiles: MapTileLayerPhysicsTiles,
+        /// The tiles of the design layer at the same positions,
+        /// if requested by [`AutoMapperModes::PhysicsTileLayer`].
+        /// Positions outside of the design layer are air.
+        design_tiles: Option<Vec<Tile>>,
+        width: NonZeroU16MinusOne,
+        height: NonZeroU16MinusOne,
+        /// See [`AutoMapperInputModes::DesignTileLayer`].
+        off_x: u16,
+        /// See [`AutoMapperInputModes::DesignTileLayer`].
+        off_y: u16,
+        /// See [`AutoMapperInputModes::DesignTileLayer`].
+        full_width: NonZeroU16MinusOne,
+        /// See [`AutoMapperInputModes::DesignTileLayer`].
+        full_height: NonZeroU16MinusOne,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AutoMapperOutputModes {
-    DesignTileLayer { tiles: Vec<Tile> },
+    DesignTileLayer {
+        tiles: Vec<Tile>,
+    },
+    /// Must be the same kind of physics tiles as the input.
+    PhysicsTileLayer {
+        tiles: MapTileLayerPhysicsTiles,
+    },
 }
 
 pub trait AutoMapperInterface {
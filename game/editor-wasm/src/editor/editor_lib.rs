@@ -6,6 +6,7 @@ pub mod editor_lib {
     use editor::editor::{EditorInterface, EditorResult};
     use egui::FontDefinitions;
     use graphics::graphics::graphics::Graphics;
+    use i18n::translations::Translations;
     use sound::sound::SoundManager;
 
     type EditorLibFunc<'a> = libloading::Symbol<
@@ -82,6 +83,19 @@ pub mod editor_lib {
                 func(file);
             }
         }
+
+        fn set_translations(&mut self, translations: Translations) {
+            unsafe {
+                let func: libloading::Symbol<unsafe extern "Rust" fn(Translations) -> ()> = self
+                    .lib
+                    .as_ref()
+                    .unwrap()
+                    .get(b"editor_set_translations")
+                    .unwrap();
+
+                func(translations);
+            }
+        }
     }
 
     impl Drop for EditorLib {
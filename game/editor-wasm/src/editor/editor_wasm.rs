@@ -12,6 +12,7 @@ pub mod editor_wasm {
     use egui::FontDefinitions;
     use graphics::graphics::graphics::Graphics;
     use graphics_backend::backend::GraphicsBackend;
+    use i18n::translations::Translations;
     use sound::sound::SoundManager;
     use wasm_logic_fs::fs::WasmFileSystemLogic;
     use wasm_logic_graphics::WasmGraphicsLogic;
@@ -72,5 +73,8 @@ pub mod editor_wasm {
 
         #[wasm_func_auto_call]
         fn file_hovered(&mut self, file: Option<PathBuf>) {}
+
+        #[wasm_func_auto_call]
+        fn set_translations(&mut self, translations: Translations) {}
     }
 }
@@ -8,6 +8,7 @@ use editor::editor::{Editor, EditorInterface, EditorResult};
 use egui::FontDefinitions;
 use graphics::graphics::graphics::Graphics;
 use graphics_backend::backend::GraphicsBackend;
+use i18n::translations::Translations;
 use rayon::ThreadPool;
 use sound::sound::SoundManager;
 use tracing::instrument;
@@ -57,6 +58,7 @@ impl EditorWasmManager {
         io: &Io,
         thread_pool: &Arc<ThreadPool>,
         font_data: &FontDefinitions,
+        translations: &Translations,
     ) -> Self {
         let cache = Arc::new(Cache::<20250506>::new(MODS_PATH, io));
         // check if loading was finished
@@ -108,11 +110,13 @@ impl EditorWasmManager {
                 EditorWrapper::Native(Box::new(state))
             }
         };
-        Self {
+        let mut res = Self {
             state,
             fs_change_watcher,
             fs_change_watcher_lib,
-        }
+        };
+        res.set_translations(translations.clone());
+        res
     }
 
     pub fn should_reload(&self) -> bool {
@@ -135,6 +139,11 @@ impl EditorInterface for EditorWasmManager {
     fn file_hovered(&mut self, file: Option<PathBuf>) {
         self.state.as_mut().file_hovered(file)
     }
+
+    #[instrument(level = "trace", skip_all)]
+    fn set_translations(&mut self, translations: Translations) {
+        self.state.as_mut().set_translations(translations)
+    }
 }
 
 #[derive(Default)]
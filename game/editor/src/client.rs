@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     collections::VecDeque,
     sync::{Arc, atomic::AtomicBool},
     time::Duration,
@@ -23,9 +24,11 @@ use crate::{
     action_logic::{redo_action, undo_action},
     actions::actions::{EditorAction, EditorActionGroup},
     event::{
-        ActionDbg, AdminChangeConfig, AdminConfigState, ClientProps, EditorCommand, EditorEvent,
-        EditorEventAutoMap, EditorEventClientToServer, EditorEventGenerator, EditorEventLayerIndex,
-        EditorEventOverwriteMap, EditorEventRuleTy, EditorEventServerToClient, EditorNetEvent,
+        ActionDbg, AdminChangeConfig, AdminChangePermissions, AdminConfigState, ClientProps,
+        EditorCommand, EditorEvent, EditorEventAutoMap, EditorEventClientToServer,
+        EditorEventGenerator, EditorEventLayerIndex, EditorEventOverwriteMap, EditorEventRuleTy,
+        EditorEventServerToClient, EditorLockTarget, EditorNetEvent, EditorPermissions,
+        EditorResync,
     },
     map::{EditorLayer, EditorLayerTile, EditorMap},
     network::{EditorNetwork, NetworkState},
@@ -47,6 +50,7 @@ pub struct EditorClient {
     pub(crate) clients: Vec<ClientProps>,
     pub(crate) server_id: u64,
     pub(crate) allows_remote_admin: bool,
+    pub(crate) permissions: EditorPermissions,
 
     pub(crate) msgs: VecDeque<(String, String)>,
 
@@ -54,12 +58,37 @@ pub struct EditorClient {
     pub(crate) redo_label: Option<String>,
 
     pub(crate) should_save: bool,
+    /// Increased every time the map changed, e.g. to invalidate caches.
+    pub(crate) map_generation: u64,
 
     last_keep_alive_id_and_time: (Option<u64>, Duration),
     time: SteadyClock,
 
     mapper_name: String,
     color: [u8; 3],
+
+    // for reconnecting
+    server_addr: String,
+    server_cert: NetworkClientCertCheckMode<'static>,
+    server_password: String,
+    session_id: Option<u64>,
+    next_event_id: u64,
+}
+
+fn cert_check_mode_owned(mode: &NetworkClientCertCheckMode) -> NetworkClientCertCheckMode<'static> {
+    match mode {
+        NetworkClientCertCheckMode::CheckByCert { cert } => {
+            NetworkClientCertCheckMode::CheckByCert {
+                cert: Cow::Owned(cert.to_vec()),
+            }
+        }
+        NetworkClientCertCheckMode::CheckByPubKeyHash { hash } => {
+            NetworkClientCertCheckMode::CheckByPubKeyHash {
+                hash: Cow::Owned(hash.clone().into_owned()),
+            }
+        }
+        NetworkClientCertCheckMode::DisableCheck => NetworkClientCertCheckMode::DisableCheck,
+    }
 }
 
 impl EditorClient {
@@ -76,6 +105,7 @@ impl EditorClient {
         let has_events: Arc<AtomicBool> = Default::default();
         let event_generator = Arc::new(EditorEventGenerator::new(has_events.clone()));
 
+        let server_cert = cert_check_mode_owned(&server_info);
         let res = Self {
             network: EditorNetwork::new_client(
                 time,
@@ -91,6 +121,7 @@ impl EditorClient {
             clients: Default::default(),
             server_id: Default::default(),
             allows_remote_admin: false,
+            permissions: Default::default(),
             msgs: Default::default(),
 
             undo_label: None,
@@ -103,17 +134,57 @@ impl EditorClient {
             time: time.clone(),
 
             should_save: !local_client,
+            map_generation: 0,
+
+            server_addr: server_addr.to_string(),
+            server_cert,
+            server_password,
+            session_id: None,
+            next_event_id: 0,
         };
 
-        res.network
+        res.send_auth();
+
+        res
+    }
+
+    fn send_auth(&self) {
+        self.network
             .send(EditorEvent::Client(EditorEventClientToServer::Auth {
-                password: server_password,
-                is_local_client: local_client,
-                mapper_name: res.mapper_name.clone(),
-                color: res.color,
+                password: self.server_password.clone(),
+                is_local_client: self.local_client,
+                mapper_name: self.mapper_name.clone(),
+                color: self.color,
+                resync: self.session_id.map(|session_id| EditorResync {
+                    session_id,
+                    next_event_id: self.next_event_id,
+                }),
             }));
+    }
 
-        res
+    /// Only remote clients can reconnect, the local client
+    /// shares the lifetime of the server.
+    pub fn can_reconnect(&self) -> bool {
+        !self.local_client
+    }
+
+    /// Connects to the server again. The server then only sends
+    /// the actions that were missed since the connection dropped,
+    /// or the whole map if they are not known anymore.
+    pub fn reconnect(&mut self) {
+        let has_events: Arc<AtomicBool> = Default::default();
+        let event_generator = Arc::new(EditorEventGenerator::new(has_events.clone()));
+        self.network = EditorNetwork::new_client(
+            &self.time,
+            event_generator.clone(),
+            &self.server_addr,
+            cert_check_mode_owned(&self.server_cert),
+        );
+        self.has_events = has_events;
+        self.event_generator = event_generator;
+        self.last_keep_alive_id_and_time = (None, self.time.now());
+
+        self.send_auth();
     }
 
     pub fn net_state(&self) -> NetworkState {
@@ -227,13 +298,17 @@ impl EditorClient {
                                 action,
                                 redo_label,
                                 undo_label,
+                                event_id,
                             }
                             | EditorEventServerToClient::UndoAction {
                                 action,
                                 redo_label,
                                 undo_label,
+                                event_id,
                             } => {
                                 self.should_save = true;
+                                self.map_generation += 1;
+                                self.next_event_id = event_id + 1;
                                 if !self.local_client {
                                     let actions: Box<dyn Iterator<Item = _>> = if undo_event {
                                         Box::new(action.actions.into_iter().rev())
@@ -273,6 +348,8 @@ impl EditorClient {
                                 self.notifications.push(EditorNotification::Error(err));
                             }
                             EditorEventServerToClient::Map(map) => {
+                                self.map_generation += 1;
+                                self.next_event_id = map.next_event_id;
                                 res = Some(map);
                             }
                             EditorEventServerToClient::Infos(infos) => {
@@ -281,8 +358,10 @@ impl EditorClient {
                             EditorEventServerToClient::Info {
                                 server_id,
                                 allows_remote_admin,
+                                session_id,
                             } => {
                                 self.server_id = server_id;
+                                self.session_id = Some(session_id);
                                 self.allows_remote_admin = allows_remote_admin;
                             }
                             EditorEventServerToClient::Chat { from, msg } => {
@@ -310,6 +389,9 @@ impl EditorClient {
                                     state.state = cur_state;
                                 }
                             }
+                            EditorEventServerToClient::Permissions(permissions) => {
+                                self.permissions = permissions;
+                            }
                             EditorEventServerToClient::AutoMapRuleNotFound(auto_map) => {
                                 if self.send_auto_map_rule(&auto_map, auto_mapper) {
                                     self.network.send(EditorEvent::Client(
@@ -452,6 +534,28 @@ impl EditorClient {
         ));
     }
 
+    pub fn admin_change_permissions(&self, change: AdminChangePermissions) {
+        self.network.send(EditorEvent::Client(
+            EditorEventClientToServer::AdminChangePermissions(change),
+        ));
+    }
+
+    pub fn lock(&self, target: EditorLockTarget, lock: bool) {
+        self.network
+            .send(EditorEvent::Client(EditorEventClientToServer::Lock {
+                target,
+                lock,
+            }));
+    }
+
+    /// The client that locked the target, if it is not this client.
+    pub fn foreign_lock_owner(&self, target: &EditorLockTarget) -> Option<&ClientProps> {
+        self.permissions
+            .lock_owner(target)
+            .filter(|owner| *owner != self.server_id)
+            .and_then(|owner| self.clients.iter().find(|c| c.server_id == owner))
+    }
+
     pub fn dbg_action(&self, props: ActionDbg) {
         self.network
             .send(EditorEvent::Client(EditorEventClientToServer::DbgAction(
@@ -43,7 +43,9 @@ use graphics::{
 };
 use graphics_types::{commands::TexFlags, rendering::State, types::GraphicsMemoryAllocationType};
 use hiarc::HiarcTrait;
+use i18n::translations::Translations;
 use image_utils::{png::load_png_image_as_rgba, utils::texture_2d_to_3d};
+use legacy_map::mapdef_06::EntityTiles;
 use map::{
     file::MapFileReader,
     map::{
@@ -86,7 +88,7 @@ use sound::{
     scene_handle::SoundSceneHandle, scene_object::SceneObject, sound::SoundManager,
     sound_mt::SoundMultiThreaded,
 };
-use ui_base::ui::UiCreator;
+use ui_base::{translate::set_translations, ui::UiCreator};
 
 use crate::{
     client::EditorClient,
@@ -95,6 +97,7 @@ use crate::{
     fs::{read_file_editor, write_file_editor},
     hotkeys::{BindsPerEvent, EditorBindsFile, EditorHotkeyEvent},
     image_store_container::{ImageStoreContainer, load_image_store_container},
+    journal::{EditorJournal, EditorJournalEntry, EditorJournalSavePoint},
     map::{
         EditorActiveAnimationProps, EditorAnimationProps, EditorAnimations, EditorAnimationsProps,
         EditorArbitraryLayerProps, EditorColorAnimation, EditorCommonGroupOrLayerAttr,
@@ -109,6 +112,7 @@ use crate::{
         EditorSoundLayerProps, EditorTileLayerProps, EditorTileLayerPropsSelection,
         ResourceSelection,
     },
+    map_import::EditorMapImport,
     map_tools::{
         finish_design_quad_layer_buffer, finish_design_tile_layer_buffer,
         finish_physics_layer_buffer, upload_design_quad_layer_buffer,
@@ -117,6 +121,7 @@ use crate::{
     notifications::{EditorNotification, EditorNotifications},
     options::EditorOptions,
     physics_layers::PhysicsLayerOverlaysDdnet,
+    prefab::EditorPrefabs,
     server::EditorServer,
     sound_store_container::{SoundStoreContainer, load_sound_store_container},
     tab::EditorTab,
@@ -199,6 +204,7 @@ pub struct Editor {
     // editor tool
     tools: Tools,
     auto_mapper: TileLayerAutoMapper,
+    prefabs: EditorPrefabs,
 
     editor_options: EditorOptions,
 
@@ -239,7 +245,8 @@ pub struct Editor {
 
     hovered_file: Option<PathBuf>,
 
-    save_tasks: Vec<IoRuntimeTask<()>>,
+    save_tasks: Vec<(IoRuntimeTask<()>, Option<EditorJournalSavePoint>)>,
+    playtest_task: Option<IoRuntimeTask<EditorPlaytest>>,
 }
 
 #[derive(Debug, Clone)]
@@ -377,6 +384,7 @@ impl Editor {
             cached_binds_per_event: None,
 
             auto_mapper: TileLayerAutoMapper::new(graphics, io.clone().into(), tp.clone()),
+            prefabs: EditorPrefabs::new(io.clone().into(), tp.clone()),
             middle_down_pointer_pos: None,
             current_scroll_delta: Default::default(),
             current_pointer_pos: Default::default(),
@@ -414,6 +422,7 @@ impl Editor {
             thread_pool: tp.clone(),
 
             save_tasks: Default::default(),
+            playtest_task: None,
 
             hovered_file: Default::default(),
 
@@ -627,12 +636,17 @@ impl Editor {
                 dbg_panel: Default::default(),
                 assets_store: Default::default(),
                 assets_store_open: Default::default(),
+                history_open: false,
+                physics_numbers: Default::default(),
+                journal_recovery: None,
+                map_import: None,
+                reachability: Default::default(),
             },
         );
         self.active_tab = name.into();
     }
 
-    fn map_to_editor_map_impl(
+    pub(crate) fn map_to_editor_map_impl(
         graphics_mt: GraphicsMultiThreaded,
         sound_mt: SoundMultiThreaded,
         tp: &Arc<rayon::ThreadPool>,
@@ -1137,13 +1151,7 @@ impl Editor {
         Ok(name.replace(".twmap", ""))
     }
 
-    fn load_legacy_map(
-        &mut self,
-        path: &Path,
-        options: MapLoadWithServerOptions,
-    ) -> anyhow::Result<()> {
-        let name = Self::path_to_tab_name(path)?;
-
+    fn read_legacy_map(&self, path: &Path) -> anyhow::Result<(Map, HashMap<Hash, Vec<u8>>)> {
         let tp = self.thread_pool.clone();
         let fs = self.io.fs.clone();
         let path_buf = path.to_path_buf();
@@ -1176,7 +1184,18 @@ impl Editor {
                     .map(|(hash, res)| (hash, res.buf)),
             )
             .collect();
-        let map = self.map_to_editor_map(map.map, resources);
+        Ok((map.map, resources))
+    }
+
+    fn load_legacy_map(
+        &mut self,
+        path: &Path,
+        options: MapLoadWithServerOptions,
+    ) -> anyhow::Result<()> {
+        let name = Self::path_to_tab_name(path)?;
+
+        let (map, resources) = self.read_legacy_map(path)?;
+        let map = self.map_to_editor_map(map, resources);
 
         let server = EditorServer::new(
             &self.time,
@@ -1230,9 +1249,15 @@ impl Editor {
                 dbg_panel: Default::default(),
                 assets_store: Default::default(),
                 assets_store_open: Default::default(),
+                history_open: false,
+                physics_numbers: Default::default(),
+                journal_recovery: None,
+                map_import: None,
+                reachability: Default::default(),
             },
         );
-        self.active_tab = name;
+        self.active_tab = name.clone();
+        self.init_tab_journal(&name);
 
         Ok(())
     }
@@ -1251,19 +1276,11 @@ impl Editor {
         )
     }
 
-    fn load_map_impl(
-        &mut self,
-        path: &Path,
-        options: MapLoadWithServerOptions,
-    ) -> anyhow::Result<()> {
-        let name = Self::path_to_tab_name(path)?;
-
+    fn read_map(&self, path: &Path) -> anyhow::Result<(Map, HashMap<Hash, Vec<u8>>)> {
         let fs = self.io.fs.clone();
         let tp = self.thread_pool.clone();
-        let load_path = path.to_path_buf();
         let path = path.to_path_buf();
-        let (map, resources) = self
-            .io
+        self.io
             .rt
             .spawn(async move {
                 let file = read_file_editor(&fs, &path).await?;
@@ -1319,8 +1336,18 @@ impl Editor {
 
                 Ok((map, resource_files))
             })
-            .get()?;
+            .get()
+    }
 
+    fn load_map_impl(
+        &mut self,
+        path: &Path,
+        options: MapLoadWithServerOptions,
+    ) -> anyhow::Result<()> {
+        let name = Self::path_to_tab_name(path)?;
+
+        let load_path = path.to_path_buf();
+        let (map, resources) = self.read_map(path)?;
         let map = self.map_to_editor_map(map, resources);
 
         let server = EditorServer::new(
@@ -1375,13 +1402,118 @@ impl Editor {
                 dbg_panel: Default::default(),
                 assets_store: Default::default(),
                 assets_store_open: Default::default(),
+                history_open: false,
+                physics_numbers: Default::default(),
+                journal_recovery: None,
+                map_import: None,
+                reachability: Default::default(),
             },
         );
-        self.active_tab = name;
+        self.active_tab = name.clone();
+        self.init_tab_journal(&name);
 
         Ok(())
     }
 
+    /// Starts journaling the actions of the tab. If the journal still contains
+    /// entries of a previous session, the user is asked to recover them.
+    fn init_tab_journal(&mut self, name: &str) {
+        let Some(tab) = self.tabs.get_mut(name) else {
+            return;
+        };
+        let (Some(server), Some(path)) = (&mut tab.server, &tab.auto_saver.path) else {
+            return;
+        };
+        let entries = match EditorJournal::read(&self.io, path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                log::warn!("reading the editor journal failed: {err}");
+                Default::default()
+            }
+        };
+        server.set_journal(Some(
+            EditorJournal::new(&self.io, path).with_entries(&entries),
+        ));
+        if !entries.is_empty() {
+            tab.journal_recovery = Some(entries);
+            self.ui.modal_dialog_mode = EditorModalDialogMode::RecoverJournal {
+                tab: name.to_string(),
+            };
+        }
+    }
+
+    /// Clears the journal of the tab and applies the given entries
+    /// again, which also writes them to the cleared journal.
+    fn replay_tab_journal(&mut self, name: &str, entries: Vec<EditorJournalEntry>) {
+        let Some(tab) = self.tabs.get_mut(name) else {
+            return;
+        };
+        let Some(server) = &mut tab.server else {
+            return;
+        };
+        if let Some(journal) = server.journal_mut() {
+            journal.reset();
+        }
+        if entries.is_empty() {
+            return;
+        }
+        let res = server.replay_journal(
+            entries,
+            &self.thread_pool,
+            &self.sound_mt,
+            &self.graphics.get_graphics_mt(),
+            &self.graphics.shader_storage_handle,
+            &self.graphics.buffer_object_handle,
+            &self.graphics.backend_handle,
+            &self.graphics.texture_handle,
+            &mut tab.map,
+            &mut self.notifications_overlay,
+        );
+        tab.client.should_save = true;
+        if let Err(err) = res {
+            log::error!("{err}");
+            self.notifications_overlay
+                .add_err(err.to_string(), Duration::from_secs(10));
+        }
+    }
+
+    /// Reloads the last save of the tab and replays its journal
+    /// up to (including) the entry at `index`.
+    fn restore_tab_journal(&mut self, name: &str, index: usize) {
+        let Some(path) = self
+            .tabs
+            .get(name)
+            .and_then(|tab| tab.auto_saver.path.clone())
+        else {
+            return;
+        };
+        let mut entries = match EditorJournal::read(&self.io, &path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                log::error!("{err}");
+                self.notifications_overlay
+                    .add_err(err.to_string(), Duration::from_secs(10));
+                return;
+            }
+        };
+        entries.truncate(index + 1);
+
+        self.load_map(&path, Default::default());
+        let name = self.active_tab.clone();
+        let Some(tab) = self.tabs.get_mut(&name) else {
+            return;
+        };
+        // the entries are replayed right away, no need to ask the user
+        tab.journal_recovery = None;
+        if matches!(
+            &self.ui.modal_dialog_mode,
+            EditorModalDialogMode::RecoverJournal { tab } if *tab == name
+        ) {
+            self.ui.modal_dialog_mode = EditorModalDialogMode::None;
+        }
+        self.replay_tab_journal(&name, entries);
+    }
+
     /// Loads either a legacy or new map based on the file extension.
     fn load_map(&mut self, path: &Path, options: MapLoadWithServerOptions) {
         let res = if path.extension().is_some_and(|ext| ext == "map") {
@@ -1396,6 +1528,27 @@ impl Editor {
         }
     }
 
+    /// Reads another map, parts of it can then be imported into the active tab.
+    fn import_map(&mut self, path: &Path) {
+        let res = if path.extension().is_some_and(|ext| ext == "map") {
+            self.read_legacy_map(path)
+        } else {
+            self.read_map(path)
+        };
+        match res {
+            Ok((map, resources)) => {
+                if let Some(tab) = self.tabs.get_mut(&self.active_tab) {
+                    tab.map_import = Some(EditorMapImport::new(path, map, resources));
+                }
+            }
+            Err(err) => {
+                log::error!("{err}");
+                self.notifications_overlay
+                    .add_err(err.to_string(), Duration::from_secs(10));
+            }
+        }
+    }
+
     pub fn save_map_legacy(
         tab: &mut EditorTab,
         io: &Io,
@@ -1448,8 +1601,14 @@ impl Editor {
     ) -> (Map, HashMap<String, Vec<u8>>, PathBuf) {
         tab.auto_saver.path = Some(path.to_path_buf());
         let map: Map = tab.map.clone().into();
-        let resources = tab
-            .map
+        let resources = Self::tab_resource_files(tab);
+        (map, resources, path.to_path_buf())
+    }
+
+    /// All resource files of the tab's map, keyed by their path
+    /// relative to the data directory.
+    fn tab_resource_files(tab: &EditorTab) -> HashMap<String, Vec<u8>> {
+        tab.map
             .resources
             .images
             .iter()
@@ -1543,8 +1702,7 @@ impl Editor {
                 )
                 .collect::<Vec<_>>()
             }))
-            .collect::<HashMap<_, _>>();
-        (map, resources, path.to_path_buf())
+            .collect::<HashMap<_, _>>()
     }
 
     pub fn save_map_tab(
@@ -1552,15 +1710,19 @@ impl Editor {
         io: &Io,
         tp: &Arc<rayon::ThreadPool>,
 
-        save_tasks: &mut Vec<IoRuntimeTask<()>>,
+        save_tasks: &mut Vec<(IoRuntimeTask<()>, Option<EditorJournalSavePoint>)>,
         notifications_overlay: &mut ClientNotifications,
         path: &Path,
     ) {
         tab.client.should_save = false;
+        let journal_save = tab
+            .server
+            .as_mut()
+            .and_then(|server| Self::journal_save_point(server, io, path));
         if path.extension().is_some_and(|ext| ext == "map") {
             match Self::save_map_legacy(tab, io, tp, path) {
                 Ok(task) => {
-                    save_tasks.push(task);
+                    save_tasks.push((task, journal_save));
                 }
                 Err(err) => {
                     log::error!("{err}");
@@ -1572,7 +1734,7 @@ impl Editor {
             let tp = tp.clone();
             let fs = io.fs.clone();
 
-            save_tasks.push(io.rt.spawn(async move {
+            let task = io.rt.spawn(async move {
                 fs.create_dir("map/maps".as_ref()).await?;
                 fs.create_dir("map/resources/images".as_ref()).await?;
                 fs.create_dir("map/resources/sounds".as_ref()).await?;
@@ -1585,10 +1747,29 @@ impl Editor {
                     fs.write_file(path.as_ref(), resource).await?;
                 }
                 Ok(())
-            }));
+            });
+            save_tasks.push((task, journal_save));
         }
     }
 
+    /// The journal only contains changes since the last save, the saved
+    /// entries are removed once the save succeeded.
+    /// Maps that were never saved get a new journal.
+    fn journal_save_point(
+        server: &mut EditorServer,
+        io: &Io,
+        path: &Path,
+    ) -> Option<EditorJournalSavePoint> {
+        if server.journal().is_none() {
+            server.set_journal(Some(EditorJournal::new(io, path)));
+        }
+        let journal = server.journal_mut()?;
+        if journal.path() != EditorJournal::path_for_map(path) {
+            journal.set_map_path(path);
+        }
+        Some(journal.save_point())
+    }
+
     pub fn save_map(&mut self, path: &Path) {
         if let Some(tab) = self.tabs.get_mut(&self.active_tab) {
             Self::save_map_tab(
@@ -1663,6 +1844,74 @@ impl Editor {
         all_saved
     }
 
+    /// Serializes the active tab's map as playtest map, without touching
+    /// the tab's save path or the file system. Once serialized, the editor
+    /// asks the client to start a local server on it.
+    fn playtest(&mut self) {
+        if self.playtest_task.is_some() {
+            return;
+        }
+        let Some(tab) = self.tabs.get(&self.active_tab) else {
+            let msg = "No map was loaded to be playtested.";
+            log::info!("{msg}");
+            self.notifications_overlay
+                .add_err(msg, Duration::from_secs(10));
+            return;
+        };
+
+        let mut map: Map = tab.map.clone().into();
+        let resources = Self::tab_resource_files(tab);
+
+        if self.editor_options.playtest_spawn_at_camera {
+            let pos = tab.map.groups.user.pos;
+            let width = map.groups.physics.attr.width.get() as usize;
+            let height = map.groups.physics.attr.height.get() as usize;
+            if let Some(MapLayerPhysics::Game(layer)) = map
+                .groups
+                .physics
+                .layers
+                .iter_mut()
+                .find(|layer| matches!(layer, MapLayerPhysics::Game(_)))
+                && pos.x >= 0.0
+                && pos.y >= 0.0
+                && (pos.x as usize) < width
+                && (pos.y as usize) < height
+            {
+                // the camera spawn must be the only spawn, else the
+                // game mod might pick another one
+                let spawns = [
+                    EntityTiles::Spawn as u8,
+                    EntityTiles::SpawnRed as u8,
+                    EntityTiles::SpawnBlue as u8,
+                ];
+                for tile in layer.tiles.iter_mut() {
+                    if spawns.contains(&tile.index) {
+                        tile.index = 0;
+                    }
+                }
+                layer.tiles[pos.y as usize * width + pos.x as usize].index =
+                    EntityTiles::Spawn as u8;
+            } else {
+                let msg = "The camera is outside of the game layer, \
+                    the map's spawns are used instead.";
+                log::info!("{msg}");
+                self.notifications_overlay
+                    .add_warn(msg, Duration::from_secs(5));
+            }
+        }
+
+        let game_mod = self.editor_options.playtest_game_mod.clone();
+        let tp = self.thread_pool.clone();
+        self.playtest_task = Some(self.io.rt.spawn(async move {
+            Ok(EditorPlaytest {
+                map_name: PLAYTEST_MAP_NAME.to_string(),
+                game_mod,
+                map_file: map.write(&tp)?,
+                resource_files: resources,
+            })
+        }));
+    }
+
     fn update(&mut self) {
         let time_now = self.time.now();
         let time_diff = time_now - self.last_time;
@@ -1690,6 +1939,7 @@ impl Editor {
                 map,
                 resources,
                 live_edited_layers,
+                ..
             })) = update_res
             {
                 let map = Map::read(&MapFileReader::new(map).unwrap(), &self.thread_pool).unwrap();
@@ -2218,6 +2468,29 @@ impl Editor {
                 .clamp(0.2, 200.0);
             }
 
+            // pick the start of the reachability preview instead of using the tools
+            if tab.reachability.picking_start {
+                if self.latest_pointer.primary_pressed() {
+                    let pos = ui_pos_to_world_pos(
+                        &self.graphics.canvas_handle,
+                        ui_canvas,
+                        tab.map.groups.user.zoom,
+                        vec2::new(self.current_pointer_pos.x, self.current_pointer_pos.y),
+                        tab.map.groups.user.pos.x,
+                        tab.map.groups.user.pos.y,
+                        0.0,
+                        0.0,
+                        100.0,
+                        100.0,
+                        tab.map.groups.user.parallax_aware_zoom,
+                    );
+                    tab.reachability.picking_start = false;
+                    tab.reachability.start = Some(pos);
+                    tab.reachability.compute(&tab.map, &self.io);
+                }
+                return;
+            }
+
             // change active tool set
             match tab.map.active_layer() {
                 Some(layer) => match layer {
@@ -2600,6 +2873,18 @@ impl Editor {
         self.graphics.stream_handle.render_lines(&lines, state);
     }
 
+    fn render_reachability(&self) {
+        let Some(tab) = self.tabs.get(&self.active_tab) else {
+            return;
+        };
+
+        tab.reachability.render(
+            &self.graphics.canvas_handle,
+            &self.graphics.stream_handle,
+            &tab.map,
+        );
+    }
+
     fn render_ui(
         &mut self,
         input: egui::RawInput,
@@ -2630,6 +2915,7 @@ impl Editor {
             tools: &mut self.tools,
             editor_options: &mut self.editor_options,
             auto_mapper: &mut self.auto_mapper,
+            prefabs: &mut self.prefabs,
             io: &self.io,
 
             quad_tile_images_container: &mut self.quad_tile_images_container,
@@ -2653,6 +2939,7 @@ impl Editor {
                     self.new_map("new-map", Default::default());
                 }
                 EditorUiEvent::OpenFile { name } => self.load_map(&name, Default::default()),
+                EditorUiEvent::ImportFromMap { name } => self.import_map(&name),
                 EditorUiEvent::SaveFile { name } => {
                     self.save_map(&name);
                 }
@@ -2730,6 +3017,9 @@ impl Editor {
                 EditorUiEvent::Minimize => {
                     forced_result = Some(EditorResult::Minimize);
                 }
+                EditorUiEvent::Playtest => {
+                    self.playtest();
+                }
                 EditorUiEvent::Undo => {
                     if let Some(tab) = self.tabs.get(&self.active_tab) {
                         tab.client.undo();
@@ -2740,6 +3030,18 @@ impl Editor {
                         tab.client.redo();
                     }
                 }
+                EditorUiEvent::RecoverJournal { tab, replay } => {
+                    let entries = self
+                        .tabs
+                        .get_mut(&tab)
+                        .and_then(|tab| tab.journal_recovery.take())
+                        .filter(|_| replay)
+                        .unwrap_or_default();
+                    self.replay_tab_journal(&tab, entries);
+                }
+                EditorUiEvent::RestoreJournal { tab, index } => {
+                    self.restore_tab_journal(&tab, index);
+                }
                 EditorUiEvent::CursorWorldPos { pos } => {
                     if let Some(tab) = self.tabs.get_mut(&self.active_tab) {
                         let now = self.time.now();
@@ -2767,6 +3069,11 @@ impl Editor {
                         tab.client.admin_change_cfg(state);
                     }
                 }
+                EditorUiEvent::AdminChangePermissions { change } => {
+                    if let Some(tab) = self.tabs.get(&self.active_tab) {
+                        tab.client.admin_change_permissions(change);
+                    }
+                }
                 EditorUiEvent::DbgAction(props) => {
                     if let Some(tab) = self.tabs.get(&self.active_tab) {
                         tab.client.dbg_action(props);
@@ -2813,10 +3120,30 @@ impl Editor {
     }
 }
 
+/// The map name of playtest maps.
+pub const PLAYTEST_MAP_NAME: &str = "editor_playtest";
+
+/// A map that was serialized by the editor and should be played
+/// on a local server.
+///
+/// The map is never written to disk, the local server
+/// uses the files directly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorPlaytest {
+    pub map_name: String,
+    pub game_mod: String,
+    pub map_file: Vec<u8>,
+    /// The resource files by their path, e.g. `map/resources/images/<name>_<hash>.png`.
+    pub resource_files: HashMap<String, Vec<u8>>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum EditorResult {
     Close,
     Minimize,
+    /// The editor should be minimized and the
+    /// map should be played on a local server.
+    Playtest(EditorPlaytest),
     PlatformOutput(egui::PlatformOutput),
 }
 
@@ -2825,6 +3152,9 @@ pub trait EditorInterface {
 
     fn file_dropped(&mut self, file: PathBuf);
     fn file_hovered(&mut self, file: Option<PathBuf>);
+
+    /// The translations of the language the client uses.
+    fn set_translations(&mut self, translations: Translations);
 }
 
 impl EditorInterface for Editor {
@@ -2841,12 +3171,15 @@ impl EditorInterface for Editor {
         // render the grid, if active
         self.render_grid();
 
+        // render the movement reachability preview, if active
+        self.render_reachability();
+
         // render the tools directly after the world
         // the handling/update of the tools & world happens after the UI tho
         self.render_tools(&self.latest_canvas_rect.clone());
 
         // then render the UI above it
-        let (unused_rect, input_state, canvas_size, ui_output, forced_result) =
+        let (unused_rect, input_state, canvas_size, ui_output, mut forced_result) =
             self.render_ui(input, config);
         self.latest_canvas_rect = canvas_size.unwrap_or_else(|| {
             Rect::from_min_size(
@@ -2913,11 +3246,20 @@ impl EditorInterface for Editor {
 
         // handle save tasks
         let mut unfinished_tasks = Vec::default();
-        for task in self.save_tasks.drain(..) {
+        for (task, journal_save) in self.save_tasks.drain(..) {
             if task.is_finished() {
                 match task.get() {
                     Ok(_) => {
                         log::info!("Map saved.");
+                        if let Some(journal_save) = &journal_save {
+                            for journal in self
+                                .tabs
+                                .values_mut()
+                                .filter_map(|tab| tab.server.as_mut()?.journal_mut())
+                            {
+                                journal.saved(journal_save);
+                            }
+                        }
                         self.notifications_overlay
                             .add_info("Map saved.", Duration::from_secs(2));
                         // ignore
@@ -2929,11 +3271,29 @@ impl EditorInterface for Editor {
                     }
                 }
             } else {
-                unfinished_tasks.push(task);
+                unfinished_tasks.push((task, journal_save));
             }
         }
         std::mem::swap(&mut self.save_tasks, &mut unfinished_tasks);
 
+        // handle the playtest task
+        if self
+            .playtest_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            match self.playtest_task.take().unwrap().get() {
+                Ok(playtest) => {
+                    forced_result = Some(EditorResult::Playtest(playtest));
+                }
+                Err(err) => {
+                    log::error!("{err}");
+                    self.notifications_overlay
+                        .add_err(err.to_string(), Duration::from_secs(10));
+                }
+            }
+        }
+
         // render the overlay for notifications
         for ev in self.notifications.take() {
             match ev {
@@ -3045,4 +3405,8 @@ impl EditorInterface for Editor {
     fn file_hovered(&mut self, file: Option<PathBuf>) {
         self.hovered_file = file;
     }
+
+    fn set_translations(&mut self, translations: Translations) {
+        set_translations(&self.ui.ui.context.egui_ctx, Arc::new(translations));
+    }
 }
@@ -26,6 +26,7 @@ use crate::{
     image_store_container::ImageStoreContainer,
     notifications::EditorNotifications,
     options::EditorOptions,
+    prefab::EditorPrefabs,
     sound_store_container::SoundStoreContainer,
     tools::{tile_layer::auto_mapper::TileLayerAutoMapper, tool::Tools},
     ui::{
@@ -51,6 +52,7 @@ pub struct EditorUiRenderPipe<'a> {
     pub editor_options: &'a mut EditorOptions,
 
     pub auto_mapper: &'a mut TileLayerAutoMapper,
+    pub prefabs: &'a mut EditorPrefabs,
 
     pub notifications: &'a EditorNotifications,
     pub io: &'a Io,
@@ -143,6 +145,7 @@ impl EditorUiRender {
                     editor_options: pipe.editor_options,
 
                     auto_mapper: pipe.auto_mapper,
+                    prefabs: pipe.prefabs,
 
                     pointer_is_used: &mut needs_pointer,
                     io: pipe.io,
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     sync::{Arc, atomic::AtomicBool},
     time::Duration,
 };
@@ -44,6 +44,19 @@ pub struct EditorEventOverwriteMap {
 
     /// Currently live edited layers (auto mapper)
     pub live_edited_layers: Vec<EditorEventLayerIndex>,
+
+    /// The id of the next undo/redo event the server sends.
+    pub next_event_id: u64,
+}
+
+/// Allows a reconnecting client to only receive the
+/// undo/redo events it missed, instead of the whole map.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EditorResync {
+    /// Identifies the server instance the client was connected to.
+    pub session_id: u64,
+    /// The id of the first undo/redo event the client did not receive.
+    pub next_event_id: u64,
 }
 
 /// The client props the server knows about.
@@ -72,6 +85,57 @@ pub struct AdminChangeConfig {
     pub state: AdminConfigState,
 }
 
+/// A group of the map, used for locks and edit rights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EditorGroupIndex {
+    Physics,
+    Design {
+        is_background: bool,
+        group_index: usize,
+    },
+}
+
+/// A whole group or a single layer of a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EditorLockTarget {
+    pub group: EditorGroupIndex,
+    pub layer_index: Option<usize>,
+}
+
+/// Locks and edit rights that the server enforces.
+///
+/// Clients are identified by their [`ClientProps::server_id`].
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EditorPermissions {
+    /// Locked groups or layers and the client that owns the lock.
+    pub locks: HashMap<EditorLockTarget, u64>,
+    /// Groups that only the listed clients are allowed to edit.
+    pub group_editors: HashMap<EditorGroupIndex, HashSet<u64>>,
+    /// Clients that are not allowed to edit the map at all.
+    pub read_only: HashSet<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AdminPermissionChange {
+    ReadOnly {
+        server_id: u64,
+        read_only: bool,
+    },
+    /// `None` allows all clients to edit the group.
+    GroupEditors {
+        group: EditorGroupIndex,
+        server_ids: Option<HashSet<u64>>,
+    },
+    /// Removes the lock of any client.
+    Unlock(EditorLockTarget),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminChangePermissions {
+    pub password: String,
+    pub change: AdminPermissionChange,
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct ActionDbg {
     pub num_actions: usize,
@@ -93,6 +157,9 @@ pub struct EditorEventAutoMap {
     pub name: String,
     pub hash: Hash,
     pub seed: u64,
+    /// If `Some`, the auto mapper runs on this physics layer
+    /// and the design layer above is only used as input for the rule.
+    pub physics_layer_index: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +179,7 @@ pub enum EditorEventClientToServer {
         is_local_client: bool,
         mapper_name: String,
         color: [u8; 3],
+        resync: Option<EditorResync>,
     },
     Command(EditorCommand),
     LoadAutoMap {
@@ -133,6 +201,12 @@ pub enum EditorEventClientToServer {
         password: String,
     },
     AdminChangeConfig(AdminChangeConfig),
+    AdminChangePermissions(AdminChangePermissions),
+    /// Locks or unlocks a group or layer for all other clients.
+    Lock {
+        target: EditorLockTarget,
+        lock: bool,
+    },
     DbgAction(ActionDbg),
 }
 
@@ -143,11 +217,14 @@ pub enum EditorEventServerToClient {
         action: EditorActionGroup,
         redo_label: Option<String>,
         undo_label: Option<String>,
+        /// Increases by one for every undo/redo event.
+        event_id: u64,
     },
     UndoAction {
         action: EditorActionGroup,
         redo_label: Option<String>,
         undo_label: Option<String>,
+        event_id: u64,
     },
     AutoMapRuleNotFound(EditorEventAutoMap),
     AutoMapRuleLiveEditNotFound {
@@ -165,6 +242,8 @@ pub enum EditorEventServerToClient {
         server_id: u64,
         /// Allows remotely controlled administration (e.g. changing config)
         allows_remote_admin: bool,
+        /// See [`EditorResync::session_id`].
+        session_id: u64,
     },
     Chat {
         from: String,
@@ -174,6 +253,7 @@ pub enum EditorEventServerToClient {
     AdminState {
         cur_state: AdminConfigState,
     },
+    Permissions(EditorPermissions),
 }
 
 /// editor events are a collection of either actions or commands
@@ -0,0 +1,345 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use base_io::{io::Io, runtime::IoRuntimeTask};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::actions::{EditorActionGroup, EditorActionInterface},
+    fs::{read_file_editor, write_file_editor},
+};
+
+/// What happened to the action group of a journal entry.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EditorJournalOp {
+    Do,
+    Undo,
+    Redo,
+}
+
+/// A single action group that was applied by the editor server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorJournalEntry {
+    /// Time since the unix epoch.
+    pub time: Duration,
+    pub op: EditorJournalOp,
+    /// For undo entries this is the group that was undone.
+    pub group: EditorActionGroup,
+}
+
+impl EditorJournalEntry {
+    pub fn label(&self) -> String {
+        let info = |undo: bool| {
+            self.group
+                .actions
+                .iter()
+                .map(|act| {
+                    if undo {
+                        act.undo_info()
+                    } else {
+                        act.redo_info()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match self.op {
+            EditorJournalOp::Do => info(false),
+            EditorJournalOp::Undo => format!("Undo: {}", info(true)),
+            EditorJournalOp::Redo => format!("Redo: {}", info(false)),
+        }
+    }
+}
+
+/// The part of a journal entry that is kept in memory,
+/// e.g. for the history panel.
+#[derive(Debug, Clone)]
+pub struct EditorJournalSummary {
+    pub time: Duration,
+    pub label: String,
+}
+
+/// The state of the journal when a save started,
+/// see [`EditorJournal::save_point`].
+#[derive(Debug, Clone)]
+pub struct EditorJournalSavePoint {
+    path: PathBuf,
+    generation: u64,
+    entries: usize,
+}
+
+/// Writes every action group the editor server applied
+/// to a file next to the map, so the changes since the last
+/// save can be replayed after a crash.
+///
+/// Entries are bincode encoded and prefixed by their length,
+/// a partially written last entry is ignored when reading.
+/// The file system has no append operation, so the whole
+/// journal is rewritten whenever it changed.
+pub struct EditorJournal {
+    io: Io,
+    path: PathBuf,
+    summaries: Vec<EditorJournalSummary>,
+    /// The encoded entries, one per summary.
+    entries: Vec<Vec<u8>>,
+    /// Increased every time the journal is reset.
+    generation: u64,
+
+    dirty: bool,
+    write_task: Option<IoRuntimeTask<()>>,
+}
+
+impl EditorJournal {
+    /// The journal file of the map at `map_path`.
+    pub fn path_for_map(map_path: &Path) -> PathBuf {
+        let mut path = map_path.to_path_buf().into_os_string();
+        path.push(".journal");
+        path.into()
+    }
+
+    /// Creates the journal of the map, nothing is written
+    /// until the first entry is added.
+    pub fn new(io: &Io, map_path: &Path) -> Self {
+        Self {
+            io: io.clone(),
+            path: Self::path_for_map(map_path),
+            summaries: Default::default(),
+            entries: Default::default(),
+            generation: 0,
+
+            dirty: false,
+            write_task: None,
+        }
+    }
+
+    /// Keeps the entries of a previous session, until
+    /// the journal is reset.
+    pub fn with_entries(mut self, entries: &[EditorJournalEntry]) -> Self {
+        for entry in entries {
+            self.push(entry);
+        }
+        self.dirty = false;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Moves the journal to the map at `map_path`, e.g.
+    /// if the map is saved under a new name.
+    pub fn set_map_path(&mut self, map_path: &Path) {
+        self.path = Self::path_for_map(map_path);
+        self.dirty = true;
+        self.update();
+    }
+
+    /// The entries written by this journal since it was last reset.
+    pub fn summaries(&self) -> &[EditorJournalSummary] {
+        &self.summaries
+    }
+
+    fn push(&mut self, entry: &EditorJournalEntry) {
+        match encode_entry(entry) {
+            Ok(bytes) => {
+                self.summaries.push(EditorJournalSummary {
+                    time: entry.time,
+                    label: entry.label(),
+                });
+                self.entries.push(bytes);
+                self.dirty = true;
+            }
+            Err(err) => {
+                log::error!("encoding the editor journal entry failed: {err}");
+            }
+        }
+    }
+
+    pub fn append(&mut self, op: EditorJournalOp, group: &EditorActionGroup) {
+        let entry = EditorJournalEntry {
+            time: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default(),
+            op,
+            group: group.clone(),
+        };
+        self.push(&entry);
+        self.update();
+    }
+
+    /// Removes all entries.
+    pub fn reset(&mut self) {
+        self.summaries.clear();
+        self.entries.clear();
+        self.generation += 1;
+        self.dirty = true;
+        self.update();
+    }
+
+    /// Remembers the entries that are part of a save that
+    /// is about to start, see [`Self::saved`].
+    pub fn save_point(&self) -> EditorJournalSavePoint {
+        EditorJournalSavePoint {
+            path: self.path.clone(),
+            generation: self.generation,
+            entries: self.entries.len(),
+        }
+    }
+
+    /// Removes the entries that were saved to the map,
+    /// must only be called after the save succeeded.
+    /// Entries added while saving are kept.
+    pub fn saved(&mut self, save_point: &EditorJournalSavePoint) {
+        if save_point.path != self.path || save_point.generation != self.generation {
+            return;
+        }
+        let count = save_point.entries.min(self.entries.len());
+        self.summaries.drain(..count);
+        self.entries.drain(..count);
+        self.dirty = true;
+        self.update();
+    }
+
+    fn write(&mut self) -> IoRuntimeTask<()> {
+        self.dirty = false;
+        let fs = self.io.fs.clone();
+        let path = self.path.clone();
+        let file = self.entries.concat();
+        self.io.rt.spawn(async move {
+            if let Some(parent) = path.parent().filter(|_| !path.is_absolute()) {
+                fs.create_dir(parent).await?;
+            }
+            write_file_editor(&fs, &path, file).await
+        })
+    }
+
+    /// Writes the journal if it changed and the previous
+    /// write finished, so the writes stay in order.
+    pub fn update(&mut self) {
+        if self
+            .write_task
+            .as_ref()
+            .is_some_and(|task| !task.is_finished())
+        {
+            return;
+        }
+        if let Some(Err(err)) = self.write_task.take().map(|task| task.get()) {
+            log::error!(
+                "writing the editor journal {} failed: {err}",
+                self.path.display()
+            );
+        }
+        if self.dirty {
+            self.write_task = Some(self.write());
+        }
+    }
+
+    /// Reads all entries of the journal of the map at `map_path`.
+    ///
+    /// A missing journal has no entries.
+    pub fn read(io: &Io, map_path: &Path) -> anyhow::Result<Vec<EditorJournalEntry>> {
+        let fs = io.fs.clone();
+        let path = Self::path_for_map(map_path);
+        let file = io
+            .rt
+            .spawn(async move { read_file_editor(&fs, &path).await })
+            .get();
+        match file {
+            Ok(file) => Ok(decode_entries(&file)),
+            Err(err)
+                if err
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|err| err.kind() == std::io::ErrorKind::NotFound) =>
+            {
+                Ok(Default::default())
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl Drop for EditorJournal {
+    fn drop(&mut self) {
+        let write_task = self.write_task.take();
+        let write_task = if self.dirty {
+            // the previous write must finish first
+            drop(write_task);
+            Some(self.write())
+        } else {
+            write_task
+        };
+        if let Some(Err(err)) = write_task.map(|task| task.get()) {
+            log::error!(
+                "writing the editor journal {} failed: {err}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+fn encode_entry(entry: &EditorJournalEntry) -> anyhow::Result<Vec<u8>> {
+    let entry = bincode::serde::encode_to_vec(entry, bincode::config::standard())?;
+    let mut bytes = (entry.len() as u64).to_le_bytes().to_vec();
+    bytes.extend(entry);
+    Ok(bytes)
+}
+
+fn decode_entries(mut file: &[u8]) -> Vec<EditorJournalEntry> {
+    let mut entries = Vec::new();
+    while file.len() >= size_of::<u64>() {
+        let (len, rest) = file.split_at(size_of::<u64>());
+        let len = u64::from_le_bytes(len.try_into().unwrap()) as usize;
+        if rest.len() < len {
+            log::warn!("the last editor journal entry was only partially written, ignoring it");
+            break;
+        }
+        let (entry, rest) = rest.split_at(len);
+        match bincode::serde::decode_from_slice::<EditorJournalEntry, _>(
+            entry,
+            bincode::config::standard(),
+        ) {
+            Ok((entry, _)) => entries.push(entry),
+            Err(err) => {
+                log::warn!("invalid editor journal entry, ignoring the rest: {err}");
+                break;
+            }
+        }
+        file = rest;
+    }
+    entries
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::actions::actions::{ActSetCommands, EditorAction, EditorActionGroup};
+
+    use super::{EditorJournalEntry, EditorJournalOp, decode_entries, encode_entry};
+
+    #[test]
+    fn partial_last_entry() {
+        let entry = |op| EditorJournalEntry {
+            time: Duration::from_secs(1),
+            op,
+            group: EditorActionGroup {
+                actions: vec![EditorAction::SetCommands(ActSetCommands {
+                    old_commands: Default::default(),
+                    new_commands: Default::default(),
+                })],
+                identifier: None,
+            },
+        };
+        let mut file = encode_entry(&entry(EditorJournalOp::Do)).unwrap();
+        file.extend(encode_entry(&entry(EditorJournalOp::Undo)).unwrap());
+        let last = encode_entry(&entry(EditorJournalOp::Redo)).unwrap();
+        file.extend(&last[..last.len() - 1]);
+
+        let entries = decode_entries(&file);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].op, EditorJournalOp::Do);
+        assert_eq!(entries[1].op, EditorJournalOp::Undo);
+    }
+}
@@ -12,15 +12,24 @@ pub mod explain;
 pub mod fs;
 pub mod hotkeys;
 pub mod image_store_container;
+pub mod journal;
 pub mod map;
+pub mod map_diff;
+pub mod map_import;
 pub mod map_tools;
 pub mod network;
 pub mod notifications;
 pub mod options;
+pub mod permissions;
 pub mod physics_layers;
+pub mod physics_numbers;
+pub mod prefab;
+pub mod reachability;
 pub mod server;
 pub mod sound_store_container;
 pub mod tab;
+#[cfg(test)]
+mod test_utils;
 pub mod tile_overlays;
 pub mod tools;
 pub mod ui;
@@ -646,6 +646,8 @@ pub trait EditorMapGroupsInterface {
     fn selected_layers(&'_ self) -> Vec<EditorLayerUnionRef<'_>>;
 
     fn live_edited_layers(&self) -> Vec<EditorEventLayerIndex>;
+    /// Whether the auto mapper live edits a tile layer, for all tile layers.
+    fn live_edit_states(&self) -> Vec<(EditorEventLayerIndex, bool)>;
 }
 
 #[derive(Debug, Clone, Default)]
@@ -983,6 +985,39 @@ impl EditorMapGroupsInterface for EditorGroups {
             })
             .collect()
     }
+
+    fn live_edit_states(&self) -> Vec<(EditorEventLayerIndex, bool)> {
+        self.background
+            .iter()
+            .enumerate()
+            .map(|(group_index, g)| (true, group_index, g))
+            .chain(
+                self.foreground
+                    .iter()
+                    .enumerate()
+                    .map(|(group_index, g)| (false, group_index, g)),
+            )
+            .flat_map(|(is_background, group_index, group)| {
+                group
+                    .layers
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(layer_index, layer)| {
+                        let EditorLayer::Tile(layer) = layer else {
+                            return None;
+                        };
+                        Some((
+                            EditorEventLayerIndex {
+                                is_background,
+                                group_index,
+                                layer_index,
+                            },
+                            layer.user.live_edit.is_some(),
+                        ))
+                    })
+            })
+            .collect()
+    }
 }
 
 impl EditorMapInterface for EditorMap {
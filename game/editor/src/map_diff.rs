@@ -0,0 +1,2076 @@
+//! Structural diffs & three-way merges of maps.
+//!
+//! A diff is expressed as editor actions, that turn the old map into the new one,
+//! when applied in order to an editor map that was loaded from the old map.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use anyhow::anyhow;
+use base::hash::Hash;
+use map::{
+    map::{
+        Map,
+        groups::{
+            MapGroup, MapGroupPhysics,
+            layers::{
+                design::{MapLayer, MapLayerQuad, MapLayerSound, MapLayerTile},
+                physics::MapLayerPhysics,
+                tiles::MapTileLayerPhysicsTiles,
+            },
+        },
+        resources::MapResourceRef,
+    },
+    types::NonZeroU16MinusOne,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::actions::actions::{
+    ActAddColorAnim, ActAddGroup, ActAddImage, ActAddImage2dArray, ActAddPhysicsTileLayer,
+    ActAddPosAnim, ActAddQuadLayer, ActAddRemColorAnim, ActAddRemGroup, ActAddRemImage,
+    ActAddRemPhysicsTileLayer, ActAddRemPosAnim, ActAddRemQuadLayer, ActAddRemSound,
+    ActAddRemSoundAnim, ActAddRemSoundLayer, ActAddRemTileLayer, ActAddSound, ActAddSoundAnim,
+    ActAddSoundLayer, ActAddTileLayer, ActChangeDesignLayerName, ActChangeGroupAttr,
+    ActChangeGroupName, ActChangePhysicsGroupAttr, ActChangeQuadAttr, ActChangeQuadLayerAttr,
+    ActChangeSoundAttr, ActChangeSoundLayerAttr, ActChangeSwitch, ActChangeTeleporter,
+    ActChangeTileLayerDesignAttr, ActChangeTuneZone, ActQuadLayerAddQuads, ActQuadLayerAddRemQuads,
+    ActQuadLayerRemQuads, ActRemColorAnim, ActRemGroup, ActRemImage, ActRemImage2dArray,
+    ActRemPhysicsTileLayer, ActRemPosAnim, ActRemQuadLayer, ActRemSound, ActRemSoundAnim,
+    ActRemSoundLayer, ActRemTileLayer, ActReplColorAnim, ActReplPosAnim, ActReplSoundAnim,
+    ActSetCommands, ActSetConfigVariables, ActSetMetadata, ActSoundLayerAddRemSounds,
+    ActSoundLayerAddSounds, ActSoundLayerRemSounds, ActTileLayerReplTilesBase,
+    ActTileLayerReplaceTiles, ActTilePhysicsLayerReplTilesBase, ActTilePhysicsLayerReplaceTiles,
+    EditorAction, EditorActionGroup, EditorActionInterface,
+};
+
+/// Tile layers are compared in square regions of this size (in tiles).
+pub const DIFF_REGION_SIZE: usize = 32;
+
+/// A rectangular region of tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapDiffRegion {
+    pub x: u16,
+    pub y: u16,
+    pub w: u16,
+    pub h: u16,
+}
+
+impl MapDiffRegion {
+    fn extend(&mut self, x: u16, y: u16) {
+        let right = (self.x + self.w).max(x + 1);
+        let bottom = (self.y + self.h).max(y + 1);
+        self.x = self.x.min(x);
+        self.y = self.y.min(y);
+        self.w = right - self.x;
+        self.h = bottom - self.y;
+    }
+}
+
+/// Identifies a layer of a map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MapDiffLayer {
+    Design {
+        is_background: bool,
+        group_index: usize,
+        layer_index: usize,
+    },
+    Physics {
+        layer_index: usize,
+    },
+}
+
+impl Display for MapDiffLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Design {
+                is_background,
+                group_index,
+                layer_index,
+            } => write!(
+                f,
+                "layer #{layer_index} of group #{group_index} in {}",
+                if *is_background {
+                    "background"
+                } else {
+                    "foreground"
+                }
+            ),
+            Self::Physics { layer_index } => {
+                write!(f, "layer #{layer_index} of the physics group")
+            }
+        }
+    }
+}
+
+/// Returns the regions in which the tiles of two equally sized layers differ.
+///
+/// The layer is split into regions of [`DIFF_REGION_SIZE`] and for every
+/// region with changes, the bounding box of the changed tiles is returned.
+pub fn changed_regions<T: PartialEq>(
+    old: &[T],
+    new: &[T],
+    width: usize,
+    height: usize,
+) -> Vec<MapDiffRegion> {
+    let mut regions = Vec::new();
+    for region_y in (0..height).step_by(DIFF_REGION_SIZE) {
+        for region_x in (0..width).step_by(DIFF_REGION_SIZE) {
+            let mut region: Option<MapDiffRegion> = None;
+            for y in region_y..(region_y + DIFF_REGION_SIZE).min(height) {
+                for x in region_x..(region_x + DIFF_REGION_SIZE).min(width) {
+                    let index = y * width + x;
+                    if old[index] != new[index] {
+                        let (x, y) = (x as u16, y as u16);
+                        match &mut region {
+                            Some(region) => region.extend(x, y),
+                            None => region = Some(MapDiffRegion { x, y, w: 1, h: 1 }),
+                        }
+                    }
+                }
+            }
+            regions.extend(region);
+        }
+    }
+    regions
+}
+
+pub fn region_tiles<T: Copy>(tiles: &[T], width: usize, region: &MapDiffRegion) -> Vec<T> {
+    (region.y as usize..(region.y + region.h) as usize)
+        .flat_map(|y| {
+            let start = y * width + region.x as usize;
+            tiles[start..start + region.w as usize].iter().copied()
+        })
+        .collect()
+}
+
+/// Animation points only compare their time, so compare the serialized animations instead.
+fn anim_eq<T: Serialize>(a: &T, b: &T) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+fn physics_tiles(layer: &MapLayerPhysics) -> Option<MapTileLayerPhysicsTiles> {
+    match layer {
+        MapLayerPhysics::Arbitrary(_) => None,
+        MapLayerPhysics::Game(layer) => Some(MapTileLayerPhysicsTiles::Game(layer.tiles.clone())),
+        MapLayerPhysics::Front(layer) => Some(MapTileLayerPhysicsTiles::Front(layer.tiles.clone())),
+        MapLayerPhysics::Tele(layer) => {
+            Some(MapTileLayerPhysicsTiles::Tele(layer.base.tiles.clone()))
+        }
+        MapLayerPhysics::Speedup(layer) => {
+            Some(MapTileLayerPhysicsTiles::Speedup(layer.tiles.clone()))
+        }
+        MapLayerPhysics::Switch(layer) => {
+            Some(MapTileLayerPhysicsTiles::Switch(layer.base.tiles.clone()))
+        }
+        MapLayerPhysics::Tune(layer) => {
+            Some(MapTileLayerPhysicsTiles::Tune(layer.base.tiles.clone()))
+        }
+    }
+}
+
+fn physics_regions(
+    old: &MapTileLayerPhysicsTiles,
+    new: &MapTileLayerPhysicsTiles,
+    width: usize,
+    height: usize,
+) -> Vec<(
+    MapDiffRegion,
+    MapTileLayerPhysicsTiles,
+    MapTileLayerPhysicsTiles,
+)> {
+    macro_rules! regions {
+        ($ty:ident, $old:ident, $new:ident) => {
+            changed_regions($old, $new, width, height)
+                .into_iter()
+                .map(|region| {
+                    (
+                        region,
+                        MapTileLayerPhysicsTiles::$ty(region_tiles($old, width, &region)),
+                        MapTileLayerPhysicsTiles::$ty(region_tiles($new, width, &region)),
+                    )
+                })
+                .collect()
+        };
+    }
+    match (old, new) {
+        (MapTileLayerPhysicsTiles::Game(old), MapTileLayerPhysicsTiles::Game(new)) => {
+            regions!(Game, old, new)
+        }
+        (MapTileLayerPhysicsTiles::Front(old), MapTileLayerPhysicsTiles::Front(new)) => {
+            regions!(Front, old, new)
+        }
+        (MapTileLayerPhysicsTiles::Tele(old), MapTileLayerPhysicsTiles::Tele(new)) => {
+            regions!(Tele, old, new)
+        }
+        (MapTileLayerPhysicsTiles::Speedup(old), MapTileLayerPhysicsTiles::Speedup(new)) => {
+            regions!(Speedup, old, new)
+        }
+        (MapTileLayerPhysicsTiles::Switch(old), MapTileLayerPhysicsTiles::Switch(new)) => {
+            regions!(Switch, old, new)
+        }
+        (MapTileLayerPhysicsTiles::Tune(old), MapTileLayerPhysicsTiles::Tune(new)) => {
+            regions!(Tune, old, new)
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn layer_ty(layer: &MapLayerPhysics) -> std::mem::Discriminant<MapLayerPhysics> {
+    std::mem::discriminant(layer)
+}
+
+/// The difference between two maps, as editor actions.
+#[derive(Debug, Default, Clone)]
+pub struct MapDiff {
+    pub actions: Vec<EditorAction>,
+}
+
+impl MapDiff {
+    /// Computes the actions that turn `old` into `new`.
+    ///
+    /// `files` must contain the files (by hash) of all resources that
+    /// are added or removed.
+    ///
+    /// Resources are matched by their hash, so resources that only moved are kept
+    /// at their old index & resources with the same hash are only added once.
+    pub fn new(old: &Map, new: &Map, files: &HashMap<Hash, Vec<u8>>) -> anyhow::Result<Self> {
+        let mut diff = Self::default();
+
+        let images = ResourceChanges::new(&old.resources.images, &new.resources.images, files)?;
+        let image_arrays = ResourceChanges::new(
+            &old.resources.image_arrays,
+            &new.resources.image_arrays,
+            files,
+        )?;
+        let sounds = ResourceChanges::new(&old.resources.sounds, &new.resources.sounds, files)?;
+        // the layers must use the indices that the resources have after the diff
+        let mut new = new.clone();
+        for_each_index(&mut new, |ty, index| {
+            let indices = match ty {
+                MapIndexTy::Image => &images.indices,
+                MapIndexTy::ImageArray => &image_arrays.indices,
+                MapIndexTy::Sound => &sounds.indices,
+                MapIndexTy::PosAnim | MapIndexTy::ColorAnim | MapIndexTy::SoundAnim => return,
+            };
+            if let Some(new_index) = indices.get(*index) {
+                *index = *new_index;
+            }
+        });
+        let new = &new;
+
+        // new resources & animations must exist before layers can use them,
+        // while removed ones can only be removed after no layer uses them anymore.
+        diff.grow_resources(&images, &image_arrays, &sounds);
+        diff.grow_animations(old, new);
+        diff.diff_groups(true, &old.groups.background, &new.groups.background)?;
+        diff.diff_groups(false, &old.groups.foreground, &new.groups.foreground)?;
+        diff.diff_physics(&old.groups.physics, &new.groups.physics)?;
+        diff.diff_config(old, new);
+        diff.shrink_resources(images, image_arrays, sounds);
+        diff.shrink_animations(old, new);
+
+        Ok(diff)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Human readable description of every action.
+    pub fn summary(&self) -> Vec<String> {
+        self.actions
+            .iter()
+            .map(|action| action.redo_info())
+            .collect()
+    }
+
+    pub fn into_action_group(self) -> EditorActionGroup {
+        EditorActionGroup {
+            actions: self.actions,
+            identifier: None,
+        }
+    }
+
+    fn grow_resources(
+        &mut self,
+        images: &ResourceChanges,
+        image_arrays: &ResourceChanges,
+        sounds: &ResourceChanges,
+    ) {
+        self.actions.extend(
+            images
+                .added
+                .iter()
+                .cloned()
+                .map(|base| EditorAction::AddImage(ActAddImage { base })),
+        );
+        self.actions.extend(
+            image_arrays
+                .added
+                .iter()
+                .cloned()
+                .map(|base| EditorAction::AddImage2dArray(ActAddImage2dArray { base })),
+        );
+        self.actions
+            .extend(sounds.added.iter().cloned().map(|base| {
+                EditorAction::AddSound(ActAddSound {
+                    base: ActAddRemSound {
+                        res: base.res,
+                        file: base.file,
+                        index: base.index,
+                    },
+                })
+            }));
+    }
+
+    fn shrink_resources(
+        &mut self,
+        images: ResourceChanges,
+        image_arrays: ResourceChanges,
+        sounds: ResourceChanges,
+    ) {
+        self.actions.extend(
+            images
+                .removed
+                .into_iter()
+                .map(|base| EditorAction::RemImage(ActRemImage { base })),
+        );
+        self.actions.extend(
+            image_arrays
+                .removed
+                .into_iter()
+                .map(|base| EditorAction::RemImage2dArray(ActRemImage2dArray { base })),
+        );
+        self.actions.extend(sounds.removed.into_iter().map(|base| {
+            EditorAction::RemSound(ActRemSound {
+                base: ActAddRemSound {
+                    res: base.res,
+                    file: base.file,
+                    index: base.index,
+                },
+            })
+        }));
+    }
+
+    fn grow_animations(&mut self, old: &Map, new: &Map) {
+        let (old, new) = (&old.animations, &new.animations);
+        for (index, (old_anim, new_anim)) in old.pos.iter().zip(new.pos.iter()).enumerate() {
+            if !anim_eq(old_anim, new_anim) {
+                self.actions.push(EditorAction::ReplPosAnim(ActReplPosAnim {
+                    base: ActAddRemPosAnim {
+                        index,
+                        anim: new_anim.clone(),
+                    },
+                }));
+            }
+        }
+        for (index, anim) in new.pos.iter().enumerate().skip(old.pos.len()) {
+            self.actions.push(EditorAction::AddPosAnim(ActAddPosAnim {
+                base: ActAddRemPosAnim {
+                    index,
+                    anim: anim.clone(),
+                },
+            }));
+        }
+        for (index, (old_anim, new_anim)) in old.color.iter().zip(new.color.iter()).enumerate() {
+            if !anim_eq(old_anim, new_anim) {
+                self.actions
+                    .push(EditorAction::ReplColorAnim(ActReplColorAnim {
+                        base: ActAddRemColorAnim {
+                            index,
+                            anim: new_anim.clone(),
+                        },
+                    }));
+            }
+        }
+        for (index, anim) in new.color.iter().enumerate().skip(old.color.len()) {
+            self.actions
+                .push(EditorAction::AddColorAnim(ActAddColorAnim {
+                    base: ActAddRemColorAnim {
+                        index,
+                        anim: anim.clone(),
+                    },
+                }));
+        }
+        for (index, (old_anim, new_anim)) in old.sound.iter().zip(new.sound.iter()).enumerate() {
+            if !anim_eq(old_anim, new_anim) {
+                self.actions
+                    .push(EditorAction::ReplSoundAnim(ActReplSoundAnim {
+                        base: ActAddRemSoundAnim {
+                            index,
+                            anim: new_anim.clone(),
+                        },
+                    }));
+            }
+        }
+        for (index, anim) in new.sound.iter().enumerate().skip(old.sound.len()) {
+            self.actions
+                .push(EditorAction::AddSoundAnim(ActAddSoundAnim {
+                    base: ActAddRemSoundAnim {
+                        index,
+                        anim: anim.clone(),
+                    },
+                }));
+        }
+    }
+
+    fn shrink_animations(&mut self, old: &Map, new: &Map) {
+        let (old, new) = (&old.animations, &new.animations);
+        for (index, anim) in old.pos.iter().enumerate().skip(new.pos.len()).rev() {
+            self.actions.push(EditorAction::RemPosAnim(ActRemPosAnim {
+                base: ActAddRemPosAnim {
+                    index,
+                    anim: anim.clone(),
+                },
+            }));
+        }
+        for (index, anim) in old.color.iter().enumerate().skip(new.color.len()).rev() {
+            self.actions
+                .push(EditorAction::RemColorAnim(ActRemColorAnim {
+                    base: ActAddRemColorAnim {
+                        index,
+                        anim: anim.clone(),
+                    },
+                }));
+        }
+        for (index, anim) in old.sound.iter().enumerate().skip(new.sound.len()).rev() {
+            self.actions
+                .push(EditorAction::RemSoundAnim(ActRemSoundAnim {
+                    base: ActAddRemSoundAnim {
+                        index,
+                        anim: anim.clone(),
+                    },
+                }));
+        }
+    }
+
+    fn diff_groups(
+        &mut self,
+        is_background: bool,
+        old: &[MapGroup],
+        new: &[MapGroup],
+    ) -> anyhow::Result<()> {
+        for (group_index, (old_group, new_group)) in old.iter().zip(new.iter()).enumerate() {
+            if old_group.attr != new_group.attr {
+                self.actions
+                    .push(EditorAction::ChangeGroupAttr(ActChangeGroupAttr {
+                        is_background,
+                        group_index,
+                        old_attr: old_group.attr,
+                        new_attr: new_group.attr,
+                    }));
+            }
+            if old_group.name != new_group.name {
+                self.actions
+                    .push(EditorAction::ChangeGroupName(ActChangeGroupName {
+                        is_background,
+                        group_index,
+                        old_name: old_group.name.clone(),
+                        new_name: new_group.name.clone(),
+                    }));
+            }
+            self.diff_layers(
+                is_background,
+                group_index,
+                &old_group.layers,
+                &new_group.layers,
+            )?;
+        }
+        for (index, group) in old.iter().enumerate().skip(new.len()).rev() {
+            self.actions.push(EditorAction::RemGroup(ActRemGroup {
+                base: ActAddRemGroup {
+                    is_background,
+                    index,
+                    group: group.clone(),
+                },
+            }));
+        }
+        for (index, group) in new.iter().enumerate().skip(old.len()) {
+            self.actions.push(EditorAction::AddGroup(ActAddGroup {
+                base: ActAddRemGroup {
+                    is_background,
+                    index,
+                    group: group.clone(),
+                },
+            }));
+        }
+        Ok(())
+    }
+
+    fn add_layer(
+        &mut self,
+        is_background: bool,
+        group_index: usize,
+        index: usize,
+        layer: &MapLayer,
+    ) -> anyhow::Result<()> {
+        self.actions.push(match layer {
+            MapLayer::Abritrary(_) => anyhow::bail!("arbitrary layers are not supported"),
+            MapLayer::Tile(layer) => EditorAction::AddTileLayer(ActAddTileLayer {
+                base: ActAddRemTileLayer {
+                    is_background,
+                    group_index,
+                    index,
+                    layer: layer.clone(),
+                },
+            }),
+            MapLayer::Quad(layer) => EditorAction::AddQuadLayer(ActAddQuadLayer {
+                base: ActAddRemQuadLayer {
+                    is_background,
+                    group_index,
+                    index,
+                    layer: layer.clone(),
+                },
+            }),
+            MapLayer::Sound(layer) => EditorAction::AddSoundLayer(ActAddSoundLayer {
+                base: ActAddRemSoundLayer {
+                    is_background,
+                    group_index,
+                    index,
+                    layer: layer.clone(),
+                },
+            }),
+        });
+        Ok(())
+    }
+
+    fn rem_layer(
+        &mut self,
+        is_background: bool,
+        group_index: usize,
+        index: usize,
+        layer: &MapLayer,
+    ) -> anyhow::Result<()> {
+        self.actions.push(match layer {
+            MapLayer::Abritrary(_) => anyhow::bail!("arbitrary layers are not supported"),
+            MapLayer::Tile(layer) => EditorAction::RemTileLayer(ActRemTileLayer {
+                base: ActAddRemTileLayer {
+                    is_background,
+                    group_index,
+                    index,
+                    layer: layer.clone(),
+                },
+            }),
+            MapLayer::Quad(layer) => EditorAction::RemQuadLayer(ActRemQuadLayer {
+                base: ActAddRemQuadLayer {
+                    is_background,
+                    group_index,
+                    index,
+                    layer: layer.clone(),
+                },
+            }),
+            MapLayer::Sound(layer) => EditorAction::RemSoundLayer(ActRemSoundLayer {
+                base: ActAddRemSoundLayer {
+                    is_background,
+                    group_index,
+                    index,
+                    layer: layer.clone(),
+                },
+            }),
+        });
+        Ok(())
+    }
+
+    fn diff_layers(
+        &mut self,
+        is_background: bool,
+        group_index: usize,
+        old: &[MapLayer],
+        new: &[MapLayer],
+    ) -> anyhow::Result<()> {
+        for (layer_index, (old_layer, new_layer)) in old.iter().zip(new.iter()).enumerate() {
+            if old_layer == new_layer {
+                continue;
+            }
+            let name_change = |old_name: &str, new_name: &str| {
+                (old_name != new_name).then(|| {
+                    EditorAction::ChangeDesignLayerName(ActChangeDesignLayerName {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        old_name: old_name.to_string(),
+                        new_name: new_name.to_string(),
+                    })
+                })
+            };
+            match (old_layer, new_layer) {
+                (MapLayer::Tile(old_layer), MapLayer::Tile(new_layer)) => {
+                    self.diff_tile_layer(
+                        is_background,
+                        group_index,
+                        layer_index,
+                        old_layer,
+                        new_layer,
+                    );
+                    self.actions
+                        .extend(name_change(&old_layer.name, &new_layer.name));
+                }
+                (MapLayer::Quad(old_layer), MapLayer::Quad(new_layer)) => {
+                    self.diff_quad_layer(
+                        is_background,
+                        group_index,
+                        layer_index,
+                        old_layer,
+                        new_layer,
+                    );
+                    self.actions
+                        .extend(name_change(&old_layer.name, &new_layer.name));
+                }
+                (MapLayer::Sound(old_layer), MapLayer::Sound(new_layer)) => {
+                    self.diff_sound_layer(
+                        is_background,
+                        group_index,
+                        layer_index,
+                        old_layer,
+                        new_layer,
+                    );
+                    self.actions
+                        .extend(name_change(&old_layer.name, &new_layer.name));
+                }
+                _ => {
+                    // different layer types are replaced completely
+                    self.rem_layer(is_background, group_index, layer_index, old_layer)?;
+                    self.add_layer(is_background, group_index, layer_index, new_layer)?;
+                }
+            }
+        }
+        for (index, layer) in old.iter().enumerate().skip(new.len()).rev() {
+            self.rem_layer(is_background, group_index, index, layer)?;
+        }
+        for (index, layer) in new.iter().enumerate().skip(old.len()) {
+            self.add_layer(is_background, group_index, index, layer)?;
+        }
+        Ok(())
+    }
+
+    fn diff_tile_layer(
+        &mut self,
+        is_background: bool,
+        group_index: usize,
+        layer_index: usize,
+        old: &MapLayerTile,
+        new: &MapLayerTile,
+    ) {
+        let size_change = old.attr.width != new.attr.width || old.attr.height != new.attr.height;
+        if old.attr != new.attr {
+            self.actions.push(EditorAction::ChangeTileLayerDesignAttr(
+                ActChangeTileLayerDesignAttr {
+                    is_background,
+                    group_index,
+                    layer_index,
+                    old_attr: old.attr,
+                    new_attr: new.attr,
+                    old_tiles: old.tiles.clone(),
+                    new_tiles: if size_change {
+                        new.tiles.clone()
+                    } else {
+                        old.tiles.clone()
+                    },
+                },
+            ));
+        }
+        if size_change {
+            return;
+        }
+        let width = old.attr.width.get() as usize;
+        let height = old.attr.height.get() as usize;
+        for region in changed_regions(&old.tiles, &new.tiles, width, height) {
+            self.actions.push(EditorAction::TileLayerReplaceTiles(
+                ActTileLayerReplaceTiles {
+                    base: ActTileLayerReplTilesBase {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        old_tiles: region_tiles(&old.tiles, width, &region),
+                        new_tiles: region_tiles(&new.tiles, width, &region),
+                        x: region.x,
+                        y: region.y,
+                        w: NonZeroU16MinusOne::new(region.w).unwrap(),
+                        h: NonZeroU16MinusOne::new(region.h).unwrap(),
+                    },
+                },
+            ));
+        }
+    }
+
+    fn diff_quad_layer(
+        &mut self,
+        is_background: bool,
+        group_index: usize,
+        layer_index: usize,
+        old: &MapLayerQuad,
+        new: &MapLayerQuad,
+    ) {
+        if old.attr != new.attr {
+            self.actions
+                .push(EditorAction::ChangeQuadLayerAttr(ActChangeQuadLayerAttr {
+                    is_background,
+                    group_index,
+                    layer_index,
+                    old_attr: old.attr,
+                    new_attr: new.attr,
+                }));
+        }
+        for (index, (old_quad, new_quad)) in old.quads.iter().zip(new.quads.iter()).enumerate() {
+            if old_quad != new_quad {
+                self.actions
+                    .push(EditorAction::ChangeQuadAttr(Box::new(ActChangeQuadAttr {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index,
+                        old_attr: *old_quad,
+                        new_attr: *new_quad,
+                    })));
+            }
+        }
+        if old.quads.len() > new.quads.len() {
+            self.actions
+                .push(EditorAction::QuadLayerRemQuads(ActQuadLayerRemQuads {
+                    base: ActQuadLayerAddRemQuads {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index: new.quads.len(),
+                        quads: old.quads[new.quads.len()..].to_vec(),
+                    },
+                }));
+        } else if old.quads.len() < new.quads.len() {
+            self.actions
+                .push(EditorAction::QuadLayerAddQuads(ActQuadLayerAddQuads {
+                    base: ActQuadLayerAddRemQuads {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index: old.quads.len(),
+                        quads: new.quads[old.quads.len()..].to_vec(),
+                    },
+                }));
+        }
+    }
+
+    fn diff_sound_layer(
+        &mut self,
+        is_background: bool,
+        group_index: usize,
+        layer_index: usize,
+        old: &MapLayerSound,
+        new: &MapLayerSound,
+    ) {
+        if old.attr != new.attr {
+            self.actions.push(EditorAction::ChangeSoundLayerAttr(
+                ActChangeSoundLayerAttr {
+                    is_background,
+                    group_index,
+                    layer_index,
+                    old_attr: old.attr,
+                    new_attr: new.attr,
+                },
+            ));
+        }
+        for (index, (old_sound, new_sound)) in old.sounds.iter().zip(new.sounds.iter()).enumerate()
+        {
+            if old_sound != new_sound {
+                self.actions
+                    .push(EditorAction::ChangeSoundAttr(ActChangeSoundAttr {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index,
+                        old_attr: *old_sound,
+                        new_attr: *new_sound,
+                    }));
+            }
+        }
+        if old.sounds.len() > new.sounds.len() {
+            self.actions
+                .push(EditorAction::SoundLayerRemSounds(ActSoundLayerRemSounds {
+                    base: ActSoundLayerAddRemSounds {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index: new.sounds.len(),
+                        sounds: old.sounds[new.sounds.len()..].to_vec(),
+                    },
+                }));
+        } else if old.sounds.len() < new.sounds.len() {
+            self.actions
+                .push(EditorAction::SoundLayerAddSounds(ActSoundLayerAddSounds {
+                    base: ActSoundLayerAddRemSounds {
+                        is_background,
+                        group_index,
+                        layer_index,
+                        index: old.sounds.len(),
+                        sounds: new.sounds[old.sounds.len()..].to_vec(),
+                    },
+                }));
+        }
+    }
+
+    fn diff_physics(&mut self, old: &MapGroupPhysics, new: &MapGroupPhysics) -> anyhow::Result<()> {
+        // remove layers that the new map does not have
+        let mut layers: Vec<&MapLayerPhysics> = old.layers.iter().collect();
+        for (index, layer) in old.layers.iter().enumerate().rev() {
+            if !new.layers.iter().any(|l| layer_ty(l) == layer_ty(layer)) {
+                self.actions
+                    .push(EditorAction::RemPhysicsTileLayer(ActRemPhysicsTileLayer {
+                        base: ActAddRemPhysicsTileLayer {
+                            index,
+                            layer: layer.clone(),
+                        },
+                    }));
+                layers.remove(index);
+            }
+        }
+
+        // resize the remaining layers, the tiles are taken from the new map directly
+        let size_change = old.attr != new.attr;
+        if size_change {
+            let new_layer_tiles = layers
+                .iter()
+                .map(|layer| {
+                    new.layers
+                        .iter()
+                        .find(|l| layer_ty(l) == layer_ty(layer))
+                        .and_then(physics_tiles)
+                        .ok_or_else(|| anyhow!("arbitrary physics layers are not supported"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let old_layer_tiles = layers
+                .iter()
+                .map(|layer| {
+                    physics_tiles(layer)
+                        .ok_or_else(|| anyhow!("arbitrary physics layers are not supported"))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            self.actions.push(EditorAction::ChangePhysicsGroupAttr(
+                ActChangePhysicsGroupAttr {
+                    old_attr: old.attr,
+                    new_attr: new.attr,
+                    old_layer_tiles,
+                    new_layer_tiles,
+                },
+            ));
+        }
+
+        // add the layers that the old map did not have
+        for (index, layer) in new.layers.iter().enumerate() {
+            if !old.layers.iter().any(|l| layer_ty(l) == layer_ty(layer)) {
+                self.actions
+                    .push(EditorAction::AddPhysicsTileLayer(ActAddPhysicsTileLayer {
+                        base: ActAddRemPhysicsTileLayer {
+                            index,
+                            layer: layer.clone(),
+                        },
+                    }));
+            }
+        }
+
+        let width = new.attr.width.get() as usize;
+        let height = new.attr.height.get() as usize;
+        for (layer_index, new_layer) in new.layers.iter().enumerate() {
+            let Some(old_layer) = old
+                .layers
+                .iter()
+                .find(|l| layer_ty(l) == layer_ty(new_layer))
+            else {
+                continue;
+            };
+            if !size_change
+                && let (Some(old_tiles), Some(new_tiles)) =
+                    (physics_tiles(old_layer), physics_tiles(new_layer))
+            {
+                for (region, old_tiles, new_tiles) in
+                    physics_regions(&old_tiles, &new_tiles, width, height)
+                {
+                    self.actions
+                        .push(EditorAction::TilePhysicsLayerReplaceTiles(
+                            ActTilePhysicsLayerReplaceTiles {
+                                base: ActTilePhysicsLayerReplTilesBase {
+                                    layer_index,
+                                    old_tiles,
+                                    new_tiles,
+                                    x: region.x,
+                                    y: region.y,
+                                    w: NonZeroU16MinusOne::new(region.w).unwrap(),
+                                    h: NonZeroU16MinusOne::new(region.h).unwrap(),
+                                },
+                            },
+                        ));
+                }
+            }
+            self.diff_physics_names(old_layer, new_layer);
+        }
+        Ok(())
+    }
+
+    fn diff_physics_names(&mut self, old: &MapLayerPhysics, new: &MapLayerPhysics) {
+        match (old, new) {
+            (MapLayerPhysics::Tele(old), MapLayerPhysics::Tele(new)) => {
+                let indices = old.tele_names.keys().chain(new.tele_names.keys());
+                for index in indices.copied().collect::<std::collections::BTreeSet<_>>() {
+                    let old_name = old.tele_names.get(&index).cloned().unwrap_or_default();
+                    let new_name = new.tele_names.get(&index).cloned().unwrap_or_default();
+                    if old_name != new_name {
+                        self.actions
+                            .push(EditorAction::ChangeTeleporter(ActChangeTeleporter {
+                                index,
+                                old_name,
+                                new_name,
+                            }));
+                    }
+                }
+            }
+            (MapLayerPhysics::Switch(old), MapLayerPhysics::Switch(new)) => {
+                let indices = old.switch_names.keys().chain(new.switch_names.keys());
+                for index in indices.copied().collect::<std::collections::BTreeSet<_>>() {
+                    let old_name = old.switch_names.get(&index).cloned().unwrap_or_default();
+                    let new_name = new.switch_names.get(&index).cloned().unwrap_or_default();
+                    if old_name != new_name {
+                        self.actions
+                            .push(EditorAction::ChangeSwitch(ActChangeSwitch {
+                                index,
+                                old_name,
+                                new_name,
+                            }));
+                    }
+                }
+            }
+            (MapLayerPhysics::Tune(old), MapLayerPhysics::Tune(new)) => {
+                let indices = old.tune_zones.keys().chain(new.tune_zones.keys());
+                for index in indices.copied().collect::<std::collections::BTreeSet<_>>() {
+                    let old_zone = old.tune_zones.get(&index);
+                    let new_zone = new.tune_zones.get(&index);
+                    if old_zone != new_zone {
+                        self.actions
+                            .push(EditorAction::ChangeTuneZone(ActChangeTuneZone {
+                                index,
+                                old_name: old_zone.map(|z| z.name.clone()).unwrap_or_default(),
+                                new_name: new_zone.map(|z| z.name.clone()).unwrap_or_default(),
+                                old_tunes: old_zone.map(|z| z.tunes.clone()).unwrap_or_default(),
+                                new_tunes: new_zone.map(|z| z.tunes.clone()).unwrap_or_default(),
+                                old_enter_msg: old_zone.and_then(|z| z.enter_msg.clone()),
+                                new_enter_msg: new_zone.and_then(|z| z.enter_msg.clone()),
+                                old_leave_msg: old_zone.and_then(|z| z.leave_msg.clone()),
+                                new_leave_msg: new_zone.and_then(|z| z.leave_msg.clone()),
+                            }));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn diff_config(&mut self, old: &Map, new: &Map) {
+        if old.config.commands != new.config.commands {
+            self.actions.push(EditorAction::SetCommands(ActSetCommands {
+                old_commands: old.config.commands.clone(),
+                new_commands: new.config.commands.clone(),
+            }));
+        }
+        if old.config.config_variables != new.config.config_variables {
+            self.actions
+                .push(EditorAction::SetConfigVariables(ActSetConfigVariables {
+                    old_config_variables: old.config.config_variables.clone(),
+                    new_config_variables: new.config.config_variables.clone(),
+                }));
+        }
+        if old.meta != new.meta {
+            self.actions.push(EditorAction::SetMetadata(ActSetMetadata {
+                old_meta: old.meta.clone(),
+                new_meta: new.meta.clone(),
+            }));
+        }
+    }
+}
+
+/// A part of the map that was changed differently in both merged maps.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapMergeConflict {
+    /// What part of the map conflicted.
+    pub what: String,
+    /// The layer, if the conflict is inside a single layer.
+    pub layer: Option<MapDiffLayer>,
+    /// The tile region, if the conflict is inside a tile layer.
+    pub region: Option<MapDiffRegion>,
+}
+
+impl Display for MapMergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.what)?;
+        if let Some(layer) = &self.layer {
+            write!(f, " in {layer}")?;
+        }
+        if let Some(MapDiffRegion { x, y, w, h }) = &self.region {
+            write!(f, " @({x}, {y})-({}, {})", x + w, y + h)?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of a three-way merge.
+///
+/// Conflicting changes always keep "our" version.
+#[derive(Debug, Clone)]
+pub struct MapMerge {
+    pub map: Map,
+    pub conflicts: Vec<MapMergeConflict>,
+}
+
+impl MapMerge {
+    /// Merges the changes of `ours` & `theirs` relative to their common ancestor `base`.
+    ///
+    /// Fails if the merged map is not valid.
+    pub fn new(base: &Map, ours: &Map, theirs: &Map) -> anyhow::Result<Self> {
+        let mut merge = MergeCtx::default();
+        let (mut base, mut ours, mut theirs) = (base.clone(), ours.clone(), theirs.clone());
+        let mut map = ours.clone();
+
+        // resources & animations are merged first, then all maps are brought into the
+        // index space of the merged map, so that the layers can be merged by their values.
+        let used = [used_indices(&mut ours), used_indices(&mut theirs)];
+        let mut remaps: [HashMap<MapIndexTy, Vec<Option<usize>>>; 3] = Default::default();
+        let mut add_remaps = |ty: MapIndexTy, lists: [Vec<Option<usize>>; 3]| {
+            for (remap, list) in remaps.iter_mut().zip(lists) {
+                remap.insert(ty, list);
+            }
+        };
+
+        let (images, lists) = merge.indexed(
+            "image",
+            MapIndexTy::Image,
+            [
+                &base.resources.images,
+                &ours.resources.images,
+                &theirs.resources.images,
+            ],
+            &used,
+            |a, b| a == b,
+        );
+        map.resources.images = images;
+        add_remaps(MapIndexTy::Image, lists);
+        let (image_arrays, lists) = merge.indexed(
+            "tile layer image",
+            MapIndexTy::ImageArray,
+            [
+                &base.resources.image_arrays,
+                &ours.resources.image_arrays,
+                &theirs.resources.image_arrays,
+            ],
+            &used,
+            |a, b| a == b,
+        );
+        map.resources.image_arrays = image_arrays;
+        add_remaps(MapIndexTy::ImageArray, lists);
+        let (sounds, lists) = merge.indexed(
+            "sound",
+            MapIndexTy::Sound,
+            [
+                &base.resources.sounds,
+                &ours.resources.sounds,
+                &theirs.resources.sounds,
+            ],
+            &used,
+            |a, b| a == b,
+        );
+        map.resources.sounds = sounds;
+        add_remaps(MapIndexTy::Sound, lists);
+
+        let (pos, lists) = merge.anims(
+            "pos animation",
+            MapIndexTy::PosAnim,
+            [
+                &base.animations.pos,
+                &ours.animations.pos,
+                &theirs.animations.pos,
+            ],
+            &used,
+        );
+        map.animations.pos = pos;
+        add_remaps(MapIndexTy::PosAnim, lists);
+        let (color, lists) = merge.anims(
+            "color animation",
+            MapIndexTy::ColorAnim,
+            [
+                &base.animations.color,
+                &ours.animations.color,
+                &theirs.animations.color,
+            ],
+            &used,
+        );
+        map.animations.color = color;
+        add_remaps(MapIndexTy::ColorAnim, lists);
+        let (sound, lists) = merge.anims(
+            "sound animation",
+            MapIndexTy::SoundAnim,
+            [
+                &base.animations.sound,
+                &ours.animations.sound,
+                &theirs.animations.sound,
+            ],
+            &used,
+        );
+        map.animations.sound = sound;
+        add_remaps(MapIndexTy::SoundAnim, lists);
+
+        for (side, remap) in [&mut base, &mut ours, &mut theirs].into_iter().zip(&remaps) {
+            for_each_index(side, |ty, index| {
+                // indices of removed items never match a valid index
+                *index = remap[&ty]
+                    .get(*index)
+                    .copied()
+                    .flatten()
+                    .unwrap_or(usize::MAX);
+            });
+        }
+
+        map.groups.background = merge.groups(
+            true,
+            &base.groups.background,
+            &ours.groups.background,
+            &theirs.groups.background,
+        );
+        map.groups.foreground = merge.groups(
+            false,
+            &base.groups.foreground,
+            &ours.groups.foreground,
+            &theirs.groups.foreground,
+        );
+        map.groups.physics = merge.physics(
+            &base.groups.physics,
+            &ours.groups.physics,
+            &theirs.groups.physics,
+        );
+
+        map.config.commands = merge.value(
+            "commands",
+            &base.config.commands,
+            &ours.config.commands,
+            &theirs.config.commands,
+        );
+        map.config.config_variables = merge.value(
+            "config variables",
+            &base.config.config_variables,
+            &ours.config.config_variables,
+            &theirs.config.config_variables,
+        );
+        map.meta = merge.value("meta data", &base.meta, &ours.meta, &theirs.meta);
+
+        Map::validate_all_resource_and_anim_indices(&map.resources, &map.animations, &map.groups)
+            .map_err(|err| anyhow!("the merged map is invalid: {err}"))?;
+
+        Ok(Self {
+            map,
+            conflicts: merge.conflicts,
+        })
+    }
+}
+
+/// How the resources of one kind change from the old to the new map.
+///
+/// Resources are matched by their hash, like when importing maps.
+/// Resources of both maps keep their old order, new resources are appended
+/// & old resources that the new map does not use are removed.
+struct ResourceChanges {
+    /// The new resources, appended after the old ones.
+    added: Vec<ActAddRemImage>,
+    /// The old resources that are removed, from back to front.
+    removed: Vec<ActAddRemImage>,
+    /// The index of every resource of the new map, after all changes.
+    indices: Vec<usize>,
+}
+
+impl ResourceChanges {
+    fn new(
+        old: &[MapResourceRef],
+        new: &[MapResourceRef],
+        files: &HashMap<Hash, Vec<u8>>,
+    ) -> anyhow::Result<Self> {
+        let file_of = |res: &MapResourceRef| {
+            files
+                .get(&res.meta.blake3_hash)
+                .cloned()
+                .ok_or_else(|| anyhow!("file of resource {} is missing", res.name.as_str()))
+        };
+        let new_hashes: HashSet<Hash> = new.iter().map(|res| res.meta.blake3_hash).collect();
+
+        let mut resources: Vec<&MapResourceRef> = Vec::new();
+        let mut removed = Vec::new();
+        for (index, res) in old.iter().enumerate() {
+            if new_hashes.contains(&res.meta.blake3_hash) {
+                resources.push(res);
+            } else {
+                removed.push(ActAddRemImage {
+                    res: res.clone(),
+                    file: file_of(res)?,
+                    index,
+                });
+            }
+        }
+        removed.reverse();
+
+        let mut added = Vec::new();
+        let mut indices = Vec::new();
+        for res in new {
+            let hash = res.meta.blake3_hash;
+            let index = match resources.iter().position(|r| r.meta.blake3_hash == hash) {
+                Some(index) => index,
+                None => {
+                    added.push(ActAddRemImage {
+                        res: res.clone(),
+                        file: file_of(res)?,
+                        index: old.len() + added.len(),
+                    });
+                    resources.push(res);
+                    resources.len() - 1
+                }
+            };
+            indices.push(index);
+        }
+
+        Ok(Self {
+            added,
+            removed,
+            indices,
+        })
+    }
+}
+
+/// The kinds of map items that layers reference by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MapIndexTy {
+    Image,
+    ImageArray,
+    Sound,
+    PosAnim,
+    ColorAnim,
+    SoundAnim,
+}
+
+/// Calls `f` for every resource & animation index that the design layers use.
+fn for_each_index(map: &mut Map, mut f: impl FnMut(MapIndexTy, &mut usize)) {
+    let groups = map
+        .groups
+        .background
+        .iter_mut()
+        .chain(map.groups.foreground.iter_mut());
+    for layer in groups.flat_map(|group| group.layers.iter_mut()) {
+        match layer {
+            MapLayer::Abritrary(_) => {}
+            MapLayer::Tile(layer) => {
+                if let Some(index) = &mut layer.attr.image_array {
+                    f(MapIndexTy::ImageArray, index);
+                }
+                if let Some(index) = &mut layer.attr.color_anim {
+                    f(MapIndexTy::ColorAnim, index);
+                }
+            }
+            MapLayer::Quad(layer) => {
+                if let Some(index) = &mut layer.attr.image {
+                    f(MapIndexTy::Image, index);
+                }
+                for quad in layer.quads.iter_mut() {
+                    if let Some(index) = &mut quad.pos_anim {
+                        f(MapIndexTy::PosAnim, index);
+                    }
+                    if let Some(index) = &mut quad.color_anim {
+                        f(MapIndexTy::ColorAnim, index);
+                    }
+                }
+            }
+            MapLayer::Sound(layer) => {
+                if let Some(index) = &mut layer.attr.sound {
+                    f(MapIndexTy::Sound, index);
+                }
+                for sound in layer.sounds.iter_mut() {
+                    if let Some(index) = &mut sound.pos_anim {
+                        f(MapIndexTy::PosAnim, index);
+                    }
+                    if let Some(index) = &mut sound.sound_anim {
+                        f(MapIndexTy::SoundAnim, index);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn used_indices(map: &mut Map) -> HashSet<(MapIndexTy, usize)> {
+    let mut used = HashSet::default();
+    for_each_index(map, |ty, index| {
+        used.insert((ty, *index));
+    });
+    used
+}
+
+/// Three-way merges a single value, `None` means the value conflicted.
+fn merge_value<T: Clone>(base: &T, ours: &T, theirs: &T, eq: impl Fn(&T, &T) -> bool) -> Option<T> {
+    if eq(ours, base) {
+        Some(theirs.clone())
+    } else if eq(theirs, base) || eq(ours, theirs) {
+        Some(ours.clone())
+    } else {
+        None
+    }
+}
+
+/// Three-way merges the tiles of a layer tile by tile.
+///
+/// Returns the merged tiles and the regions with conflicting tiles.
+pub fn merge_tiles<T: Copy + PartialEq>(
+    base: &[T],
+    ours: &[T],
+    theirs: &[T],
+    width: usize,
+    height: usize,
+) -> (Vec<T>, Vec<MapDiffRegion>) {
+    let mut conflicts = vec![false; base.len()];
+    let tiles = base
+        .iter()
+        .zip(ours.iter())
+        .zip(theirs.iter())
+        .enumerate()
+        .map(|(index, ((base, ours), theirs))| {
+            merge_value(base, ours, theirs, |a, b| a == b).unwrap_or_else(|| {
+                conflicts[index] = true;
+                *ours
+            })
+        })
+        .collect();
+    let no_conflicts = vec![false; base.len()];
+    (
+        tiles,
+        changed_regions(&no_conflicts, &conflicts, width, height),
+    )
+}
+
+#[derive(Debug, Default)]
+struct MergeCtx {
+    conflicts: Vec<MapMergeConflict>,
+}
+
+impl MergeCtx {
+    fn conflict(&mut self, what: &str, layer: Option<MapDiffLayer>) {
+        self.conflicts.push(MapMergeConflict {
+            what: what.to_string(),
+            layer,
+            region: None,
+        });
+    }
+
+    fn value<T: Clone + PartialEq>(&mut self, what: &str, base: &T, ours: &T, theirs: &T) -> T {
+        merge_value(base, ours, theirs, |a, b| a == b).unwrap_or_else(|| {
+            self.conflict(what, None);
+            ours.clone()
+        })
+    }
+
+    /// Merges items that layers reference by index, e.g. images.
+    ///
+    /// Items are matched by `eq`, so items that were removed or reordered on one side
+    /// do not change the meaning of the other side's layers. An item that one side removed,
+    /// while the other side still uses it, is kept & reported as conflict.
+    ///
+    /// Returns the merged items & the merged index of every item of base, ours & theirs.
+    fn indexed<T: Clone>(
+        &mut self,
+        what: &str,
+        ty: MapIndexTy,
+        [base, ours, theirs]: [&Vec<T>; 3],
+        [ours_used, theirs_used]: &[HashSet<(MapIndexTy, usize)>; 2],
+        eq: impl Fn(&T, &T) -> bool,
+    ) -> (Vec<T>, [Vec<Option<usize>>; 3]) {
+        let contains = |items: &[T], item: &T| items.iter().any(|i| eq(i, item));
+        let mut items: Vec<T> = Vec::new();
+        for (side, other, used) in [(ours, theirs, ours_used), (theirs, ours, theirs_used)] {
+            for (index, item) in side.iter().enumerate() {
+                if contains(&items, item) {
+                    continue;
+                }
+                if contains(base, item) && !contains(other, item) {
+                    if !used.contains(&(ty, index)) {
+                        continue;
+                    }
+                    self.conflict(&format!("removed, but still used {what} #{index}"), None);
+                }
+                items.push(item.clone());
+            }
+        }
+        let remap = |list: &[T]| {
+            list.iter()
+                .map(|item| items.iter().position(|i| eq(i, item)))
+                .collect()
+        };
+        let remaps = [remap(base), remap(ours), remap(theirs)];
+        (items, remaps)
+    }
+
+    /// Merges animations, which are usually edited in place or appended.
+    ///
+    /// If one side removed animations, they are matched by their value instead, see [`Self::indexed`].
+    fn anims<T: Clone + Serialize>(
+        &mut self,
+        what: &str,
+        ty: MapIndexTy,
+        [base, ours, theirs]: [&Vec<T>; 3],
+        used: &[HashSet<(MapIndexTy, usize)>; 2],
+    ) -> (Vec<T>, [Vec<Option<usize>>; 3]) {
+        if ours.len() < base.len() || theirs.len() < base.len() {
+            return self.indexed(what, ty, [base, ours, theirs], used, anim_eq);
+        }
+        let mut anims: Vec<T> = base
+            .iter()
+            .zip(ours.iter())
+            .zip(theirs.iter())
+            .enumerate()
+            .map(|(index, ((base, ours), theirs))| {
+                merge_value(base, ours, theirs, anim_eq).unwrap_or_else(|| {
+                    self.conflict(&format!("{what} #{index}"), None);
+                    ours.clone()
+                })
+            })
+            .collect();
+        // both sides' new animations are appended, ours first
+        anims.extend(ours[base.len()..].iter().cloned());
+        anims.extend(theirs[base.len()..].iter().cloned());
+        let added_by_ours = ours.len() - base.len();
+        let remaps = [
+            (0..base.len()).map(Some).collect(),
+            (0..ours.len()).map(Some).collect(),
+            (0..theirs.len())
+                .map(|index| {
+                    Some(if index < base.len() {
+                        index
+                    } else {
+                        index + added_by_ours
+                    })
+                })
+                .collect(),
+        ];
+        (anims, remaps)
+    }
+
+    fn tiles<T: Copy + PartialEq>(
+        &mut self,
+        layer: MapDiffLayer,
+        base: &[T],
+        ours: &[T],
+        theirs: &[T],
+        width: usize,
+        height: usize,
+    ) -> Vec<T> {
+        let (tiles, regions) = merge_tiles(base, ours, theirs, width, height);
+        self.conflicts
+            .extend(regions.into_iter().map(|region| MapMergeConflict {
+                what: "tiles".to_string(),
+                layer: Some(layer),
+                region: Some(region),
+            }));
+        tiles
+    }
+
+    fn groups(
+        &mut self,
+        is_background: bool,
+        base: &[MapGroup],
+        ours: &[MapGroup],
+        theirs: &[MapGroup],
+    ) -> Vec<MapGroup> {
+        let what = if is_background {
+            "background groups"
+        } else {
+            "foreground groups"
+        };
+        if base.len() != ours.len() || base.len() != theirs.len() {
+            return self.value(what, &base.to_vec(), &ours.to_vec(), &theirs.to_vec());
+        }
+        base.iter()
+            .zip(ours.iter())
+            .zip(theirs.iter())
+            .enumerate()
+            .map(|(group_index, ((base, ours), theirs))| {
+                let what = format!(
+                    "group #{group_index} in {}",
+                    if is_background {
+                        "background"
+                    } else {
+                        "foreground"
+                    }
+                );
+                let mut group = ours.clone();
+                group.attr = self.value(
+                    &format!("attributes of {what}"),
+                    &base.attr,
+                    &ours.attr,
+                    &theirs.attr,
+                );
+                group.name = self.value(
+                    &format!("name of {what}"),
+                    &base.name,
+                    &ours.name,
+                    &theirs.name,
+                );
+                let same_shape = base.layers.len() == ours.layers.len()
+                    && base.layers.len() == theirs.layers.len()
+                    && base
+                        .layers
+                        .iter()
+                        .zip(ours.layers.iter())
+                        .zip(theirs.layers.iter())
+                        .all(|((b, o), t)| design_shape_eq(b, o) && design_shape_eq(b, t));
+                group.layers = if same_shape {
+                    base.layers
+                        .iter()
+                        .zip(ours.layers.iter())
+                        .zip(theirs.layers.iter())
+                        .enumerate()
+                        .map(|(layer_index, ((base, ours), theirs))| {
+                            self.design_layer(
+                                MapDiffLayer::Design {
+                                    is_background,
+                                    group_index,
+                                    layer_index,
+                                },
+                                base,
+                                ours,
+                                theirs,
+                            )
+                        })
+                        .collect()
+                } else {
+                    self.value(
+                        &format!("layers of {what}"),
+                        &base.layers,
+                        &ours.layers,
+                        &theirs.layers,
+                    )
+                };
+                group
+            })
+            .collect()
+    }
+
+    fn design_layer(
+        &mut self,
+        layer: MapDiffLayer,
+        base: &MapLayer,
+        ours: &MapLayer,
+        theirs: &MapLayer,
+    ) -> MapLayer {
+        match (base, ours, theirs) {
+            (MapLayer::Tile(base), MapLayer::Tile(ours), MapLayer::Tile(theirs)) => {
+                let mut res = ours.clone();
+                res.attr = merge_value(&base.attr, &ours.attr, &theirs.attr, |a, b| a == b)
+                    .unwrap_or_else(|| {
+                        self.conflict("attributes", Some(layer));
+                        ours.attr
+                    });
+                res.name = merge_value(&base.name, &ours.name, &theirs.name, |a, b| a == b)
+                    .unwrap_or_else(|| {
+                        self.conflict("name", Some(layer));
+                        ours.name.clone()
+                    });
+                res.tiles = self.tiles(
+                    layer,
+                    &base.tiles,
+                    &ours.tiles,
+                    &theirs.tiles,
+                    base.attr.width.get() as usize,
+                    base.attr.height.get() as usize,
+                );
+                MapLayer::Tile(res)
+            }
+            (MapLayer::Quad(base), MapLayer::Quad(ours), MapLayer::Quad(theirs)) => {
+                let mut res = ours.clone();
+                res.attr = merge_value(&base.attr, &ours.attr, &theirs.attr, |a, b| a == b)
+                    .unwrap_or_else(|| {
+                        self.conflict("attributes", Some(layer));
+                        ours.attr
+                    });
+                res.name = merge_value(&base.name, &ours.name, &theirs.name, |a, b| a == b)
+                    .unwrap_or_else(|| {
+                        self.conflict("name", Some(layer));
+                        ours.name.clone()
+                    });
+                res.quads = self.items("quads", layer, &base.quads, &ours.quads, &theirs.quads);
+                MapLayer::Quad(res)
+            }
+            (MapLayer::Sound(base), MapLayer::Sound(ours), MapLayer::Sound(theirs)) => {
+                let mut res = ours.clone();
+                res.attr = merge_value(&base.attr, &ours.attr, &theirs.attr, |a, b| a == b)
+                    .unwrap_or_else(|| {
+                        self.conflict("attributes", Some(layer));
+                        ours.attr
+                    });
+                res.name = merge_value(&base.name, &ours.name, &theirs.name, |a, b| a == b)
+                    .unwrap_or_else(|| {
+                        self.conflict("name", Some(layer));
+                        ours.name.clone()
+                    });
+                res.sounds =
+                    self.items("sounds", layer, &base.sounds, &ours.sounds, &theirs.sounds);
+                MapLayer::Sound(res)
+            }
+            _ => merge_value(base, ours, theirs, |a, b| a == b).unwrap_or_else(|| {
+                self.conflict("layer", Some(layer));
+                ours.clone()
+            }),
+        }
+    }
+
+    /// Merges quads or sounds one by one, if their count did not change.
+    fn items<T: Clone + PartialEq>(
+        &mut self,
+        what: &str,
+        layer: MapDiffLayer,
+        base: &Vec<T>,
+        ours: &Vec<T>,
+        theirs: &Vec<T>,
+    ) -> Vec<T> {
+        if base.len() == ours.len() && base.len() == theirs.len() {
+            base.iter()
+                .zip(ours.iter())
+                .zip(theirs.iter())
+                .enumerate()
+                .map(|(index, ((base, ours), theirs))| {
+                    merge_value(base, ours, theirs, |a, b| a == b).unwrap_or_else(|| {
+                        self.conflict(&format!("{what} #{index}"), Some(layer));
+                        ours.clone()
+                    })
+                })
+                .collect()
+        } else {
+            merge_value(base, ours, theirs, |a, b| a == b).unwrap_or_else(|| {
+                self.conflict(what, Some(layer));
+                ours.clone()
+            })
+        }
+    }
+
+    fn physics(
+        &mut self,
+        base: &MapGroupPhysics,
+        ours: &MapGroupPhysics,
+        theirs: &MapGroupPhysics,
+    ) -> MapGroupPhysics {
+        let same_shape = base.attr == ours.attr
+            && base.attr == theirs.attr
+            && base.layers.len() == ours.layers.len()
+            && base.layers.len() == theirs.layers.len()
+            && base
+                .layers
+                .iter()
+                .zip(ours.layers.iter())
+                .zip(theirs.layers.iter())
+                .all(|((b, o), t)| layer_ty(b) == layer_ty(o) && layer_ty(b) == layer_ty(t));
+        if !same_shape {
+            let physics_eq =
+                |a: &MapGroupPhysics, b: &MapGroupPhysics| a.attr == b.attr && a.layers == b.layers;
+            return merge_value(base, ours, theirs, physics_eq).unwrap_or_else(|| {
+                self.conflict("physics group", None);
+                ours.clone()
+            });
+        }
+
+        let width = base.attr.width.get() as usize;
+        let height = base.attr.height.get() as usize;
+        let layers = base
+            .layers
+            .iter()
+            .zip(ours.layers.iter())
+            .zip(theirs.layers.iter())
+            .enumerate()
+            .map(|(layer_index, ((base, ours), theirs))| {
+                let layer = MapDiffLayer::Physics { layer_index };
+                let mut res = ours.clone();
+                match (base, ours, theirs, &mut res) {
+                    (
+                        MapLayerPhysics::Game(base),
+                        MapLayerPhysics::Game(ours),
+                        MapLayerPhysics::Game(theirs),
+                        MapLayerPhysics::Game(res),
+                    )
+                    | (
+                        MapLayerPhysics::Front(base),
+                        MapLayerPhysics::Front(ours),
+                        MapLayerPhysics::Front(theirs),
+                        MapLayerPhysics::Front(res),
+                    ) => {
+                        res.tiles = self.tiles(
+                            layer,
+                            &base.tiles,
+                            &ours.tiles,
+                            &theirs.tiles,
+                            width,
+                            height,
+                        );
+                    }
+                    (
+                        MapLayerPhysics::Tele(base),
+                        MapLayerPhysics::Tele(ours),
+                        MapLayerPhysics::Tele(theirs),
+                        MapLayerPhysics::Tele(res),
+                    ) => {
+                        res.base.tiles = self.tiles(
+                            layer,
+                            &base.base.tiles,
+                            &ours.base.tiles,
+                            &theirs.base.tiles,
+                            width,
+                            height,
+                        );
+                        res.tele_names = merge_value(
+                            &base.tele_names,
+                            &ours.tele_names,
+                            &theirs.tele_names,
+                            |a, b| a == b,
+                        )
+                        .unwrap_or_else(|| {
+                            self.conflict("teleporter names", Some(layer));
+                            ours.tele_names.clone()
+                        });
+                    }
+                    (
+                        MapLayerPhysics::Speedup(base),
+                        MapLayerPhysics::Speedup(ours),
+                        MapLayerPhysics::Speedup(theirs),
+                        MapLayerPhysics::Speedup(res),
+                    ) => {
+                        res.tiles = self.tiles(
+                            layer,
+                            &base.tiles,
+                            &ours.tiles,
+                            &theirs.tiles,
+                            width,
+                            height,
+                        );
+                    }
+                    (
+                        MapLayerPhysics::Switch(base),
+                        MapLayerPhysics::Switch(ours),
+                        MapLayerPhysics::Switch(theirs),
+                        MapLayerPhysics::Switch(res),
+                    ) => {
+                        res.base.tiles = self.tiles(
+                            layer,
+                            &base.base.tiles,
+                            &ours.base.tiles,
+                            &theirs.base.tiles,
+                            width,
+                            height,
+                        );
+                        res.switch_names = merge_value(
+                            &base.switch_names,
+                            &ours.switch_names,
+                            &theirs.switch_names,
+                            |a, b| a == b,
+                        )
+                        .unwrap_or_else(|| {
+                            self.conflict("switch names", Some(layer));
+                            ours.switch_names.clone()
+                        });
+                    }
+                    (
+                        MapLayerPhysics::Tune(base),
+                        MapLayerPhysics::Tune(ours),
+                        MapLayerPhysics::Tune(theirs),
+                        MapLayerPhysics::Tune(res),
+                    ) => {
+                        res.base.tiles = self.tiles(
+                            layer,
+                            &base.base.tiles,
+                            &ours.base.tiles,
+                            &theirs.base.tiles,
+                            width,
+                            height,
+                        );
+                        res.tune_zones = merge_value(
+                            &base.tune_zones,
+                            &ours.tune_zones,
+                            &theirs.tune_zones,
+                            |a, b| a == b,
+                        )
+                        .unwrap_or_else(|| {
+                            self.conflict("tune zones", Some(layer));
+                            ours.tune_zones.clone()
+                        });
+                    }
+                    _ => {
+                        if let Some(merged) = merge_value(base, ours, theirs, |a, b| a == b) {
+                            return merged;
+                        }
+                        self.conflict("layer", Some(layer));
+                    }
+                }
+                res
+            })
+            .collect();
+
+        MapGroupPhysics {
+            attr: base.attr,
+            layers,
+        }
+    }
+}
+
+/// Whether two design layers have the same type & size,
+/// so that their contents can be merged.
+fn design_shape_eq(a: &MapLayer, b: &MapLayer) -> bool {
+    match (a, b) {
+        (MapLayer::Tile(a), MapLayer::Tile(b)) => {
+            a.attr.width == b.attr.width && a.attr.height == b.attr.height
+        }
+        (MapLayer::Quad(_), MapLayer::Quad(_)) | (MapLayer::Sound(_), MapLayer::Sound(_)) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use base::hash::generate_hash_for;
+    use image_utils::png::save_png_image;
+    use map::map::{
+        Map,
+        groups::{
+            MapGroup,
+            layers::{
+                design::{MapLayer, MapLayerQuad, MapLayerQuadsAttrs},
+                physics::MapLayerPhysics,
+            },
+        },
+        resources::{MapResourceMetaData, MapResourceRef},
+    };
+
+    use crate::test_utils::{TestBackend, arctic, io};
+
+    use super::{MapDiff, MapDiffRegion, MapMerge, changed_regions, merge_tiles};
+
+    #[test]
+    fn diff_applies() {
+        let io = io();
+        let tp = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap(),
+        );
+        let backend = TestBackend::new(&io, &tp);
+
+        let old = arctic(&io, &tp);
+        let mut new = old.clone();
+        if let MapLayerPhysics::Game(layer) = &mut new.groups.physics.layers[0] {
+            layer.tiles[0].index = 1;
+        }
+        new.groups.background[0].name = "renamed".to_string();
+        new.groups.background.pop();
+        new.meta.memo = "changed".to_string();
+
+        let diff = MapDiff::new(&old, &new, &Default::default()).unwrap();
+        assert!(!diff.is_empty());
+
+        let mut map = backend.editor_map(&tp, old);
+        for action in diff.actions {
+            backend.do_action(&tp, action, &mut map).unwrap();
+        }
+        let map: Map = map.into();
+        assert_eq!(map.as_json(), new.as_json());
+    }
+
+    #[test]
+    fn diff_matches_resources_by_hash() {
+        let io = io();
+        let tp = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap(),
+        );
+        let backend = TestBackend::new(&io, &tp);
+
+        let mut files = HashMap::default();
+        let mut image = |name: &str, color: u8| {
+            let file = save_png_image(&[color; 2 * 2 * 4], 2, 2).unwrap();
+            let blake3_hash = generate_hash_for(&file);
+            files.insert(blake3_hash, file);
+            MapResourceRef {
+                name: name.try_into().unwrap(),
+                meta: MapResourceMetaData {
+                    blake3_hash,
+                    ty: "png".try_into().unwrap(),
+                },
+                hq_meta: None,
+            }
+        };
+        let (a, b, c) = (image("a", 1), image("b", 2), image("c", 3));
+        let b_copy = MapResourceRef {
+            name: "b_copy".try_into().unwrap(),
+            ..b.clone()
+        };
+        let quads = |image: usize| {
+            MapLayer::Quad(MapLayerQuad {
+                attr: MapLayerQuadsAttrs {
+                    image: Some(image),
+                    high_detail: false,
+                },
+                quads: Vec::new(),
+                name: String::new(),
+            })
+        };
+        let layer_images = |map: &Map| {
+            map.groups
+                .foreground
+                .last()
+                .unwrap()
+                .layers
+                .iter()
+                .map(|layer| match layer {
+                    MapLayer::Quad(layer) => {
+                        map.resources.images[layer.attr.image.unwrap()]
+                            .meta
+                            .blake3_hash
+                    }
+                    _ => unreachable!(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut old = arctic(&io, &tp);
+        old.resources.images = vec![a.clone(), b.clone()];
+        old.groups.foreground.push(MapGroup {
+            attr: Default::default(),
+            layers: vec![quads(0), quads(1)],
+            name: String::new(),
+        });
+
+        // b is moved to the front & used twice, a is removed & c is added
+        let mut new = old.clone();
+        new.resources.images = vec![b.clone(), b_copy, c.clone()];
+        new.groups.foreground.last_mut().unwrap().layers = vec![quads(0), quads(1), quads(2)];
+
+        let diff = MapDiff::new(&old, &new, &files).unwrap();
+        let mut map = backend.editor_map_with_resources(&tp, old, files);
+        for action in diff.actions {
+            backend.do_action(&tp, action, &mut map).unwrap();
+        }
+        let map: Map = map.into();
+        assert_eq!(map.resources.images, vec![b, c]);
+        assert_eq!(layer_images(&map), layer_images(&new));
+    }
+
+    #[test]
+    fn merge_remaps_resources() {
+        let io = io();
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+
+        let image = |name: &str, hash: u8| MapResourceRef {
+            name: name.try_into().unwrap(),
+            meta: MapResourceMetaData {
+                blake3_hash: [hash; 32],
+                ty: "png".try_into().unwrap(),
+            },
+            hq_meta: None,
+        };
+        let quads = |image: Option<usize>| {
+            MapLayer::Quad(MapLayerQuad {
+                attr: MapLayerQuadsAttrs {
+                    image,
+                    high_detail: false,
+                },
+                quads: Vec::new(),
+                name: String::new(),
+            })
+        };
+
+        let mut base = arctic(&io, &tp);
+        base.resources.images = vec![image("a", 1), image("b", 2)];
+        base.groups.foreground.push(MapGroup {
+            attr: Default::default(),
+            layers: vec![quads(Some(1)), quads(None)],
+            name: String::new(),
+        });
+
+        // ours removes the unused image, theirs adds a new one
+        let mut ours = base.clone();
+        ours.resources.images.remove(0);
+        ours.groups.foreground.last_mut().unwrap().layers[0] = quads(Some(0));
+        let mut theirs = base.clone();
+        theirs.resources.images.push(image("c", 3));
+        theirs.groups.foreground.last_mut().unwrap().layers[1] = quads(Some(2));
+
+        let merge = MapMerge::new(&base, &ours, &theirs).unwrap();
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(
+            merge.map.resources.images,
+            vec![image("b", 2), image("c", 3)]
+        );
+        assert_eq!(
+            merge.map.groups.foreground.last().unwrap().layers,
+            vec![quads(Some(0)), quads(Some(1))]
+        );
+
+        // theirs still uses the image that ours removed
+        let mut theirs = base.clone();
+        theirs.groups.foreground.last_mut().unwrap().layers[1] = quads(Some(0));
+        let merge = MapMerge::new(&base, &ours, &theirs).unwrap();
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(
+            merge.map.resources.images,
+            vec![image("b", 2), image("a", 1)]
+        );
+        assert_eq!(
+            merge.map.groups.foreground.last().unwrap().layers,
+            vec![quads(Some(0)), quads(Some(1))]
+        );
+    }
+
+    #[test]
+    fn regions() {
+        let old = vec![0; 40 * 3];
+        let mut new = old.clone();
+        new[1] = 1;
+        new[40 + 2] = 1;
+        new[2 * 40 + 35] = 1;
+        assert_eq!(
+            changed_regions(&old, &new, 40, 3),
+            vec![
+                MapDiffRegion {
+                    x: 1,
+                    y: 0,
+                    w: 2,
+                    h: 2
+                },
+                MapDiffRegion {
+                    x: 35,
+                    y: 2,
+                    w: 1,
+                    h: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn three_way_tiles() {
+        let base = [0, 0, 0, 0];
+        let ours = [1, 0, 2, 0];
+        let theirs = [0, 3, 4, 0];
+        let (tiles, conflicts) = merge_tiles(&base, &ours, &theirs, 2, 2);
+        assert_eq!(tiles, vec![1, 3, 2, 0]);
+        assert_eq!(
+            conflicts,
+            vec![MapDiffRegion {
+                x: 0,
+                y: 1,
+                w: 1,
+                h: 1
+            }]
+        );
+    }
+}
@@ -0,0 +1,505 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use anyhow::anyhow;
+use base::hash::Hash;
+use map::{
+    map::{
+        Map,
+        animations::AnimBase,
+        groups::{
+            MapGroup,
+            layers::design::{MapLayer, MapLayerQuad, MapLayerSound, MapLayerTile},
+        },
+        resources::MapResourceRef,
+    },
+    skeleton::{animations::AnimBaseSkeleton, resources::MapResourceRefSkeleton},
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    actions::actions::{
+        ActAddColorAnim, ActAddGroup, ActAddImage, ActAddImage2dArray, ActAddPosAnim,
+        ActAddQuadLayer, ActAddRemColorAnim, ActAddRemGroup, ActAddRemImage, ActAddRemPosAnim,
+        ActAddRemQuadLayer, ActAddRemSound, ActAddRemSoundAnim, ActAddRemSoundLayer,
+        ActAddRemTileLayer, ActAddSound, ActAddSoundAnim, ActAddSoundLayer, ActAddTileLayer,
+        EditorAction, EditorActionGroup,
+    },
+    client::EditorClient,
+    map::{EditorLayerUnionRef, EditorMap, EditorMapInterface},
+    prefab::{map_anim_indices, map_resource_indices},
+};
+
+/// What the user selected to import.
+#[derive(Debug, Default, Clone)]
+pub struct EditorMapImportSelection {
+    /// `(is_background, group_index)`
+    pub groups: BTreeSet<(bool, usize)>,
+    /// `(is_background, group_index, layer_index)`
+    pub layers: BTreeSet<(bool, usize, usize)>,
+
+    pub images: BTreeSet<usize>,
+    pub image_arrays: BTreeSet<usize>,
+    pub sounds: BTreeSet<usize>,
+
+    pub pos_anims: BTreeSet<usize>,
+    pub color_anims: BTreeSet<usize>,
+    pub sound_anims: BTreeSet<usize>,
+}
+
+/// Maps the indices of the other map to the ones of the edited map.
+#[derive(Debug, Default)]
+struct IndexRemap {
+    images: HashMap<usize, usize>,
+    image_arrays: HashMap<usize, usize>,
+    sounds: HashMap<usize, usize>,
+
+    pos_anims: HashMap<usize, usize>,
+    color_anims: HashMap<usize, usize>,
+    sound_anims: HashMap<usize, usize>,
+}
+
+impl IndexRemap {
+    fn layer(&self, layer: &MapLayer) -> Option<MapLayer> {
+        match layer {
+            MapLayer::Abritrary(_) => None,
+            MapLayer::Tile(layer) => {
+                let mut layer = layer.clone();
+                layer.attr.image_array = layer.attr.image_array.map(|i| self.image_arrays[&i]);
+                layer.attr.color_anim = layer.attr.color_anim.map(|i| self.color_anims[&i]);
+                Some(MapLayer::Tile(layer))
+            }
+            MapLayer::Quad(layer) => {
+                let mut layer = layer.clone();
+                layer.attr.image = layer.attr.image.map(|i| self.images[&i]);
+                for quad in &mut layer.quads {
+                    quad.pos_anim = quad.pos_anim.map(|i| self.pos_anims[&i]);
+                    quad.color_anim = quad.color_anim.map(|i| self.color_anims[&i]);
+                }
+                Some(MapLayer::Quad(layer))
+            }
+            MapLayer::Sound(layer) => {
+                let mut layer = layer.clone();
+                layer.attr.sound = layer.attr.sound.map(|i| self.sounds[&i]);
+                for sound in &mut layer.sounds {
+                    sound.pos_anim = sound.pos_anim.map(|i| self.pos_anims[&i]);
+                    sound.sound_anim = sound.sound_anim.map(|i| self.sound_anims[&i]);
+                }
+                Some(MapLayer::Sound(layer))
+            }
+        }
+    }
+}
+
+/// Another map, whose design groups, layers, resources & animations
+/// can be imported into the edited map.
+pub struct EditorMapImport {
+    pub name: String,
+    pub map: Map,
+    resource_files: HashMap<Hash, Vec<u8>>,
+
+    pub selection: EditorMapImportSelection,
+}
+
+impl EditorMapImport {
+    pub fn new(path: &Path, map: Map, resource_files: HashMap<Hash, Vec<u8>>) -> Self {
+        Self {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            map,
+            resource_files,
+            selection: Default::default(),
+        }
+    }
+
+    fn group(&self, is_background: bool, group_index: usize) -> Option<&MapGroup> {
+        if is_background {
+            self.map.groups.background.get(group_index)
+        } else {
+            self.map.groups.foreground.get(group_index)
+        }
+    }
+
+    /// The selected layers that are not part of a selected group.
+    fn single_layers(&self) -> impl Iterator<Item = &MapLayer> {
+        self.selection
+            .layers
+            .iter()
+            .filter(|(is_background, group_index, _)| {
+                !self
+                    .selection
+                    .groups
+                    .contains(&(*is_background, *group_index))
+            })
+            .filter_map(|&(is_background, group_index, layer_index)| {
+                self.group(is_background, group_index)?
+                    .layers
+                    .get(layer_index)
+            })
+    }
+
+    /// The selection including all resources & animations
+    /// that the selected groups & layers use.
+    fn used(&self) -> EditorMapImportSelection {
+        let mut used = self.selection.clone();
+        let layers = self
+            .selection
+            .groups
+            .iter()
+            .filter_map(|&(is_background, group_index)| self.group(is_background, group_index))
+            .flat_map(|group| group.layers.iter())
+            .chain(self.single_layers());
+        for layer in layers {
+            match layer {
+                MapLayer::Abritrary(_) => {}
+                MapLayer::Tile(MapLayerTile { attr, .. }) => {
+                    used.image_arrays.extend(attr.image_array);
+                    used.color_anims.extend(attr.color_anim);
+                }
+                MapLayer::Quad(MapLayerQuad { attr, quads, .. }) => {
+                    used.images.extend(attr.image);
+                    for quad in quads {
+                        used.pos_anims.extend(quad.pos_anim);
+                        used.color_anims.extend(quad.color_anim);
+                    }
+                }
+                MapLayer::Sound(MapLayerSound { attr, sounds, .. }) => {
+                    used.sounds.extend(attr.sound);
+                    for sound in sounds {
+                        used.pos_anims.extend(sound.pos_anim);
+                        used.sound_anims.extend(sound.sound_anim);
+                    }
+                }
+            }
+        }
+        used
+    }
+
+    fn import_resources<U>(
+        &self,
+        indices: &BTreeSet<usize>,
+        resources: &[MapResourceRef],
+        map_resources: &[MapResourceRefSkeleton<U>],
+        actions: &mut Vec<EditorAction>,
+        add: impl Fn(MapResourceRef, Vec<u8>, usize) -> EditorAction,
+    ) -> anyhow::Result<HashMap<usize, usize>> {
+        let resources = indices
+            .iter()
+            .map(|&index| {
+                let res = resources
+                    .get(index)
+                    .ok_or_else(|| anyhow!("resource #{index} does not exist"))?;
+                let file = self
+                    .resource_files
+                    .get(&res.meta.blake3_hash)
+                    .ok_or_else(|| {
+                        anyhow!("the file of resource \"{}\" is missing", res.name.as_str())
+                    })?;
+                // high quality versions are not imported
+                anyhow::Ok((
+                    MapResourceRef {
+                        hq_meta: None,
+                        ..res.clone()
+                    },
+                    file.as_slice(),
+                ))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let new_indices = map_resource_indices(
+            map_resources,
+            resources.iter().map(|(res, file)| (res, *file)),
+            actions,
+            add,
+        );
+        Ok(indices.iter().copied().zip(new_indices).collect())
+    }
+
+    fn import_anims<A, P: DeserializeOwned + PartialOrd + Clone + PartialEq>(
+        indices: &BTreeSet<usize>,
+        anims: &[AnimBase<P>],
+        map_anims: &[AnimBaseSkeleton<A, P>],
+        actions: &mut Vec<EditorAction>,
+        add: impl Fn(AnimBase<P>, usize) -> EditorAction,
+    ) -> anyhow::Result<HashMap<usize, usize>> {
+        let anims = indices
+            .iter()
+            .map(|&index| {
+                anims
+                    .get(index)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("animation #{index} does not exist"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let new_indices = map_anim_indices(map_anims, &anims, actions, add);
+        Ok(indices.iter().copied().zip(new_indices).collect())
+    }
+
+    /// Imports the selection as a single undoable action group.
+    ///
+    /// Resources that already exist in the map (same hash) and
+    /// identical animations are reused.
+    /// Selected groups are added after the existing groups, single layers
+    /// are added to the group of the active layer.
+    pub fn import(&self, map: &EditorMap, client: &EditorClient) -> anyhow::Result<()> {
+        let used = self.used();
+        let mut actions = Vec::new();
+
+        let remap = IndexRemap {
+            images: self.import_resources(
+                &used.images,
+                &self.map.resources.images,
+                &map.resources.images,
+                &mut actions,
+                |res, file, index| {
+                    EditorAction::AddImage(ActAddImage {
+                        base: ActAddRemImage { res, file, index },
+                    })
+                },
+            )?,
+            image_arrays: self.import_resources(
+                &used.image_arrays,
+                &self.map.resources.image_arrays,
+                &map.resources.image_arrays,
+                &mut actions,
+                |res, file, index| {
+                    EditorAction::AddImage2dArray(ActAddImage2dArray {
+                        base: ActAddRemImage { res, file, index },
+                    })
+                },
+            )?,
+            sounds: self.import_resources(
+                &used.sounds,
+                &self.map.resources.sounds,
+                &map.resources.sounds,
+                &mut actions,
+                |res, file, index| {
+                    EditorAction::AddSound(ActAddSound {
+                        base: ActAddRemSound { res, file, index },
+                    })
+                },
+            )?,
+            pos_anims: Self::import_anims(
+                &used.pos_anims,
+                &self.map.animations.pos,
+                &map.animations.pos,
+                &mut actions,
+                |anim, index| {
+                    EditorAction::AddPosAnim(ActAddPosAnim {
+                        base: ActAddRemPosAnim { index, anim },
+                    })
+                },
+            )?,
+            color_anims: Self::import_anims(
+                &used.color_anims,
+                &self.map.animations.color,
+                &map.animations.color,
+                &mut actions,
+                |anim, index| {
+                    EditorAction::AddColorAnim(ActAddColorAnim {
+                        base: ActAddRemColorAnim { index, anim },
+                    })
+                },
+            )?,
+            sound_anims: Self::import_anims(
+                &used.sound_anims,
+                &self.map.animations.sound,
+                &map.animations.sound,
+                &mut actions,
+                |anim, index| {
+                    EditorAction::AddSoundAnim(ActAddSoundAnim {
+                        base: ActAddRemSoundAnim { index, anim },
+                    })
+                },
+            )?,
+        };
+
+        let mut next_background_group = map.groups.background.len();
+        let mut next_foreground_group = map.groups.foreground.len();
+        for &(is_background, group_index) in &self.selection.groups {
+            let Some(group) = self.group(is_background, group_index) else {
+                continue;
+            };
+            let next_group = if is_background {
+                &mut next_background_group
+            } else {
+                &mut next_foreground_group
+            };
+            actions.push(EditorAction::AddGroup(ActAddGroup {
+                base: ActAddRemGroup {
+                    is_background,
+                    index: *next_group,
+                    group: MapGroup {
+                        attr: group.attr,
+                        layers: group
+                            .layers
+                            .iter()
+                            .filter_map(|layer| remap.layer(layer))
+                            .collect(),
+                        name: group.name.clone(),
+                    },
+                },
+            }));
+            *next_group += 1;
+        }
+
+        let mut layers = self
+            .single_layers()
+            .filter_map(|layer| remap.layer(layer))
+            .peekable();
+        if layers.peek().is_some() {
+            let Some(EditorLayerUnionRef::Design {
+                group,
+                group_index,
+                is_background,
+                ..
+            }) = map.active_layer()
+            else {
+                anyhow::bail!(
+                    "Select a design layer of the group the single layers should be imported into."
+                );
+            };
+            for (index, layer) in (group.layers.len()..).zip(layers) {
+                actions.push(match layer {
+                    MapLayer::Abritrary(_) => continue,
+                    MapLayer::Tile(layer) => EditorAction::AddTileLayer(ActAddTileLayer {
+                        base: ActAddRemTileLayer {
+                            is_background,
+                            group_index,
+                            index,
+                            layer,
+                        },
+                    }),
+                    MapLayer::Quad(layer) => EditorAction::AddQuadLayer(ActAddQuadLayer {
+                        base: ActAddRemQuadLayer {
+                            is_background,
+                            group_index,
+                            index,
+                            layer,
+                        },
+                    }),
+                    MapLayer::Sound(layer) => EditorAction::AddSoundLayer(ActAddSoundLayer {
+                        base: ActAddRemSoundLayer {
+                            is_background,
+                            group_index,
+                            index,
+                            layer,
+                        },
+                    }),
+                });
+            }
+        }
+
+        anyhow::ensure!(!actions.is_empty(), "Nothing was selected for the import.");
+        client.execute_group(EditorActionGroup {
+            actions,
+            identifier: None,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, path::Path};
+
+    use map::{
+        map::{
+            groups::{
+                MapGroup,
+                layers::design::{MapLayer, MapLayerQuad, MapLayerQuadsAttrs},
+            },
+            resources::{MapResourceMetaData, MapResourceRef},
+        },
+        skeleton::resources::MapResourceRefSkeleton,
+    };
+
+    use crate::{
+        actions::actions::{ActAddImage, ActAddRemImage, EditorAction},
+        test_utils::{arctic, io},
+    };
+
+    use super::{EditorMapImport, IndexRemap};
+
+    #[test]
+    fn remaps_resource_indices() {
+        let io = io();
+        let tp = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+
+        let image = |name: &str, hash: u8| MapResourceRef {
+            name: name.try_into().unwrap(),
+            meta: MapResourceMetaData {
+                blake3_hash: [hash; 32],
+                ty: "png".try_into().unwrap(),
+            },
+            hq_meta: None,
+        };
+        let quads = |image: Option<usize>| {
+            MapLayer::Quad(MapLayerQuad {
+                attr: MapLayerQuadsAttrs {
+                    image,
+                    high_detail: false,
+                },
+                quads: Vec::new(),
+                name: String::new(),
+            })
+        };
+
+        // the last two images are the same file
+        let mut other = arctic(&io, &tp);
+        other.resources.images = vec![image("a", 1), image("b", 2), image("c", 2)];
+        other.groups.foreground.push(MapGroup {
+            attr: Default::default(),
+            layers: vec![quads(Some(1)), quads(Some(2)), quads(None)],
+            name: String::new(),
+        });
+        let group_index = other.groups.foreground.len() - 1;
+        let resource_files = HashMap::from([([2; 32], Vec::new())]);
+        let mut import = EditorMapImport::new(Path::new("other.twmap"), other, resource_files);
+        import.selection.groups.insert((false, group_index));
+
+        let used = import.used();
+        assert_eq!(used.images.iter().copied().collect::<Vec<_>>(), vec![1, 2]);
+
+        // the edited map already contains an unrelated image
+        let map_images = vec![MapResourceRefSkeleton {
+            def: image("x", 9),
+            user: (),
+        }];
+        let mut actions = Vec::new();
+        let remap = IndexRemap {
+            images: import
+                .import_resources(
+                    &used.images,
+                    &import.map.resources.images,
+                    &map_images,
+                    &mut actions,
+                    |res, file, index| {
+                        EditorAction::AddImage(ActAddImage {
+                            base: ActAddRemImage { res, file, index },
+                        })
+                    },
+                )
+                .unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(actions.len(), 1);
+        assert_eq!(remap.images, HashMap::from([(1, 1), (2, 1)]));
+
+        let layers: Vec<_> = import.map.groups.foreground[group_index]
+            .layers
+            .iter()
+            .filter_map(|layer| match remap.layer(layer) {
+                Some(MapLayer::Quad(layer)) => Some(layer.attr.image),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(layers, vec![Some(1), Some(1), None]);
+    }
+}
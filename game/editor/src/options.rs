@@ -18,4 +18,9 @@ pub struct EditorOptions {
     pub hotkeys_open: bool,
     pub hotkeys_edit: Option<EditorHotkeyEdit>,
     pub hotkeys_write_in_order: Arc<tokio::sync::Mutex<VecDeque<EditorBindsFile>>>,
+
+    /// The game mod the local server loads when playtesting.
+    pub playtest_game_mod: String,
+    /// Spawn at the editor camera when playtesting.
+    pub playtest_spawn_at_camera: bool,
 }
@@ -0,0 +1,319 @@
+use crate::{
+    actions::actions::EditorAction,
+    event::{EditorGroupIndex, EditorLockTarget, EditorPermissions},
+};
+
+/// The part of the map an action modifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditorActionScope {
+    /// Resources, animations and server settings.
+    Global,
+    /// Adds, removes or moves groups, which changes
+    /// the indices of other groups.
+    Groups,
+    /// The attributes of a group.
+    Group(EditorGroupIndex),
+    /// Adds, removes or moves layers or modifies
+    /// all layers of a group at once.
+    Layers(EditorGroupIndex),
+    Layer(EditorGroupIndex, usize),
+}
+
+fn design(is_background: bool, group_index: usize) -> EditorGroupIndex {
+    EditorGroupIndex::Design {
+        is_background,
+        group_index,
+    }
+}
+
+pub fn action_scopes(act: &EditorAction) -> Vec<EditorActionScope> {
+    use EditorActionScope::*;
+    match act {
+        EditorAction::MoveGroup(_) | EditorAction::AddGroup(_) | EditorAction::RemGroup(_) => {
+            vec![Groups]
+        }
+        EditorAction::MoveLayer(act) => vec![
+            Layers(design(act.old_is_background, act.old_group)),
+            Layers(design(act.new_is_background, act.new_group)),
+        ],
+        EditorAction::AddImage(_)
+        | EditorAction::AddImage2dArray(_)
+        | EditorAction::AddSound(_)
+        | EditorAction::RemImage(_)
+        | EditorAction::RemImage2dArray(_)
+        | EditorAction::RemSound(_)
+        | EditorAction::AddPosAnim(_)
+        | EditorAction::ReplPosAnim(_)
+        | EditorAction::RemPosAnim(_)
+        | EditorAction::AddColorAnim(_)
+        | EditorAction::ReplColorAnim(_)
+        | EditorAction::RemColorAnim(_)
+        | EditorAction::AddSoundAnim(_)
+        | EditorAction::ReplSoundAnim(_)
+        | EditorAction::RemSoundAnim(_)
+        | EditorAction::SetCommands(_)
+        | EditorAction::SetConfigVariables(_)
+        | EditorAction::SetMetadata(_) => vec![Global],
+        EditorAction::LayerChangeImageIndex(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::LayerChangeSoundIndex(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::QuadLayerAddQuads(act) => vec![Layer(
+            design(act.base.is_background, act.base.group_index),
+            act.base.layer_index,
+        )],
+        EditorAction::SoundLayerAddSounds(act) => vec![Layer(
+            design(act.base.is_background, act.base.group_index),
+            act.base.layer_index,
+        )],
+        EditorAction::QuadLayerRemQuads(act) => vec![Layer(
+            design(act.base.is_background, act.base.group_index),
+            act.base.layer_index,
+        )],
+        EditorAction::SoundLayerRemSounds(act) => vec![Layer(
+            design(act.base.is_background, act.base.group_index),
+            act.base.layer_index,
+        )],
+        EditorAction::AddTileLayer(act) => {
+            vec![Layers(design(act.base.is_background, act.base.group_index))]
+        }
+        EditorAction::AddQuadLayer(act) => {
+            vec![Layers(design(act.base.is_background, act.base.group_index))]
+        }
+        EditorAction::AddSoundLayer(act) => {
+            vec![Layers(design(act.base.is_background, act.base.group_index))]
+        }
+        EditorAction::RemTileLayer(act) => {
+            vec![Layers(design(act.base.is_background, act.base.group_index))]
+        }
+        EditorAction::RemQuadLayer(act) => {
+            vec![Layers(design(act.base.is_background, act.base.group_index))]
+        }
+        EditorAction::RemSoundLayer(act) => {
+            vec![Layers(design(act.base.is_background, act.base.group_index))]
+        }
+        EditorAction::AddPhysicsTileLayer(_)
+        | EditorAction::RemPhysicsTileLayer(_)
+        | EditorAction::ChangePhysicsGroupAttr(_) => vec![Layers(EditorGroupIndex::Physics)],
+        EditorAction::TileLayerReplaceTiles(act) => vec![Layer(
+            design(act.base.is_background, act.base.group_index),
+            act.base.layer_index,
+        )],
+        EditorAction::TilePhysicsLayerReplaceTiles(act) => {
+            vec![Layer(EditorGroupIndex::Physics, act.base.layer_index)]
+        }
+        EditorAction::ChangeGroupAttr(act) => {
+            vec![Group(design(act.is_background, act.group_index))]
+        }
+        EditorAction::ChangeGroupName(act) => {
+            vec![Group(design(act.is_background, act.group_index))]
+        }
+        EditorAction::ChangeTileLayerDesignAttr(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::ChangeQuadLayerAttr(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::ChangeSoundLayerAttr(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::ChangeDesignLayerName(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::ChangeQuadAttr(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::ChangeSoundAttr(act) => vec![Layer(
+            design(act.is_background, act.group_index),
+            act.layer_index,
+        )],
+        EditorAction::ChangeTeleporter(_)
+        | EditorAction::ChangeSwitch(_)
+        | EditorAction::ChangeTuneZone(_) => vec![Group(EditorGroupIndex::Physics)],
+    }
+}
+
+impl EditorPermissions {
+    pub fn lock_owner(&self, target: &EditorLockTarget) -> Option<u64> {
+        self.locks.get(target).copied()
+    }
+
+    pub fn can_edit_group(&self, server_id: u64, group: &EditorGroupIndex) -> bool {
+        self.group_editors
+            .get(group)
+            .is_none_or(|editors| editors.contains(&server_id))
+    }
+
+    fn is_locked_by_other(&self, server_id: u64, target: &EditorLockTarget) -> bool {
+        self.lock_owner(target)
+            .is_some_and(|owner| owner != server_id)
+    }
+
+    /// Privileged clients (admins & the local client) ignore
+    /// edit rights, but not the locks of other clients.
+    fn check_group(
+        &self,
+        server_id: u64,
+        privileged: bool,
+        group: EditorGroupIndex,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            privileged || self.can_edit_group(server_id, &group),
+            "You are not allowed to edit this group."
+        );
+        anyhow::ensure!(
+            !self.is_locked_by_other(
+                server_id,
+                &EditorLockTarget {
+                    group,
+                    layer_index: None,
+                }
+            ),
+            "This group is locked by another mapper."
+        );
+        Ok(())
+    }
+
+    pub fn check_action(
+        &self,
+        server_id: u64,
+        privileged: bool,
+        act: &EditorAction,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            privileged || !self.read_only.contains(&server_id),
+            "You are only allowed to view this map."
+        );
+        for scope in action_scopes(act) {
+            match scope {
+                EditorActionScope::Global => {}
+                EditorActionScope::Groups => {
+                    let is_design =
+                        |group: &EditorGroupIndex| matches!(group, EditorGroupIndex::Design { .. });
+                    anyhow::ensure!(
+                        !self.locks.keys().any(|target| is_design(&target.group))
+                            && !self.group_editors.keys().any(is_design),
+                        "Groups can not be added, removed or moved while design groups \
+                        or layers are locked or have restricted edit rights."
+                    );
+                }
+                EditorActionScope::Group(group) => {
+                    self.check_group(server_id, privileged, group)?;
+                }
+                EditorActionScope::Layers(group) => {
+                    self.check_group(server_id, privileged, group)?;
+                    anyhow::ensure!(
+                        !self
+                            .locks
+                            .keys()
+                            .any(|target| target.group == group && target.layer_index.is_some()),
+                        "Layers of this group can not be added, removed or modified \
+                        all at once while single layers of it are locked."
+                    );
+                }
+                EditorActionScope::Layer(group, layer_index) => {
+                    self.check_group(server_id, privileged, group)?;
+                    anyhow::ensure!(
+                        !self.is_locked_by_other(
+                            server_id,
+                            &EditorLockTarget {
+                                group,
+                                layer_index: Some(layer_index),
+                            }
+                        ),
+                        "This layer is locked by another mapper."
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the client is allowed to lock or unlock the target.
+    pub fn check_lock(
+        &self,
+        server_id: u64,
+        privileged: bool,
+        target: &EditorLockTarget,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            privileged || !self.read_only.contains(&server_id),
+            "You are only allowed to view this map."
+        );
+        self.check_group(server_id, privileged, target.group)?;
+        match target.layer_index {
+            Some(_) => {
+                anyhow::ensure!(
+                    !self.is_locked_by_other(server_id, target),
+                    "This layer is locked by another mapper."
+                );
+            }
+            None => {
+                anyhow::ensure!(
+                    !self
+                        .locks
+                        .iter()
+                        .any(|(lock, owner)| lock.group == target.group
+                            && lock.layer_index.is_some()
+                            && *owner != server_id),
+                    "Layers of this group are locked by another mapper."
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        actions::actions::{ActChangeGroupName, ActMoveGroup, EditorAction},
+        event::{EditorGroupIndex, EditorLockTarget, EditorPermissions},
+    };
+
+    #[test]
+    fn locked_group() {
+        let group = EditorGroupIndex::Design {
+            is_background: true,
+            group_index: 1,
+        };
+        let mut permissions = EditorPermissions::default();
+        permissions.locks.insert(
+            EditorLockTarget {
+                group,
+                layer_index: None,
+            },
+            0,
+        );
+        let rename = EditorAction::ChangeGroupName(ActChangeGroupName {
+            is_background: true,
+            group_index: 1,
+            old_name: "a".into(),
+            new_name: "b".into(),
+        });
+        assert!(permissions.check_action(0, false, &rename).is_ok());
+        assert!(permissions.check_action(1, false, &rename).is_err());
+        // locks also apply to privileged clients
+        assert!(permissions.check_action(1, true, &rename).is_err());
+
+        let move_group = EditorAction::MoveGroup(ActMoveGroup {
+            old_is_background: false,
+            old_group: 0,
+            new_is_background: false,
+            new_group: 1,
+        });
+        assert!(permissions.check_action(0, false, &move_group).is_err());
+
+        permissions.read_only.insert(0);
+        assert!(permissions.check_action(0, false, &rename).is_err());
+    }
+}
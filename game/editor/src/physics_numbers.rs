@@ -0,0 +1,469 @@
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use map::{
+    lint::teles_without_destination,
+    map::groups::layers::tiles::{
+        MapTileLayerPhysicsTiles, SwitchTile, TeleTile, TileBase, TuneTile,
+    },
+    skeleton::groups::layers::physics::MapLayerPhysicsSkeleton,
+    tiles::DdraceTileNum,
+    types::NonZeroU16MinusOne,
+};
+
+use crate::{
+    actions::actions::{
+        ActChangeSwitch, ActChangeTeleporter, ActChangeTuneZone, ActTilePhysicsLayerReplTilesBase,
+        ActTilePhysicsLayerReplaceTiles, EditorAction, EditorActionGroup,
+    },
+    map::EditorGroupPhysics,
+    map_diff::{changed_regions, region_tiles},
+};
+
+/// The physics layers that identify their tiles by a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PhysicsNumberLayer {
+    Tele,
+    Switch,
+    Tune,
+}
+
+impl PhysicsNumberLayer {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PhysicsNumberLayer::Tele => "Teleporters",
+            PhysicsNumberLayer::Switch => "Switches",
+            PhysicsNumberLayer::Tune => "Tune zones",
+        }
+    }
+}
+
+/// Where & how often a single number is used.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PhysicsNumberUsage {
+    pub name: String,
+    /// All tiles with this number.
+    pub tiles: usize,
+    /// Teleporter entries.
+    pub ins: usize,
+    /// Teleporter exits.
+    pub outs: usize,
+    /// Teleporter checkpoints.
+    pub checkpoints: usize,
+    pub warnings: Vec<&'static str>,
+}
+
+pub type PhysicsNumberUsages = BTreeMap<u8, PhysicsNumberUsage>;
+
+fn tile_is(index: u8, tile: DdraceTileNum) -> bool {
+    index == tile as u8
+}
+
+pub fn tele_usages(
+    tiles: &[TeleTile],
+    names: impl IntoIterator<Item = (u8, String)>,
+) -> PhysicsNumberUsages {
+    let mut usages = PhysicsNumberUsages::default();
+    for tile in tiles.iter().filter(|tile| tile.base.index != 0) {
+        let usage = usages.entry(tile.number).or_default();
+        usage.tiles += 1;
+        let index = tile.base.index;
+        if tile_is(index, DdraceTileNum::TeleIn)
+            || tile_is(index, DdraceTileNum::TeleInEvil)
+            || tile_is(index, DdraceTileNum::TeleInWeapon)
+            || tile_is(index, DdraceTileNum::TeleInHook)
+            || tile_is(index, DdraceTileNum::TeleCheckIn)
+            || tile_is(index, DdraceTileNum::TeleCheckInEvil)
+        {
+            usage.ins += 1;
+        } else if tile_is(index, DdraceTileNum::TeleOut)
+            || tile_is(index, DdraceTileNum::TeleCheckOut)
+        {
+            usage.outs += 1;
+        } else if tile_is(index, DdraceTileNum::TeleCheck) {
+            usage.checkpoints += 1;
+        }
+    }
+    // same as the map lint
+    for index in teles_without_destination(tiles) {
+        let tile = &tiles[index];
+        let warning = if tile_is(tile.base.index, DdraceTileNum::TeleCheck) {
+            "checkpoint without checkpoint exit"
+        } else {
+            "teleporter without exit"
+        };
+        let usage = usages.entry(tile.number).or_default();
+        if !usage.warnings.contains(&warning) {
+            usage.warnings.push(warning);
+        }
+    }
+    add_names(&mut usages, names, "unused name");
+    usages
+}
+
+pub fn switch_usages(
+    tiles: &[SwitchTile],
+    names: impl IntoIterator<Item = (u8, String)>,
+) -> PhysicsNumberUsages {
+    let mut usages = PhysicsNumberUsages::default();
+    for tile in tiles.iter().filter(|tile| tile.base.index != 0) {
+        usages.entry(tile.number).or_default().tiles += 1;
+    }
+    add_names(&mut usages, names, "unused name");
+    usages
+}
+
+pub fn tune_usages(
+    tiles: &[TuneTile],
+    names: impl IntoIterator<Item = (u8, String)>,
+) -> PhysicsNumberUsages {
+    let mut usages = PhysicsNumberUsages::default();
+    for tile in tiles.iter().filter(|tile| tile.base.index != 0) {
+        usages.entry(tile.number).or_default().tiles += 1;
+    }
+    add_names(&mut usages, names, "unused tune zone");
+    usages
+}
+
+fn add_names(
+    usages: &mut PhysicsNumberUsages,
+    names: impl IntoIterator<Item = (u8, String)>,
+    unused_warning: &'static str,
+) {
+    for (number, name) in names {
+        let usage = usages.entry(number).or_default();
+        if usage.tiles == 0 {
+            usage.warnings.push(unused_warning);
+        }
+        usage.name = name;
+    }
+}
+
+/// Overview of all tele, switch & tune zone numbers of the map.
+#[derive(Debug, Default, Clone)]
+pub struct PhysicsNumbersOverview {
+    pub layers: BTreeMap<PhysicsNumberLayer, PhysicsNumberUsages>,
+}
+
+impl PhysicsNumbersOverview {
+    pub fn new(physics: &EditorGroupPhysics) -> Self {
+        let mut layers: BTreeMap<_, _> = Default::default();
+        for layer in physics.layers.iter() {
+            match layer {
+                MapLayerPhysicsSkeleton::Tele(layer) => {
+                    layers.insert(
+                        PhysicsNumberLayer::Tele,
+                        tele_usages(
+                            &layer.layer.base.tiles,
+                            layer
+                                .layer
+                                .tele_names
+                                .iter()
+                                .map(|(i, name)| (*i, name.clone())),
+                        ),
+                    );
+                }
+                MapLayerPhysicsSkeleton::Switch(layer) => {
+                    layers.insert(
+                        PhysicsNumberLayer::Switch,
+                        switch_usages(
+                            &layer.layer.base.tiles,
+                            layer
+                                .layer
+                                .switch_names
+                                .iter()
+                                .map(|(i, name)| (*i, name.clone())),
+                        ),
+                    );
+                }
+                MapLayerPhysicsSkeleton::Tune(layer) => {
+                    layers.insert(
+                        PhysicsNumberLayer::Tune,
+                        tune_usages(
+                            &layer.layer.base.tiles,
+                            layer
+                                .layer
+                                .tune_zones
+                                .iter()
+                                .map(|(i, zone)| (*i, zone.name.clone())),
+                        ),
+                    );
+                }
+                MapLayerPhysicsSkeleton::Arbitrary(_)
+                | MapLayerPhysicsSkeleton::Game(_)
+                | MapLayerPhysicsSkeleton::Front(_)
+                | MapLayerPhysicsSkeleton::Speedup(_) => {}
+            }
+        }
+        Self { layers }
+    }
+}
+
+/// Returns the index of the physics layer and its tiles.
+fn number_layer(
+    physics: &EditorGroupPhysics,
+    ty: PhysicsNumberLayer,
+) -> Option<(usize, MapTileLayerPhysicsTiles)> {
+    physics
+        .layers
+        .iter()
+        .enumerate()
+        .find_map(|(index, layer)| match (ty, layer) {
+            (PhysicsNumberLayer::Tele, MapLayerPhysicsSkeleton::Tele(layer)) => Some((
+                index,
+                MapTileLayerPhysicsTiles::Tele(layer.layer.base.tiles.clone()),
+            )),
+            (PhysicsNumberLayer::Switch, MapLayerPhysicsSkeleton::Switch(layer)) => Some((
+                index,
+                MapTileLayerPhysicsTiles::Switch(layer.layer.base.tiles.clone()),
+            )),
+            (PhysicsNumberLayer::Tune, MapLayerPhysicsSkeleton::Tune(layer)) => Some((
+                index,
+                MapTileLayerPhysicsTiles::Tune(layer.layer.base.tiles.clone()),
+            )),
+            _ => None,
+        })
+}
+
+fn tile_numbers(tiles: &MapTileLayerPhysicsTiles) -> Vec<Option<u8>> {
+    fn numbers<T: AsRef<TileBase>>(tiles: &[T], number: impl Fn(&T) -> u8) -> Vec<Option<u8>> {
+        tiles
+            .iter()
+            .map(|tile| (tile.as_ref().index != 0).then(|| number(tile)))
+            .collect()
+    }
+    match tiles {
+        MapTileLayerPhysicsTiles::Tele(tiles) => numbers(tiles, |tile| tile.number),
+        MapTileLayerPhysicsTiles::Switch(tiles) => numbers(tiles, |tile| tile.number),
+        MapTileLayerPhysicsTiles::Tune(tiles) => numbers(tiles, |tile| tile.number),
+        MapTileLayerPhysicsTiles::Arbitrary(_)
+        | MapTileLayerPhysicsTiles::Game(_)
+        | MapTileLayerPhysicsTiles::Front(_)
+        | MapTileLayerPhysicsTiles::Speedup(_) => Default::default(),
+    }
+}
+
+/// The tile position of the `nth` usage of `number`, wraps around
+/// if `nth` is bigger than the amount of usages.
+pub fn usage_pos(
+    physics: &EditorGroupPhysics,
+    ty: PhysicsNumberLayer,
+    number: u8,
+    nth: usize,
+) -> Option<(u16, u16)> {
+    let (_, tiles) = number_layer(physics, ty)?;
+    let width = physics.attr.width.get() as usize;
+    let usages: Vec<usize> = tile_numbers(&tiles)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, n)| (n == Some(number)).then_some(index))
+        .collect();
+    if usages.is_empty() {
+        return None;
+    }
+    let index = usages[nth % usages.len()];
+    Some(((index % width) as u16, (index / width) as u16))
+}
+
+/// Replaces `old` with `new` in all tiles.
+pub fn renumber_tiles(
+    tiles: &MapTileLayerPhysicsTiles,
+    width: usize,
+    height: usize,
+    layer_index: usize,
+    old: u8,
+    new: u8,
+) -> Vec<EditorAction> {
+    macro_rules! renumber {
+        ($ty:ident, $tiles:ident) => {{
+            let mut new_tiles = $tiles.clone();
+            new_tiles
+                .iter_mut()
+                .filter(|tile| tile.base.index != 0 && tile.number == old)
+                .for_each(|tile| tile.number = new);
+            changed_regions($tiles, &new_tiles, width, height)
+                .into_iter()
+                .map(|region| {
+                    EditorAction::TilePhysicsLayerReplaceTiles(ActTilePhysicsLayerReplaceTiles {
+                        base: ActTilePhysicsLayerReplTilesBase {
+                            layer_index,
+                            old_tiles: MapTileLayerPhysicsTiles::$ty(region_tiles(
+                                $tiles, width, &region,
+                            )),
+                            new_tiles: MapTileLayerPhysicsTiles::$ty(region_tiles(
+                                &new_tiles, width, &region,
+                            )),
+                            x: region.x,
+                            y: region.y,
+                            w: NonZeroU16MinusOne::new(region.w).unwrap(),
+                            h: NonZeroU16MinusOne::new(region.h).unwrap(),
+                        },
+                    })
+                })
+                .collect()
+        }};
+    }
+    match tiles {
+        MapTileLayerPhysicsTiles::Tele(tiles) => renumber!(Tele, tiles),
+        MapTileLayerPhysicsTiles::Switch(tiles) => renumber!(Switch, tiles),
+        MapTileLayerPhysicsTiles::Tune(tiles) => renumber!(Tune, tiles),
+        MapTileLayerPhysicsTiles::Arbitrary(_)
+        | MapTileLayerPhysicsTiles::Game(_)
+        | MapTileLayerPhysicsTiles::Front(_)
+        | MapTileLayerPhysicsTiles::Speedup(_) => Default::default(),
+    }
+}
+
+/// Moves all tiles & the name (or tune zone) of `old` to `new`,
+/// as a single action group, so it can be undone at once.
+pub fn renumber(
+    physics: &EditorGroupPhysics,
+    ty: PhysicsNumberLayer,
+    old: u8,
+    new: u8,
+) -> anyhow::Result<EditorActionGroup> {
+    anyhow::ensure!(new != 0, "number 0 can not be used.");
+    anyhow::ensure!(old != new, "the new number is the same as the old one.");
+    let overview = PhysicsNumbersOverview::new(physics);
+    let usages = overview
+        .layers
+        .get(&ty)
+        .ok_or_else(|| anyhow!("the map has no layer for {}", ty.name().to_lowercase()))?;
+    anyhow::ensure!(
+        !usages.contains_key(&new),
+        "number {new} is already in use."
+    );
+
+    let (layer_index, tiles) =
+        number_layer(physics, ty).ok_or_else(|| anyhow!("layer was not found"))?;
+    let mut actions = renumber_tiles(
+        &tiles,
+        physics.attr.width.get() as usize,
+        physics.attr.height.get() as usize,
+        layer_index,
+        old,
+        new,
+    );
+
+    for layer in physics.layers.iter() {
+        match (ty, layer) {
+            (PhysicsNumberLayer::Tele, MapLayerPhysicsSkeleton::Tele(layer)) => {
+                if let Some(name) = layer.layer.tele_names.get(&old) {
+                    actions.push(EditorAction::ChangeTeleporter(ActChangeTeleporter {
+                        index: old,
+                        old_name: name.clone(),
+                        new_name: Default::default(),
+                    }));
+                    actions.push(EditorAction::ChangeTeleporter(ActChangeTeleporter {
+                        index: new,
+                        old_name: Default::default(),
+                        new_name: name.clone(),
+                    }));
+                }
+            }
+            (PhysicsNumberLayer::Switch, MapLayerPhysicsSkeleton::Switch(layer)) => {
+                if let Some(name) = layer.layer.switch_names.get(&old) {
+                    actions.push(EditorAction::ChangeSwitch(ActChangeSwitch {
+                        index: old,
+                        old_name: name.clone(),
+                        new_name: Default::default(),
+                    }));
+                    actions.push(EditorAction::ChangeSwitch(ActChangeSwitch {
+                        index: new,
+                        old_name: Default::default(),
+                        new_name: name.clone(),
+                    }));
+                }
+            }
+            (PhysicsNumberLayer::Tune, MapLayerPhysicsSkeleton::Tune(layer)) => {
+                if let Some(zone) = layer.layer.tune_zones.get(&old) {
+                    actions.push(EditorAction::ChangeTuneZone(ActChangeTuneZone {
+                        index: old,
+                        old_name: zone.name.clone(),
+                        new_name: Default::default(),
+                        old_tunes: zone.tunes.clone(),
+                        new_tunes: Default::default(),
+                        old_enter_msg: zone.enter_msg.clone(),
+                        new_enter_msg: None,
+                        old_leave_msg: zone.leave_msg.clone(),
+                        new_leave_msg: None,
+                    }));
+                    actions.push(EditorAction::ChangeTuneZone(ActChangeTuneZone {
+                        index: new,
+                        old_name: Default::default(),
+                        new_name: zone.name.clone(),
+                        old_tunes: Default::default(),
+                        new_tunes: zone.tunes.clone(),
+                        old_enter_msg: None,
+                        new_enter_msg: zone.enter_msg.clone(),
+                        old_leave_msg: None,
+                        new_leave_msg: zone.leave_msg.clone(),
+                    }));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    anyhow::ensure!(!actions.is_empty(), "number {old} is not used.");
+    Ok(EditorActionGroup {
+        actions,
+        identifier: None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use map::{
+        map::groups::layers::tiles::{MapTileLayerPhysicsTiles, TeleTile, TileBase, TileFlags},
+        tiles::DdraceTileNum,
+    };
+
+    use crate::actions::actions::EditorAction;
+
+    use super::{renumber_tiles, tele_usages};
+
+    fn tele(index: DdraceTileNum, number: u8) -> TeleTile {
+        TeleTile {
+            base: TileBase {
+                index: index as u8,
+                flags: TileFlags::empty(),
+            },
+            number,
+        }
+    }
+
+    #[test]
+    fn tele_usages_and_renumber() {
+        let tiles = vec![
+            tele(DdraceTileNum::TeleIn, 1),
+            tele(DdraceTileNum::TeleOut, 1),
+            tele(DdraceTileNum::TeleIn, 2),
+            tele(DdraceTileNum::Air, 2),
+            tele(DdraceTileNum::TeleCheck, 3),
+            tele(DdraceTileNum::TeleCheckOut, 3),
+        ];
+        let usages = tele_usages(&tiles, [(1, "a".to_string()), (4, "b".to_string())]);
+
+        assert_eq!(usages[&1].name, "a");
+        assert_eq!((usages[&1].ins, usages[&1].outs), (1, 1));
+        assert!(usages[&1].warnings.is_empty());
+        assert_eq!(usages[&2].tiles, 1);
+        assert_eq!(usages[&2].warnings, vec!["teleporter without exit"]);
+        assert_eq!((usages[&3].checkpoints, usages[&3].outs), (1, 1));
+        assert!(usages[&3].warnings.is_empty());
+        assert_eq!(usages[&4].warnings, vec!["unused name"]);
+
+        // 3x2 layer, only the row of number 2 changes
+        let actions = renumber_tiles(&MapTileLayerPhysicsTiles::Tele(tiles), 3, 2, 0, 2, 5);
+        assert_eq!(actions.len(), 1);
+        let EditorAction::TilePhysicsLayerReplaceTiles(act) = &actions[0] else {
+            panic!("expected a tile replace action");
+        };
+        assert_eq!((act.base.x, act.base.y), (2, 0));
+        let MapTileLayerPhysicsTiles::Tele(new_tiles) = &act.base.new_tiles else {
+            panic!("expected tele tiles");
+        };
+        assert_eq!(new_tiles[0].number, 5);
+    }
+}
@@ -0,0 +1,1439 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::anyhow;
+use assets_base::tar::{new_tar, read_tar_files, tar_add_file};
+use base::hash::{Hash, generate_hash_for};
+use base_io::{io::IoFileSys, runtime::IoRuntimeTask};
+use map::{
+    map::{
+        animations::{AnimBase, AnimPointColor, AnimPointPos, AnimPointSound},
+        groups::layers::{
+            design::{
+                MapLayerQuad, MapLayerQuadsAttrs, MapLayerSound, MapLayerSoundAttrs, MapLayerTile,
+                SoundShape,
+            },
+            tiles::{
+                MapTileLayerAttr, MapTileLayerPhysicsTiles, MapTileLayerTiles, Tile, TileBase,
+            },
+        },
+        resources::MapResourceRef,
+    },
+    skeleton::{animations::AnimBaseSkeleton, resources::MapResourceRefSkeleton},
+    types::NonZeroU16MinusOne,
+};
+use math::math::vector::{ffixed, fvec2, ivec2};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+
+use crate::{
+    actions::actions::{
+        ActAddColorAnim, ActAddImage, ActAddImage2dArray, ActAddPosAnim, ActAddQuadLayer,
+        ActAddRemColorAnim, ActAddRemImage, ActAddRemPosAnim, ActAddRemQuadLayer, ActAddRemSound,
+        ActAddRemSoundAnim, ActAddRemSoundLayer, ActAddRemTileLayer, ActAddSound, ActAddSoundAnim,
+        ActAddSoundLayer, ActAddTileLayer, ActQuadLayerAddQuads, ActQuadLayerAddRemQuads,
+        ActSoundLayerAddRemSounds, ActSoundLayerAddSounds, ActTileLayerReplTilesBase,
+        ActTileLayerReplaceTiles, ActTilePhysicsLayerReplTilesBase,
+        ActTilePhysicsLayerReplaceTiles, EditorAction, EditorActionGroup,
+    },
+    client::EditorClient,
+    map::{
+        EditorLayer, EditorLayerUnionRef, EditorMap, EditorMapGroupsInterface, EditorMapInterface,
+        EditorPhysicsLayer, EditorResource,
+    },
+    notifications::{EditorNotification, EditorNotifications},
+    tools::tile_layer::selection::TileSelectionRange,
+    ui::top_toolbar::tile_mirror::{
+        mirror_map_tiles_x, mirror_map_tiles_y, rotate_map_tiles_plus_90,
+    },
+};
+
+/// The file inside the prefab's tar that describes the prefab.
+const PREFAB_DESC_FILE: &str = "prefab.txt";
+
+/// A resource used by the layers of a prefab.
+///
+/// The file is stored next to the description inside the prefab's tar.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorPrefabResource {
+    pub res: MapResourceRef,
+    #[serde(skip)]
+    pub file: Vec<u8>,
+}
+
+/// A design layer of a prefab.
+///
+/// Resource & animation indices point into the prefab's own lists.
+/// Tile layers are exactly as large as the prefab, quads & sounds
+/// are positioned relative to the prefab's top left corner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EditorPrefabDesignLayer {
+    Tile(MapLayerTile),
+    Quad(MapLayerQuad),
+    Sound(MapLayerSound),
+}
+
+/// A reusable part of a map that spans multiple layers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditorPrefab {
+    pub width: NonZeroU16MinusOne,
+    pub height: NonZeroU16MinusOne,
+
+    pub images: Vec<EditorPrefabResource>,
+    pub image_arrays: Vec<EditorPrefabResource>,
+    pub sounds: Vec<EditorPrefabResource>,
+
+    pub pos_anims: Vec<AnimBase<AnimPointPos>>,
+    pub color_anims: Vec<AnimBase<AnimPointColor>>,
+    pub sound_anims: Vec<AnimBase<AnimPointSound>>,
+
+    pub design_layers: Vec<EditorPrefabDesignLayer>,
+    pub physics_layers: Vec<MapTileLayerPhysicsTiles>,
+}
+
+/// Copies a `range` sized area out of a `w` x `h` tile layer,
+/// tiles outside of the layer are empty.
+fn copy_area<T: Copy + Default>(
+    tiles: &[T],
+    w: NonZeroU16MinusOne,
+    h: NonZeroU16MinusOne,
+    range: &TileSelectionRange,
+) -> Vec<T> {
+    let (w, h) = (w.get() as usize, h.get() as usize);
+    (range.y as usize..range.y as usize + range.h.get() as usize)
+        .flat_map(|y| {
+            (range.x as usize..range.x as usize + range.w.get() as usize).map(move |x| (x, y))
+        })
+        .map(|(x, y)| {
+            if x < w && y < h {
+                tiles[y * w + x]
+            } else {
+                T::default()
+            }
+        })
+        .collect()
+}
+
+/// The part of a prefab placed at some position that lies inside of a layer.
+struct PlaceArea {
+    range: TileSelectionRange,
+    /// Offset of the area inside the prefab.
+    prefab_x: usize,
+    prefab_y: usize,
+}
+
+impl PlaceArea {
+    fn new(
+        pos: ivec2,
+        prefab_w: NonZeroU16MinusOne,
+        prefab_h: NonZeroU16MinusOne,
+        layer_w: NonZeroU16MinusOne,
+        layer_h: NonZeroU16MinusOne,
+    ) -> Option<Self> {
+        let x0 = pos.x.clamp(0, layer_w.get() as i32);
+        let y0 = pos.y.clamp(0, layer_h.get() as i32);
+        let x1 = (pos.x + prefab_w.get() as i32).clamp(0, layer_w.get() as i32);
+        let y1 = (pos.y + prefab_h.get() as i32).clamp(0, layer_h.get() as i32);
+
+        Some(Self {
+            range: TileSelectionRange {
+                x: x0 as u16,
+                y: y0 as u16,
+                w: NonZeroU16MinusOne::new((x1 - x0) as u16)?,
+                h: NonZeroU16MinusOne::new((y1 - y0) as u16)?,
+            },
+            prefab_x: (x0 - pos.x) as usize,
+            prefab_y: (y0 - pos.y) as usize,
+        })
+    }
+
+    /// Returns the old tiles of the layer and the new tiles.
+    ///
+    /// Empty tiles of the prefab keep the tiles of the layer.
+    fn place<T: Copy + AsRef<TileBase>>(
+        &self,
+        layer_tiles: &[T],
+        layer_w: NonZeroU16MinusOne,
+        prefab_tiles: &[T],
+        prefab_w: NonZeroU16MinusOne,
+    ) -> (Vec<T>, Vec<T>) {
+        let (w, h) = (self.range.w.get() as usize, self.range.h.get() as usize);
+        let (layer_w, prefab_w) = (layer_w.get() as usize, prefab_w.get() as usize);
+        let mut old_tiles = Vec::with_capacity(w * h);
+        let mut new_tiles = Vec::with_capacity(w * h);
+        for y in 0..h {
+            for x in 0..w {
+                let old =
+                    layer_tiles[(self.range.y as usize + y) * layer_w + self.range.x as usize + x];
+                let new = prefab_tiles[(self.prefab_y + y) * prefab_w + self.prefab_x + x];
+                old_tiles.push(old);
+                new_tiles.push(if new.as_ref().index != 0 { new } else { old });
+            }
+        }
+        (old_tiles, new_tiles)
+    }
+}
+
+/// Adds the map resource to the prefab if not already happened.
+fn prefab_resource<U, P>(
+    resources: &mut Vec<EditorPrefabResource>,
+    indices: &mut HashMap<usize, usize>,
+    map_resources: &[MapResourceRefSkeleton<EditorResource<U, P>>],
+    index: usize,
+) -> anyhow::Result<usize> {
+    if let Some(index) = indices.get(&index) {
+        return Ok(*index);
+    }
+    let res = map_resources
+        .get(index)
+        .ok_or_else(|| anyhow!("resource #{index} does not exist"))?;
+    resources.push(EditorPrefabResource {
+        res: MapResourceRef {
+            hq_meta: None,
+            ..res.def.clone()
+        },
+        file: res.user.file.as_ref().clone(),
+    });
+    indices.insert(index, resources.len() - 1);
+    Ok(resources.len() - 1)
+}
+
+/// Adds the map animation to the prefab if not already happened.
+fn prefab_anim<A, P: DeserializeOwned + PartialOrd + Clone>(
+    anims: &mut Vec<AnimBase<P>>,
+    indices: &mut HashMap<usize, usize>,
+    map_anims: &[AnimBaseSkeleton<A, P>],
+    index: usize,
+) -> anyhow::Result<usize> {
+    if let Some(index) = indices.get(&index) {
+        return Ok(*index);
+    }
+    let anim = map_anims
+        .get(index)
+        .ok_or_else(|| anyhow!("animation #{index} does not exist"))?;
+    anims.push(anim.def.clone());
+    indices.insert(index, anims.len() - 1);
+    Ok(anims.len() - 1)
+}
+
+/// Finds the map resources that match the given resources by their hash,
+/// all others are added to the map.
+///
+/// Resources with the same hash are only added once.
+pub(crate) fn map_resource_indices<'a, U>(
+    map_resources: &[MapResourceRefSkeleton<U>],
+    resources: impl IntoIterator<Item = (&'a MapResourceRef, &'a [u8])>,
+    actions: &mut Vec<EditorAction>,
+    add: impl Fn(MapResourceRef, Vec<u8>, usize) -> EditorAction,
+) -> Vec<usize> {
+    let mut added: HashMap<Hash, usize> = Default::default();
+    resources
+        .into_iter()
+        .map(|(res, file)| {
+            let hash = res.meta.blake3_hash;
+            map_resources
+                .iter()
+                .position(|r| r.def.meta.blake3_hash == hash)
+                .unwrap_or_else(|| {
+                    let next_index = map_resources.len() + added.len();
+                    *added.entry(hash).or_insert_with(|| {
+                        actions.push(add(res.clone(), file.to_vec(), next_index));
+                        next_index
+                    })
+                })
+        })
+        .collect()
+}
+
+/// Finds identical map animations, all others are added to the map.
+///
+/// Identical animations are only added once.
+pub(crate) fn map_anim_indices<A, P: DeserializeOwned + PartialOrd + Clone + PartialEq>(
+    map_anims: &[AnimBaseSkeleton<A, P>],
+    anims: &[AnimBase<P>],
+    actions: &mut Vec<EditorAction>,
+    add: impl Fn(AnimBase<P>, usize) -> EditorAction,
+) -> Vec<usize> {
+    let mut added: Vec<&AnimBase<P>> = Default::default();
+    anims
+        .iter()
+        .map(|anim| {
+            map_anims
+                .iter()
+                .position(|a| a.def == *anim)
+                .or_else(|| {
+                    added
+                        .iter()
+                        .position(|a| *a == anim)
+                        .map(|index| map_anims.len() + index)
+                })
+                .unwrap_or_else(|| {
+                    let next_index = map_anims.len() + added.len();
+                    actions.push(add(anim.clone(), next_index));
+                    added.push(anim);
+                    next_index
+                })
+        })
+        .collect()
+}
+
+fn layer_index(layer: &EditorLayerUnionRef<'_>) -> (Option<(bool, usize)>, usize) {
+    match layer {
+        EditorLayerUnionRef::Physics { layer_index, .. } => (None, *layer_index),
+        EditorLayerUnionRef::Design {
+            is_background,
+            group_index,
+            layer_index,
+            ..
+        } => (Some((*is_background, *group_index)), *layer_index),
+    }
+}
+
+fn physics_tiles_count(tiles: &MapTileLayerPhysicsTiles) -> Option<usize> {
+    match tiles {
+        MapTileLayerPhysicsTiles::Arbitrary(_) => None,
+        MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
+            Some(tiles.len())
+        }
+        MapTileLayerPhysicsTiles::Tele(tiles) => Some(tiles.len()),
+        MapTileLayerPhysicsTiles::Speedup(tiles) => Some(tiles.len()),
+        MapTileLayerPhysicsTiles::Switch(tiles) => Some(tiles.len()),
+        MapTileLayerPhysicsTiles::Tune(tiles) => Some(tiles.len()),
+    }
+}
+
+fn physics_layer_name(tiles: &MapTileLayerPhysicsTiles) -> &'static str {
+    match tiles {
+        MapTileLayerPhysicsTiles::Arbitrary(_) => "arbitrary",
+        MapTileLayerPhysicsTiles::Game(_) => "game",
+        MapTileLayerPhysicsTiles::Front(_) => "front",
+        MapTileLayerPhysicsTiles::Tele(_) => "tele",
+        MapTileLayerPhysicsTiles::Speedup(_) => "speedup",
+        MapTileLayerPhysicsTiles::Switch(_) => "switch",
+        MapTileLayerPhysicsTiles::Tune(_) => "tune",
+    }
+}
+
+impl EditorPrefab {
+    /// Collects the tiles inside of `range` and the quads & sounds
+    /// whose center lies inside of it from the active and all selected layers.
+    ///
+    /// Design layers are only taken from a single group: the group
+    /// of the active layer or otherwise the first group with a selected layer.
+    pub fn capture(map: &EditorMap, range: &TileSelectionRange) -> anyhow::Result<Self> {
+        let mut layers: Vec<_> = map.active_layer().into_iter().collect();
+        for layer in map.groups.selected_layers() {
+            if !layers.iter().any(|l| layer_index(l) == layer_index(&layer)) {
+                layers.push(layer);
+            }
+        }
+        let design_group = layers.iter().find_map(|l| layer_index(l).0);
+
+        let mut prefab = Self {
+            width: range.w,
+            height: range.h,
+            images: Default::default(),
+            image_arrays: Default::default(),
+            sounds: Default::default(),
+            pos_anims: Default::default(),
+            color_anims: Default::default(),
+            sound_anims: Default::default(),
+            design_layers: Default::default(),
+            physics_layers: Default::default(),
+        };
+        let mut images = HashMap::default();
+        let mut image_arrays = HashMap::default();
+        let mut sounds = HashMap::default();
+        let mut pos_anims = HashMap::default();
+        let mut color_anims = HashMap::default();
+        let mut sound_anims = HashMap::default();
+
+        let (x0, y0) = (range.x as f64, range.y as f64);
+        let (x1, y1) = (x0 + range.w.get() as f64, y0 + range.h.get() as f64);
+        let in_range = |pos: &fvec2| {
+            let (x, y) = (pos.x.to_num::<f64>(), pos.y.to_num::<f64>());
+            x >= x0 && x < x1 && y >= y0 && y < y1
+        };
+        let offset = fvec2::new(ffixed::from_num(range.x), ffixed::from_num(range.y));
+
+        for layer in layers {
+            match layer {
+                EditorLayerUnionRef::Physics {
+                    layer, group_attr, ..
+                } => {
+                    let (w, h) = (group_attr.width, group_attr.height);
+                    let tiles = match layer {
+                        EditorPhysicsLayer::Arbitrary(_) => continue,
+                        EditorPhysicsLayer::Game(layer) => MapTileLayerPhysicsTiles::Game(
+                            copy_area(&layer.layer.tiles, w, h, range),
+                        ),
+                        EditorPhysicsLayer::Front(layer) => MapTileLayerPhysicsTiles::Front(
+                            copy_area(&layer.layer.tiles, w, h, range),
+                        ),
+                        EditorPhysicsLayer::Tele(layer) => MapTileLayerPhysicsTiles::Tele(
+                            copy_area(&layer.layer.base.tiles, w, h, range),
+                        ),
+                        EditorPhysicsLayer::Speedup(layer) => MapTileLayerPhysicsTiles::Speedup(
+                            copy_area(&layer.layer.tiles, w, h, range),
+                        ),
+                        EditorPhysicsLayer::Switch(layer) => MapTileLayerPhysicsTiles::Switch(
+                            copy_area(&layer.layer.base.tiles, w, h, range),
+                        ),
+                        EditorPhysicsLayer::Tune(layer) => MapTileLayerPhysicsTiles::Tune(
+                            copy_area(&layer.layer.base.tiles, w, h, range),
+                        ),
+                    };
+                    prefab.physics_layers.push(tiles);
+                }
+                EditorLayerUnionRef::Design {
+                    layer,
+                    group_index,
+                    is_background,
+                    ..
+                } => {
+                    if design_group != Some((is_background, group_index)) {
+                        continue;
+                    }
+                    match layer {
+                        EditorLayer::Abritrary(_) => {}
+                        EditorLayer::Tile(layer) => {
+                            let attr = &layer.layer.attr;
+                            let image_array = attr
+                                .image_array
+                                .map(|i| {
+                                    prefab_resource(
+                                        &mut prefab.image_arrays,
+                                        &mut image_arrays,
+                                        &map.resources.image_arrays,
+                                        i,
+                                    )
+                                })
+                                .transpose()?;
+                            let color_anim = attr
+                                .color_anim
+                                .map(|i| {
+                                    prefab_anim(
+                                        &mut prefab.color_anims,
+                                        &mut color_anims,
+                                        &map.animations.color,
+                                        i,
+                                    )
+                                })
+                                .transpose()?;
+                            prefab.design_layers.push(EditorPrefabDesignLayer::Tile(
+                                MapLayerTile {
+                                    attr: MapTileLayerAttr {
+                                        width: range.w,
+                                        height: range.h,
+                                        image_array,
+                                        color_anim,
+                                        ..*attr
+                                    },
+                                    tiles: copy_area(
+                                        &layer.layer.tiles,
+                                        attr.width,
+                                        attr.height,
+                                        range,
+                                    ),
+                                    name: layer.layer.name.clone(),
+                                },
+                            ));
+                        }
+                        EditorLayer::Quad(layer) => {
+                            let mut quads = Vec::new();
+                            for quad in layer.layer.quads.iter().filter(|q| in_range(&q.points[4]))
+                            {
+                                let mut quad = *quad;
+                                quad.points.iter_mut().for_each(|p| *p -= offset);
+                                quad.pos_anim = quad
+                                    .pos_anim
+                                    .map(|i| {
+                                        prefab_anim(
+                                            &mut prefab.pos_anims,
+                                            &mut pos_anims,
+                                            &map.animations.pos,
+                                            i,
+                                        )
+                                    })
+                                    .transpose()?;
+                                quad.color_anim = quad
+                                    .color_anim
+                                    .map(|i| {
+                                        prefab_anim(
+                                            &mut prefab.color_anims,
+                                            &mut color_anims,
+                                            &map.animations.color,
+                                            i,
+                                        )
+                                    })
+                                    .transpose()?;
+                                quads.push(quad);
+                            }
+                            if quads.is_empty() {
+                                continue;
+                            }
+                            let image = layer
+                                .layer
+                                .attr
+                                .image
+                                .map(|i| {
+                                    prefab_resource(
+                                        &mut prefab.images,
+                                        &mut images,
+                                        &map.resources.images,
+                                        i,
+                                    )
+                                })
+                                .transpose()?;
+                            prefab.design_layers.push(EditorPrefabDesignLayer::Quad(
+                                MapLayerQuad {
+                                    attr: MapLayerQuadsAttrs {
+                                        image,
+                                        ..layer.layer.attr
+                                    },
+                                    quads,
+                                    name: layer.layer.name.clone(),
+                                },
+                            ));
+                        }
+                        EditorLayer::Sound(layer) => {
+                            let mut layer_sounds = Vec::new();
+                            for sound in layer.layer.sounds.iter().filter(|s| in_range(&s.pos)) {
+                                let mut sound = *sound;
+                                sound.pos -= offset;
+                                sound.pos_anim = sound
+                                    .pos_anim
+                                    .map(|i| {
+                                        prefab_anim(
+                                            &mut prefab.pos_anims,
+                                            &mut pos_anims,
+                                            &map.animations.pos,
+                                            i,
+                                        )
+                                    })
+                                    .transpose()?;
+                                sound.sound_anim = sound
+                                    .sound_anim
+                                    .map(|i| {
+                                        prefab_anim(
+                                            &mut prefab.sound_anims,
+                                            &mut sound_anims,
+                                            &map.animations.sound,
+                                            i,
+                                        )
+                                    })
+                                    .transpose()?;
+                                layer_sounds.push(sound);
+                            }
+                            if layer_sounds.is_empty() {
+                                continue;
+                            }
+                            let sound = layer
+                                .layer
+                                .attr
+                                .sound
+                                .map(|i| {
+                                    prefab_resource(
+                                        &mut prefab.sounds,
+                                        &mut sounds,
+                                        &map.resources.sounds,
+                                        i,
+                                    )
+                                })
+                                .transpose()?;
+                            prefab.design_layers.push(EditorPrefabDesignLayer::Sound(
+                                MapLayerSound {
+                                    attr: MapLayerSoundAttrs {
+                                        sound,
+                                        ..layer.layer.attr
+                                    },
+                                    sounds: layer_sounds,
+                                    name: layer.layer.name.clone(),
+                                },
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        anyhow::ensure!(
+            !prefab.design_layers.is_empty() || !prefab.physics_layers.is_empty(),
+            "The active or selected layers contain nothing that can be saved as prefab."
+        );
+        Ok(prefab)
+    }
+
+    /// Makes sure a prefab, e.g. one downloaded from the assets server,
+    /// can be transformed & placed without panicking.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let tiles_count = self.width.get() as usize * self.height.get() as usize;
+        let check_index = |index: Option<usize>, len: usize| {
+            anyhow::ensure!(
+                index.is_none_or(|index| index < len),
+                "prefab references a resource or animation that does not exist"
+            );
+            Ok(())
+        };
+        for layer in &self.design_layers {
+            match layer {
+                EditorPrefabDesignLayer::Tile(layer) => {
+                    anyhow::ensure!(
+                        layer.attr.width == self.width
+                            && layer.attr.height == self.height
+                            && layer.tiles.len() == tiles_count,
+                        "tile layer \"{}\" is not as large as the prefab",
+                        layer.name
+                    );
+                    check_index(layer.attr.image_array, self.image_arrays.len())?;
+                    check_index(layer.attr.color_anim, self.color_anims.len())?;
+                }
+                EditorPrefabDesignLayer::Quad(layer) => {
+                    check_index(layer.attr.image, self.images.len())?;
+                    for quad in &layer.quads {
+                        check_index(quad.pos_anim, self.pos_anims.len())?;
+                        check_index(quad.color_anim, self.color_anims.len())?;
+                    }
+                }
+                EditorPrefabDesignLayer::Sound(layer) => {
+                    check_index(layer.attr.sound, self.sounds.len())?;
+                    for sound in &layer.sounds {
+                        check_index(sound.pos_anim, self.pos_anims.len())?;
+                        check_index(sound.sound_anim, self.sound_anims.len())?;
+                    }
+                }
+            }
+        }
+        for tiles in &self.physics_layers {
+            anyhow::ensure!(
+                physics_tiles_count(tiles) == Some(tiles_count),
+                "{} layer is not as large as the prefab",
+                physics_layer_name(tiles)
+            );
+        }
+        Ok(())
+    }
+
+    fn transform_tiles(
+        &mut self,
+        tp: &Arc<rayon::ThreadPool>,
+        transform: fn(&Arc<rayon::ThreadPool>, usize, &mut MapTileLayerTiles),
+    ) {
+        let w = self.width.get() as usize;
+        for layer in &mut self.design_layers {
+            if let EditorPrefabDesignLayer::Tile(layer) = layer {
+                let mut tiles = MapTileLayerTiles::Design(std::mem::take(&mut layer.tiles));
+                transform(tp, w, &mut tiles);
+                if let MapTileLayerTiles::Design(tiles) = tiles {
+                    layer.tiles = tiles;
+                }
+            }
+        }
+        for layer in &mut self.physics_layers {
+            let mut tiles = MapTileLayerTiles::Physics(std::mem::replace(
+                layer,
+                MapTileLayerPhysicsTiles::Arbitrary(Default::default()),
+            ));
+            transform(tp, w, &mut tiles);
+            if let MapTileLayerTiles::Physics(tiles) = tiles {
+                *layer = tiles;
+            }
+        }
+    }
+
+    fn transform_positions(&mut self, transform: impl Fn(&mut fvec2)) {
+        for layer in &mut self.design_layers {
+            match layer {
+                EditorPrefabDesignLayer::Tile(_) => {}
+                EditorPrefabDesignLayer::Quad(layer) => layer
+                    .quads
+                    .iter_mut()
+                    .flat_map(|q| q.points.iter_mut())
+                    .for_each(&transform),
+                EditorPrefabDesignLayer::Sound(layer) => {
+                    layer.sounds.iter_mut().for_each(|s| transform(&mut s.pos))
+                }
+            }
+        }
+    }
+
+    /// Mirrors all layers horizontally.
+    ///
+    /// Animations are kept as they are.
+    pub fn mirror_x(&mut self, tp: &Arc<rayon::ThreadPool>) {
+        self.transform_tiles(tp, mirror_map_tiles_x);
+        let w = ffixed::from_num(self.width.get());
+        self.transform_positions(|pos| pos.x = w - pos.x);
+    }
+
+    /// Mirrors all layers vertically.
+    ///
+    /// Animations are kept as they are.
+    pub fn mirror_y(&mut self, tp: &Arc<rayon::ThreadPool>) {
+        self.transform_tiles(tp, mirror_map_tiles_y);
+        let h = ffixed::from_num(self.height.get());
+        self.transform_positions(|pos| pos.y = h - pos.y);
+    }
+
+    /// Rotates all layers by 90° clockwise.
+    ///
+    /// Animations are kept as they are.
+    pub fn rotate_plus_90(&mut self, tp: &Arc<rayon::ThreadPool>) {
+        self.transform_tiles(tp, rotate_map_tiles_plus_90);
+        let h = ffixed::from_num(self.height.get());
+        self.transform_positions(|pos| *pos = fvec2::new(h - pos.y, pos.x));
+        std::mem::swap(&mut self.width, &mut self.height);
+        for layer in &mut self.design_layers {
+            match layer {
+                EditorPrefabDesignLayer::Tile(layer) => {
+                    layer.attr.width = self.width;
+                    layer.attr.height = self.height;
+                }
+                EditorPrefabDesignLayer::Quad(_) => {}
+                EditorPrefabDesignLayer::Sound(layer) => {
+                    for sound in &mut layer.sounds {
+                        if let SoundShape::Rect { size } = &mut sound.shape {
+                            std::mem::swap(&mut size.x, &mut size.y);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Places the prefab with its top left corner at `pos` (in tiles).
+    ///
+    /// Design layers are placed into the group of the active layer,
+    /// the tiles, quads & sounds are added to a layer of the same kind
+    /// that uses the same resource, preferably with the same name.
+    /// If no such layer exists, a new one is added.
+    /// Physics tiles are placed into the physics layer of the same type.
+    pub fn place(&self, map: &EditorMap, pos: ivec2, client: &EditorClient) {
+        let mut actions = Vec::new();
+
+        let images = map_resource_indices(
+            &map.resources.images,
+            self.images.iter().map(|r| (&r.res, r.file.as_slice())),
+            &mut actions,
+            |res, file, index| {
+                EditorAction::AddImage(ActAddImage {
+                    base: ActAddRemImage { res, file, index },
+                })
+            },
+        );
+        let image_arrays = map_resource_indices(
+            &map.resources.image_arrays,
+            self.image_arrays
+                .iter()
+                .map(|r| (&r.res, r.file.as_slice())),
+            &mut actions,
+            |res, file, index| {
+                EditorAction::AddImage2dArray(ActAddImage2dArray {
+                    base: ActAddRemImage { res, file, index },
+                })
+            },
+        );
+        let sounds = map_resource_indices(
+            &map.resources.sounds,
+            self.sounds.iter().map(|r| (&r.res, r.file.as_slice())),
+            &mut actions,
+            |res, file, index| {
+                EditorAction::AddSound(ActAddSound {
+                    base: ActAddRemSound { res, file, index },
+                })
+            },
+        );
+        let pos_anims = map_anim_indices(
+            &map.animations.pos,
+            &self.pos_anims,
+            &mut actions,
+            |anim, index| {
+                EditorAction::AddPosAnim(ActAddPosAnim {
+                    base: ActAddRemPosAnim { index, anim },
+                })
+            },
+        );
+        let color_anims = map_anim_indices(
+            &map.animations.color,
+            &self.color_anims,
+            &mut actions,
+            |anim, index| {
+                EditorAction::AddColorAnim(ActAddColorAnim {
+                    base: ActAddRemColorAnim { index, anim },
+                })
+            },
+        );
+        let sound_anims = map_anim_indices(
+            &map.animations.sound,
+            &self.sound_anims,
+            &mut actions,
+            |anim, index| {
+                EditorAction::AddSoundAnim(ActAddSoundAnim {
+                    base: ActAddRemSoundAnim { index, anim },
+                })
+            },
+        );
+
+        let offset = fvec2::new(ffixed::from_num(pos.x), ffixed::from_num(pos.y));
+        let design_group = map.active_layer().and_then(|l| layer_index(&l).0);
+        match design_group {
+            Some((is_background, group_index)) if !self.design_layers.is_empty() => {
+                let groups = if is_background {
+                    &map.groups.background
+                } else {
+                    &map.groups.foreground
+                };
+                let group = &groups[group_index];
+                let mut next_layer_index = group.layers.len();
+                // every layer is only used once, so the old tiles of an action
+                // are never changed by a previous action of this group
+                let mut used_layers: HashSet<usize> = Default::default();
+                let mut find_layer = |matches: &dyn Fn(&EditorLayer) -> bool, name: &str| {
+                    let layer_index = group
+                        .layers
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, l)| !used_layers.contains(i) && matches(l))
+                        .min_by_key(|(_, l)| l.name() != name)
+                        .map(|(i, _)| i);
+                    if let Some(layer_index) = layer_index {
+                        used_layers.insert(layer_index);
+                    }
+                    layer_index
+                };
+
+                for layer in &self.design_layers {
+                    match layer {
+                        EditorPrefabDesignLayer::Tile(layer) => {
+                            let image_array = layer.attr.image_array.map(|i| image_arrays[i]);
+                            let color_anim = layer.attr.color_anim.map(|i| color_anims[i]);
+                            let layer_index = find_layer(
+                                &|l| {
+                                    matches!(l, EditorLayer::Tile(l)
+                                        if l.layer.attr.image_array == image_array)
+                                },
+                                &layer.name,
+                            );
+                            if let Some(EditorLayer::Tile(map_layer)) =
+                                layer_index.map(|i| &group.layers[i])
+                            {
+                                let attr = &map_layer.layer.attr;
+                                let Some(area) = PlaceArea::new(
+                                    pos,
+                                    self.width,
+                                    self.height,
+                                    attr.width,
+                                    attr.height,
+                                ) else {
+                                    continue;
+                                };
+                                let (old_tiles, new_tiles) = area.place(
+                                    &map_layer.layer.tiles,
+                                    attr.width,
+                                    &layer.tiles,
+                                    self.width,
+                                );
+                                actions.push(EditorAction::TileLayerReplaceTiles(
+                                    ActTileLayerReplaceTiles {
+                                        base: ActTileLayerReplTilesBase {
+                                            is_background,
+                                            group_index,
+                                            layer_index: layer_index.unwrap(),
+                                            old_tiles,
+                                            new_tiles,
+                                            x: area.range.x,
+                                            y: area.range.y,
+                                            w: area.range.w,
+                                            h: area.range.h,
+                                        },
+                                    },
+                                ));
+                            } else {
+                                // new layers are as large as the physics group
+                                let (width, height) = (
+                                    map.groups.physics.attr.width,
+                                    map.groups.physics.attr.height,
+                                );
+                                let mut tiles = vec![
+                                    Tile::default();
+                                    width.get() as usize * height.get() as usize
+                                ];
+                                if let Some(area) =
+                                    PlaceArea::new(pos, self.width, self.height, width, height)
+                                {
+                                    let (_, new_tiles) =
+                                        area.place(&tiles, width, &layer.tiles, self.width);
+                                    let area_w = area.range.w.get() as usize;
+                                    for (y, row) in new_tiles.chunks_exact(area_w).enumerate() {
+                                        let start = (area.range.y as usize + y)
+                                            * width.get() as usize
+                                            + area.range.x as usize;
+                                        tiles[start..start + area_w].copy_from_slice(row);
+                                    }
+                                }
+                                actions.push(EditorAction::AddTileLayer(ActAddTileLayer {
+                                    base: ActAddRemTileLayer {
+                                        is_background,
+                                        group_index,
+                                        index: next_layer_index,
+                                        layer: MapLayerTile {
+                                            attr: MapTileLayerAttr {
+                                                width,
+                                                height,
+                                                image_array,
+                                                color_anim,
+                                                ..layer.attr
+                                            },
+                                            tiles,
+                                            name: layer.name.clone(),
+                                        },
+                                    },
+                                }));
+                                next_layer_index += 1;
+                            }
+                        }
+                        EditorPrefabDesignLayer::Quad(layer) => {
+                            let image = layer.attr.image.map(|i| images[i]);
+                            let quads = layer
+                                .quads
+                                .iter()
+                                .map(|quad| {
+                                    let mut quad = *quad;
+                                    quad.points.iter_mut().for_each(|p| *p += offset);
+                                    quad.pos_anim = quad.pos_anim.map(|i| pos_anims[i]);
+                                    quad.color_anim = quad.color_anim.map(|i| color_anims[i]);
+                                    quad
+                                })
+                                .collect();
+                            let layer_index = find_layer(
+                                &|l| matches!(l, EditorLayer::Quad(l) if l.layer.attr.image == image),
+                                &layer.name,
+                            );
+                            if let Some(EditorLayer::Quad(map_layer)) =
+                                layer_index.map(|i| &group.layers[i])
+                            {
+                                actions.push(EditorAction::QuadLayerAddQuads(
+                                    ActQuadLayerAddQuads {
+                                        base: ActQuadLayerAddRemQuads {
+                                            is_background,
+                                            group_index,
+                                            layer_index: layer_index.unwrap(),
+                                            index: map_layer.layer.quads.len(),
+                                            quads,
+                                        },
+                                    },
+                                ));
+                            } else {
+                                actions.push(EditorAction::AddQuadLayer(ActAddQuadLayer {
+                                    base: ActAddRemQuadLayer {
+                                        is_background,
+                                        group_index,
+                                        index: next_layer_index,
+                                        layer: MapLayerQuad {
+                                            attr: MapLayerQuadsAttrs {
+                                                image,
+                                                ..layer.attr
+                                            },
+                                            quads,
+                                            name: layer.name.clone(),
+                                        },
+                                    },
+                                }));
+                                next_layer_index += 1;
+                            }
+                        }
+                        EditorPrefabDesignLayer::Sound(layer) => {
+                            let sound = layer.attr.sound.map(|i| sounds[i]);
+                            let layer_sounds = layer
+                                .sounds
+                                .iter()
+                                .map(|s| {
+                                    let mut s = *s;
+                                    s.pos += offset;
+                                    s.pos_anim = s.pos_anim.map(|i| pos_anims[i]);
+                                    s.sound_anim = s.sound_anim.map(|i| sound_anims[i]);
+                                    s
+                                })
+                                .collect();
+                            let layer_index = find_layer(
+                                &|l| matches!(l, EditorLayer::Sound(l) if l.layer.attr.sound == sound),
+                                &layer.name,
+                            );
+                            if let Some(EditorLayer::Sound(map_layer)) =
+                                layer_index.map(|i| &group.layers[i])
+                            {
+                                actions.push(EditorAction::SoundLayerAddSounds(
+                                    ActSoundLayerAddSounds {
+                                        base: ActSoundLayerAddRemSounds {
+                                            is_background,
+                                            group_index,
+                                            layer_index: layer_index.unwrap(),
+                                            index: map_layer.layer.sounds.len(),
+                                            sounds: layer_sounds,
+                                        },
+                                    },
+                                ));
+                            } else {
+                                actions.push(EditorAction::AddSoundLayer(ActAddSoundLayer {
+                                    base: ActAddRemSoundLayer {
+                                        is_background,
+                                        group_index,
+                                        index: next_layer_index,
+                                        layer: MapLayerSound {
+                                            attr: MapLayerSoundAttrs {
+                                                sound,
+                                                ..layer.attr
+                                            },
+                                            sounds: layer_sounds,
+                                            name: layer.name.clone(),
+                                        },
+                                    },
+                                }));
+                                next_layer_index += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            None if !self.design_layers.is_empty() => {
+                client.notifications.push(EditorNotification::Warning(
+                    "The design layers of the prefab were not placed, \
+                    select a design layer of the group they should be placed in."
+                        .to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        let physics = &map.groups.physics;
+        let area = PlaceArea::new(
+            pos,
+            self.width,
+            self.height,
+            physics.attr.width,
+            physics.attr.height,
+        );
+        for tiles in self.physics_layers.iter() {
+            let Some(area) = &area else {
+                break;
+            };
+            let w = physics.attr.width;
+            let placed = physics
+                .layers
+                .iter()
+                .enumerate()
+                .find_map(|(layer_index, layer)| {
+                    let (old_tiles, new_tiles) = match (layer, tiles) {
+                        (
+                            EditorPhysicsLayer::Game(layer),
+                            MapTileLayerPhysicsTiles::Game(tiles),
+                        ) => {
+                            let (old, new) = area.place(&layer.layer.tiles, w, tiles, self.width);
+                            (
+                                MapTileLayerPhysicsTiles::Game(old),
+                                MapTileLayerPhysicsTiles::Game(new),
+                            )
+                        }
+                        (
+                            EditorPhysicsLayer::Front(layer),
+                            MapTileLayerPhysicsTiles::Front(tiles),
+                        ) => {
+                            let (old, new) = area.place(&layer.layer.tiles, w, tiles, self.width);
+                            (
+                                MapTileLayerPhysicsTiles::Front(old),
+                                MapTileLayerPhysicsTiles::Front(new),
+                            )
+                        }
+                        (
+                            EditorPhysicsLayer::Tele(layer),
+                            MapTileLayerPhysicsTiles::Tele(tiles),
+                        ) => {
+                            let (old, new) =
+                                area.place(&layer.layer.base.tiles, w, tiles, self.width);
+                            (
+                                MapTileLayerPhysicsTiles::Tele(old),
+                                MapTileLayerPhysicsTiles::Tele(new),
+                            )
+                        }
+                        (
+                            EditorPhysicsLayer::Speedup(layer),
+                            MapTileLayerPhysicsTiles::Speedup(tiles),
+                        ) => {
+                            let (old, new) = area.place(&layer.layer.tiles, w, tiles, self.width);
+                            (
+                                MapTileLayerPhysicsTiles::Speedup(old),
+                                MapTileLayerPhysicsTiles::Speedup(new),
+                            )
+                        }
+                        (
+                            EditorPhysicsLayer::Switch(layer),
+                            MapTileLayerPhysicsTiles::Switch(tiles),
+                        ) => {
+                            let (old, new) =
+                                area.place(&layer.layer.base.tiles, w, tiles, self.width);
+                            (
+                                MapTileLayerPhysicsTiles::Switch(old),
+                                MapTileLayerPhysicsTiles::Switch(new),
+                            )
+                        }
+                        (
+                            EditorPhysicsLayer::Tune(layer),
+                            MapTileLayerPhysicsTiles::Tune(tiles),
+                        ) => {
+                            let (old, new) =
+                                area.place(&layer.layer.base.tiles, w, tiles, self.width);
+                            (
+                                MapTileLayerPhysicsTiles::Tune(old),
+                                MapTileLayerPhysicsTiles::Tune(new),
+                            )
+                        }
+                        _ => return None,
+                    };
+                    Some(EditorAction::TilePhysicsLayerReplaceTiles(
+                        ActTilePhysicsLayerReplaceTiles {
+                            base: ActTilePhysicsLayerReplTilesBase {
+                                layer_index,
+                                old_tiles,
+                                new_tiles,
+                                x: area.range.x,
+                                y: area.range.y,
+                                w: area.range.w,
+                                h: area.range.h,
+                            },
+                        },
+                    ))
+                });
+            match placed {
+                Some(action) => actions.push(action),
+                None => {
+                    let name = physics_layer_name(tiles);
+                    client.notifications.push(EditorNotification::Warning(format!(
+                        "The map has no {name} layer, the prefab's {name} tiles were not placed."
+                    )));
+                }
+            }
+        }
+
+        if !actions.is_empty() {
+            client.execute_group(EditorActionGroup {
+                actions,
+                identifier: None,
+            });
+        }
+    }
+
+    /// The prefab's tar contains a json description
+    /// and the files of all resources.
+    pub fn to_tar(&self) -> anyhow::Result<Vec<u8>> {
+        let mut tar = new_tar();
+        tar_add_file(
+            &mut tar,
+            PREFAB_DESC_FILE,
+            &serde_json::to_vec_pretty(self)?,
+        );
+        for (dir, resources) in [
+            ("images", &self.images),
+            ("image_arrays", &self.image_arrays),
+            ("sounds", &self.sounds),
+        ] {
+            for (index, res) in resources.iter().enumerate() {
+                tar_add_file(
+                    &mut tar,
+                    format!("{dir}/{index}.{}", res.res.meta.ty.as_str()),
+                    &res.file,
+                );
+            }
+        }
+        Ok(tar.into_inner()?)
+    }
+
+    pub fn from_tar(file: &[u8]) -> anyhow::Result<Self> {
+        let mut files = read_tar_files(file.into())?;
+        let desc = files
+            .remove(Path::new(PREFAB_DESC_FILE))
+            .ok_or_else(|| anyhow!("the prefab has no {PREFAB_DESC_FILE}"))?;
+        let mut prefab: Self = serde_json::from_slice(&desc)?;
+        for (dir, resources) in [
+            ("images", &mut prefab.images),
+            ("image_arrays", &mut prefab.image_arrays),
+            ("sounds", &mut prefab.sounds),
+        ] {
+            for (index, res) in resources.iter_mut().enumerate() {
+                let path: PathBuf = format!("{dir}/{index}.{}", res.res.meta.ty.as_str()).into();
+                res.file = files
+                    .remove(&path)
+                    .ok_or_else(|| anyhow!("the prefab has no {path:?}"))?;
+                anyhow::ensure!(
+                    generate_hash_for(&res.file) == res.res.meta.blake3_hash,
+                    "the hash of {path:?} does not match the resource"
+                );
+            }
+        }
+        prefab.validate()?;
+        Ok(prefab)
+    }
+}
+
+/// All prefabs found in `editor/prefabs` and in the folder
+/// prefabs downloaded from the assets server are stored in.
+pub struct EditorPrefabs {
+    pub prefabs: BTreeMap<String, EditorPrefab>,
+    pub selected: Option<String>,
+    pub new_name: String,
+
+    // ui shown
+    pub active: bool,
+
+    load_task: Option<IoRuntimeTask<BTreeMap<String, EditorPrefab>>>,
+    save_tasks: Vec<IoRuntimeTask<()>>,
+
+    io: IoFileSys,
+    pub tp: Arc<rayon::ThreadPool>,
+}
+
+impl EditorPrefabs {
+    pub fn new(io: IoFileSys, tp: Arc<rayon::ThreadPool>) -> Self {
+        let mut res = Self {
+            prefabs: Default::default(),
+            selected: None,
+            new_name: Default::default(),
+            active: false,
+            load_task: None,
+            save_tasks: Default::default(),
+            io,
+            tp,
+        };
+        res.reload();
+        res
+    }
+
+    /// Prefab names are used as file names and on the assets server.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
+
+    pub fn reload(&mut self) {
+        let fs = self.io.fs.clone();
+        self.load_task = Some(self.io.rt.spawn(async move {
+            let mut prefabs = BTreeMap::default();
+            // local prefabs win over downloaded ones
+            for dir in ["downloaded/editor/prefabs", "editor/prefabs"] {
+                let files = fs
+                    .files_in_dir_recursive(dir.as_ref())
+                    .await
+                    .unwrap_or_default();
+                for (path, file) in files {
+                    if path.extension().is_none_or(|ext| ext != "tar") {
+                        continue;
+                    }
+                    let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                        continue;
+                    };
+                    match EditorPrefab::from_tar(&file) {
+                        Ok(prefab) => {
+                            prefabs.insert(name.to_string(), prefab);
+                        }
+                        Err(err) => {
+                            log::warn!("failed to load prefab {dir}/{path:?}: {err}");
+                        }
+                    }
+                }
+            }
+            Ok(prefabs)
+        }));
+    }
+
+    pub fn save(&mut self, name: String, prefab: EditorPrefab) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            Self::is_valid_name(&name),
+            "Prefab names may only contain letters, digits, '_' and '-'."
+        );
+        let file = prefab.to_tar()?;
+        self.prefabs.insert(name.clone(), prefab);
+        self.selected = Some(name.clone());
+
+        let fs = self.io.fs.clone();
+        self.save_tasks.push(self.io.rt.spawn(async move {
+            let dir: &Path = "editor/prefabs".as_ref();
+            fs.create_dir(dir).await?;
+            fs.write_file(&dir.join(format!("{name}.tar")), file)
+                .await?;
+            Ok(())
+        }));
+        Ok(())
+    }
+
+    pub fn update(&mut self, notifications: &EditorNotifications) {
+        if self
+            .load_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            match self.load_task.take().unwrap().get() {
+                Ok(prefabs) => {
+                    self.prefabs = prefabs;
+                }
+                Err(err) => {
+                    notifications.push(EditorNotification::Error(format!(
+                        "Failed to load prefabs: {err}"
+                    )));
+                }
+            }
+        }
+        let (finished, unfinished) = std::mem::take(&mut self.save_tasks)
+            .into_iter()
+            .partition::<Vec<_>, _>(|task| task.is_finished());
+        self.save_tasks = unfinished;
+        for task in finished {
+            if let Err(err) = task.get() {
+                notifications.push(EditorNotification::Error(format!(
+                    "Failed to save the prefab: {err}"
+                )));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use map::{
+        map::{
+            animations::{AnimBase, AnimPointPos},
+            groups::layers::tiles::{MapTileLayerPhysicsTiles, Tile, TileBase, TileFlags},
+            resources::{MapResourceMetaData, MapResourceRef},
+        },
+        skeleton::{animations::AnimBaseSkeleton, resources::MapResourceRefSkeleton},
+        types::NonZeroU16MinusOne,
+    };
+    use math::math::vector::ivec2;
+
+    use crate::actions::actions::{
+        ActAddImage, ActAddPosAnim, ActAddRemImage, ActAddRemPosAnim, EditorAction,
+    };
+
+    use super::{EditorPrefab, PlaceArea, map_anim_indices, map_resource_indices};
+
+    fn prefab(w: u16, h: u16) -> EditorPrefab {
+        EditorPrefab {
+            width: NonZeroU16MinusOne::new(w).unwrap(),
+            height: NonZeroU16MinusOne::new(h).unwrap(),
+            images: Default::default(),
+            image_arrays: Default::default(),
+            sounds: Default::default(),
+            pos_anims: Default::default(),
+            color_anims: Default::default(),
+            sound_anims: Default::default(),
+            design_layers: Default::default(),
+            physics_layers: vec![MapTileLayerPhysicsTiles::Game(
+                (0..w * h)
+                    .map(|i| TileBase {
+                        index: (i % 2) as u8,
+                        flags: TileFlags::empty(),
+                    })
+                    .collect(),
+            )],
+        }
+    }
+
+    #[test]
+    fn tar_roundtrip_and_placement() {
+        let prefab = prefab(3, 2);
+        let loaded = EditorPrefab::from_tar(&prefab.to_tar().unwrap()).unwrap();
+        assert_eq!(loaded.physics_layers, prefab.physics_layers);
+
+        let size = |v| NonZeroU16MinusOne::new(v).unwrap();
+        // partially outside of the layer
+        let area = PlaceArea::new(ivec2::new(-1, 1), size(3), size(2), size(4), size(2)).unwrap();
+        assert_eq!((area.range.x, area.range.y), (0, 1));
+        assert_eq!((area.range.w.get(), area.range.h.get()), (2, 1));
+        assert_eq!((area.prefab_x, area.prefab_y), (1, 0));
+
+        let layer = vec![
+            Tile {
+                index: 5,
+                flags: TileFlags::empty(),
+            };
+            8
+        ];
+        let MapTileLayerPhysicsTiles::Game(tiles) = &prefab.physics_layers[0] else {
+            unreachable!()
+        };
+        let (old, new) = area.place(&layer, size(4), tiles, prefab.width);
+        assert_eq!(old, layer[4..6]);
+        // empty prefab tiles keep the tiles of the layer
+        assert_eq!(new.iter().map(|t| t.index).collect::<Vec<_>>(), vec![1, 5]);
+
+        assert!(PlaceArea::new(ivec2::new(4, 0), size(3), size(2), size(4), size(2)).is_none());
+    }
+
+    #[test]
+    fn resource_and_anim_indices() {
+        let image = |name: &str, hash: u8| MapResourceRef {
+            name: name.try_into().unwrap(),
+            meta: MapResourceMetaData {
+                blake3_hash: [hash; 32],
+                ty: "png".try_into().unwrap(),
+            },
+            hq_meta: None,
+        };
+        let map_images = vec![MapResourceRefSkeleton {
+            def: image("a", 1),
+            user: (),
+        }];
+        let images = [image("a", 1), image("b", 2), image("c", 2), image("d", 3)];
+
+        let mut actions = Vec::new();
+        let indices = map_resource_indices(
+            &map_images,
+            images.iter().map(|res| (res, &[] as &[u8])),
+            &mut actions,
+            |res, file, index| {
+                EditorAction::AddImage(ActAddImage {
+                    base: ActAddRemImage { res, file, index },
+                })
+            },
+        );
+        // existing & duplicated images are only added once
+        assert_eq!(indices, vec![0, 1, 1, 2]);
+        let added: Vec<_> = actions
+            .iter()
+            .map(|action| match action {
+                EditorAction::AddImage(act) => (act.base.res.name.to_string(), act.base.index),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(added, vec![("b".to_string(), 1), ("d".to_string(), 2)]);
+
+        let anim = |name: &str| AnimBase::<AnimPointPos> {
+            points: Vec::new(),
+            synchronized: false,
+            name: name.to_string(),
+        };
+        let map_anims = vec![AnimBaseSkeleton {
+            def: anim("a"),
+            user: (),
+        }];
+        let anims = [anim("b"), anim("a"), anim("c"), anim("b")];
+
+        let mut actions = Vec::new();
+        let indices = map_anim_indices(&map_anims, &anims, &mut actions, |anim, index| {
+            EditorAction::AddPosAnim(ActAddPosAnim {
+                base: ActAddRemPosAnim { index, anim },
+            })
+        });
+        assert_eq!(indices, vec![1, 0, 2, 1]);
+        let added: Vec<_> = actions
+            .iter()
+            .map(|action| match action {
+                EditorAction::AddPosAnim(act) => (act.base.anim.name.clone(), act.base.index),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(added, vec![("b".to_string(), 1), ("c".to_string(), 2)]);
+    }
+}
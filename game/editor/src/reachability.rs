@@ -0,0 +1,602 @@
+use std::{
+    collections::{BTreeSet, HashSet, VecDeque},
+    num::NonZeroU16,
+    ops::ControlFlow,
+};
+
+use base_io::{io::Io, runtime::IoRuntimeTask};
+use camera::CameraInterface;
+use game_interface::types::{id_gen::IdGenerator, id_types::CharacterId, input::CharacterInput};
+use graphics::handles::{
+    canvas::canvas::GraphicsCanvasHandle,
+    stream::stream::GraphicsStreamHandle,
+    stream_types::{StreamedLine, StreamedQuad},
+    texture::texture::TextureType,
+};
+use graphics_types::rendering::State;
+use map::map::groups::{MapGroupAttr, MapGroupPhysics};
+use math::math::{
+    round_to_int,
+    vector::{dvec2, ffixed, fvec2, ubvec4, vec2},
+};
+use vanilla::{
+    collision::collision::Collision,
+    entities::character::{
+        character::Character,
+        core::character_core::{Core, CoreEvents, CorePipe, CoreReusable, PHYSICAL_SIZE},
+        hook::character_hook::{CharacterHook, Hook, HookedCharacters},
+        pos::character_pos::{CharacterPos, CharacterPositionPlayfield},
+    },
+    simulation_pipe::simulation_pipe::{GameWorldPendingEvents, SimulationPipeCharactersGetter},
+};
+
+use crate::map::EditorMap;
+
+/// The amount of ticks a single input of the search is held.
+const STEP_TICKS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct SimInput {
+    dir: i32,
+    jump: bool,
+    /// The direction the hook is fired in, as long as it is held.
+    hook: Option<vec2>,
+}
+
+/// The state of the simulated character, cheap to copy
+/// for every branch of the search.
+#[derive(Debug, Clone, Copy)]
+struct SimCharacter {
+    pos: vec2,
+    core: Core,
+    hook: Hook,
+}
+
+impl SimCharacter {
+    fn new(pos: vec2) -> Self {
+        Self {
+            pos,
+            core: Core::default(),
+            hook: Hook::None,
+        }
+    }
+
+    fn grounded(&self, collision: &Collision) -> bool {
+        collision.check_pointf(
+            self.pos.x + PHYSICAL_SIZE / 2.0,
+            self.pos.y + PHYSICAL_SIZE / 2.0 + 5.0,
+        ) || collision.check_pointf(
+            self.pos.x - PHYSICAL_SIZE / 2.0,
+            self.pos.y + PHYSICAL_SIZE / 2.0 + 5.0,
+        )
+    }
+
+    /// A coarse key of the state, similar states are only searched once.
+    fn search_key(&self) -> [i32; 7] {
+        let (hook_ty, hook_pos) = match self.hook {
+            Hook::None => (0, vec2::default()),
+            Hook::Active {
+                hook_pos,
+                hook_state,
+                ..
+            } => (1 + hook_state as i32, hook_pos),
+            Hook::WaitsForRelease => (-1, vec2::default()),
+        };
+        [
+            (self.pos.x / 8.0).floor() as i32,
+            (self.pos.y / 8.0).floor() as i32,
+            (self.core.vel.x / 2.0).round() as i32,
+            (self.core.vel.y / 2.0).round() as i32,
+            self.core.jumps.flag & 2,
+            hook_ty,
+            round_to_int(hook_pos.x / 32.0) * 0x10000 + round_to_int(hook_pos.y / 32.0),
+        ]
+    }
+
+    fn tile(&self) -> (i32, i32) {
+        (
+            (self.pos.x / 32.0).floor() as i32,
+            (self.pos.y / 32.0).floor() as i32,
+        )
+    }
+
+    fn is_dead(&self, collision: &Collision) -> bool {
+        let width = collision.get_playfield_width() as f32 * 32.0;
+        let height = collision.get_playfield_height() as f32 * 32.0;
+        collision.is_death(self.pos.x, self.pos.y)
+            || self.pos.x < 0.0
+            || self.pos.y < 0.0
+            || self.pos.x >= width
+            || self.pos.y >= height
+    }
+}
+
+/// The search only knows about a single character.
+struct NoCharacters;
+
+impl SimulationPipeCharactersGetter for NoCharacters {
+    fn for_other_characters_in_range_mut(
+        &mut self,
+        _char_pos: &vec2,
+        _radius: f32,
+        _for_each_func: &mut dyn FnMut(&mut Character),
+    ) {
+    }
+
+    fn get_other_character_id_and_cores_iter_by_ids_mut(
+        &mut self,
+        _ids: &[CharacterId],
+        _for_each_func: &mut dyn FnMut(
+            &CharacterId,
+            &mut Core,
+            &mut CoreReusable,
+            &mut CharacterPos,
+        ) -> ControlFlow<()>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    fn get_other_character_pos_by_id(&self, _other_char_id: &CharacterId) -> &vec2 {
+        unreachable!("there are no other characters")
+    }
+
+    fn get_other_character_by_id_mut(&mut self, _other_char_id: &CharacterId) -> &mut Character {
+        unreachable!("there are no other characters")
+    }
+}
+
+/// Ticks [`SimCharacter`]s with the vanilla character core,
+/// using only the map's collision
+/// (no other characters, weapons or special tiles).
+struct SimWorld<'a> {
+    collision: &'a Collision,
+    character_id: CharacterId,
+    pos: CharacterPos,
+    hook: CharacterHook,
+    events: GameWorldPendingEvents,
+}
+
+impl<'a> SimWorld<'a> {
+    fn new(collision: &'a Collision, start: vec2) -> Self {
+        let character_id: CharacterId = IdGenerator::new().next_id();
+        let field = CharacterPositionPlayfield::new(
+            NonZeroU16::new(collision.get_playfield_width() as u16).unwrap(),
+            NonZeroU16::new(collision.get_playfield_height() as u16).unwrap(),
+        );
+        Self {
+            collision,
+            character_id,
+            pos: field.get_character_pos(start, character_id),
+            hook: HookedCharacters::default().get_new_hook(character_id),
+            events: Default::default(),
+        }
+    }
+
+    fn tick(&mut self, character: &mut SimCharacter, input: &SimInput) {
+        self.pos.move_pos(character.pos);
+        self.hook.set(character.hook, None);
+
+        let mut inp = CharacterInput::default();
+        inp.state.dir.set(input.dir);
+        inp.state.jump.set(input.jump);
+        inp.state.hook.set(input.hook.is_some());
+        if let (Some(dir), Hook::None) = (input.hook, character.hook) {
+            character.core.queued_hooks.cursor = dvec2::new(dir.x as f64, dir.y as f64);
+        }
+
+        let core = &mut character.core;
+        core.physics_tick(
+            &mut self.pos,
+            &mut self.hook,
+            true,
+            true,
+            &mut CorePipe {
+                characters: &mut NoCharacters,
+                input: &inp,
+            },
+            self.collision,
+            CoreEvents {
+                game_pending_events: &self.events,
+                character_id: &self.character_id,
+            },
+        );
+        core.physics_move(
+            &mut self.pos,
+            &mut CorePipe {
+                characters: &mut NoCharacters,
+                input: &inp,
+            },
+            self.collision,
+        );
+        core.physics_quantize(&mut self.pos, &mut self.hook);
+        // sounds & effects are not interesting for the search
+        self.events.clear();
+
+        character.pos = *self.pos.pos();
+        character.hook = self.hook.hook();
+    }
+}
+
+/// The result of a reachability search.
+/// All positions are in tiles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReachabilityResult {
+    pub start: vec2,
+    /// The `hook_length` tuning at the start position.
+    pub hook_length: f32,
+    /// Ground jump (+ air jump at the apex) arcs
+    /// while holding left, nothing or right.
+    pub jump_arcs: Vec<Vec<vec2>>,
+    /// Tiles the character touched during the search.
+    pub reachable_tiles: BTreeSet<(i32, i32)>,
+    /// The search stopped because it reached the
+    /// state limit before exploring all inputs.
+    pub truncated: bool,
+}
+
+/// Simulates a jump from `start`, optionally doing the air jump
+/// at the apex, until the character lands again.
+fn jump_arc(
+    world: &mut SimWorld,
+    start: SimCharacter,
+    dir: i32,
+    air_jump: bool,
+    max_ticks: u32,
+) -> Vec<vec2> {
+    let mut character = start;
+    let mut points = vec![character.pos / 32.0];
+    let mut did_air_jump = false;
+    for tick in 0..max_ticks {
+        let do_air_jump = air_jump && !did_air_jump && tick > 1 && character.core.vel.y >= 0.0;
+        did_air_jump |= do_air_jump;
+        world.tick(
+            &mut character,
+            &SimInput {
+                dir,
+                jump: tick == 0 || do_air_jump,
+                hook: None,
+            },
+        );
+        points.push(character.pos / 32.0);
+        if character.is_dead(world.collision)
+            || (tick > 1 && character.core.vel.y >= 0.0 && character.grounded(world.collision))
+        {
+            break;
+        }
+    }
+    points
+}
+
+/// Searches all inputs a player could make within `max_ticks`
+/// ticks from `start` (in game units) in a breadth first manner.
+///
+/// The search is fully deterministic, the same map & start
+/// always results in the same result.
+pub fn simulate(
+    collision: &Collision,
+    start: vec2,
+    max_ticks: u32,
+    max_states: usize,
+) -> ReachabilityResult {
+    let mut world = SimWorld::new(collision, start);
+    let start_character = SimCharacter::new(start);
+
+    let jump_arcs = [-1, 0, 1]
+        .into_iter()
+        .flat_map(|dir| [(dir, false), (dir, true)])
+        .map(|(dir, air_jump)| jump_arc(&mut world, start_character, dir, air_jump, max_ticks))
+        .collect();
+
+    let hook_dirs: Vec<vec2> = (0..8)
+        .map(|i| {
+            let angle = i as f32 * std::f32::consts::FRAC_PI_4;
+            vec2::new(angle.cos(), angle.sin())
+        })
+        .collect();
+
+    let mut reachable_tiles = BTreeSet::default();
+    let mut visited: HashSet<[i32; 7]> = Default::default();
+    let mut queue: VecDeque<(SimCharacter, u32)> = Default::default();
+    let mut truncated = false;
+
+    reachable_tiles.insert(start_character.tile());
+    visited.insert(start_character.search_key());
+    queue.push_back((start_character, 0));
+
+    'search: while let Some((character, ticks)) = queue.pop_front() {
+        if ticks >= max_ticks {
+            continue;
+        }
+        let hooks: Vec<Option<vec2>> = if matches!(character.hook, Hook::None) {
+            std::iter::once(None)
+                .chain(hook_dirs.iter().copied().map(Some))
+                .collect()
+        } else {
+            // keep holding (the direction is only used for new hooks) or release
+            vec![None, Some(vec2::default())]
+        };
+        for dir in [-1, 0, 1] {
+            for jump in [false, true] {
+                for hook in hooks.iter().copied() {
+                    let mut next = character;
+                    let mut dead = false;
+                    for tick in 0..STEP_TICKS {
+                        world.tick(
+                            &mut next,
+                            &SimInput {
+                                dir,
+                                jump: jump && tick == 0,
+                                hook,
+                            },
+                        );
+                        if next.is_dead(collision) {
+                            dead = true;
+                            break;
+                        }
+                        reachable_tiles.insert(next.tile());
+                    }
+                    if dead || !visited.insert(next.search_key()) {
+                        continue;
+                    }
+                    if visited.len() >= max_states {
+                        truncated = true;
+                        break 'search;
+                    }
+                    queue.push_back((next, ticks + STEP_TICKS));
+                }
+            }
+        }
+    }
+
+    ReachabilityResult {
+        start: start / 32.0,
+        hook_length: collision.get_tune_at(&start).hook_length / 32.0,
+        jump_arcs,
+        reachable_tiles,
+        truncated,
+    }
+}
+
+/// Preview of where a player can move from a start position,
+/// using the physics layers & tunings (including tune zones) of the map.
+pub struct EditorReachability {
+    pub open: bool,
+    /// The next click into the world sets the start position.
+    pub picking_start: bool,
+    /// The start position in tiles.
+    pub start: Option<vec2>,
+
+    pub max_ticks: u32,
+    pub max_states: usize,
+
+    pub result: Option<ReachabilityResult>,
+    task: Option<IoRuntimeTask<ReachabilityResult>>,
+}
+
+impl Default for EditorReachability {
+    fn default() -> Self {
+        Self {
+            open: false,
+            picking_start: false,
+            start: None,
+
+            max_ticks: 150,
+            max_states: 20000,
+
+            result: None,
+            task: None,
+        }
+    }
+}
+
+impl EditorReachability {
+    /// Starts a new search in the background, an already running search is canceled.
+    pub fn compute(&mut self, map: &EditorMap, io: &Io) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let physics = &map.groups.physics;
+        let physics_group = MapGroupPhysics {
+            attr: physics.attr,
+            layers: physics.layers.iter().map(|l| l.clone().into()).collect(),
+        };
+        let (max_ticks, max_states) = (self.max_ticks, self.max_states);
+        self.task = Some(
+            io.rt
+                .spawn(async move {
+                    let collision = Collision::new(physics_group, true)?;
+                    Ok(simulate(&collision, start * 32.0, max_ticks, max_states))
+                })
+                .cancelable(),
+        );
+    }
+
+    pub fn is_computing(&self) -> bool {
+        self.task.is_some()
+    }
+
+    /// Takes the result of a finished search.
+    pub fn update(&mut self) -> anyhow::Result<()> {
+        if self.task.as_ref().is_some_and(|task| task.is_finished())
+            && let Some(task) = self.task.take()
+        {
+            self.result = Some(task.get()?);
+        }
+        Ok(())
+    }
+
+    pub fn render(
+        &self,
+        canvas_handle: &GraphicsCanvasHandle,
+        stream_handle: &GraphicsStreamHandle,
+        map: &EditorMap,
+    ) {
+        let Some(result) = self.result.as_ref().filter(|_| self.open) else {
+            return;
+        };
+
+        let mut state = State::new();
+        map.game_camera().project(
+            canvas_handle,
+            &mut state,
+            Some(&MapGroupAttr {
+                offset: Default::default(),
+                parallax: fvec2::new(ffixed::from_num(100), ffixed::from_num(100)),
+                clipping: None,
+            }),
+        );
+
+        let quads: Vec<StreamedQuad> = result
+            .reachable_tiles
+            .iter()
+            .map(|&(x, y)| {
+                StreamedQuad::default()
+                    .from_pos_and_size(vec2::new(x as f32, y as f32), vec2::new(1.0, 1.0))
+                    .tex_free_form(
+                        vec2::new(0.0, 0.0),
+                        vec2::new(1.0, 0.0),
+                        vec2::new(1.0, 1.0),
+                        vec2::new(0.0, 1.0),
+                    )
+                    .color(ubvec4::new(100, 255, 100, 50))
+            })
+            .collect();
+        stream_handle.render_quads(&quads, state, TextureType::None);
+
+        let mut lines: Vec<StreamedLine> = Default::default();
+        for (index, arc) in result.jump_arcs.iter().enumerate() {
+            // single jumps are drawn more transparent than double jumps
+            let color = if index % 2 == 0 {
+                ubvec4::new(255, 255, 100, 128)
+            } else {
+                ubvec4::new(255, 200, 50, 255)
+            };
+            lines.extend(arc.windows(2).map(|points| {
+                StreamedLine::new()
+                    .with_color(color)
+                    .from_pos([points[0], points[1]])
+            }));
+        }
+
+        const HOOK_SEGMENTS: usize = 64;
+        let hook_point = |i: usize| {
+            let angle = i as f32 / HOOK_SEGMENTS as f32 * std::f32::consts::TAU;
+            result.start + vec2::new(angle.cos(), angle.sin()) * result.hook_length
+        };
+        lines.extend((0..HOOK_SEGMENTS).map(|i| {
+            StreamedLine::new()
+                .with_color(ubvec4::new(100, 200, 255, 255))
+                .from_pos([hook_point(i), hook_point(i + 1)])
+        }));
+
+        let cross = 0.25;
+        lines.extend([
+            StreamedLine::new()
+                .with_color(ubvec4::new(255, 255, 255, 255))
+                .from_pos([
+                    result.start - vec2::new(cross, cross),
+                    result.start + vec2::new(cross, cross),
+                ]),
+            StreamedLine::new()
+                .with_color(ubvec4::new(255, 255, 255, 255))
+                .from_pos([
+                    result.start + vec2::new(-cross, cross),
+                    result.start + vec2::new(cross, -cross),
+                ]),
+        ]);
+
+        stream_handle.render_lines(&lines, state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use map::{
+        map::groups::{
+            MapGroupPhysics, MapGroupPhysicsAttr,
+            layers::{
+                physics::{MapLayerPhysics, MapLayerTilePhysicsBase},
+                tiles::{TileBase, TileFlags},
+            },
+        },
+        types::NonZeroU16MinusOne,
+    };
+    use math::math::vector::vec2;
+    use vanilla::{
+        collision::collision::Collision,
+        entities::character::hook::character_hook::{Hook, HookState},
+    };
+
+    use super::{SimCharacter, SimInput, SimWorld, simulate};
+
+    /// A map with a solid floor in the last row and
+    /// optionally a solid ceiling in the first row.
+    fn floor(w: u16, h: u16, ceiling: bool) -> Collision {
+        let tiles = (0..w * h)
+            .map(|i| TileBase {
+                index: if i / w == h - 1 || (ceiling && i / w == 0) {
+                    1
+                } else {
+                    0
+                },
+                flags: TileFlags::empty(),
+            })
+            .collect();
+        Collision::new(
+            MapGroupPhysics {
+                attr: MapGroupPhysicsAttr {
+                    width: NonZeroU16MinusOne::new(w).unwrap(),
+                    height: NonZeroU16MinusOne::new(h).unwrap(),
+                },
+                layers: vec![MapLayerPhysics::Game(MapLayerTilePhysicsBase { tiles })],
+            },
+            true,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn floor_reachability() {
+        let (w, h) = (20u16, 12u16);
+        let collision = floor(w, h, false);
+        let start = vec2::new(10.0 * 32.0 + 16.0, (h - 2) as f32 * 32.0 + 16.0);
+        let res = simulate(&collision, start, 50, 2000);
+        assert_eq!(res, simulate(&collision, start, 50, 2000));
+
+        assert_eq!(res.jump_arcs.len(), 6);
+        assert!(res.reachable_tiles.contains(&(10, h as i32 - 2)));
+        // walking reaches the neighbour tiles, the floor is never entered
+        assert!(res.reachable_tiles.contains(&(9, h as i32 - 2)));
+        assert!(res.reachable_tiles.contains(&(11, h as i32 - 2)));
+        assert!(!res.reachable_tiles.iter().any(|&(_, y)| y >= h as i32 - 1));
+        // jumping gets higher than the start tile
+        assert!(res.reachable_tiles.iter().any(|&(_, y)| y < h as i32 - 3));
+    }
+
+    #[test]
+    fn hook_pulls_to_ceiling() {
+        let (w, h) = (20u16, 8u16);
+        let collision = floor(w, h, true);
+        let start = vec2::new(10.0 * 32.0 + 16.0, (h - 2) as f32 * 32.0 + 16.0);
+        let mut world = SimWorld::new(&collision, start);
+        let mut character = SimCharacter::new(start);
+
+        let input = SimInput {
+            dir: 0,
+            jump: false,
+            hook: Some(vec2::new(0.0, -1.0)),
+        };
+        for _ in 0..50 {
+            world.tick(&mut character, &input);
+        }
+        assert!(matches!(
+            character.hook,
+            Hook::Active {
+                hook_state: HookState::HookGrabbed,
+                ..
+            }
+        ));
+        // the hook dragged the character up to the ceiling
+        assert!(character.pos.y < 3.0 * 32.0);
+    }
+}
@@ -21,7 +21,11 @@ use graphics::{
         texture::texture::GraphicsTextureHandle,
     },
 };
-use map::{file::MapFileReader, map::Map};
+use map::{
+    file::MapFileReader,
+    map::{Map, groups::layers::tiles::MapTileLayerPhysicsTiles},
+    skeleton::groups::layers::physics::MapLayerPhysicsSkeleton,
+};
 use math::math::vector::vec2;
 use network::network::{
     connection::NetworkConnectionId,
@@ -33,15 +37,15 @@ use sound::sound_mt::SoundMultiThreaded;
 
 use crate::{
     action_logic::{check_and_copy_tiles, do_action, merge_actions, redo_action, undo_action},
-    actions::actions::{
-        ActTileLayerReplaceTiles, EditorAction, EditorActionGroup, EditorActionInterface,
-    },
+    actions::actions::{EditorAction, EditorActionGroup, EditorActionInterface},
     dbg::{invalid::random_invalid_action, valid::random_valid_action},
     event::{
-        AdminConfigState, ClientProps, EditorCommand, EditorEvent, EditorEventAutoMap,
-        EditorEventClientToServer, EditorEventGenerator, EditorEventLayerIndex,
+        AdminConfigState, AdminPermissionChange, ClientProps, EditorCommand, EditorEvent,
+        EditorEventAutoMap, EditorEventClientToServer, EditorEventGenerator, EditorEventLayerIndex,
         EditorEventOverwriteMap, EditorEventRuleTy, EditorEventServerToClient, EditorNetEvent,
+        EditorPermissions,
     },
+    journal::{EditorJournal, EditorJournalEntry, EditorJournalOp},
     map::{EditorLayer, EditorMap, EditorMapGroupsInterface},
     network::EditorNetwork,
     tools::{
@@ -55,6 +59,9 @@ use crate::{
     },
 };
 
+/// How many undo/redo events are kept for reconnecting clients.
+const MAX_HISTORY_EVENTS: usize = 300;
+
 #[derive(Debug, Default)]
 struct Client {
     is_authed: bool,
@@ -96,6 +103,17 @@ pub struct EditorServer {
     last_client_infos: Duration,
     needs_client_info_update: bool,
 
+    journal: Option<EditorJournal>,
+
+    permissions: EditorPermissions,
+
+    /// Random id of this server instance, see [`crate::event::EditorResync`].
+    session_id: u64,
+    next_event_id: u64,
+    /// The last undo/redo events sent to the clients, so
+    /// that reconnecting clients can catch up.
+    history_events: VecDeque<(u64, EditorEventServerToClient)>,
+
     io: Io,
 }
 
@@ -137,10 +155,167 @@ impl EditorServer {
             last_client_infos: time.now(),
             time: time.clone(),
 
+            journal: None,
+
+            permissions: Default::default(),
+
+            session_id: rand::rng().next_u64(),
+            next_event_id: 0,
+            history_events: Default::default(),
+
             io,
         })
     }
 
+    /// The journal of this server, only exists if the map has a save path.
+    pub fn journal(&self) -> Option<&EditorJournal> {
+        self.journal.as_ref()
+    }
+
+    pub fn journal_mut(&mut self) -> Option<&mut EditorJournal> {
+        self.journal.as_mut()
+    }
+
+    pub fn set_journal(&mut self, journal: Option<EditorJournal>) {
+        self.journal = journal;
+    }
+
+    /// Applies journal entries on top of the map, e.g. to recover
+    /// changes that were not saved.
+    ///
+    /// The entries become part of the undo history and are written
+    /// to the current journal again.
+    pub fn replay_journal(
+        &mut self,
+        entries: Vec<EditorJournalEntry>,
+        tp: &Arc<rayon::ThreadPool>,
+        sound_mt: &SoundMultiThreaded,
+        graphics_mt: &GraphicsMultiThreaded,
+        shader_storage_handle: &GraphicsShaderStorageHandle,
+        buffer_object_handle: &GraphicsBufferObjectHandle,
+        backend_handle: &GraphicsBackendHandle,
+        texture_handle: &GraphicsTextureHandle,
+        map: &mut EditorMap,
+        notifications: &mut ClientNotifications,
+    ) -> anyhow::Result<()> {
+        for (index, entry) in entries.into_iter().enumerate() {
+            let res = match entry.op {
+                EditorJournalOp::Do => entry.group.actions.iter().try_for_each(|act| {
+                    do_action(
+                        tp,
+                        sound_mt,
+                        graphics_mt,
+                        shader_storage_handle,
+                        buffer_object_handle,
+                        backend_handle,
+                        texture_handle,
+                        act.clone(),
+                        map,
+                        false,
+                    )
+                    .map(|_| ())
+                }),
+                EditorJournalOp::Undo => entry.group.actions.iter().rev().try_for_each(|act| {
+                    undo_action(
+                        tp,
+                        sound_mt,
+                        graphics_mt,
+                        shader_storage_handle,
+                        buffer_object_handle,
+                        backend_handle,
+                        texture_handle,
+                        act.clone(),
+                        map,
+                    )
+                }),
+                EditorJournalOp::Redo => entry.group.actions.iter().try_for_each(|act| {
+                    redo_action(
+                        tp,
+                        sound_mt,
+                        graphics_mt,
+                        shader_storage_handle,
+                        buffer_object_handle,
+                        backend_handle,
+                        texture_handle,
+                        act.clone(),
+                        map,
+                    )
+                }),
+            };
+            res.map_err(|err| anyhow!("replaying journal entry #{index} failed: {err}"))?;
+
+            match entry.op {
+                EditorJournalOp::Do => {
+                    self.push_action_group(entry.group, notifications);
+                }
+                EditorJournalOp::Undo | EditorJournalOp::Redo => {
+                    self.cur_action_group = if entry.op == EditorJournalOp::Undo {
+                        self.cur_action_group.and_then(|index| index.checked_sub(1))
+                    } else {
+                        Some(
+                            self.cur_action_group
+                                .map(|index| index + 1)
+                                .unwrap_or_default()
+                                .min(self.action_groups.len().saturating_sub(1)),
+                        )
+                    };
+                    if let Some(journal) = &mut self.journal {
+                        journal.append(entry.op, &entry.group);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends an undo/redo event to all remote clients.
+    fn broadcast_history_event(&mut self, ev: impl FnOnce(u64) -> EditorEventServerToClient) {
+        let event_id = self.next_event_id;
+        self.next_event_id += 1;
+        let ev = ev(event_id);
+
+        self.clients
+            .iter()
+            .filter(|(_, client)| !client.is_local_client)
+            .for_each(|(id, _)| {
+                self.network.send_to(id, EditorEvent::Server(ev.clone()));
+            });
+
+        self.history_events.push_back((event_id, ev));
+        while self.history_events.len() > MAX_HISTORY_EVENTS {
+            self.history_events.pop_front();
+        }
+    }
+
+    /// The events a client missed, `None` if the history
+    /// does not contain all of them anymore.
+    fn missed_history_events(
+        history_events: &VecDeque<(u64, EditorEventServerToClient)>,
+        next_event_id: u64,
+        client_next_event_id: u64,
+    ) -> Option<Vec<EditorEventServerToClient>> {
+        if client_next_event_id == next_event_id {
+            return Some(Vec::new());
+        }
+        let (first_event_id, _) = history_events.front()?;
+        (client_next_event_id >= *first_event_id && client_next_event_id < next_event_id).then(
+            || {
+                history_events
+                    .iter()
+                    .filter(|(event_id, _)| *event_id >= client_next_event_id)
+                    .map(|(_, ev)| ev.clone())
+                    .collect()
+            },
+        )
+    }
+
+    fn broadcast_permissions(&self) {
+        self.network
+            .send(EditorEvent::Server(EditorEventServerToClient::Permissions(
+                self.permissions.clone(),
+            )));
+    }
+
     fn broadcast_client_infos(&self) {
         self.network
             .send(EditorEvent::Server(EditorEventServerToClient::Infos(
@@ -151,21 +326,75 @@ impl EditorServer {
     fn auto_map(
         rule: &mut TileLayerAutoMapperRuleType,
         auto_map: EditorEventAutoMap,
-        map: &mut EditorMap,
-    ) -> anyhow::Result<ActTileLayerReplaceTiles> {
+        map: &EditorMap,
+    ) -> anyhow::Result<EditorAction> {
         let groups = if auto_map.is_background {
-            &mut map.groups.background
+            &map.groups.background
         } else {
-            &mut map.groups.foreground
+            &map.groups.foreground
         };
-        let group = groups
-            .get_mut(auto_map.group_index)
-            .ok_or_else(|| anyhow!("Group index out of bounds"))?;
-        let layer = group
-            .layers
-            .get_mut(auto_map.layer_index)
-            .ok_or_else(|| anyhow!("Layer index is out of bounds"))?;
-        let EditorLayer::Tile(layer) = layer else {
+        let layer = groups
+            .get(auto_map.group_index)
+            .ok_or_else(|| anyhow!("Group index out of bounds"))
+            .and_then(|group| {
+                group
+                    .layers
+                    .get(auto_map.layer_index)
+                    .ok_or_else(|| anyhow!("Layer index is out of bounds"))
+            });
+
+        if let Some(physics_layer_index) = auto_map.physics_layer_index {
+            // the design layer is only an input, which not every rule needs
+            let design_layer = match layer {
+                Ok(EditorLayer::Tile(layer)) => {
+                    Some((layer.layer.attr, layer.layer.tiles.as_slice()))
+                }
+                _ => None,
+            };
+            let physics = &map.groups.physics;
+            let tiles = match physics
+                .layers
+                .get(physics_layer_index)
+                .ok_or_else(|| anyhow!("Physics layer index is out of bounds"))?
+            {
+                MapLayerPhysicsSkeleton::Arbitrary(_) => {
+                    anyhow::bail!("Arbitrary physics layers can't be auto mapped")
+                }
+                MapLayerPhysicsSkeleton::Game(layer) => {
+                    MapTileLayerPhysicsTiles::Game(layer.layer.tiles.clone())
+                }
+                MapLayerPhysicsSkeleton::Front(layer) => {
+                    MapTileLayerPhysicsTiles::Front(layer.layer.tiles.clone())
+                }
+                MapLayerPhysicsSkeleton::Tele(layer) => {
+                    MapTileLayerPhysicsTiles::Tele(layer.layer.base.tiles.clone())
+                }
+                MapLayerPhysicsSkeleton::Speedup(layer) => {
+                    MapTileLayerPhysicsTiles::Speedup(layer.layer.tiles.clone())
+                }
+                MapLayerPhysicsSkeleton::Switch(layer) => {
+                    MapTileLayerPhysicsTiles::Switch(layer.layer.base.tiles.clone())
+                }
+                MapLayerPhysicsSkeleton::Tune(layer) => {
+                    MapTileLayerPhysicsTiles::Tune(layer.layer.base.tiles.clone())
+                }
+            };
+            let action = rule.run_physics_layer(
+                auto_map.seed,
+                physics.attr,
+                tiles,
+                design_layer,
+                0,
+                0,
+                physics.attr.width,
+                physics.attr.height,
+                physics_layer_index,
+            )?;
+
+            return Ok(EditorAction::TilePhysicsLayerReplaceTiles(action));
+        }
+
+        let EditorLayer::Tile(layer) = layer? else {
             anyhow::bail!("Layer is not of type tile");
         };
 
@@ -182,7 +411,7 @@ impl EditorServer {
             auto_map.layer_index,
         )?;
 
-        Ok(action)
+        Ok(EditorAction::TileLayerReplaceTiles(action))
     }
 
     fn live_edit(
@@ -218,6 +447,77 @@ impl EditorServer {
         })
     }
 
+    /// Applies the action group of a client.
+    ///
+    /// The permissions of all actions are checked before any action
+    /// is applied, so a group is either rejected as a whole or applied.
+    /// If an action fails, the actions applied before it are returned
+    /// together with the error message for the client.
+    fn do_client_action_group(
+        &mut self,
+        server_id: u64,
+        privileged: bool,
+        act: EditorActionGroup,
+        tp: &Arc<rayon::ThreadPool>,
+        sound_mt: &SoundMultiThreaded,
+        graphics_mt: &GraphicsMultiThreaded,
+        shader_storage_handle: &GraphicsShaderStorageHandle,
+        buffer_object_handle: &GraphicsBufferObjectHandle,
+        backend_handle: &GraphicsBackendHandle,
+        texture_handle: &GraphicsTextureHandle,
+        map: &mut EditorMap,
+    ) -> (EditorActionGroup, Option<String>) {
+        let mut valid_act = EditorActionGroup {
+            actions: Vec::new(),
+            identifier: act.identifier.clone(),
+        };
+        if let Err(err) = act
+            .actions
+            .iter()
+            .try_for_each(|act| self.permissions.check_action(server_id, privileged, act))
+        {
+            return (
+                valid_act,
+                Some(format!("Failed to execute your action: {err}")),
+            );
+        }
+        for act in act.actions {
+            match do_action(
+                tp,
+                sound_mt,
+                graphics_mt,
+                shader_storage_handle,
+                buffer_object_handle,
+                backend_handle,
+                texture_handle,
+                self.prepare_action(map, act),
+                map,
+                true,
+            ) {
+                Ok(act) => {
+                    self.action_log
+                        .push_front(format!("[DO] {}", act.redo_info()));
+                    valid_act.actions.push(act);
+                }
+                Err(err) => {
+                    return (
+                        valid_act,
+                        Some(format!(
+                            "Failed to execute your action\n\
+                            This is usually caused if a \
+                            previous action invalidates \
+                            this action, e.g. by a different user.\n\
+                            If all users are inactive, executing \
+                            the same action again should work; \
+                            if not it means it's a bug.\n{err}"
+                        )),
+                    );
+                }
+            }
+        }
+        (valid_act, None)
+    }
+
     fn prepare_action(&mut self, map: &mut EditorMap, act: EditorAction) -> EditorAction {
         if let EditorAction::TileLayerReplaceTiles(act) = act {
             let groups = if act.base.is_background {
@@ -284,6 +584,60 @@ impl EditorServer {
         }
     }
 
+    /// Adds an applied action group to the undo history,
+    /// merging it with the last group if both share an identifier.
+    fn push_action_group(
+        &mut self,
+        valid_act: EditorActionGroup,
+        notifications: &mut ClientNotifications,
+    ) {
+        if let Some(journal) = &mut self.journal {
+            journal.append(EditorJournalOp::Do, &valid_act);
+        }
+
+        if let Some(cur_action_group) = self.cur_action_group {
+            self.action_groups.truncate(cur_action_group + 1);
+        } else {
+            self.action_groups.clear();
+        }
+
+        if self.action_groups.last_mut().is_some_and(|group| {
+            group
+                .identifier
+                .as_ref()
+                // explicitly check for some here
+                .is_some_and(|identifier| Some(identifier) == valid_act.identifier.as_ref())
+        }) {
+            let group = self.action_groups.last_mut().unwrap();
+            group.actions.append(&mut valid_act.actions.clone());
+
+            match merge_actions(&mut group.actions) {
+                Ok(had_merge) => {
+                    if had_merge {
+                        let merged_action = group.actions.last().unwrap();
+                        self.action_log
+                            .push_front(format!("[MERGED] {}", merged_action.redo_info()));
+                    }
+                }
+                Err(err) => {
+                    log::error!("{err}{}", err.backtrace());
+                    notifications.add_err(err.to_string(), Duration::from_secs(10));
+                }
+            }
+        } else {
+            let new_index = self.action_groups.len();
+            self.action_groups.push(valid_act);
+            self.cur_action_group = Some(new_index);
+        }
+
+        // Make sure memory doesn't exhaust
+        while self.action_groups.len() > 300 {
+            self.action_groups.remove(0);
+            self.cur_action_group = self.cur_action_group.map(|index| index.saturating_sub(1));
+        }
+        self.action_log.truncate(4000);
+    }
+
     fn handle_client_ev(
         &mut self,
         id: NetworkConnectionId,
@@ -307,6 +661,7 @@ impl EditorServer {
                 is_local_client,
                 mapper_name,
                 color,
+                resync,
             } = &ev
             {
                 if self.password.eq(password) {
@@ -326,7 +681,34 @@ impl EditorServer {
                         stats: client.props.stats,
                     };
 
-                    if !*is_local_client {
+                    let missed_events = resync
+                        .filter(|resync| resync.session_id == self.session_id)
+                        .and_then(|resync| {
+                            Self::missed_history_events(
+                                &self.history_events,
+                                self.next_event_id,
+                                resync.next_event_id,
+                            )
+                        });
+                    if let Some(missed_events) = missed_events {
+                        log::info!(
+                            "Editor server: resyncing client with {} events",
+                            missed_events.len()
+                        );
+                        for ev in missed_events {
+                            self.network.send_to(&id, EditorEvent::Server(ev));
+                        }
+                        // the client might still have live edits that were stopped in the meantime
+                        for (layer_index, live_edit) in map.groups.live_edit_states() {
+                            self.network.send_to(
+                                &id,
+                                EditorEvent::Server(EditorEventServerToClient::AutoMapLiveEdit {
+                                    layer_index,
+                                    live_edit,
+                                }),
+                            );
+                        }
+                    } else if !*is_local_client {
                         let resources: HashMap<_, _> = map
                             .resources
                             .images
@@ -365,6 +747,7 @@ impl EditorServer {
                                     map: map_bytes,
                                     resources,
                                     live_edited_layers: map.groups.live_edited_layers(),
+                                    next_event_id: self.next_event_id,
                                 },
                             )),
                         );
@@ -375,8 +758,15 @@ impl EditorServer {
                         EditorEvent::Server(EditorEventServerToClient::Info {
                             server_id: client.props.server_id,
                             allows_remote_admin: self.admin_password.is_some(),
+                            session_id: self.session_id,
                         }),
                     );
+                    self.network.send_to(
+                        &id,
+                        EditorEvent::Server(EditorEventServerToClient::Permissions(
+                            self.permissions.clone(),
+                        )),
+                    );
                     self.broadcast_client_infos();
                 } else {
                     self.network.send_to(
@@ -387,114 +777,43 @@ impl EditorServer {
                     );
                 }
             } else if client.is_authed {
+                let server_id = client.props.server_id;
+                let privileged = client.is_admin || client.is_local_client;
                 match ev {
                     EditorEventClientToServer::Action(act) => {
-                        let mut valid_act = EditorActionGroup {
-                            actions: Vec::new(),
-                            identifier: act.identifier.clone(),
-                        };
-                        for act in act.actions {
-                            match do_action(
-                                tp,
-                                sound_mt,
-                                graphics_mt,
-                                shader_storage_handle,
-                                buffer_object_handle,
-                                backend_handle,
-                                texture_handle,
-                                self.prepare_action(map, act),
-                                map,
-                                true,
-                            ) {
-                                Ok(act) => {
-                                    self.action_log
-                                        .push_front(format!("[DO] {}", act.redo_info()));
-                                    valid_act.actions.push(act);
-                                }
-                                Err(err) => {
-                                    self.network.send_to(
-                                        &id,
-                                        EditorEvent::Server(EditorEventServerToClient::Error(
-                                            format!(
-                                                "Failed to execute your action\n\
-                                                This is usually caused if a \
-                                                previous action invalidates \
-                                                this action, e.g. by a different user.\n\
-                                                If all users are inactive, executing \
-                                                the same action again should work; \
-                                                if not it means it's a bug.\n{err}"
-                                            ),
-                                        )),
-                                    );
-                                    break;
-                                }
-                            }
+                        let (valid_act, err) = self.do_client_action_group(
+                            server_id,
+                            privileged,
+                            act,
+                            tp,
+                            sound_mt,
+                            graphics_mt,
+                            shader_storage_handle,
+                            buffer_object_handle,
+                            backend_handle,
+                            texture_handle,
+                            map,
+                        );
+                        if let Some(err) = err {
+                            self.network.send_to(
+                                &id,
+                                EditorEvent::Server(EditorEventServerToClient::Error(err)),
+                            );
                         }
                         if !valid_act.actions.is_empty() {
                             *should_save = true;
-                            if let Some(cur_action_group) = self.cur_action_group {
-                                self.action_groups.truncate(cur_action_group + 1);
-                            } else {
-                                self.action_groups.clear();
-                            }
+                            self.push_action_group(valid_act.clone(), notifications);
 
-                            if self.action_groups.last_mut().is_some_and(|group| {
-                                group
-                                    .identifier
-                                    .as_ref()
-                                    // explicitly check for some here
-                                    .is_some_and(|identifier| {
-                                        Some(identifier) == valid_act.identifier.as_ref()
-                                    })
-                            }) {
-                                let group = self.action_groups.last_mut().unwrap();
-                                group.actions.append(&mut valid_act.actions.clone());
-
-                                match merge_actions(&mut group.actions) {
-                                    Ok(had_merge) => {
-                                        if had_merge {
-                                            let merged_action = group.actions.last().unwrap();
-                                            self.action_log.push_front(format!(
-                                                "[MERGED] {}",
-                                                merged_action.redo_info()
-                                            ));
-                                        }
-                                    }
-                                    Err(err) => {
-                                        log::error!("{err}{}", err.backtrace());
-                                        notifications
-                                            .add_err(err.to_string(), Duration::from_secs(10));
-                                    }
+                            let undo_label = self.undo_label();
+                            let redo_label = self.redo_label();
+                            self.broadcast_history_event(|event_id| {
+                                EditorEventServerToClient::RedoAction {
+                                    action: valid_act,
+                                    undo_label,
+                                    redo_label,
+                                    event_id,
                                 }
-                            } else {
-                                let new_index = self.action_groups.len();
-                                self.action_groups.push(valid_act.clone());
-                                self.cur_action_group = Some(new_index);
-                            }
-
-                            // Make sure memory doesn't exhaust
-                            while self.action_groups.len() > 300 {
-                                self.action_groups.remove(0);
-                                self.cur_action_group =
-                                    self.cur_action_group.map(|index| index.saturating_sub(1));
-                            }
-                            self.action_log.truncate(4000);
-
-                            self.clients
-                                .iter()
-                                .filter(|(_, client)| !client.is_local_client)
-                                .for_each(|(id, _)| {
-                                    self.network.send_to(
-                                        id,
-                                        EditorEvent::Server(
-                                            EditorEventServerToClient::RedoAction {
-                                                action: valid_act.clone(),
-                                                undo_label: self.undo_label(),
-                                                redo_label: self.redo_label(),
-                                            },
-                                        ),
-                                    );
-                                });
+                            });
                         }
                     }
                     EditorEventClientToServer::Command(cmd) => match cmd {
@@ -508,6 +827,33 @@ impl EditorServer {
                                     })))
                                 && !self.action_groups.is_empty()
                             {
+                                let group_index = if is_undo {
+                                    self.cur_action_group.unwrap_or_default()
+                                } else {
+                                    self.cur_action_group
+                                        .map(|index| index + 1)
+                                        .unwrap_or_default()
+                                };
+                                if let Some(err) =
+                                    self.action_groups.get(group_index).and_then(|group| {
+                                        group.actions.iter().find_map(|act| {
+                                            self.permissions
+                                                .check_action(server_id, privileged, act)
+                                                .err()
+                                        })
+                                    })
+                                {
+                                    self.network.send_to(
+                                        &id,
+                                        EditorEvent::Server(EditorEventServerToClient::Error(
+                                            format!(
+                                                "Failed to {} the last action: {err}",
+                                                if is_undo { "undo" } else { "redo" }
+                                            ),
+                                        )),
+                                    );
+                                    return;
+                                }
                                 *should_save = true;
                                 if !is_undo {
                                     self.cur_action_group =
@@ -592,6 +938,17 @@ impl EditorServer {
                                     panic!("action group did not exists. logic bug")
                                 };
 
+                                if let Some(journal) = &mut self.journal {
+                                    journal.append(
+                                        if is_undo {
+                                            EditorJournalOp::Undo
+                                        } else {
+                                            EditorJournalOp::Redo
+                                        },
+                                        &group,
+                                    );
+                                }
+
                                 if is_undo {
                                     self.cur_action_group = match self.cur_action_group {
                                         Some(index) => index.checked_sub(1),
@@ -603,25 +960,23 @@ impl EditorServer {
 
                                 let undo_label = self.undo_label();
                                 let redo_label = self.redo_label();
-                                let act = if is_undo {
-                                    EditorEventServerToClient::UndoAction {
-                                        action: group,
-                                        redo_label,
-                                        undo_label,
-                                    }
-                                } else {
-                                    EditorEventServerToClient::RedoAction {
-                                        action: group,
-                                        redo_label,
-                                        undo_label,
+                                self.broadcast_history_event(|event_id| {
+                                    if is_undo {
+                                        EditorEventServerToClient::UndoAction {
+                                            action: group,
+                                            redo_label,
+                                            undo_label,
+                                            event_id,
+                                        }
+                                    } else {
+                                        EditorEventServerToClient::RedoAction {
+                                            action: group,
+                                            redo_label,
+                                            undo_label,
+                                            event_id,
+                                        }
                                     }
-                                };
-                                self.clients
-                                    .iter()
-                                    .filter(|(_, client)| !client.is_local_client)
-                                    .for_each(|(id, _)| {
-                                        self.network.send_to(id, EditorEvent::Server(act.clone()));
-                                    });
+                                });
 
                                 self.action_log.truncate(4000);
                             }
@@ -682,6 +1037,60 @@ impl EditorServer {
                             }
                         }
                     }
+                    EditorEventClientToServer::AdminChangePermissions(change) => {
+                        if self.admin_password == Some(change.password) {
+                            match change.change {
+                                AdminPermissionChange::ReadOnly {
+                                    server_id,
+                                    read_only,
+                                } => {
+                                    if read_only {
+                                        self.permissions.read_only.insert(server_id);
+                                    } else {
+                                        self.permissions.read_only.remove(&server_id);
+                                    }
+                                }
+                                AdminPermissionChange::GroupEditors { group, server_ids } => {
+                                    match server_ids {
+                                        Some(server_ids) => {
+                                            self.permissions
+                                                .group_editors
+                                                .insert(group, server_ids);
+                                        }
+                                        None => {
+                                            self.permissions.group_editors.remove(&group);
+                                        }
+                                    }
+                                }
+                                AdminPermissionChange::Unlock(target) => {
+                                    self.permissions.locks.remove(&target);
+                                }
+                            }
+                            self.broadcast_permissions();
+                        }
+                    }
+                    EditorEventClientToServer::Lock { target, lock } => {
+                        let is_owner = self.permissions.lock_owner(&target) == Some(server_id);
+                        if !lock {
+                            // own locks can always be removed
+                            if is_owner {
+                                self.permissions.locks.remove(&target);
+                                self.broadcast_permissions();
+                            }
+                        } else if let Err(err) =
+                            self.permissions.check_lock(server_id, privileged, &target)
+                        {
+                            self.network.send_to(
+                                &id,
+                                EditorEvent::Server(EditorEventServerToClient::Error(format!(
+                                    "Failed to lock: {err}"
+                                ))),
+                            );
+                        } else if !is_owner {
+                            self.permissions.locks.insert(target, server_id);
+                            self.broadcast_permissions();
+                        }
+                    }
                     EditorEventClientToServer::DbgAction(props) => {
                         if self.admin_password.is_none()
                             && self.clients.values().any(|c| c.is_local_client)
@@ -897,9 +1306,7 @@ impl EditorServer {
                                     self.handle_client_ev(
                                         id,
                                         EditorEventClientToServer::Action(EditorActionGroup {
-                                            actions: vec![EditorAction::TileLayerReplaceTiles(
-                                                action,
-                                            )],
+                                            actions: vec![action],
                                             identifier: Some("auto-mapper".to_string()),
                                         }),
                                         tp,
@@ -1031,6 +1438,10 @@ impl EditorServer {
         notifications: &mut ClientNotifications,
         should_save: &mut bool,
     ) {
+        if let Some(journal) = &mut self.journal {
+            journal.update();
+        }
+
         let now = self.time.now();
         if self.needs_client_info_update
             && now.saturating_sub(self.last_client_infos) > Duration::from_millis(10)
@@ -1073,7 +1484,15 @@ impl EditorServer {
                                 self.broadcast_client_infos();
                             }
                             NetworkEvent::Disconnected { .. } => {
-                                self.clients.remove(&id);
+                                if let Some(client) = self.clients.remove(&id)
+                                    && client.is_authed
+                                {
+                                    let server_id = client.props.server_id;
+                                    self.permissions
+                                        .locks
+                                        .retain(|_, owner| *owner != server_id);
+                                    self.broadcast_permissions();
+                                }
 
                                 self.broadcast_client_infos();
                             }
@@ -1146,3 +1565,355 @@ impl EditorServer {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{BTreeMap, VecDeque},
+        sync::Arc,
+    };
+
+    use base::steady_clock::SteadyClock;
+    use base_http::http::HttpClient;
+    use base_io::io::Io;
+    use editor_interface::auto_mapper::AutoMapperPhysicsLayer;
+    use map::{
+        map::{
+            Map,
+            groups::layers::{design::MapLayer, physics::MapLayerPhysics, tiles::TileFlags},
+        },
+        tiles::DdraceTileNum,
+    };
+
+    use crate::{
+        actions::actions::{ActChangeGroupName, EditorAction, EditorActionGroup},
+        event::{
+            EditorEventAutoMap, EditorEventServerToClient, EditorGroupIndex, EditorLockTarget,
+        },
+        test_utils::{TestBackend, arctic, io},
+        tools::tile_layer::{
+            auto_mapper::{
+                TileLayerAutoMapperCheckGroup, TileLayerAutoMapperEditorRule,
+                TileLayerAutoMapperRuleType, TileLayerAutoMapperRun, TileLayerAutoMapperTile,
+                TileLayerAutoMapperTileExpr, TileLayerAutoMapperTileType, TileOffsetNonZero,
+            },
+            legacy_rules::{LegacyRule, LegacyRulesLoading},
+        },
+    };
+
+    use super::{EditorServer, MAX_HISTORY_EVENTS};
+
+    fn history(event_ids: std::ops::Range<u64>) -> VecDeque<(u64, EditorEventServerToClient)> {
+        event_ids
+            .map(|event_id| {
+                (
+                    event_id,
+                    EditorEventServerToClient::RedoAction {
+                        action: EditorActionGroup {
+                            actions: Vec::new(),
+                            identifier: None,
+                        },
+                        redo_label: None,
+                        undo_label: None,
+                        event_id,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn event_ids(events: Option<Vec<EditorEventServerToClient>>) -> Option<Vec<u64>> {
+        events.map(|events| {
+            events
+                .into_iter()
+                .map(|ev| match ev {
+                    EditorEventServerToClient::RedoAction { event_id, .. } => event_id,
+                    _ => panic!("unexpected event"),
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn missed_history_events() {
+        // the history is full & only contains the last events
+        let next_event_id = MAX_HISTORY_EVENTS as u64 + 10;
+        let history = history(10..next_event_id);
+        let missed = |client_next_event_id| {
+            event_ids(EditorServer::missed_history_events(
+                &history,
+                next_event_id,
+                client_next_event_id,
+            ))
+        };
+
+        assert_eq!(missed(next_event_id), Some(Vec::new()));
+        assert_eq!(
+            missed(next_event_id - 2),
+            Some(vec![next_event_id - 2, next_event_id - 1])
+        );
+        assert_eq!(missed(10), Some((10..next_event_id).collect()));
+        // more than MAX_HISTORY_EVENTS behind
+        assert_eq!(missed(9), None);
+        assert_eq!(missed(0), None);
+        // ahead of the server
+        assert_eq!(missed(next_event_id + 1), None);
+
+        assert_eq!(
+            event_ids(EditorServer::missed_history_events(&VecDeque::new(), 5, 3)),
+            None
+        );
+    }
+
+    const RULES: &str = "[Test]\nIndex 1\n\nIndex 2\nPos 0 -1 EMPTY\nRandom 50\n";
+
+    fn rule() -> TileLayerAutoMapperRuleType {
+        let rules = LegacyRulesLoading::new(RULES.as_bytes()).unwrap();
+        TileLayerAutoMapperRuleType::LegacyRules {
+            rule: LegacyRule {
+                config: rules.configs.into_values().next().unwrap(),
+            },
+            loading_data: Arc::new(RULES.as_bytes().to_vec()),
+        }
+    }
+
+    /// Makes every air tile of the game layer unhookable, if the tile above is not air.
+    ///
+    /// With `design_layer` the tile above is checked in the design layer.
+    fn physics_rule(design_layer: bool) -> TileLayerAutoMapperRuleType {
+        TileLayerAutoMapperRuleType::EditorRule(TileLayerAutoMapperEditorRule {
+            runs: vec![TileLayerAutoMapperRun {
+                tiles: vec![TileLayerAutoMapperTile {
+                    tile_index: DdraceTileNum::NoHook as u8,
+                    tile_flags: TileFlags::empty(),
+                    tile_number: None,
+                    tile_delay: None,
+                    tile_type: TileLayerAutoMapperTileType::SpawnOnly,
+                    randomness: None,
+                    check_groups: BTreeMap::from([(
+                        TileOffsetNonZero::new(0, -1).unwrap(),
+                        TileLayerAutoMapperCheckGroup {
+                            negate: true,
+                            tile: TileLayerAutoMapperTileExpr {
+                                tile_index: DdraceTileNum::Air as u8,
+                                tile_flags: None,
+                                tile_number: None,
+                            },
+                            design_layer,
+                            operation: None,
+                        },
+                    )]),
+                    grid_size: 0,
+                    check_tile_offset: 0,
+                }],
+                active_tile: None,
+            }],
+            physics_layers: vec![AutoMapperPhysicsLayer::Game],
+            active_run: 0,
+        })
+    }
+
+    /// The first design tile layer of the map.
+    fn design_tile_layer(map: &Map) -> (bool, usize, usize) {
+        [
+            (true, &map.groups.background),
+            (false, &map.groups.foreground),
+        ]
+        .into_iter()
+        .flat_map(|(is_background, groups)| {
+            groups
+                .iter()
+                .enumerate()
+                .flat_map(move |(group_index, group)| {
+                    group
+                        .layers
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, layer)| matches!(layer, MapLayer::Tile(_)))
+                        .map(move |(layer_index, _)| (is_background, group_index, layer_index))
+                })
+        })
+        .next()
+        .unwrap()
+    }
+
+    /// The headless auto mapper (e.g. the map-auto-mapper tool)
+    /// must produce the same tiles as the editor for the same rule & seed.
+    #[test]
+    fn auto_map_parity() {
+        let io = io();
+        let tp = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap(),
+        );
+        let backend = TestBackend::new(&io, &tp);
+
+        let map = arctic(&io, &tp);
+        let (is_background, group_index, layer_index) = design_tile_layer(&map);
+
+        for seed in [0, 7] {
+            let mut editor_map = backend.editor_map(&tp, map.clone());
+            let action = EditorServer::auto_map(
+                &mut rule(),
+                EditorEventAutoMap {
+                    is_background,
+                    group_index,
+                    layer_index,
+                    resource_and_hash: Default::default(),
+                    name: "test".to_string(),
+                    hash: Default::default(),
+                    seed,
+                    physics_layer_index: None,
+                },
+                &mut editor_map,
+            )
+            .unwrap();
+            backend.do_action(&tp, action, &mut editor_map).unwrap();
+            let editor_map: Map = editor_map.into();
+
+            let mut headless_map = map.clone();
+            rule()
+                .run_on_map_layer(
+                    &mut headless_map,
+                    seed,
+                    is_background,
+                    group_index,
+                    layer_index,
+                )
+                .unwrap();
+
+            assert_eq!(editor_map.as_json(), headless_map.as_json());
+        }
+    }
+
+    /// Same as [`auto_map_parity`] for physics layers, with & without design input.
+    #[test]
+    fn auto_map_physics_parity() {
+        let io = io();
+        let tp = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap(),
+        );
+        let backend = TestBackend::new(&io, &tp);
+
+        let map = arctic(&io, &tp);
+        let physics_layer_index = map
+            .groups
+            .physics
+            .layers
+            .iter()
+            .position(|layer| matches!(layer, MapLayerPhysics::Game(_)))
+            .unwrap();
+
+        for design_layer in [Some(design_tile_layer(&map)), None] {
+            // without design input, the selected layer does not need to exist
+            let (is_background, group_index, layer_index) =
+                design_layer.unwrap_or((false, usize::MAX, usize::MAX));
+            let mut editor_map = backend.editor_map(&tp, map.clone());
+            let action = EditorServer::auto_map(
+                &mut physics_rule(design_layer.is_some()),
+                EditorEventAutoMap {
+                    is_background,
+                    group_index,
+                    layer_index,
+                    resource_and_hash: Default::default(),
+                    name: "test".to_string(),
+                    hash: Default::default(),
+                    seed: 0,
+                    physics_layer_index: Some(physics_layer_index),
+                },
+                &editor_map,
+            )
+            .unwrap();
+            backend.do_action(&tp, action, &mut editor_map).unwrap();
+            let editor_map: Map = editor_map.into();
+
+            let mut headless_map = map.clone();
+            physics_rule(design_layer.is_some())
+                .run_on_map_physics_layer(&mut headless_map, 0, design_layer, physics_layer_index)
+                .unwrap();
+
+            assert_ne!(headless_map.as_json(), map.as_json());
+            assert_eq!(editor_map.as_json(), headless_map.as_json());
+        }
+    }
+
+    /// A group of actions is rejected as a whole,
+    /// if the client is not allowed to apply one of them.
+    #[test]
+    fn action_group_permissions() {
+        let io = io();
+        let tp = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap(),
+        );
+        let backend = TestBackend::new(&io, &tp);
+
+        let mut server = EditorServer::new(
+            &SteadyClock::start(),
+            None,
+            None,
+            String::new(),
+            None,
+            Io::from(io.clone(), Arc::new(HttpClient::new())),
+        )
+        .unwrap();
+        // group 1 is locked by client 0
+        server.permissions.locks.insert(
+            EditorLockTarget {
+                group: EditorGroupIndex::Design {
+                    is_background: true,
+                    group_index: 1,
+                },
+                layer_index: None,
+            },
+            0,
+        );
+
+        let map = arctic(&io, &tp);
+        let mut editor_map = backend.editor_map(&tp, map.clone());
+        let rename = |group_index: usize| {
+            EditorAction::ChangeGroupName(ActChangeGroupName {
+                is_background: true,
+                group_index,
+                old_name: map.groups.background[group_index].name.clone(),
+                new_name: "renamed".to_string(),
+            })
+        };
+        let mut do_client_action_group = |server_id| {
+            server.do_client_action_group(
+                server_id,
+                false,
+                EditorActionGroup {
+                    actions: vec![rename(0), rename(1)],
+                    identifier: None,
+                },
+                &tp,
+                &backend.sound.get_sound_mt(),
+                &backend.graphics.get_graphics_mt(),
+                &backend.graphics.shader_storage_handle,
+                &backend.graphics.buffer_object_handle,
+                &backend.graphics.backend_handle,
+                &backend.graphics.texture_handle,
+                &mut editor_map,
+            )
+        };
+
+        let (applied, err) = do_client_action_group(1);
+        assert!(applied.actions.is_empty());
+        assert!(err.is_some());
+
+        let (applied, err) = do_client_action_group(0);
+        assert_eq!(applied.actions.len(), 2);
+        assert!(err.is_none());
+
+        assert_eq!(editor_map.groups.background[0].name, "renamed");
+        assert_eq!(editor_map.groups.background[1].name, "renamed");
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use client_render_base::map::map::RenderMap;
 use sound::{sound_listener::SoundListener, sound_play_handle::SoundPlayHandle};
@@ -6,7 +6,11 @@ use sound::{sound_listener::SoundListener, sound_play_handle::SoundPlayHandle};
 use crate::{
     client::EditorClient,
     event::{ActionDbg, AdminChangeConfig},
+    journal::EditorJournalEntry,
     map::EditorMap,
+    map_import::EditorMapImport,
+    physics_numbers::{PhysicsNumberLayer, PhysicsNumbersOverview},
+    reachability::EditorReachability,
     server::EditorServer,
     tools::auto_saver::AutoSaver,
 };
@@ -64,6 +68,18 @@ pub struct AssetsStore {
     pub cur_play: Option<(String, SoundPlayHandle, SoundListener)>,
 }
 
+#[derive(Debug, Default)]
+pub struct PhysicsNumbersPanel {
+    pub open: bool,
+
+    /// The usage the camera jumps to next, per number.
+    pub next_usage: HashMap<(PhysicsNumberLayer, u8), usize>,
+    /// The number that is currently renumbered & its new number.
+    pub renumber: Option<(PhysicsNumberLayer, u8, u8)>,
+    /// The overview & the map generation it was created for.
+    pub overview: Option<(u64, Arc<PhysicsNumbersOverview>)>,
+}
+
 /// a tab, representing a map that is currently edited
 pub struct EditorTab {
     pub map: EditorMap,
@@ -81,4 +97,15 @@ pub struct EditorTab {
 
     pub assets_store_open: bool,
     pub assets_store: AssetsStore,
+
+    pub history_open: bool,
+    pub physics_numbers: PhysicsNumbersPanel,
+    /// Journal entries of a previous session that
+    /// the user did not yet replay or discard.
+    pub journal_recovery: Option<Vec<EditorJournalEntry>>,
+
+    /// Another map the user imports groups, layers or resources from.
+    pub map_import: Option<EditorMapImport>,
+
+    pub reachability: EditorReachability,
 }
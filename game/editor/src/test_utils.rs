@@ -0,0 +1,162 @@
+//! Helpers for tests that need an editor map, which requires
+//! (headless) graphics & sound.
+
+use std::{collections::HashMap, sync::Arc};
+
+use base::hash::Hash;
+use base_fs::filesys::FileSystem;
+use base_io::io::IoFileSys;
+use graphics::graphics::graphics::Graphics;
+use graphics_backend::{
+    backend::{
+        GraphicsBackend, GraphicsBackendBase, GraphicsBackendIoLoading, GraphicsBackendLoading,
+    },
+    window::{BackendRawDisplayHandle, BackendWindow},
+};
+use graphics_types::types::WindowProps;
+use map::{
+    file::MapFileReader,
+    map::{Map, groups::layers::design::MapLayer, resources::Resources},
+};
+use sound::sound::SoundManager;
+use sound_backend::sound_backend::SoundBackend;
+
+use crate::{
+    action_logic::do_action, actions::actions::EditorAction, editor::Editor, map::EditorMap,
+};
+
+pub fn io() -> IoFileSys {
+    let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../");
+    std::env::set_current_dir(workspace_root).unwrap();
+    IoFileSys::new(|rt| {
+        Arc::new(
+            FileSystem::new(rt, "ddnet-test", "ddnet-test", "ddnet-test", "ddnet-test").unwrap(),
+        )
+    })
+}
+
+/// The arctic map without its resources, since the tests do not load resource files.
+pub fn arctic(io: &IoFileSys, tp: &rayon::ThreadPool) -> Map {
+    let fs = io.fs.clone();
+    let file = io
+        .rt
+        .spawn(async move { fs.read_file("map/maps/arctic.twmap.tar".as_ref()).await })
+        .get()
+        .unwrap();
+    let mut map = Map::read(&MapFileReader::new(file).unwrap(), tp).unwrap();
+    map.resources = Resources {
+        images: Vec::new(),
+        image_arrays: Vec::new(),
+        sounds: Vec::new(),
+    };
+    let groups = map
+        .groups
+        .background
+        .iter_mut()
+        .chain(map.groups.foreground.iter_mut());
+    for layer in groups.flat_map(|group| group.layers.iter_mut()) {
+        match layer {
+            MapLayer::Abritrary(_) => {}
+            MapLayer::Tile(layer) => layer.attr.image_array = None,
+            MapLayer::Quad(layer) => layer.attr.image = None,
+            MapLayer::Sound(layer) => layer.attr.sound = None,
+        }
+    }
+    map
+}
+
+/// Null graphics & sound backends.
+pub struct TestBackend {
+    pub graphics: Graphics,
+    pub sound: SoundManager,
+}
+
+impl TestBackend {
+    pub fn new(io: &IoFileSys, tp: &Arc<rayon::ThreadPool>) -> Self {
+        let config_gfx = config::config::ConfigGfx {
+            backend: "null".into(),
+        };
+        let (backend_base, stream_data) = GraphicsBackendBase::new(
+            GraphicsBackendIoLoading::new(&config_gfx, io),
+            GraphicsBackendLoading::new(
+                &config_gfx,
+                &Default::default(),
+                &Default::default(),
+                BackendRawDisplayHandle::Headless,
+                None,
+                io.clone(),
+            )
+            .unwrap(),
+            tp,
+            BackendWindow::Headless {
+                width: 64,
+                height: 64,
+            },
+        )
+        .unwrap();
+        let graphics = Graphics::new(
+            GraphicsBackend::new(backend_base),
+            stream_data,
+            WindowProps {
+                canvas_width: 64,
+                canvas_height: 64,
+                window_width: 64.0,
+                window_height: 64.0,
+            },
+        );
+        let sound = SoundManager::new(
+            SoundBackend::new(&config::config::ConfigSound {
+                backend: "None".to_string(),
+                limits: Default::default(),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        Self { graphics, sound }
+    }
+
+    pub fn editor_map(&self, tp: &Arc<rayon::ThreadPool>, map: Map) -> EditorMap {
+        self.editor_map_with_resources(tp, map, Default::default())
+    }
+
+    /// `resources` contains the files (by hash) of all resources of the map.
+    pub fn editor_map_with_resources(
+        &self,
+        tp: &Arc<rayon::ThreadPool>,
+        map: Map,
+        resources: HashMap<Hash, Vec<u8>>,
+    ) -> EditorMap {
+        Editor::map_to_editor_map_impl(
+            self.graphics.get_graphics_mt(),
+            self.sound.get_sound_mt(),
+            tp,
+            &self.sound.scene_handle,
+            &self.graphics.backend_handle,
+            &self.graphics.shader_storage_handle,
+            &self.graphics.buffer_object_handle,
+            &self.graphics.texture_handle,
+            map,
+            resources,
+        )
+    }
+
+    pub fn do_action(
+        &self,
+        tp: &Arc<rayon::ThreadPool>,
+        action: EditorAction,
+        map: &mut EditorMap,
+    ) -> anyhow::Result<EditorAction> {
+        do_action(
+            tp,
+            &self.sound.get_sound_mt(),
+            &self.graphics.get_graphics_mt(),
+            &self.graphics.shader_storage_handle,
+            &self.graphics.buffer_object_handle,
+            &self.graphics.backend_handle,
+            &self.graphics.texture_handle,
+            action,
+            map,
+            false,
+        )
+    }
+}
@@ -12,6 +12,7 @@ use base_io::{io::IoFileSys, runtime::IoRuntimeTask};
 use editor_auto_mapper_wasm::manager::{AutoMapperWasmManager, WasmModule};
 use editor_interface::auto_mapper::{
     AutoMapperInputModes, AutoMapperInterface, AutoMapperModes, AutoMapperOutputModes,
+    AutoMapperPhysicsLayer,
 };
 use egui::{Rect, vec2};
 use egui_file_dialog::FileDialog;
@@ -26,7 +27,17 @@ use graphics_types::{
 };
 use image_utils::utils::texture_2d_to_3d;
 use map::{
-    map::groups::layers::tiles::{MapTileLayerAttr, Tile, TileFlags},
+    map::{
+        Map,
+        groups::{
+            MapGroupPhysicsAttr,
+            layers::{
+                design::MapLayer,
+                physics::MapLayerPhysics,
+                tiles::{MapTileLayerAttr, MapTileLayerPhysicsTiles, Tile, TileBase, TileFlags},
+            },
+        },
+    },
     types::NonZeroU16MinusOne,
 };
 use math::math::{Rng, vector::ivec2};
@@ -34,7 +45,11 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    actions::actions::{ActTileLayerReplTilesBase, ActTileLayerReplaceTiles},
+    action_logic::check_and_copy_tiles,
+    actions::actions::{
+        ActTileLayerReplTilesBase, ActTileLayerReplaceTiles, ActTilePhysicsLayerReplTilesBase,
+        ActTilePhysicsLayerReplaceTiles,
+    },
     fs::read_file_editor,
     notifications::{EditorNotification, EditorNotifications},
 };
@@ -65,6 +80,10 @@ pub struct TileLayerAutoMapperTileExpr {
     pub tile_index: u8,
     /// tile flag
     pub tile_flags: Option<TileFlags>,
+    /// The tele, switch or tune number of the tile.
+    /// Only used for physics layers.
+    #[serde(default)]
+    pub tile_number: Option<u8>,
 }
 
 /// Never zero in both components
@@ -140,6 +159,12 @@ pub struct TileLayerAutoMapperCheckGroup {
     /// The tile expression for the automapper.
     pub tile: TileLayerAutoMapperTileExpr,
 
+    /// Check the tile of the design layer that was given as input,
+    /// instead of the tile of the layer that is auto mapped.
+    /// Only used for physics layers.
+    #[serde(default)]
+    pub design_layer: bool,
+
     /// Optional expression evaluated by the given boolean operator.
     pub operation: Option<(
         TileLayerAutoMapperOperator,
@@ -151,6 +176,14 @@ pub struct TileLayerAutoMapperCheckGroup {
 pub struct TileLayerAutoMapperTile {
     pub tile_index: u8,
     pub tile_flags: TileFlags,
+    /// If `Some`, sets the tele, switch or tune number of the tile.
+    /// Only used for physics layers.
+    #[serde(default)]
+    pub tile_number: Option<u8>,
+    /// If `Some`, sets the delay of a switch tile.
+    /// Only used for physics layers.
+    #[serde(default)]
+    pub tile_delay: Option<u8>,
 
     pub tile_type: TileLayerAutoMapperTileType,
     /// how often should this tile appear
@@ -190,6 +223,112 @@ pub trait EditorAutoMapperInterface {
         group_index: usize,
         layer_index: usize,
     ) -> anyhow::Result<ActTileLayerReplaceTiles>;
+
+    /// Runs the auto mapper on a physics layer.
+    ///
+    /// `design_layer` is only used if the auto mapper
+    /// requested a design layer as input.
+    fn run_physics_layer(
+        &mut self,
+        seed: u64,
+        attr: MapGroupPhysicsAttr,
+        all_tiles: MapTileLayerPhysicsTiles,
+        design_layer: Option<(MapTileLayerAttr, &[Tile])>,
+        sub_x: u16,
+        sub_y: u16,
+        sub_w: NonZeroU16MinusOne,
+        sub_h: NonZeroU16MinusOne,
+        layer_index: usize,
+    ) -> anyhow::Result<ActTilePhysicsLayerReplaceTiles>;
+}
+
+/// Expands the sub rect by the neighbouring tiles of the auto mapper.
+///
+/// If the auto mapper has no neighbouring tiles, the whole layer is used.
+fn auto_mapper_sub_rect(
+    neighbouring_tiles: Option<NonZeroU16>,
+    full_width: NonZeroU16MinusOne,
+    full_height: NonZeroU16MinusOne,
+    sub_x: u16,
+    sub_y: u16,
+    sub_w: NonZeroU16MinusOne,
+    sub_h: NonZeroU16MinusOne,
+) -> (u16, u16, NonZeroU16MinusOne, NonZeroU16MinusOne) {
+    match neighbouring_tiles {
+        Some(expand_size) => {
+            let sub_x = sub_x.saturating_sub(expand_size.get());
+            let sub_y = sub_y.saturating_sub(expand_size.get());
+
+            let width = NonZeroU16MinusOne::new(
+                (sub_w.get() as u32 + expand_size.get() as u32 * 2)
+                    .clamp(1, full_width.get().saturating_sub(sub_x) as u32) as u16,
+            )
+            .unwrap();
+            let height = NonZeroU16MinusOne::new(
+                (sub_h.get() as u32 + expand_size.get() as u32 * 2)
+                    .clamp(1, full_height.get().saturating_sub(sub_y) as u32)
+                    as u16,
+            )
+            .unwrap();
+            (sub_x, sub_y, width, height)
+        }
+        None => (0, 0, full_width, full_height),
+    }
+}
+
+/// Copies the tiles of a sub rect out of a layer,
+/// tiles outside of the layer are air.
+fn auto_mapper_sub_tiles<T: Copy + Default>(
+    all_tiles: &[T],
+    full_width: NonZeroU16MinusOne,
+    full_height: NonZeroU16MinusOne,
+    sub_x: u16,
+    sub_y: u16,
+    width: NonZeroU16MinusOne,
+    height: NonZeroU16MinusOne,
+) -> Vec<T> {
+    let mut res_tiles = vec![T::default(); width.get() as usize * height.get() as usize];
+    res_tiles
+        .chunks_mut(width.get() as usize)
+        .enumerate()
+        .filter(|(index, _)| index + (sub_y as usize) < full_height.get() as usize)
+        .for_each(|(index, chunk)| {
+            let copy_tiles_y_offset = (index + sub_y as usize) * full_width.get() as usize;
+            let copy_width = (width.get() as usize)
+                .min((full_width.get() as usize).saturating_sub(sub_x as usize));
+            chunk[..copy_width].copy_from_slice(
+                &all_tiles[copy_tiles_y_offset + sub_x as usize
+                    ..copy_tiles_y_offset + sub_x as usize + copy_width],
+            );
+        });
+    res_tiles
+}
+
+fn auto_mapper_sub_physics_tiles(
+    all_tiles: &MapTileLayerPhysicsTiles,
+    full_width: NonZeroU16MinusOne,
+    full_height: NonZeroU16MinusOne,
+    sub_x: u16,
+    sub_y: u16,
+    width: NonZeroU16MinusOne,
+    height: NonZeroU16MinusOne,
+) -> anyhow::Result<MapTileLayerPhysicsTiles> {
+    macro_rules! sub {
+        ($tiles:ident) => {
+            auto_mapper_sub_tiles($tiles, full_width, full_height, sub_x, sub_y, width, height)
+        };
+    }
+    Ok(match all_tiles {
+        MapTileLayerPhysicsTiles::Arbitrary(_) => {
+            anyhow::bail!("arbitrary physics layers are not supported by auto mappers.")
+        }
+        MapTileLayerPhysicsTiles::Game(tiles) => MapTileLayerPhysicsTiles::Game(sub!(tiles)),
+        MapTileLayerPhysicsTiles::Front(tiles) => MapTileLayerPhysicsTiles::Front(sub!(tiles)),
+        MapTileLayerPhysicsTiles::Tele(tiles) => MapTileLayerPhysicsTiles::Tele(sub!(tiles)),
+        MapTileLayerPhysicsTiles::Speedup(tiles) => MapTileLayerPhysicsTiles::Speedup(sub!(tiles)),
+        MapTileLayerPhysicsTiles::Switch(tiles) => MapTileLayerPhysicsTiles::Switch(sub!(tiles)),
+        MapTileLayerPhysicsTiles::Tune(tiles) => MapTileLayerPhysicsTiles::Tune(sub!(tiles)),
+    })
 }
 
 impl<T: AutoMapperInterface> EditorAutoMapperInterface for T {
@@ -198,67 +337,44 @@ impl<T: AutoMapperInterface> EditorAutoMapperInterface for T {
         seed: u64,
         attr: MapTileLayerAttr,
         all_tiles: Vec<Tile>,
-        mut sub_x: u16,
-        mut sub_y: u16,
+        sub_x: u16,
+        sub_y: u16,
         sub_w: NonZeroU16MinusOne,
         sub_h: NonZeroU16MinusOne,
         is_background: bool,
         group_index: usize,
         layer_index: usize,
     ) -> anyhow::Result<ActTileLayerReplaceTiles> {
-        let mut width = attr.width;
-        let mut height = attr.height;
-
         let neighbouring_tiles = self
             .supported_modes()
             .into_iter()
-            .map(|mode| {
-                let AutoMapperModes::DesignTileLayer { neighbouring_tiles } = mode;
-
-                neighbouring_tiles
+            .find_map(|mode| match mode {
+                AutoMapperModes::DesignTileLayer { neighbouring_tiles } => Some(neighbouring_tiles),
+                AutoMapperModes::PhysicsTileLayer { .. } => None,
             })
-            .next()
             .ok_or_else(|| anyhow!("Design tile layer auto mapper not available on this rule."))?;
 
-        let deleted_tiles = match neighbouring_tiles {
-            Some(expand_size) => {
-                sub_x = sub_x.saturating_sub(expand_size.get());
-                sub_y = sub_y.saturating_sub(expand_size.get());
-
-                width = NonZeroU16MinusOne::new(
-                    (sub_w.get() as u32 + expand_size.get() as u32 * 2)
-                        .clamp(1, attr.width.get().saturating_sub(sub_x) as u32)
-                        as u16,
-                )
-                .unwrap();
-                height = NonZeroU16MinusOne::new(
-                    (sub_h.get() as u32 + expand_size.get() as u32 * 2)
-                        .clamp(1, attr.height.get().saturating_sub(sub_y) as u32)
-                        as u16,
-                )
-                .unwrap();
-
-                let mut res_tiles =
-                    vec![Default::default(); width.get() as usize * height.get() as usize];
-                res_tiles
-                    .chunks_mut(width.get() as usize)
-                    .enumerate()
-                    .for_each(|(index, chunk)| {
-                        let copy_tiles_y_offset =
-                            (index + sub_y as usize) * attr.width.get() as usize;
-                        chunk.copy_from_slice(
-                            &all_tiles[copy_tiles_y_offset + sub_x as usize
-                                ..copy_tiles_y_offset + sub_x as usize + width.get() as usize],
-                        );
-                    });
-
-                res_tiles
-            }
-            None => {
-                sub_x = 0;
-                sub_y = 0;
-                all_tiles
-            }
+        let (sub_x, sub_y, width, height) = auto_mapper_sub_rect(
+            neighbouring_tiles,
+            attr.width,
+            attr.height,
+            sub_x,
+            sub_y,
+            sub_w,
+            sub_h,
+        );
+        let deleted_tiles = if neighbouring_tiles.is_some() {
+            auto_mapper_sub_tiles(
+                &all_tiles,
+                attr.width,
+                attr.height,
+                sub_x,
+                sub_y,
+                width,
+                height,
+            )
+        } else {
+            all_tiles
         };
 
         let Ok(AutoMapperOutputModes::DesignTileLayer { tiles }) = self.run(
@@ -295,12 +411,119 @@ impl<T: AutoMapperInterface> EditorAutoMapperInterface for T {
             },
         })
     }
+
+    fn run_physics_layer(
+        &mut self,
+        seed: u64,
+        attr: MapGroupPhysicsAttr,
+        all_tiles: MapTileLayerPhysicsTiles,
+        design_layer: Option<(MapTileLayerAttr, &[Tile])>,
+        sub_x: u16,
+        sub_y: u16,
+        sub_w: NonZeroU16MinusOne,
+        sub_h: NonZeroU16MinusOne,
+        layer_index: usize,
+    ) -> anyhow::Result<ActTilePhysicsLayerReplaceTiles> {
+        let layer = AutoMapperPhysicsLayer::of(&all_tiles).ok_or_else(|| {
+            anyhow!("arbitrary physics layers are not supported by auto mappers.")
+        })?;
+        let (neighbouring_tiles, design_layer_input) = self
+            .supported_modes()
+            .into_iter()
+            .find_map(|mode| match mode {
+                AutoMapperModes::PhysicsTileLayer {
+                    layer: mode_layer,
+                    neighbouring_tiles,
+                    design_layer_input,
+                } if mode_layer == layer => Some((neighbouring_tiles, design_layer_input)),
+                _ => None,
+            })
+            .ok_or_else(|| anyhow!("{layer:?} layer auto mapper not available on this rule."))?;
+
+        let (sub_x, sub_y, width, height) = auto_mapper_sub_rect(
+            neighbouring_tiles,
+            attr.width,
+            attr.height,
+            sub_x,
+            sub_y,
+            sub_w,
+            sub_h,
+        );
+        let deleted_tiles = if neighbouring_tiles.is_some() {
+            auto_mapper_sub_physics_tiles(
+                &all_tiles,
+                attr.width,
+                attr.height,
+                sub_x,
+                sub_y,
+                width,
+                height,
+            )?
+        } else {
+            all_tiles
+        };
+        let design_tiles = if design_layer_input {
+            let (design_attr, design_tiles) = design_layer
+                .ok_or_else(|| anyhow!("This auto mapper needs a design tile layer as input."))?;
+            Some(auto_mapper_sub_tiles(
+                design_tiles,
+                design_attr.width,
+                design_attr.height,
+                sub_x,
+                sub_y,
+                width,
+                height,
+            ))
+        } else {
+            None
+        };
+
+        let Ok(AutoMapperOutputModes::PhysicsTileLayer { tiles }) = self.run(
+            seed,
+            AutoMapperInputModes::PhysicsTileLayer {
+                tiles: deleted_tiles.clone(),
+                design_tiles,
+                width,
+                height,
+                off_x: sub_x,
+                off_y: sub_y,
+                full_width: attr.width,
+                full_height: attr.height,
+            },
+        ) else {
+            anyhow::bail!("wanted physics tile layer auto mapper, got different output instead.");
+        };
+
+        if AutoMapperPhysicsLayer::of(&tiles) != Some(layer) {
+            anyhow::bail!("auto mapper changed the type of the physics layer.");
+        }
+        if tiles.tiles_count() != deleted_tiles.tiles_count() {
+            anyhow::bail!("auto mapper changed number of tiles.");
+        }
+
+        Ok(ActTilePhysicsLayerReplaceTiles {
+            base: ActTilePhysicsLayerReplTilesBase {
+                layer_index,
+                old_tiles: deleted_tiles,
+                new_tiles: tiles,
+                x: sub_x,
+                y: sub_y,
+                w: width,
+                h: height,
+            },
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TileLayerAutoMapperEditorRule {
     pub runs: Vec<TileLayerAutoMapperRun>,
 
+    /// If not empty, the rule runs on these physics layers
+    /// instead of design tile layers.
+    #[serde(default)]
+    pub physics_layers: Vec<AutoMapperPhysicsLayer>,
+
     #[serde(skip)]
     pub active_run: usize,
 }
@@ -313,112 +536,210 @@ impl Default for TileLayerAutoMapperEditorRule {
 
                 active_tile: Default::default(),
             }],
+            physics_layers: Default::default(),
             active_run: 0,
         }
     }
 }
 
-impl AutoMapperInterface for TileLayerAutoMapperEditorRule {
-    fn supported_modes(&self) -> Vec<AutoMapperModes> {
-        let mut grid_size = 0;
-        self.runs.iter().for_each(|run| {
-            run.tiles.iter().for_each(|tile| {
-                let min = tile
-                    .check_groups
-                    .keys()
-                    .map(|g| {
-                        let g = g.get();
-                        g.x.min(g.y)
-                    })
-                    .min()
-                    .unwrap_or(0);
-                let max = tile
-                    .check_groups
-                    .keys()
-                    .map(|g| {
-                        let g = g.get();
-                        g.x.max(g.y)
-                    })
-                    .max()
-                    .unwrap_or(0)
-                    .max(min.abs());
-                grid_size = grid_size.max(max as usize + 1).max(3);
-            });
-        });
-        vec![AutoMapperModes::DesignTileLayer {
-            neighbouring_tiles: NonZeroU16::new(grid_size as u16),
-        }]
+/// The tile data the rules work on,
+/// design tiles simply have no number or delay.
+#[derive(Debug, Clone, Copy, Default)]
+struct RuleTile {
+    base: TileBase,
+    /// tele, switch or tune number
+    number: u8,
+    /// switch delay
+    delay: u8,
+}
+
+impl RuleTile {
+    fn from_physics(tiles: &MapTileLayerPhysicsTiles) -> Vec<Self> {
+        match tiles {
+            MapTileLayerPhysicsTiles::Arbitrary(_) => Vec::new(),
+            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => tiles
+                .iter()
+                .map(|&base| Self {
+                    base,
+                    ..Default::default()
+                })
+                .collect(),
+            MapTileLayerPhysicsTiles::Tele(tiles) => tiles
+                .iter()
+                .map(|tile| Self {
+                    base: tile.base,
+                    number: tile.number,
+                    ..Default::default()
+                })
+                .collect(),
+            MapTileLayerPhysicsTiles::Speedup(tiles) => tiles
+                .iter()
+                .map(|tile| Self {
+                    base: tile.base,
+                    ..Default::default()
+                })
+                .collect(),
+            MapTileLayerPhysicsTiles::Switch(tiles) => tiles
+                .iter()
+                .map(|tile| Self {
+                    base: tile.base,
+                    number: tile.number,
+                    delay: tile.delay,
+                })
+                .collect(),
+            MapTileLayerPhysicsTiles::Tune(tiles) => tiles
+                .iter()
+                .map(|tile| Self {
+                    base: tile.base,
+                    number: tile.number,
+                    ..Default::default()
+                })
+                .collect(),
+        }
     }
 
-    fn run(
-        &mut self,
-        seed: u64,
-        input: AutoMapperInputModes,
-    ) -> Result<AutoMapperOutputModes, String> {
-        let AutoMapperInputModes::DesignTileLayer {
-            mut tiles,
-            width,
-            height,
-            off_x,
-            off_y,
-            ..
-        } = input;
+    /// Writes the rule tiles back, keeping the data the rules don't know about
+    /// (e.g. speedup force).
+    fn to_physics(rule_tiles: &[Self], tiles: &mut MapTileLayerPhysicsTiles) {
+        match tiles {
+            MapTileLayerPhysicsTiles::Arbitrary(_) => {}
+            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
+                tiles
+                    .iter_mut()
+                    .zip(rule_tiles)
+                    .for_each(|(tile, rule_tile)| *tile = rule_tile.base);
+            }
+            MapTileLayerPhysicsTiles::Tele(tiles) => {
+                tiles
+                    .iter_mut()
+                    .zip(rule_tiles)
+                    .for_each(|(tile, rule_tile)| {
+                        tile.base = rule_tile.base;
+                        tile.number = rule_tile.number;
+                    });
+            }
+            MapTileLayerPhysicsTiles::Speedup(tiles) => {
+                tiles
+                    .iter_mut()
+                    .zip(rule_tiles)
+                    .for_each(|(tile, rule_tile)| tile.base = rule_tile.base);
+            }
+            MapTileLayerPhysicsTiles::Switch(tiles) => {
+                tiles
+                    .iter_mut()
+                    .zip(rule_tiles)
+                    .for_each(|(tile, rule_tile)| {
+                        tile.base = rule_tile.base;
+                        tile.number = rule_tile.number;
+                        tile.delay = rule_tile.delay;
+                    });
+            }
+            MapTileLayerPhysicsTiles::Tune(tiles) => {
+                tiles
+                    .iter_mut()
+                    .zip(rule_tiles)
+                    .for_each(|(tile, rule_tile)| {
+                        tile.base = rule_tile.base;
+                        tile.number = rule_tile.number;
+                    });
+            }
+        }
+    }
+}
+
+fn eval_expression(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    tiles: &[RuleTile],
+    design_tiles: Option<&[Tile]>,
+    group_grid: &TileOffsetNonZero,
+    expr: &TileLayerAutoMapperCheckGroup,
+) -> bool {
+    let grid_offset = group_grid.get();
+    let real_x = x as i32 + grid_offset.x;
+    let real_y = y as i32 + grid_offset.y;
+    let result = if real_x >= 0 && real_y >= 0 && real_x < width as i32 && real_y < height as i32 {
+        let index = real_y as usize * width + real_x as usize;
+        let new_tile = if expr.design_layer {
+            design_tiles.map(|tiles| RuleTile {
+                base: tiles[index],
+                ..Default::default()
+            })
+        } else {
+            Some(tiles[index])
+        };
+        new_tile.is_some_and(|new_tile| {
+            expr.tile.tile_index == new_tile.base.index
+                && expr
+                    .tile
+                    .tile_flags
+                    .is_none_or(|flags| flags == new_tile.base.flags)
+                && expr
+                    .tile
+                    .tile_number
+                    .is_none_or(|number| number == new_tile.number)
+        })
+    } else {
+        false
+    };
+
+    let result = if expr.negate { !result } else { result };
+
+    if let Some((op, tile)) = &expr.operation {
+        let right_result =
+            eval_expression(x, y, width, height, tiles, design_tiles, group_grid, tile);
+
+        match op {
+            TileLayerAutoMapperOperator::Or => result || right_result,
+            TileLayerAutoMapperOperator::And => result && right_result,
+        }
+    } else {
+        result
+    }
+}
+
+impl TileLayerAutoMapperEditorRule {
+    fn uses_design_layer(&self) -> bool {
+        fn uses_design_layer(group: &TileLayerAutoMapperCheckGroup) -> bool {
+            group.design_layer
+                || group
+                    .operation
+                    .as_ref()
+                    .is_some_and(|(_, group)| uses_design_layer(group))
+        }
+        self.runs.iter().any(|run| {
+            run.tiles
+                .iter()
+                .any(|tile| tile.check_groups.values().any(uses_design_layer))
+        })
+    }
 
+    fn run_tiles(
+        &self,
+        seed: u64,
+        tiles: &mut [RuleTile],
+        design_tiles: Option<&[Tile]>,
+        width: NonZeroU16MinusOne,
+        height: NonZeroU16MinusOne,
+        off_x: u16,
+        off_y: u16,
+    ) {
         for run in &self.runs {
             for y in 0..height.get() as usize {
                 for x in 0..width.get() as usize {
                     for run_tile in &run.tiles {
                         let check_groups = &run_tile.check_groups;
 
-                        fn eval_expression(
-                            x: usize,
-                            y: usize,
-                            width: usize,
-                            height: usize,
-                            tiles: &[Tile],
-                            group_grid: &TileOffsetNonZero,
-                            expr: &TileLayerAutoMapperCheckGroup,
-                        ) -> bool {
-                            let grid_offset = group_grid.get();
-                            let real_x = x as i32 + grid_offset.x;
-                            let real_y = y as i32 + grid_offset.y;
-                            let result = if real_x >= 0
-                                && real_y >= 0
-                                && real_x < width as i32
-                                && real_y < height as i32
-                            {
-                                let new_tile = &tiles[real_y as usize * width + real_x as usize];
-                                expr.tile.tile_index == new_tile.index
-                                    && expr
-                                        .tile
-                                        .tile_flags
-                                        .is_none_or(|flags| flags == new_tile.flags)
-                            } else {
-                                false
-                            };
-
-                            let result = if expr.negate { !result } else { result };
-
-                            if let Some((op, tile)) = &expr.operation {
-                                let right_result =
-                                    eval_expression(x, y, width, height, tiles, group_grid, tile);
-
-                                match op {
-                                    TileLayerAutoMapperOperator::Or => result || right_result,
-                                    TileLayerAutoMapperOperator::And => result && right_result,
-                                }
-                            } else {
-                                result
-                            }
-                        }
-
                         let result = check_groups.iter().all(|(offset, group)| {
                             eval_expression(
                                 x,
                                 y,
                                 width.get() as usize,
                                 height.get() as usize,
-                                &tiles,
+                                tiles,
+                                design_tiles,
                                 offset,
                                 group,
                             )
@@ -432,8 +753,8 @@ impl AutoMapperInterface for TileLayerAutoMapperEditorRule {
                         let new_tile = &mut tiles[y * width.get() as usize + x];
                         if result
                             && (can_spawn
-                                || (must_spawn && new_tile.index == 0)
-                                || (!must_spawn && new_tile.index != 0))
+                                || (must_spawn && new_tile.base.index == 0)
+                                || (!must_spawn && new_tile.base.index != 0))
                         {
                             let mut hasher = rustc_hash::FxHasher::default();
                             hasher.write_u64(seed);
@@ -447,16 +768,118 @@ impl AutoMapperInterface for TileLayerAutoMapperEditorRule {
                                     .randomness
                                     .is_some_and(|val| rand_val <= val.get() as u64)
                             {
-                                new_tile.index = run_tile.tile_index;
-                                new_tile.flags = run_tile.tile_flags;
+                                new_tile.base.index = run_tile.tile_index;
+                                new_tile.base.flags = run_tile.tile_flags;
+                                if let Some(number) = run_tile.tile_number {
+                                    new_tile.number = number;
+                                }
+                                if let Some(delay) = run_tile.tile_delay {
+                                    new_tile.delay = delay;
+                                }
                             }
                         }
                     }
                 }
             }
         }
+    }
+}
+
+impl AutoMapperInterface for TileLayerAutoMapperEditorRule {
+    fn supported_modes(&self) -> Vec<AutoMapperModes> {
+        let mut grid_size = 0;
+        self.runs.iter().for_each(|run| {
+            run.tiles.iter().for_each(|tile| {
+                let min = tile
+                    .check_groups
+                    .keys()
+                    .map(|g| {
+                        let g = g.get();
+                        g.x.min(g.y)
+                    })
+                    .min()
+                    .unwrap_or(0);
+                let max = tile
+                    .check_groups
+                    .keys()
+                    .map(|g| {
+                        let g = g.get();
+                        g.x.max(g.y)
+                    })
+                    .max()
+                    .unwrap_or(0)
+                    .max(min.abs());
+                grid_size = grid_size.max(max as usize + 1).max(3);
+            });
+        });
+        if self.physics_layers.is_empty() {
+            vec![AutoMapperModes::DesignTileLayer {
+                neighbouring_tiles: NonZeroU16::new(grid_size as u16),
+            }]
+        } else {
+            let design_layer_input = self.uses_design_layer();
+            self.physics_layers
+                .iter()
+                .map(|&layer| AutoMapperModes::PhysicsTileLayer {
+                    layer,
+                    neighbouring_tiles: NonZeroU16::new(grid_size as u16),
+                    design_layer_input,
+                })
+                .collect()
+        }
+    }
+
+    fn run(
+        &mut self,
+        seed: u64,
+        input: AutoMapperInputModes,
+    ) -> Result<AutoMapperOutputModes, String> {
+        match input {
+            AutoMapperInputModes::DesignTileLayer {
+                tiles,
+                width,
+                height,
+                off_x,
+                off_y,
+                ..
+            } => {
+                let mut rule_tiles: Vec<_> = tiles
+                    .into_iter()
+                    .map(|base| RuleTile {
+                        base,
+                        ..Default::default()
+                    })
+                    .collect();
+                self.run_tiles(seed, &mut rule_tiles, None, width, height, off_x, off_y);
 
-        Ok(AutoMapperOutputModes::DesignTileLayer { tiles })
+                Ok(AutoMapperOutputModes::DesignTileLayer {
+                    tiles: rule_tiles.into_iter().map(|tile| tile.base).collect(),
+                })
+            }
+            AutoMapperInputModes::PhysicsTileLayer {
+                mut tiles,
+                design_tiles,
+                width,
+                height,
+                off_x,
+                off_y,
+                ..
+            } => {
+                let mut rule_tiles = RuleTile::from_physics(&tiles);
+                self.run_tiles(
+                    seed,
+                    &mut rule_tiles,
+                    design_tiles.as_deref(),
+                    width,
+                    height,
+                    off_x,
+                    off_y,
+                );
+                RuleTile::to_physics(&rule_tiles, &mut tiles);
+
+                Ok(AutoMapperOutputModes::PhysicsTileLayer { tiles })
+            }
+        }
     }
 }
 
@@ -521,6 +944,36 @@ impl EditorAutoMapperInterface for TileLayerAutoMapperRuleType {
             layer_index,
         )
     }
+
+    fn run_physics_layer(
+        &mut self,
+        seed: u64,
+        attr: MapGroupPhysicsAttr,
+        all_tiles: MapTileLayerPhysicsTiles,
+        design_layer: Option<(MapTileLayerAttr, &[Tile])>,
+        sub_x: u16,
+        sub_y: u16,
+        sub_w: NonZeroU16MinusOne,
+        sub_h: NonZeroU16MinusOne,
+        layer_index: usize,
+    ) -> anyhow::Result<ActTilePhysicsLayerReplaceTiles> {
+        let rule: &mut dyn EditorAutoMapperInterface = match self {
+            Self::EditorRule(rule) => rule,
+            Self::Wasm(rule) => &mut rule.manager,
+            Self::LegacyRules { rule, .. } => rule,
+        };
+        rule.run_physics_layer(
+            seed,
+            attr,
+            all_tiles,
+            design_layer,
+            sub_x,
+            sub_y,
+            sub_w,
+            sub_h,
+            layer_index,
+        )
+    }
 }
 
 impl TileLayerAutoMapperRuleType {
@@ -531,6 +984,240 @@ impl TileLayerAutoMapperRuleType {
             Self::LegacyRules { loading_data, .. } => generate_hash_for(loading_data),
         }
     }
+
+    /// The physics layers this rule can run on.
+    pub fn physics_layers(&self) -> Vec<AutoMapperPhysicsLayer> {
+        let modes = match self {
+            Self::EditorRule(rule) => rule.supported_modes(),
+            Self::Wasm(rule) => rule.manager.supported_modes(),
+            Self::LegacyRules { rule, .. } => rule.supported_modes(),
+        };
+        modes
+            .into_iter()
+            .filter_map(|mode| match mode {
+                AutoMapperModes::DesignTileLayer { .. } => None,
+                AutoMapperModes::PhysicsTileLayer { layer, .. } => Some(layer),
+            })
+            .collect()
+    }
+
+    /// Runs the rule over a whole design layer of a map,
+    /// exactly like the editor does when auto mapping a layer.
+    pub fn run_on_map_layer(
+        &mut self,
+        map: &mut Map,
+        seed: u64,
+        is_background: bool,
+        group_index: usize,
+        layer_index: usize,
+    ) -> anyhow::Result<()> {
+        let groups = if is_background {
+            &mut map.groups.background
+        } else {
+            &mut map.groups.foreground
+        };
+        let layer = groups
+            .get_mut(group_index)
+            .ok_or_else(|| anyhow!("Group index out of bounds"))?
+            .layers
+            .get_mut(layer_index)
+            .ok_or_else(|| anyhow!("Layer index is out of bounds"))?;
+        let MapLayer::Tile(layer) = layer else {
+            anyhow::bail!("Layer is not of type tile");
+        };
+
+        let mut act = self.run_layer(
+            seed,
+            layer.attr,
+            layer.tiles.clone(),
+            0,
+            0,
+            layer.attr.width,
+            layer.attr.height,
+            is_background,
+            group_index,
+            layer_index,
+        )?;
+        check_and_copy_tiles(
+            layer_index,
+            &mut layer.tiles,
+            &mut act.base.old_tiles,
+            &act.base.new_tiles,
+            layer.attr.width.get() as usize,
+            layer.attr.height.get() as usize,
+            act.base.x as usize,
+            act.base.y as usize,
+            act.base.w.get() as usize,
+            act.base.h.get() as usize,
+            false,
+        )
+    }
+
+    /// Runs the rule on a physics layer of a map, using the design layer
+    /// (`(is_background, group_index, layer_index)`), if any, as design input.
+    pub fn run_on_map_physics_layer(
+        &mut self,
+        map: &mut Map,
+        seed: u64,
+        design_layer: Option<(bool, usize, usize)>,
+        physics_layer_index: usize,
+    ) -> anyhow::Result<()> {
+        let design_layer = design_layer
+            .map(|(is_background, group_index, layer_index)| {
+                let groups = if is_background {
+                    &map.groups.background
+                } else {
+                    &map.groups.foreground
+                };
+                let layer = groups
+                    .get(group_index)
+                    .ok_or_else(|| anyhow!("Group index out of bounds"))?
+                    .layers
+                    .get(layer_index)
+                    .ok_or_else(|| anyhow!("Layer index is out of bounds"))?;
+                let MapLayer::Tile(design_layer) = layer else {
+                    anyhow::bail!("Layer is not of type tile");
+                };
+                Ok((design_layer.attr, design_layer.tiles.as_slice()))
+            })
+            .transpose()?;
+
+        let physics = &mut map.groups.physics;
+        let layer = physics
+            .layers
+            .get_mut(physics_layer_index)
+            .ok_or_else(|| anyhow!("Physics layer index is out of bounds"))?;
+        let tiles = match layer {
+            MapLayerPhysics::Arbitrary(_) => {
+                anyhow::bail!("Arbitrary physics layers can't be auto mapped")
+            }
+            MapLayerPhysics::Game(layer) => MapTileLayerPhysicsTiles::Game(layer.tiles.clone()),
+            MapLayerPhysics::Front(layer) => MapTileLayerPhysicsTiles::Front(layer.tiles.clone()),
+            MapLayerPhysics::Tele(layer) => {
+                MapTileLayerPhysicsTiles::Tele(layer.base.tiles.clone())
+            }
+            MapLayerPhysics::Speedup(layer) => {
+                MapTileLayerPhysicsTiles::Speedup(layer.tiles.clone())
+            }
+            MapLayerPhysics::Switch(layer) => {
+                MapTileLayerPhysicsTiles::Switch(layer.base.tiles.clone())
+            }
+            MapLayerPhysics::Tune(layer) => {
+                MapTileLayerPhysicsTiles::Tune(layer.base.tiles.clone())
+            }
+        };
+        let mut act = self.run_physics_layer(
+            seed,
+            physics.attr,
+            tiles,
+            design_layer,
+            0,
+            0,
+            physics.attr.width,
+            physics.attr.height,
+            physics_layer_index,
+        )?;
+
+        let w = physics.attr.width.get() as usize;
+        let h = physics.attr.height.get() as usize;
+        let x = act.base.x as usize;
+        let y = act.base.y as usize;
+        let sub_w = act.base.w.get() as usize;
+        let sub_h = act.base.h.get() as usize;
+        match (layer, &mut act.base.old_tiles, &act.base.new_tiles) {
+            (
+                MapLayerPhysics::Game(layer),
+                MapTileLayerPhysicsTiles::Game(old),
+                MapTileLayerPhysicsTiles::Game(new),
+            )
+            | (
+                MapLayerPhysics::Front(layer),
+                MapTileLayerPhysicsTiles::Front(old),
+                MapTileLayerPhysicsTiles::Front(new),
+            ) => check_and_copy_tiles(
+                physics_layer_index,
+                &mut layer.tiles,
+                old,
+                new,
+                w,
+                h,
+                x,
+                y,
+                sub_w,
+                sub_h,
+                false,
+            ),
+            (
+                MapLayerPhysics::Tele(layer),
+                MapTileLayerPhysicsTiles::Tele(old),
+                MapTileLayerPhysicsTiles::Tele(new),
+            ) => check_and_copy_tiles(
+                physics_layer_index,
+                &mut layer.base.tiles,
+                old,
+                new,
+                w,
+                h,
+                x,
+                y,
+                sub_w,
+                sub_h,
+                false,
+            ),
+            (
+                MapLayerPhysics::Speedup(layer),
+                MapTileLayerPhysicsTiles::Speedup(old),
+                MapTileLayerPhysicsTiles::Speedup(new),
+            ) => check_and_copy_tiles(
+                physics_layer_index,
+                &mut layer.tiles,
+                old,
+                new,
+                w,
+                h,
+                x,
+                y,
+                sub_w,
+                sub_h,
+                false,
+            ),
+            (
+                MapLayerPhysics::Switch(layer),
+                MapTileLayerPhysicsTiles::Switch(old),
+                MapTileLayerPhysicsTiles::Switch(new),
+            ) => check_and_copy_tiles(
+                physics_layer_index,
+                &mut layer.base.tiles,
+                old,
+                new,
+                w,
+                h,
+                x,
+                y,
+                sub_w,
+                sub_h,
+                false,
+            ),
+            (
+                MapLayerPhysics::Tune(layer),
+                MapTileLayerPhysicsTiles::Tune(old),
+                MapTileLayerPhysicsTiles::Tune(new),
+            ) => check_and_copy_tiles(
+                physics_layer_index,
+                &mut layer.base.tiles,
+                old,
+                new,
+                w,
+                h,
+                x,
+                y,
+                sub_w,
+                sub_h,
+                false,
+            ),
+            _ => Err(anyhow!("tiles are not compatible")),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1131,6 +1818,7 @@ impl TileLayerAutoMapper {
                                                         TileLayerAutoMapperRuleType::EditorRule(
                                                             TileLayerAutoMapperEditorRule {
                                                                 runs: rule.runs,
+                                                                physics_layers: rule.physics_layers,
                                                                 active_run: rule.active_run,
                                                             },
                                                         ),
@@ -544,7 +544,10 @@ impl AutoMapperInterface for LegacyRule {
             off_y,
             full_width,
             full_height,
-        } = input;
+        } = input
+        else {
+            return Err("legacy rules only support design tile layers.".to_string());
+        };
         let reference_layer_index = -1i32;
 
         let conf = &self.config;
@@ -259,7 +259,9 @@ fn render_grid(
                                         tile: TileLayerAutoMapperTileExpr {
                                             tile_index: tile,
                                             tile_flags: Default::default(),
+                                            tile_number: None,
                                         },
+                                        design_layer: false,
                                         operation: None,
                                     });
 
@@ -666,6 +668,8 @@ pub fn render(pipe: &mut UiRenderPipe<UserData>, ui: &mut egui::Ui, ui_state: &m
                         run.tiles.push(TileLayerAutoMapperTile {
                             tile_index: 0,
                             tile_flags: Default::default(),
+                            tile_number: None,
+                            tile_delay: None,
                             tile_type: TileLayerAutoMapperTileType::Default,
                             randomness: None,
                             check_groups: Default::default(),
@@ -894,7 +898,9 @@ pub fn render(pipe: &mut UiRenderPipe<UserData>, ui: &mut egui::Ui, ui_state: &m
                                                             tile: TileLayerAutoMapperTileExpr {
                                                                 tile_index: 1,
                                                                 tile_flags: None,
+                                                                tile_number: None,
                                                             },
+                                                            design_layer: false,
                                                             operation: None,
                                                         }),
                                                     ))
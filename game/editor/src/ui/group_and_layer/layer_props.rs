@@ -1,6 +1,7 @@
 use std::{collections::BTreeMap, ops::RangeInclusive};
 
 use base::hash::fmt_hash;
+use editor_interface::auto_mapper::AutoMapperPhysicsLayer;
 use egui::{Button, Checkbox, Color32, ComboBox, DragValue, InnerResponse, Popup};
 use legacy_map::mapdef_06::DdraceTileNum;
 use map::{
@@ -434,6 +435,26 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                 .next()
                 .unwrap_or_else(|| fg_selection.next().unwrap());
             let (bg_move_limit, g_limit, l_limit) = move_limits(&map.groups, is_background, g);
+            // physics layers that auto mappers can run on
+            let auto_mapper_physics_layers: Vec<_> = map
+                .groups
+                .physics
+                .layers
+                .iter()
+                .enumerate()
+                .filter_map(|(i, l)| {
+                    let layer = match l {
+                        EditorPhysicsLayer::Arbitrary(_) => return None,
+                        EditorPhysicsLayer::Game(_) => AutoMapperPhysicsLayer::Game,
+                        EditorPhysicsLayer::Front(_) => AutoMapperPhysicsLayer::Front,
+                        EditorPhysicsLayer::Tele(_) => AutoMapperPhysicsLayer::Tele,
+                        EditorPhysicsLayer::Speedup(_) => AutoMapperPhysicsLayer::Speedup,
+                        EditorPhysicsLayer::Switch(_) => AutoMapperPhysicsLayer::Switch,
+                        EditorPhysicsLayer::Tune(_) => AutoMapperPhysicsLayer::Tune,
+                    };
+                    Some((i, layer))
+                })
+                .collect();
             let group = group_mut(&mut map.groups, is_background, g);
             let group_attr = group.attr;
             let EditorLayer::Tile(layer) = layer_mut(group, l) else {
@@ -472,6 +493,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
 
             let mut delete_layer = false;
             let mut auto_mapper = None;
+            let mut auto_mapper_physics = None;
             let mut auto_mapper_live = None;
             let mut move_layer = None;
             let mut auto_tile = None;
@@ -736,6 +758,29 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                                     auto_mapper_live =
                                         Some(layer.user.live_edit.is_none().then_some(seed));
                                 }
+
+                                let physics_layers = layer
+                                    .user
+                                    .auto_mapper_rule
+                                    .as_ref()
+                                    .and_then(|r| rule.rules.get(r))
+                                    .map(|(r, _)| r.physics_layers())
+                                    .unwrap_or_default();
+                                if !physics_layers.is_empty() {
+                                    ui.end_row();
+                                    ui.label("Physics layer");
+                                    ui.menu_button("Run on", |ui| {
+                                        pointer_was_outside = false;
+                                        for (index, physics_layer) in auto_mapper_physics_layers
+                                            .iter()
+                                            .filter(|(_, l)| physics_layers.contains(l))
+                                        {
+                                            if ui.button(format!("{physics_layer:?}")).clicked() {
+                                                auto_mapper_physics = Some((seed, *index));
+                                            }
+                                        }
+                                    });
+                                }
                             }
 
                             ui.end_row();
@@ -956,6 +1001,33 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                         name: rule_name,
                         hash: rule.hash(),
                         seed,
+                        physics_layer_index: None,
+                    });
+                }
+            } else if let Some((seed, physics_layer_index)) = auto_mapper_physics {
+                let rule = layer.user.auto_mapper_rule.clone();
+                if let Some((resource, rule_name, (rule, _))) = resource_name
+                    .as_ref()
+                    .and_then(|r| {
+                        pipe.user_data
+                            .auto_mapper
+                            .resources
+                            .get_mut(r)
+                            .map(|rule| (r, rule))
+                    })
+                    .and_then(|(res, rules)| {
+                        rule.and_then(|r| rules.rules.get_mut(&r).map(|rule| (res, r, rule)))
+                    })
+                {
+                    tab.client.auto_map(EditorEventAutoMap {
+                        is_background,
+                        group_index: g,
+                        layer_index: l,
+                        resource_and_hash: resource.to_string(),
+                        name: rule_name,
+                        hash: rule.hash(),
+                        seed,
+                        physics_layer_index: Some(physics_layer_index),
                     });
                 }
             } else if let Some(seed) = auto_mapper_live {
@@ -982,6 +1054,7 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>, ui_st
                             name: rule_name,
                             hash: rule.hash(),
                             seed: seed.unwrap_or_default(),
+                            physics_layer_index: None,
                         },
                         seed.is_some(),
                     );
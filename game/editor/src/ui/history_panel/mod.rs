@@ -0,0 +1,80 @@
+use std::time::{Duration, SystemTime};
+
+use base::duration_ext::DurationToRaceStr;
+use egui::{Grid, ScrollArea, Window};
+
+use crate::tab::EditorTab;
+
+use super::user_data::EditorUiEvent;
+
+pub fn render(
+    ui_events: &mut Vec<EditorUiEvent>,
+    tab_name: &str,
+    editor_tab: &mut EditorTab,
+    pointer_is_used: &mut bool,
+    ui: &mut egui::Ui,
+) {
+    let mut open = editor_tab.history_open;
+    let window_res = Window::new("History").open(&mut open).show(ui.ctx(), |ui| {
+        let Some(journal) = editor_tab.server.as_ref().and_then(|s| s.journal()) else {
+            ui.label(
+                "The history is only available for maps that \
+                are saved and edited locally or hosted.",
+            );
+            return;
+        };
+        ui.label("All changes since the last save.");
+        ui.label(
+            "Restoring a change reloads the last save and \
+            applies all changes up to the selected one.",
+        );
+        ui.add_space(10.0);
+
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+            Grid::new("history-panel-grid")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (index, summary) in journal.summaries().iter().enumerate().rev() {
+                        ui.label(format!(
+                            "{} ago",
+                            Duration::from_secs(now.saturating_sub(summary.time).as_secs())
+                                .to_race_string()
+                        ));
+                        ui.label(&summary.label);
+                        if ui.button("Restore").clicked() {
+                            ui_events.push(EditorUiEvent::RestoreJournal {
+                                tab: tab_name.to_string(),
+                                index,
+                            });
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    });
+    editor_tab.history_open = open;
+
+    *pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
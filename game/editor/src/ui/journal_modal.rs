@@ -0,0 +1,46 @@
+use egui::Modal;
+use ui_base::types::UiRenderPipe;
+
+use super::user_data::{EditorModalDialogMode, EditorUiEvent, UserData};
+
+pub fn render(ui: &egui::Ui, pipe: &mut UiRenderPipe<UserData>) {
+    if let EditorModalDialogMode::RecoverJournal { tab } = pipe.user_data.modal_dialog_mode {
+        let tab = tab.clone();
+        let changes = pipe
+            .user_data
+            .editor_tabs
+            .tabs
+            .get(&tab)
+            .and_then(|t| t.journal_recovery.as_ref())
+            .map(|entries| entries.len())
+            .unwrap_or_default();
+        Modal::new("recover-journal".into()).show(ui.ctx(), |ui| {
+            ui.label(format!(
+                "The map \"{tab}\" has {changes} changes that were not saved, \
+                e.g. because the editor crashed."
+            ));
+            ui.label("Do you want to apply them on top of the last save?");
+            ui.horizontal(|ui| {
+                if ui.button("Recover changes").clicked() {
+                    pipe.user_data
+                        .ui_events
+                        .push(EditorUiEvent::RecoverJournal {
+                            tab: tab.clone(),
+                            replay: true,
+                        });
+                    *pipe.user_data.modal_dialog_mode = EditorModalDialogMode::None;
+                }
+                if ui.button("Discard changes").clicked() {
+                    pipe.user_data
+                        .ui_events
+                        .push(EditorUiEvent::RecoverJournal {
+                            tab: tab.clone(),
+                            replay: false,
+                        });
+                    *pipe.user_data.modal_dialog_mode = EditorModalDialogMode::None;
+                }
+            });
+        });
+        *pipe.user_data.pointer_is_used = true;
+    }
+}
@@ -4,6 +4,7 @@ use crate::actions::actions::{
     ActAddSoundLayer, ActAddTileLayer, EditorAction,
 };
 use crate::client::EditorClient;
+use crate::event::{EditorGroupIndex, EditorLockTarget};
 use crate::map::{EditorLayer, EditorLayerUnionRef, EditorMap, EditorPhysicsLayer};
 use crate::ui::user_data::UserDataWithTab;
 use crate::utils::ui_pos_to_world_pos;
@@ -15,7 +16,7 @@ use crate::{
     ui::utils::{group_name, layer_name, layer_name_phy},
 };
 
-use egui::{Button, Color32, Layout, collapsing_header::CollapsingState};
+use egui::{Button, Color32, Layout, RichText, collapsing_header::CollapsingState};
 use egui_extras::{Size, StripBuilder};
 use map::map::groups::MapGroup;
 use map::map::groups::layers::design::{
@@ -34,6 +35,46 @@ fn button_selected_style() -> egui::Stroke {
     egui::Stroke::new(2.0, Color32::LIGHT_GREEN)
 }
 
+/// Locks are only interesting if multiple mappers edit the map.
+fn show_locks(client: &EditorClient) -> bool {
+    client.clients.len() > 1 || !client.permissions.locks.is_empty()
+}
+
+/// Shows the lock state of a group or layer. Locks of other mappers
+/// are drawn in their color and can not be toggled.
+fn lock_btn(ui: &mut egui::Ui, client: &EditorClient, target: EditorLockTarget) {
+    let owner = client.permissions.lock_owner(&target);
+    let is_own = owner.is_some_and(|owner| owner == client.server_id);
+    let is_foreign = owner.is_some() && !is_own;
+    let foreign_owner = client.foreign_lock_owner(&target);
+
+    let mut icon = RichText::new(if owner.is_some() {
+        "\u{f023}"
+    } else {
+        "\u{f09c}"
+    });
+    if let Some(props) = foreign_owner {
+        let [r, g, b] = props.color;
+        icon = icon.color(Color32::from_rgb(r, g, b));
+    }
+    let res = ui.add(Button::new(icon).selected(is_own));
+    let res = if is_foreign {
+        res.on_hover_text(format!(
+            "Locked by {}",
+            foreign_owner
+                .map(|props| props.mapper_name.as_str())
+                .unwrap_or("another mapper")
+        ))
+    } else if is_own {
+        res.on_hover_text("Locked by you, click to unlock")
+    } else {
+        res.on_hover_text("Lock for other mappers")
+    };
+    if res.clicked() && !is_foreign {
+        client.lock(target, !is_own);
+    }
+}
+
 fn check_layer_clicked_tile(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
     if ui.input(|i| i.modifiers.ctrl && i.pointer.secondary_pressed()) {
         let pointer_pos = ui.input(|i| {
@@ -223,6 +264,19 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                         if ui.add(hide_btn).clicked() {
                             group.editor_attr_mut().hidden = !hidden;
                         }
+                        if show_locks(client) {
+                            lock_btn(
+                                ui,
+                                client,
+                                EditorLockTarget {
+                                    group: EditorGroupIndex::Design {
+                                        is_background,
+                                        group_index: g,
+                                    },
+                                    layer_index: None,
+                                },
+                            );
+                        }
                         ui.vertical_centered_justified(|ui| {
                             let btn = Button::new(group_name(group, g)).frame(false);
                             if ui.add(btn).secondary_clicked() {
@@ -254,6 +308,19 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                             if ui.add(hide_btn).clicked() {
                                 layer.editor_attr_mut().hidden = !hidden;
                             }
+                            if show_locks(client) {
+                                lock_btn(
+                                    ui,
+                                    client,
+                                    EditorLockTarget {
+                                        group: EditorGroupIndex::Design {
+                                            is_background,
+                                            group_index: g,
+                                        },
+                                        layer_index: Some(l),
+                                    },
+                                );
+                            }
 
                             ui.vertical_centered_justified(|ui| {
                                 ui.horizontal(|ui| {
@@ -490,6 +557,16 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                                                     if ui.add(hide_btn).clicked() {
                                                         group.editor_attr_mut().hidden = !hidden;
                                                     }
+                                                    if show_locks(&tab.client) {
+                                                        lock_btn(
+                                                            ui,
+                                                            &tab.client,
+                                                            EditorLockTarget {
+                                                                group: EditorGroupIndex::Physics,
+                                                                layer_index: None,
+                                                            },
+                                                        );
+                                                    }
                                                     ui.vertical_centered_justified(|ui| {
                                                         let btn =
                                                             Button::new("Physics").frame(false);
@@ -531,6 +608,17 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserDataWithTab>) {
                                                             layer.editor_attr_mut().hidden =
                                                                 !hidden;
                                                         }
+                                                        if show_locks(&tab.client) {
+                                                            lock_btn(
+                                                                ui,
+                                                                &tab.client,
+                                                                EditorLockTarget {
+                                                                    group:
+                                                                        EditorGroupIndex::Physics,
+                                                                    layer_index: Some(l),
+                                                                },
+                                                            );
+                                                        }
 
                                                         ui.vertical_centered_justified(|ui| {
                                                             let btn = ui.add(layer_btn);
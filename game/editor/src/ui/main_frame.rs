@@ -70,6 +70,8 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
         super::hotkey_panel::panel::render(ui, &mut pipe);
 
         if let NetworkState::Client(state) = tab.client.net_state() {
+            let can_reconnect = tab.client.can_reconnect();
+            let mut reconnect = false;
             match state {
                 NetworkClientState::Connecting(to) => {
                     Window::new("Network")
@@ -87,6 +89,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                                 ui.label("The server did not respond in the last few seconds.");
                                 ui.label("The connection might be dead.");
                                 ui.label("Timeout happens after around 2 minutes.");
+                                if can_reconnect && ui.button("Reconnect").clicked() {
+                                    reconnect = true;
+                                }
                             });
                     }
                 }
@@ -99,6 +104,9 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                                 but not edit it anymore.",
                             );
                             ui.label(format!("Reason: {reason}"));
+                            if can_reconnect && ui.button("Reconnect").clicked() {
+                                reconnect = true;
+                            }
                         });
                 }
                 NetworkClientState::Err(reason) => {
@@ -106,13 +114,20 @@ pub fn render(ui: &mut egui::Ui, pipe: &mut UiRenderPipe<UserData>, ui_state: &m
                         .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
                         .show(ui.ctx(), |ui| {
                             ui.label(format!("Error: {reason}"));
+                            if can_reconnect && ui.button("Reconnect").clicked() {
+                                reconnect = true;
+                            }
                         });
                 }
             }
+            if reconnect {
+                tab.client.reconnect();
+            }
         }
     }
 
     super::close_modal::render(ui, pipe);
+    super::journal_modal::render(ui, pipe);
 
     *pipe.user_data.pointer_is_used |= Popup::is_any_open(ui.ctx());
 
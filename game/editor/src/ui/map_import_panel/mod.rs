@@ -0,0 +1,171 @@
+use std::collections::BTreeSet;
+
+use egui::{CollapsingHeader, ScrollArea, Window};
+use map::map::groups::MapGroup;
+
+use crate::{
+    notifications::{EditorNotification, EditorNotifications},
+    tab::EditorTab,
+};
+
+fn checkbox<T: Ord>(ui: &mut egui::Ui, selection: &mut BTreeSet<T>, key: T, text: String) {
+    let mut checked = selection.contains(&key);
+    if ui.checkbox(&mut checked, text).changed() {
+        if checked {
+            selection.insert(key);
+        } else {
+            selection.remove(&key);
+        }
+    }
+}
+
+fn name_or_index(name: &str, index: usize) -> String {
+    if name.is_empty() {
+        format!("#{index}")
+    } else {
+        format!("#{index} {name}")
+    }
+}
+
+pub fn render(
+    tab: &mut EditorTab,
+    notifications: &EditorNotifications,
+    pointer_is_used: &mut bool,
+    ui: &mut egui::Ui,
+) {
+    let Some(map_import) = &mut tab.map_import else {
+        return;
+    };
+    let mut open = true;
+    let mut import = false;
+    let window_res = Window::new(format!("Import from {}", map_import.name))
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            ui.label(
+                "Resources & animations used by the selected groups and layers \
+                are imported automatically.",
+            );
+            ui.label("Single layers are added to the group of the active layer.");
+            ui.add_space(10.0);
+
+            let map = &map_import.map;
+            let selection = &mut map_import.selection;
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                let mut groups = |ui: &mut egui::Ui, is_background: bool, groups: &[MapGroup]| {
+                    for (group_index, group) in groups.iter().enumerate() {
+                        checkbox(
+                            ui,
+                            &mut selection.groups,
+                            (is_background, group_index),
+                            format!("Group {}", name_or_index(&group.name, group_index)),
+                        );
+                        let group_selected =
+                            selection.groups.contains(&(is_background, group_index));
+                        ui.indent(("map-import-group", is_background, group_index), |ui| {
+                            ui.add_enabled_ui(!group_selected, |ui| {
+                                for (layer_index, layer) in group.layers.iter().enumerate() {
+                                    checkbox(
+                                        ui,
+                                        &mut selection.layers,
+                                        (is_background, group_index, layer_index),
+                                        name_or_index(layer.name(), layer_index),
+                                    );
+                                }
+                            });
+                        });
+                    }
+                };
+                CollapsingHeader::new("Background groups")
+                    .default_open(true)
+                    .show(ui, |ui| groups(ui, true, &map.groups.background));
+                CollapsingHeader::new("Foreground groups")
+                    .default_open(true)
+                    .show(ui, |ui| groups(ui, false, &map.groups.foreground));
+
+                for (title, resources, selection) in [
+                    ("Images", &map.resources.images, &mut selection.images),
+                    (
+                        "Image arrays",
+                        &map.resources.image_arrays,
+                        &mut selection.image_arrays,
+                    ),
+                    ("Sounds", &map.resources.sounds, &mut selection.sounds),
+                ] {
+                    CollapsingHeader::new(title).show(ui, |ui| {
+                        for (index, res) in resources.iter().enumerate() {
+                            checkbox(
+                                ui,
+                                selection,
+                                index,
+                                name_or_index(res.name.as_str(), index),
+                            );
+                        }
+                    });
+                }
+
+                CollapsingHeader::new("Envelopes").show(ui, |ui| {
+                    for (index, anim) in map.animations.pos.iter().enumerate() {
+                        checkbox(
+                            ui,
+                            &mut selection.pos_anims,
+                            index,
+                            format!("Position {}", name_or_index(&anim.name, index)),
+                        );
+                    }
+                    for (index, anim) in map.animations.color.iter().enumerate() {
+                        checkbox(
+                            ui,
+                            &mut selection.color_anims,
+                            index,
+                            format!("Color {}", name_or_index(&anim.name, index)),
+                        );
+                    }
+                    for (index, anim) in map.animations.sound.iter().enumerate() {
+                        checkbox(
+                            ui,
+                            &mut selection.sound_anims,
+                            index,
+                            format!("Sound {}", name_or_index(&anim.name, index)),
+                        );
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+            if ui.button("Import").clicked() {
+                import = true;
+            }
+        });
+
+    if import {
+        match map_import.import(&tab.map, &tab.client) {
+            Ok(()) => open = false,
+            Err(err) => {
+                notifications.push(EditorNotification::Error(err.to_string()));
+            }
+        }
+    }
+    if !open {
+        tab.map_import = None;
+    }
+
+    *pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
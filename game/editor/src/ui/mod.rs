@@ -8,11 +8,17 @@ pub mod close_modal;
 pub mod dbg_panel;
 pub mod dotted_rect;
 pub mod group_and_layer;
+pub mod history_panel;
 pub mod hotkey_panel;
+pub mod journal_modal;
 pub mod left_panel;
 pub mod main_frame;
+pub mod map_import_panel;
 pub mod mapper_cursors;
 pub mod page;
+pub mod physics_numbers_panel;
+pub mod prefab_panel;
+pub mod reachability_panel;
 pub mod server_config_variables;
 pub mod server_settings;
 pub mod tool_overlays;
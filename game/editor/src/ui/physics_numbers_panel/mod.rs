@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use egui::{CollapsingHeader, Color32, DragValue, Grid, ScrollArea, Window};
+use math::math::vector::vec2;
+
+use crate::{
+    notifications::{EditorNotification, EditorNotifications},
+    physics_numbers::{PhysicsNumberLayer, PhysicsNumbersOverview, renumber, usage_pos},
+    tab::EditorTab,
+};
+
+pub fn render(
+    tab: &mut EditorTab,
+    notifications: &EditorNotifications,
+    pointer_is_used: &mut bool,
+    ui: &mut egui::Ui,
+) {
+    let mut open = tab.physics_numbers.open;
+    let window_res = Window::new("Tele/Switch/Tune overview")
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            let map_generation = tab.client.map_generation;
+            let overview = match &tab.physics_numbers.overview {
+                Some((generation, overview)) if *generation == map_generation => overview.clone(),
+                _ => {
+                    let overview = Arc::new(PhysicsNumbersOverview::new(&tab.map.groups.physics));
+                    tab.physics_numbers.overview = Some((map_generation, overview.clone()));
+                    overview
+                }
+            };
+            if overview.layers.is_empty() {
+                ui.label("The map has no tele, switch or tune layer.");
+                return;
+            }
+            ui.label("Click a number to move the camera through its usages.");
+            ui.add_space(10.0);
+
+            ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for (&ty, usages) in &overview.layers {
+                    CollapsingHeader::new(ty.name())
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            if usages.is_empty() {
+                                ui.label("Not used yet.");
+                                return;
+                            }
+                            let is_tele = ty == PhysicsNumberLayer::Tele;
+                            Grid::new(("physics-numbers-grid", ty))
+                                .num_columns(if is_tele { 7 } else { 4 })
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.label("Number");
+                                    ui.label("Name");
+                                    ui.label("Tiles");
+                                    if is_tele {
+                                        ui.label("In");
+                                        ui.label("Out");
+                                        ui.label("Checkpoints");
+                                    }
+                                    ui.label("");
+                                    ui.end_row();
+
+                                    for (&number, usage) in usages {
+                                        let next_usage = tab
+                                            .physics_numbers
+                                            .next_usage
+                                            .entry((ty, number))
+                                            .or_default();
+                                        if ui
+                                            .button(format!("#{number}"))
+                                            .on_hover_text(format!(
+                                                "Go to usage {} of {}",
+                                                *next_usage % usage.tiles.max(1) + 1,
+                                                usage.tiles
+                                            ))
+                                            .clicked()
+                                            && let Some((x, y)) = usage_pos(
+                                                &tab.map.groups.physics,
+                                                ty,
+                                                number,
+                                                *next_usage,
+                                            )
+                                        {
+                                            tab.map.groups.user.pos =
+                                                vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                                            *next_usage = next_usage.wrapping_add(1);
+                                        }
+                                        ui.label(&usage.name);
+                                        ui.label(usage.tiles.to_string());
+                                        if is_tele {
+                                            ui.label(usage.ins.to_string());
+                                            ui.label(usage.outs.to_string());
+                                            ui.label(usage.checkpoints.to_string());
+                                        }
+
+                                        ui.horizontal(|ui| {
+                                            let renumber_state = &mut tab.physics_numbers.renumber;
+                                            match renumber_state {
+                                                Some((renumber_ty, old, new))
+                                                    if *renumber_ty == ty && *old == number =>
+                                                {
+                                                    ui.add(
+                                                        DragValue::new(new)
+                                                            .range(1..=u8::MAX)
+                                                            .prefix("#"),
+                                                    );
+                                                    let new = *new;
+                                                    if ui.button("Apply").clicked() {
+                                                        match renumber(
+                                                            &tab.map.groups.physics,
+                                                            ty,
+                                                            number,
+                                                            new,
+                                                        ) {
+                                                            Ok(actions) => {
+                                                                tab.client.execute_group(actions);
+                                                                *renumber_state = None;
+                                                            }
+                                                            Err(err) => notifications.push(
+                                                                EditorNotification::Error(
+                                                                    err.to_string(),
+                                                                ),
+                                                            ),
+                                                        }
+                                                    } else if ui.button("Cancel").clicked() {
+                                                        *renumber_state = None;
+                                                    }
+                                                }
+                                                _ => {
+                                                    if ui.button("Renumber").clicked() {
+                                                        *renumber_state =
+                                                            Some((ty, number, number));
+                                                    }
+                                                }
+                                            }
+                                            for warning in &usage.warnings {
+                                                ui.colored_label(Color32::YELLOW, *warning);
+                                            }
+                                        });
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+            });
+        });
+    tab.physics_numbers.open = open;
+
+    *pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
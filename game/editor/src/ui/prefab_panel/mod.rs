@@ -0,0 +1,137 @@
+use egui::{Button, ScrollArea, TextEdit, Window};
+use math::math::vector::ivec2;
+
+use crate::{
+    notifications::{EditorNotification, EditorNotifications},
+    prefab::{EditorPrefab, EditorPrefabs},
+    tab::EditorTab,
+    tools::tool::Tools,
+};
+
+pub fn render(
+    prefabs: &mut EditorPrefabs,
+    tab: Option<&mut EditorTab>,
+    tools: &Tools,
+    notifications: &EditorNotifications,
+    pointer_is_used: &mut bool,
+    ui: &mut egui::Ui,
+) {
+    let mut open = prefabs.active;
+    let window_res = Window::new("Prefabs").open(&mut open).show(ui.ctx(), |ui| {
+        let Some(tab) = tab else {
+            ui.label("Open a map to use prefabs.");
+            return;
+        };
+        let range = tools.tiles.selection.range.as_ref();
+
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut prefabs.new_name).hint_text("Name"));
+            let can_save = range.is_some() && EditorPrefabs::is_valid_name(&prefabs.new_name);
+            if ui
+                .add_enabled(can_save, Button::new("Save selection as prefab"))
+                .clicked()
+                && let Some(range) = range
+            {
+                let res = EditorPrefab::capture(&tab.map, range)
+                    .and_then(|prefab| prefabs.save(prefabs.new_name.clone(), prefab));
+                if let Err(err) = res {
+                    notifications.push(EditorNotification::Error(err.to_string()));
+                }
+            }
+        });
+        ui.label(
+            "Select an area with the tile selection tool, the tiles, quads & sounds \
+            of the active and all selected layers in it are saved.",
+        );
+        ui.add_space(10.0);
+
+        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for (name, prefab) in &prefabs.prefabs {
+                let selected = prefabs.selected.as_ref() == Some(name);
+                if ui
+                    .selectable_label(
+                        selected,
+                        format!(
+                            "{name} ({}x{}, {} layers)",
+                            prefab.width.get(),
+                            prefab.height.get(),
+                            prefab.design_layers.len() + prefab.physics_layers.len()
+                        ),
+                    )
+                    .clicked()
+                {
+                    prefabs.selected = Some(name.clone());
+                }
+            }
+        });
+
+        let tp = prefabs.tp.clone();
+        if let Some(prefab) = prefabs
+            .selected
+            .as_ref()
+            .and_then(|name| prefabs.prefabs.get_mut(name))
+        {
+            ui.horizontal(|ui| {
+                if ui.button("Mirror X").clicked() {
+                    prefab.mirror_x(&tp);
+                }
+                if ui.button("Mirror Y").clicked() {
+                    prefab.mirror_y(&tp);
+                }
+                if ui.button("Rotate").clicked() {
+                    prefab.rotate_plus_90(&tp);
+                }
+                if ui
+                    .button("Place")
+                    .on_hover_text(
+                        "Places the prefab at the top left corner of the tile selection \
+                        or in the center of the view.",
+                    )
+                    .clicked()
+                {
+                    let pos = match range {
+                        Some(range) => ivec2::new(range.x as i32, range.y as i32),
+                        None => {
+                            let center = tab.map.groups.user.pos;
+                            ivec2::new(
+                                center.x as i32 - prefab.width.get() as i32 / 2,
+                                center.y as i32 - prefab.height.get() as i32 / 2,
+                            )
+                        }
+                    };
+                    prefab.place(&tab.map, pos, &tab.client);
+                }
+            });
+        }
+
+        ui.add_space(10.0);
+        if ui.button("Reload").clicked() {
+            prefabs.reload();
+        }
+        ui.label(
+            "Prefabs are stored in editor/prefabs and can be \
+            shared with other mappers using the assets server.",
+        );
+    });
+    prefabs.active = open;
+
+    *pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
@@ -0,0 +1,115 @@
+use base_io::io::Io;
+use egui::{Button, DragValue, Grid, Window};
+
+use crate::{
+    notifications::{EditorNotification, EditorNotifications},
+    tab::EditorTab,
+};
+
+pub fn render(
+    tab: &mut EditorTab,
+    io: &Io,
+    notifications: &EditorNotifications,
+    pointer_is_used: &mut bool,
+    ui: &mut egui::Ui,
+) {
+    if let Err(err) = tab.reachability.update() {
+        notifications.push(EditorNotification::Error(format!(
+            "Movement reachability failed: {err}"
+        )));
+    }
+
+    let mut open = tab.reachability.open;
+    let window_res = Window::new("Reachability")
+        .open(&mut open)
+        .show(ui.ctx(), |ui| {
+            let reachability = &mut tab.reachability;
+            ui.label(
+                "Simulates a tee over the physics layers with the tunings \
+                & tune zones of the map. Other tees, weapons and special \
+                tiles are ignored.",
+            );
+            ui.add_space(10.0);
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add(Button::new("Pick start").selected(reachability.picking_start))
+                    .on_hover_text("Click into the map to set the start position.")
+                    .clicked()
+                {
+                    reachability.picking_start = !reachability.picking_start;
+                }
+                match reachability.start {
+                    Some(start) => ui.label(format!("Start: {:.1}, {:.1}", start.x, start.y)),
+                    None => ui.label("No start picked"),
+                };
+            });
+
+            Grid::new("reachability-settings")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Search ticks");
+                    ui.add(DragValue::new(&mut reachability.max_ticks).range(10..=1000));
+                    ui.end_row();
+                    ui.label("Max states");
+                    ui.add(
+                        DragValue::new(&mut reachability.max_states)
+                            .range(1000..=200000)
+                            .speed(100),
+                    );
+                    ui.end_row();
+                });
+
+            if ui
+                .add_enabled(reachability.start.is_some(), Button::new("Recompute"))
+                .clicked()
+            {
+                reachability.compute(&tab.map, io);
+            }
+
+            ui.add_space(10.0);
+            if reachability.is_computing() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Searching...");
+                });
+            } else if let Some(result) = &reachability.result {
+                ui.label(format!(
+                    "{} reachable tiles, hook length: {:.1} tiles",
+                    result.reachable_tiles.len(),
+                    result.hook_length
+                ));
+                if result.truncated {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        "The search reached the state limit, \
+                        the reachable area might be incomplete.",
+                    );
+                }
+            }
+        });
+    tab.reachability.open = open;
+    if !open {
+        tab.reachability.picking_start = false;
+    }
+
+    *pointer_is_used |= if let Some(window_res) = &window_res {
+        let intersected = ui.input(|i| {
+            if i.pointer.primary_down() {
+                Some((
+                    !window_res.response.rect.intersects({
+                        let min = i.pointer.interact_pos().unwrap_or_default();
+                        let max = min;
+                        [min, max].into()
+                    }),
+                    i.pointer.primary_pressed(),
+                ))
+            } else {
+                None
+            }
+        });
+        intersected.is_some_and(|(outside, _)| !outside)
+    } else {
+        false
+    };
+}
@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use egui::{Grid, RichText};
+
+use crate::{
+    client::EditorClient,
+    event::{AdminChangePermissions, AdminPermissionChange, EditorGroupIndex, EditorLockTarget},
+    map::EditorMap,
+    ui::{user_data::EditorUiEvent, utils::group_name},
+};
+
+/// Read-only participants, edit rights per group and
+/// the possibility to remove locks of other mappers.
+pub fn render(
+    ui: &mut egui::Ui,
+    client: &EditorClient,
+    map: &EditorMap,
+    password: &str,
+    ui_events: &mut Vec<EditorUiEvent>,
+) {
+    let mut change = |change: AdminPermissionChange| {
+        ui_events.push(EditorUiEvent::AdminChangePermissions {
+            change: AdminChangePermissions {
+                password: password.to_string(),
+                change,
+            },
+        });
+    };
+    let permissions = &client.permissions;
+    let mapper_name = |server_id: u64| {
+        client
+            .clients
+            .iter()
+            .find(|c| c.server_id == server_id)
+            .map(|c| c.mapper_name.clone())
+            .unwrap_or_else(|| format!("#{server_id}"))
+    };
+
+    ui.label(RichText::new("Mappers").strong());
+    Grid::new("admin-panel-mappers")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for props in client.clients.iter() {
+                ui.label(&props.mapper_name);
+                let mut read_only = permissions.read_only.contains(&props.server_id);
+                if ui.checkbox(&mut read_only, "Read only").changed() {
+                    change(AdminPermissionChange::ReadOnly {
+                        server_id: props.server_id,
+                        read_only,
+                    });
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(10.0);
+    ui.label(RichText::new("Edit rights").strong());
+    ui.label("Groups without selected mappers can be edited by everyone.");
+    let groups = map
+        .groups
+        .background
+        .iter()
+        .enumerate()
+        .map(|(g, group)| {
+            (
+                EditorGroupIndex::Design {
+                    is_background: true,
+                    group_index: g,
+                },
+                format!("Background: {}", group_name(group, g)),
+            )
+        })
+        .chain([(EditorGroupIndex::Physics, "Physics".to_string())])
+        .chain(map.groups.foreground.iter().enumerate().map(|(g, group)| {
+            (
+                EditorGroupIndex::Design {
+                    is_background: false,
+                    group_index: g,
+                },
+                format!("Foreground: {}", group_name(group, g)),
+            )
+        }));
+    Grid::new("admin-panel-edit-rights")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (group, name) in groups {
+                ui.label(name);
+                let editors = permissions.group_editors.get(&group);
+                ui.menu_button(
+                    editors
+                        .map(|editors| {
+                            editors
+                                .iter()
+                                .map(|id| mapper_name(*id))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        })
+                        .unwrap_or_else(|| "Everyone".to_string()),
+                    |ui| {
+                        if ui.button("Everyone").clicked() {
+                            change(AdminPermissionChange::GroupEditors {
+                                group,
+                                server_ids: None,
+                            });
+                        }
+                        for props in client.clients.iter() {
+                            let mut can_edit =
+                                editors.is_some_and(|editors| editors.contains(&props.server_id));
+                            if ui.checkbox(&mut can_edit, &props.mapper_name).changed() {
+                                let mut editors: HashSet<_> = editors.cloned().unwrap_or_default();
+                                if can_edit {
+                                    editors.insert(props.server_id);
+                                } else {
+                                    editors.remove(&props.server_id);
+                                }
+                                change(AdminPermissionChange::GroupEditors {
+                                    group,
+                                    server_ids: (!editors.is_empty()).then_some(editors),
+                                });
+                            }
+                        }
+                    },
+                );
+                ui.end_row();
+            }
+        });
+
+    if !permissions.locks.is_empty() {
+        ui.add_space(10.0);
+        ui.label(RichText::new("Locks").strong());
+        Grid::new("admin-panel-locks")
+            .num_columns(3)
+            .show(ui, |ui| {
+                let mut locks: Vec<(&EditorLockTarget, &u64)> = permissions.locks.iter().collect();
+                locks.sort_by_key(|(target, _)| {
+                    (
+                        match target.group {
+                            EditorGroupIndex::Design {
+                                is_background,
+                                group_index,
+                            } => (if is_background { 0 } else { 2 }, group_index),
+                            EditorGroupIndex::Physics => (1, 0),
+                        },
+                        target.layer_index,
+                    )
+                });
+                for (target, owner) in locks {
+                    let group = match target.group {
+                        EditorGroupIndex::Physics => "Physics".to_string(),
+                        EditorGroupIndex::Design {
+                            is_background,
+                            group_index,
+                        } => format!(
+                            "{} #{group_index}",
+                            if is_background {
+                                "Background"
+                            } else {
+                                "Foreground"
+                            }
+                        ),
+                    };
+                    ui.label(match target.layer_index {
+                        Some(layer_index) => format!("{group}, layer #{layer_index}"),
+                        None => group,
+                    });
+                    ui.label(mapper_name(*owner));
+                    if ui.button("Unlock").clicked() {
+                        change(AdminPermissionChange::Unlock(*target));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+}
@@ -4,7 +4,10 @@ use base::hash::fmt_hash;
 use egui::{Align2, Button, DragValue, Grid, Popup, TextEdit, Window};
 use egui_file_dialog::{DialogMode, DialogState};
 use network::network::utils::create_certifified_keys;
-use ui_base::types::{UiRenderPipe, UiState};
+use ui_base::{
+    translate::UiTranslate,
+    types::{UiRenderPipe, UiState},
+};
 
 use crate::{
     explain::TEXT_ANIM_PANEL_AND_PROPS,
@@ -32,52 +35,87 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                 let menu_dialog_mode = &mut *pipe.user_data.menu_dialog_mode;
 
                 ui.horizontal(|ui| {
-                    ui.menu_button("File", |ui| {
+                    ui.menu_button(ui.tr("editor-file"), |ui| {
                         let binds = &*pipe.user_data.hotkeys;
                         let per_ev = &mut *pipe.user_data.cached_binds_per_event;
                         if ui
-                            .add(Button::new("New map").shortcut_text(binds.fmt_ev_bind(
-                                per_ev,
-                                &EditorHotkeyEvent::File(EditorHotkeyEventFile::New),
-                            )))
+                            .add(Button::new(ui.tr("editor-new-map")).shortcut_text(
+                                binds.fmt_ev_bind(
+                                    per_ev,
+                                    &EditorHotkeyEvent::File(EditorHotkeyEventFile::New),
+                                ),
+                            ))
                             .clicked()
                         {
                             pipe.user_data.ui_events.push(EditorUiEvent::NewMap);
                         }
                         if ui
-                            .add(Button::new("Open map").shortcut_text(binds.fmt_ev_bind(
-                                per_ev,
-                                &EditorHotkeyEvent::File(EditorHotkeyEventFile::Open),
-                            )))
+                            .add(Button::new(ui.tr("editor-open-map")).shortcut_text(
+                                binds.fmt_ev_bind(
+                                    per_ev,
+                                    &EditorHotkeyEvent::File(EditorHotkeyEventFile::Open),
+                                ),
+                            ))
                             .clicked()
                         {
                             *menu_dialog_mode = EditorMenuDialogMode::open(pipe.user_data.io);
                         }
                         if ui
-                            .add(Button::new("Save map").shortcut_text(binds.fmt_ev_bind(
-                                per_ev,
-                                &EditorHotkeyEvent::File(EditorHotkeyEventFile::Save),
-                            )))
+                            .add(Button::new(ui.tr("editor-save-map")).shortcut_text(
+                                binds.fmt_ev_bind(
+                                    per_ev,
+                                    &EditorHotkeyEvent::File(EditorHotkeyEventFile::Save),
+                                ),
+                            ))
                             .clicked()
                         {
                             *menu_dialog_mode = EditorMenuDialogMode::save(pipe.user_data.io);
                         }
+                        if ui
+                            .add_enabled(
+                                pipe.user_data.editor_tabs.active_tab().is_some(),
+                                Button::new(ui.tr("editor-import-map")),
+                            )
+                            .clicked()
+                        {
+                            *menu_dialog_mode = EditorMenuDialogMode::import(pipe.user_data.io);
+                        }
                         ui.separator();
-                        if ui.button("Host map").clicked() {
+                        if ui.button(ui.tr("editor-host-map")).clicked() {
                             *menu_dialog_mode = EditorMenuDialogMode::host(pipe.user_data.io);
                         }
-                        if ui.button("Join map").clicked() {
+                        if ui.button(ui.tr("editor-join-map")).clicked() {
                             *menu_dialog_mode = EditorMenuDialogMode::join(pipe.user_data.io);
                         }
                         ui.separator();
-                        if ui.add(Button::new("Minimize")).clicked() {
+                        ui.menu_button(ui.tr("editor-playtest"), |ui| {
+                            let options = &mut *pipe.user_data.editor_options;
+                            ui.horizontal(|ui| {
+                                ui.label("Game mod:");
+                                ui.add(
+                                    TextEdit::singleline(&mut options.playtest_game_mod)
+                                        .hint_text("default"),
+                                );
+                            });
+                            ui.checkbox(
+                                &mut options.playtest_spawn_at_camera,
+                                "Spawn at camera position",
+                            );
+                            if ui.button("Start playtest").clicked() {
+                                pipe.user_data.ui_events.push(EditorUiEvent::Playtest);
+                            }
+                        });
+                        ui.separator();
+                        if ui.add(Button::new(ui.tr("editor-minimize"))).clicked() {
                             pipe.user_data.ui_events.push(EditorUiEvent::Minimize);
                         }
                         if ui
-                            .add(Button::new("Close").shortcut_text(binds.fmt_ev_bind(
-                                per_ev,
-                                &EditorHotkeyEvent::File(EditorHotkeyEventFile::Close),
-                            )))
+                            .add(Button::new(ui.tr("editor-close")).shortcut_text(
+                                binds.fmt_ev_bind(
+                                    per_ev,
+                                    &EditorHotkeyEvent::File(EditorHotkeyEventFile::Close),
+                                ),
+                            ))
                             .clicked()
                         {
                             pipe.user_data.ui_events.push(EditorUiEvent::Close);
@@ -159,6 +197,36 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                         {
                             tab.auto_saver.active = !tab.auto_saver.active;
                         }
+                        if let Some(tab) = &mut pipe.user_data.editor_tabs.active_tab()
+                            && ui
+                                .add(Button::new("History").selected(tab.history_open))
+                                .clicked()
+                        {
+                            tab.history_open = !tab.history_open;
+                        }
+                        if ui
+                            .add(Button::new("Prefabs").selected(pipe.user_data.prefabs.active))
+                            .clicked()
+                        {
+                            pipe.user_data.prefabs.active = !pipe.user_data.prefabs.active;
+                        }
+                        if let Some(tab) = &mut pipe.user_data.editor_tabs.active_tab()
+                            && ui
+                                .add(
+                                    Button::new("Tele/Switch/Tune overview")
+                                        .selected(tab.physics_numbers.open),
+                                )
+                                .clicked()
+                        {
+                            tab.physics_numbers.open = !tab.physics_numbers.open;
+                        }
+                        if let Some(tab) = &mut pipe.user_data.editor_tabs.active_tab()
+                            && ui
+                                .add(Button::new("Reachability").selected(tab.reachability.open))
+                                .clicked()
+                        {
+                            tab.reachability.open = !tab.reachability.open;
+                        }
                     });
 
                     let binds = &*pipe.user_data.hotkeys;
@@ -268,6 +336,18 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                                             }
                                         },
                                     );
+                                    if let EditorAdminPanelState::Authed(state) =
+                                        &tab.admin_panel.state
+                                    {
+                                        ui.separator();
+                                        super::admin_permissions::render(
+                                            ui,
+                                            &tab.client,
+                                            &tab.map,
+                                            &state.password,
+                                            pipe.user_data.ui_events,
+                                        );
+                                    }
                                 });
                         }
 
@@ -329,6 +409,7 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
 
                 if let EditorMenuDialogMode::Open { file_dialog }
                 | EditorMenuDialogMode::Save { file_dialog }
+                | EditorMenuDialogMode::Import { file_dialog }
                 | EditorMenuDialogMode::Host {
                     mode: EditorMenuHostDialogMode::SelectMap { file_dialog },
                 } = menu_dialog_mode
@@ -357,6 +438,11 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                                     }
                                 }
                                 *menu_dialog_mode = EditorMenuDialogMode::None;
+                            } else if let EditorMenuDialogMode::Import { .. } = menu_dialog_mode {
+                                pipe.user_data
+                                    .ui_events
+                                    .push(EditorUiEvent::ImportFromMap { name: selected });
+                                *menu_dialog_mode = EditorMenuDialogMode::None;
                             } else if let EditorMenuDialogMode::Host { mode } = menu_dialog_mode {
                                 let (cert, private_key) = create_certifified_keys();
 
@@ -585,7 +671,20 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                     crate::ui::auto_mapper::auto_mapper::render(pipe, ui, ui_state);
                 }
 
+                pipe.user_data.prefabs.update(pipe.user_data.notifications);
+                if pipe.user_data.prefabs.active {
+                    crate::ui::prefab_panel::render(
+                        pipe.user_data.prefabs,
+                        pipe.user_data.editor_tabs.active_tab(),
+                        pipe.user_data.tools,
+                        pipe.user_data.notifications,
+                        pipe.user_data.pointer_is_used,
+                        ui,
+                    );
+                }
+
                 let cur_hotkeys = &mut *pipe.user_data.cur_hotkey_events;
+                let active_tab = pipe.user_data.editor_tabs.active_tab.clone();
                 if let Some(tab) = pipe.user_data.editor_tabs.active_tab() {
                     if tab.auto_saver.active {
                         crate::ui::auto_saver::render(
@@ -596,6 +695,44 @@ pub fn render(ui: &mut egui::Ui, ui_state: &mut UiState, pipe: &mut UiRenderPipe
                         );
                     }
 
+                    if tab.map_import.is_some() {
+                        crate::ui::map_import_panel::render(
+                            tab,
+                            pipe.user_data.notifications,
+                            pipe.user_data.pointer_is_used,
+                            ui,
+                        );
+                    }
+
+                    if tab.physics_numbers.open {
+                        crate::ui::physics_numbers_panel::render(
+                            tab,
+                            pipe.user_data.notifications,
+                            pipe.user_data.pointer_is_used,
+                            ui,
+                        );
+                    }
+
+                    if tab.reachability.open {
+                        crate::ui::reachability_panel::render(
+                            tab,
+                            pipe.user_data.io,
+                            pipe.user_data.notifications,
+                            pipe.user_data.pointer_is_used,
+                            ui,
+                        );
+                    }
+
+                    if tab.history_open {
+                        crate::ui::history_panel::render(
+                            pipe.user_data.ui_events,
+                            &active_tab,
+                            tab,
+                            pipe.user_data.pointer_is_used,
+                            ui,
+                        );
+                    }
+
                     if tab.server.is_some() && cur_hotkeys.remove(&EditorHotkeyEvent::DbgMode) {
                         tab.dbg_panel.show = true;
                     }
@@ -1 +1,2 @@
+pub mod admin_permissions;
 pub mod menu;
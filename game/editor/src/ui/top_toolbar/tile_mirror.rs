@@ -55,43 +55,147 @@ fn mirror_y_tiles<T: Copy + Clone + Sync + Send + AsMut<TileBase>>(
     });
     *tiles = new_tiles;
 }
-pub fn mirror_tiles_y(
+
+/// Mirrors the tiles of a `w` tiles wide area vertically.
+pub fn mirror_map_tiles_y(tp: &Arc<rayon::ThreadPool>, w: usize, tiles: &mut MapTileLayerTiles) {
+    match tiles {
+        MapTileLayerTiles::Design(tiles) => {
+            mirror_y_tiles(tp, w, tiles);
+        }
+        MapTileLayerTiles::Physics(ty) => match ty {
+            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
+            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
+                mirror_y_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Tele(tiles) => {
+                mirror_y_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Speedup(tiles) => {
+                mirror_y_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Switch(tiles) => {
+                mirror_y_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Tune(tiles) => {
+                mirror_y_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+        },
+    }
+}
+
+/// Mirrors the tiles of a `w` tiles wide area horizontally.
+pub fn mirror_map_tiles_x(tp: &Arc<rayon::ThreadPool>, w: usize, tiles: &mut MapTileLayerTiles) {
+    match tiles {
+        MapTileLayerTiles::Design(tiles) => {
+            mirror_x_tiles(tp, w, tiles);
+        }
+        MapTileLayerTiles::Physics(ty) => match ty {
+            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
+            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
+                mirror_x_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Tele(tiles) => {
+                mirror_x_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Speedup(tiles) => {
+                mirror_x_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Switch(tiles) => {
+                mirror_x_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+            MapTileLayerPhysicsTiles::Tune(tiles) => {
+                mirror_x_tiles(tp, w, tiles);
+                ensure_tile_flags_valid(tp, tiles);
+            }
+        },
+    }
+}
+
+fn rotate_plus_90_tiles<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
     tp: &Arc<rayon::ThreadPool>,
-    graphics_mt: &GraphicsMultiThreaded,
-    shader_storage_handle: &GraphicsShaderStorageHandle,
-    buffer_object_handle: &GraphicsBufferObjectHandle,
-    backend_handle: &GraphicsBackendHandle,
-    brush: &mut TileBrushTiles,
-    upload_new_layer: bool,
+    w: usize,
+    tiles: &mut Vec<T>,
 ) {
-    match &mut brush.tiles {
+    let h = tiles.len() / w;
+    let mut new_tiles = tiles.clone();
+
+    tp.install(|| {
+        // transpose
+        new_tiles
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(index, tile)| {
+                let old_index = (index % h) * w + (index / h);
+                *tile = tiles[old_index];
+            });
+        // reverse
+        new_tiles
+            .par_chunks_exact_mut(h)
+            .for_each(|chunk| chunk.reverse());
+
+        new_tiles
+            .par_iter_mut()
+            .for_each(|tile| rotate_by_plus_90(&mut tile.as_mut().flags));
+    });
+    *tiles = new_tiles;
+}
+
+/// Rotates the tiles of a `w` tiles wide area by 90° clockwise,
+/// afterwards the area is as wide as it was high before.
+pub fn rotate_map_tiles_plus_90(
+    tp: &Arc<rayon::ThreadPool>,
+    w: usize,
+    tiles: &mut MapTileLayerTiles,
+) {
+    match tiles {
         MapTileLayerTiles::Design(tiles) => {
-            mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+            rotate_plus_90_tiles(tp, w, tiles);
         }
         MapTileLayerTiles::Physics(ty) => match ty {
             MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
             MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                rotate_plus_90_tiles(tp, w, tiles);
                 ensure_tile_flags_valid(tp, tiles);
             }
             MapTileLayerPhysicsTiles::Tele(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                rotate_plus_90_tiles(tp, w, tiles);
                 ensure_tile_flags_valid(tp, tiles);
             }
             MapTileLayerPhysicsTiles::Speedup(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                rotate_plus_90_tiles(tp, w, tiles);
                 ensure_tile_flags_valid(tp, tiles);
             }
             MapTileLayerPhysicsTiles::Switch(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                rotate_plus_90_tiles(tp, w, tiles);
                 ensure_tile_flags_valid(tp, tiles);
             }
             MapTileLayerPhysicsTiles::Tune(tiles) => {
-                mirror_y_tiles(tp, brush.w.get() as usize, tiles);
+                rotate_plus_90_tiles(tp, w, tiles);
                 ensure_tile_flags_valid(tp, tiles);
             }
         },
     }
+}
+
+pub fn mirror_tiles_y(
+    tp: &Arc<rayon::ThreadPool>,
+    graphics_mt: &GraphicsMultiThreaded,
+    shader_storage_handle: &GraphicsShaderStorageHandle,
+    buffer_object_handle: &GraphicsBufferObjectHandle,
+    backend_handle: &GraphicsBackendHandle,
+    brush: &mut TileBrushTiles,
+    upload_new_layer: bool,
+) {
+    mirror_map_tiles_y(tp, brush.w.get() as usize, &mut brush.tiles);
 
     brush.last_apply.set(None);
 
@@ -131,34 +235,7 @@ pub fn mirror_tiles_x(
     brush: &mut TileBrushTiles,
     upload_new_layer: bool,
 ) {
-    match &mut brush.tiles {
-        MapTileLayerTiles::Design(tiles) => {
-            mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-        }
-        MapTileLayerTiles::Physics(ty) => match ty {
-            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
-            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tele(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Speedup(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Switch(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tune(tiles) => {
-                mirror_x_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-        },
-    }
+    mirror_map_tiles_x(tp, brush.w.get() as usize, &mut brush.tiles);
 
     brush.last_apply.set(None);
 
@@ -183,63 +260,7 @@ pub fn rotate_tiles_plus_90(
     brush: &mut TileBrushTiles,
     upload_new_layer: bool,
 ) {
-    fn rotate_tiles<T: Copy + Clone + Send + Sync + AsMut<TileBase>>(
-        tp: &Arc<rayon::ThreadPool>,
-        w: usize,
-        tiles: &mut Vec<T>,
-    ) {
-        let h = tiles.len() / w;
-        let mut new_tiles = tiles.clone();
-
-        tp.install(|| {
-            // transpose
-            new_tiles
-                .par_iter_mut()
-                .enumerate()
-                .for_each(|(index, tile)| {
-                    let old_index = (index % h) * w + (index / h);
-                    *tile = tiles[old_index];
-                });
-            // reverse
-            new_tiles
-                .par_chunks_exact_mut(h)
-                .for_each(|chunk| chunk.reverse());
-
-            new_tiles
-                .par_iter_mut()
-                .for_each(|tile| rotate_by_plus_90(&mut tile.as_mut().flags));
-        });
-        *tiles = new_tiles;
-    }
-
-    match &mut brush.tiles {
-        MapTileLayerTiles::Design(tiles) => {
-            rotate_tiles(tp, brush.w.get() as usize, tiles);
-        }
-        MapTileLayerTiles::Physics(ty) => match ty {
-            MapTileLayerPhysicsTiles::Arbitrary(_) => panic!("not implemented"),
-            MapTileLayerPhysicsTiles::Game(tiles) | MapTileLayerPhysicsTiles::Front(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tele(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Speedup(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Switch(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-            MapTileLayerPhysicsTiles::Tune(tiles) => {
-                rotate_tiles(tp, brush.w.get() as usize, tiles);
-                ensure_tile_flags_valid(tp, tiles);
-            }
-        },
-    }
+    rotate_map_tiles_plus_90(tp, brush.w.get() as usize, &mut brush.tiles);
 
     let off_x = brush.negative_offsetf.x;
     let off_y = brush.negative_offsetf.y;
@@ -21,11 +21,12 @@ use serde::{Deserialize, Serialize};
 use sound::scene_object::SceneObject;
 
 use crate::{
-    event::ActionDbg,
+    event::{ActionDbg, AdminChangePermissions},
     hotkeys::{BindsPerEvent, EditorBindsFile, EditorHotkeyEvent},
     image_store_container::ImageStoreContainer,
     notifications::EditorNotifications,
     options::EditorOptions,
+    prefab::EditorPrefabs,
     sound_store_container::SoundStoreContainer,
     tab::{EditorAdminPanelStateAuthed, EditorTab},
     tools::{tile_layer::auto_mapper::TileLayerAutoMapper, tool::Tools},
@@ -53,6 +54,10 @@ pub enum EditorUiEvent {
     SaveFile {
         name: PathBuf,
     },
+    /// Read another map to import parts of it into the active tab.
+    ImportFromMap {
+        name: PathBuf,
+    },
     SaveCurMap,
     SaveMapAndClose {
         tab: String,
@@ -68,10 +73,22 @@ pub enum EditorUiEvent {
         color: [u8; 3],
     },
     Minimize,
+    Playtest,
     Close,
     ForceClose,
     Undo,
     Redo,
+    /// Replay or discard the journal of a previous session.
+    RecoverJournal {
+        tab: String,
+        replay: bool,
+    },
+    /// Reload the last save and replay the journal up to
+    /// (including) the entry at `index`.
+    RestoreJournal {
+        tab: String,
+        index: usize,
+    },
     CursorWorldPos {
         pos: vec2,
     },
@@ -84,6 +101,9 @@ pub enum EditorUiEvent {
     AdminChangeConfig {
         state: EditorAdminPanelStateAuthed,
     },
+    AdminChangePermissions {
+        change: AdminChangePermissions,
+    },
     DbgAction(ActionDbg),
 }
 
@@ -115,6 +135,7 @@ pub enum EditorMenuDialogMode {
     None,
     Open { file_dialog: Box<FileDialog> },
     Save { file_dialog: Box<FileDialog> },
+    Import { file_dialog: Box<FileDialog> },
     Host { mode: EditorMenuHostDialogMode },
     Join(EditorMenuDialogJoinProps),
 }
@@ -214,6 +235,22 @@ impl EditorMenuDialogMode {
 
         Self::Save { file_dialog }
     }
+    pub fn import(io: &Io) -> Self {
+        let mut open_path = io.fs.get_save_path();
+        open_path.push("map/maps");
+
+        let mut file_dialog = Box::new(Self::icons(
+            FileDialog::new()
+                .title("Import from Map File")
+                .anchor(Align2::CENTER_CENTER, (0.0, 0.0))
+                .movable(false)
+                .initial_directory(open_path),
+        ));
+
+        file_dialog.pick_file();
+
+        Self::Import { file_dialog }
+    }
     pub fn host(io: &Io) -> Self {
         let mut open_path = io.fs.get_save_path();
         open_path.push("map/maps");
@@ -260,6 +297,7 @@ pub enum EditorModalDialogMode {
     None,
     CloseTab { tab: String },
     CloseEditor,
+    RecoverJournal { tab: String },
 }
 
 pub struct EditorTabsRefMut<'a> {
@@ -292,6 +330,7 @@ pub struct UserData<'a> {
     pub editor_options: &'a mut EditorOptions,
 
     pub auto_mapper: &'a mut TileLayerAutoMapper,
+    pub prefabs: &'a mut EditorPrefabs,
 
     pub pointer_is_used: &'a mut bool,
     pub io: &'a Io,
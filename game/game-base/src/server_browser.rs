@@ -173,6 +173,12 @@ pub struct ServerFilter {
     #[serde(default)]
     #[serde_as(deserialize_as = "DefaultOnError")]
     pub hide_legacy_servers: bool,
+    /// Hides servers with a higher ping (in milliseconds).
+    /// `0` disables the filter, servers without a measured ping are
+    /// never hidden.
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError")]
+    pub max_ping: u32,
 }
 
 #[derive(Debug, Hiarc, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -195,6 +201,8 @@ pub struct FilterCache {
     sort: TableSort,
     finished_maps: HashSet<NetworkReducedAsciiString<MAX_MAP_NAME_LEN>>,
     community_name: ServerTypeFilterCache,
+    /// Only relevant if the result depends on the pings.
+    pings_generation: u64,
 }
 
 #[derive(Debug, Hiarc, Default)]
@@ -257,6 +265,11 @@ pub struct ServerBrowserData {
 
     cache: FilterCache,
     filtered_sorted: Option<Arc<Vec<ServerBrowserServer>>>,
+
+    /// The last measured ping per server address,
+    /// kept across server list updates.
+    pings: HashMap<SocketAddr, Duration>,
+    pings_generation: u64,
 }
 
 #[hiarc_safer_rc_refcell]
@@ -294,6 +307,9 @@ impl ServerBrowserData {
 
             cache: Default::default(),
             filtered_sorted: Default::default(),
+
+            pings: Default::default(),
+            pings_generation: Default::default(),
         }
     }
 
@@ -303,10 +319,37 @@ impl ServerBrowserData {
 
     pub fn set_servers(&mut self, servers: Vec<ServerBrowserServer>, time: Duration) {
         if self.list.time.is_none_or(|list_time| list_time < time) {
+            let pings = std::mem::take(&mut self.pings);
+            let pings_generation = self.pings_generation;
             *self = Self::from_servers(servers, time);
+            self.pings = pings;
+            self.pings_generation = pings_generation;
         }
     }
 
+    /// Stores the measured ping of a server address.
+    pub fn set_ping(&mut self, addr: SocketAddr, ping: Duration) {
+        self.pings.insert(addr, ping);
+        self.pings_generation += 1;
+    }
+
+    /// The lowest measured ping of all addresses of the server.
+    pub fn ping(&self, server: &ServerBrowserServer) -> Option<Duration> {
+        Self::server_ping(&self.pings, server)
+    }
+
+    fn server_ping(
+        pings: &HashMap<SocketAddr, Duration>,
+        server: &ServerBrowserServer,
+    ) -> Option<Duration> {
+        server
+            .addresses
+            .iter()
+            .filter_map(|addr| pings.get(addr))
+            .min()
+            .copied()
+    }
+
     pub fn find(&self, addr: SocketAddr) -> Option<ServerBrowserServer> {
         self.list.find(addr)
     }
@@ -347,6 +390,7 @@ impl ServerBrowserData {
         filter: &'a ServerFilter,
         favorites: &'a FavoritePlayers,
         finished_maps: &'a HashSet<NetworkReducedAsciiString<MAX_MAP_NAME_LEN>>,
+        pings: &'a HashMap<SocketAddr, Duration>,
     ) -> impl Iterator<Item = &'a ServerBrowserServer> {
         servers.iter().filter(move |server| {
             (server
@@ -381,10 +425,17 @@ impl ServerBrowserData {
                         .any(|p| favorites.iter().any(|f| f.name == p.name)))
                 && (!filter.unfinished_maps || finished_maps.contains(&server.info.map.name))
                 && (!filter.hide_legacy_servers || !server.legacy_server)
+                && (filter.max_ping == 0
+                    || Self::server_ping(pings, server)
+                        .is_none_or(|ping| ping <= Duration::from_millis(filter.max_ping as u64)))
         })
     }
 
-    fn servers_sorted(servers: &mut [ServerBrowserServer], sort: &TableSort) {
+    fn servers_sorted(
+        servers: &mut [ServerBrowserServer],
+        sort: &TableSort,
+        pings: &HashMap<SocketAddr, Duration>,
+    ) {
         servers.sort_by(|d1, d2| {
             let order = match sort.name.as_str() {
                 "Name" => d1
@@ -405,7 +456,13 @@ impl ServerBrowserData {
                     .to_lowercase()
                     .cmp(&d2.info.map.name.as_str().to_lowercase()),
                 "Players" => d1.info.players.len().cmp(&d2.info.players.len()),
-                // TODO: "Ping"
+                // servers without a measured ping come last
+                "Ping" => match (Self::server_ping(pings, d1), Self::server_ping(pings, d2)) {
+                    (Some(p1), Some(p2)) => p1.cmp(&p2),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => std::cmp::Ordering::Equal,
+                },
                 _ => d1
                     .info
                     .name
@@ -428,21 +485,29 @@ impl ServerBrowserData {
         finished_maps: &HashSet<NetworkReducedAsciiString<MAX_MAP_NAME_LEN>>,
         ty_filter: ServerTypeFilter,
     ) -> Arc<Vec<ServerBrowserServer>> {
+        let ty_cache = ty_filter.to_cache();
+        let depends_on_pings = filter.max_ping != 0 || sort.name == "Ping";
         if let Some(filtered_sorted) = (self.cache.filter.eq(filter)
             && self.cache.favorites.eq(favorites)
             && self.cache.sort.eq(sort)
             && self.cache.finished_maps.eq(finished_maps)
-            && self.cache.community_name.eq(&ty_filter.to_cache()))
-        .then_some(self.filtered_sorted.as_ref())
-        .flatten()
+            && self.cache.community_name.eq(&ty_cache)
+            && (!depends_on_pings || self.cache.pings_generation == self.pings_generation))
+            .then_some(self.filtered_sorted.as_ref())
+            .flatten()
         {
             filtered_sorted.clone()
         } else {
-            let mut servers_filtered: Vec<_> =
-                Self::servers_filtered(&self.list.servers, filter, favorites, finished_maps)
-                    .cloned()
-                    .collect();
-            Self::servers_sorted(&mut servers_filtered, sort);
+            let mut servers_filtered: Vec<_> = Self::servers_filtered(
+                &self.list.servers,
+                filter,
+                favorites,
+                finished_maps,
+                &self.pings,
+            )
+            .cloned()
+            .collect();
+            Self::servers_sorted(&mut servers_filtered, sort, &self.pings);
             let servers_filtered = match ty_filter {
                 ServerTypeFilter::Community((_, community_ips)) => servers_filtered
                     .into_iter()
@@ -455,6 +520,14 @@ impl ServerBrowserData {
                 ServerTypeFilter::Internet => servers_filtered,
             };
             let servers = Arc::new(servers_filtered);
+            self.cache = FilterCache {
+                filter: filter.clone(),
+                favorites: favorites.clone(),
+                sort: sort.clone(),
+                finished_maps: finished_maps.clone(),
+                community_name: ty_cache,
+                pings_generation: self.pings_generation,
+            };
             self.filtered_sorted = Some(servers.clone());
             servers
         }
@@ -175,6 +175,10 @@ pub struct ConfigDemoRecorder {
     /// 18 is default.
     #[default = 18]
     pub crf: u8,
+    /// The zoom of the camera.
+    #[conf_valid(range(min = 0.25, max = 8.0))]
+    #[default = 1.0]
+    pub zoom: f64,
     /// Config related to rendering graphics & sound.
     pub render: ConfigRender,
     /// Sound configs used during rendering sound & graphics.
@@ -224,6 +228,74 @@ pub struct ConfigRender {
     /// character.
     #[default = false]
     pub spec_dyncam: bool,
+    /// Whether the camera automatically follows the action
+    /// while spectating and in demos.
+    #[default = false]
+    pub auto_director: bool,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigGhost {
+    /// Record ghosts of own race runs.
+    #[default = true]
+    pub record: bool,
+    /// How many of the best runs are stored per map.
+    /// Worse runs are removed.
+    #[conf_valid(range(min = 1, max = 100))]
+    #[default = 5]
+    pub max_stored: u32,
+    /// How many of the stored ghosts are shown at once,
+    /// starting with the best run.
+    #[conf_valid(range(min = 0, max = 100))]
+    #[default = 1]
+    pub max_shown: u32,
+    /// Tint every shown ghost in a distinct color.
+    #[default = true]
+    pub tint: bool,
+    /// The ghost (index in the shown ghosts) to compare
+    /// the own run against.
+    #[default = 0]
+    pub split_ghost: u32,
+    /// Show the time difference to the selected ghost.
+    #[default = true]
+    pub show_split: bool,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigChat {
+    /// Highlight chat messages that mention the name
+    /// of a local player.
+    #[default = true]
+    pub highlight_own_name: bool,
+    /// Additional words that highlight a chat message.
+    #[default = Vec::new()]
+    pub highlight_words: Vec<String>,
+    /// Show a notification for highlighted chat messages.
+    #[default = true]
+    pub highlight_notification: bool,
+    /// Account names of players whose text chat is hidden.
+    #[default = Vec::new()]
+    pub ignored_accounts: Vec<String>,
+    /// Names of players whose text chat is hidden.
+    /// Players with an account are better ignored by their account.
+    #[default = Vec::new()]
+    pub ignored_names: Vec<String>,
+    /// Write the chat to a log file per server.
+    #[default = false]
+    pub logs: bool,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigConsole {
+    /// User defined aliases, the key is the name of the alias,
+    /// the value the commands it executes.
+    pub aliases: HashMap<String, String>,
+    /// User defined variables, that can be used as `$name`
+    /// in command arguments.
+    pub vars: HashMap<String, String>,
 }
 
 #[config_default]
@@ -248,8 +320,14 @@ pub struct ConfigClient {
     pub render: ConfigRender,
     /// Configs related to spatial chat support.
     pub spatial_chat: ConfigSpatialChat,
+    /// Text chat related settings.
+    pub chat: ConfigChat,
+    /// User defined aliases & variables of the local console.
+    pub console: ConfigConsole,
     /// Configurations for the demo video encoder.
     pub recorder: ConfigDemoRecorder,
+    /// Ghost recording & playback related settings.
+    pub ghost: ConfigGhost,
     /// Apply input for prediction directly. Might cause miss prediction.
     pub instant_input: bool,
     /// Predict other entities that are not local as if the ping is 0.
@@ -319,6 +397,76 @@ impl From<&ConfigPlayerLaser> for NetworkLaserInfo {
     }
 }
 
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum ConfigHudAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigHudElement {
+    /// Whether the element is shown at all.
+    #[default = true]
+    pub visible: bool,
+    /// The point of the screen the element is attached to.
+    #[default = ConfigHudAnchor::TopLeft]
+    pub anchor: ConfigHudAnchor,
+    /// Horizontal offset from the anchor in UI points.
+    #[default = 0.0]
+    pub offset_x: f64,
+    /// Vertical offset from the anchor in UI points.
+    #[default = 0.0]
+    pub offset_y: f64,
+    /// Scale of the element.
+    #[conf_valid(range(min = 0.25, max = 4.0))]
+    #[default = 1.0]
+    pub scale: f64,
+}
+
+impl ConfigHudElement {
+    pub fn new(anchor: ConfigHudAnchor, offset_x: f64, offset_y: f64) -> Self {
+        Self {
+            anchor,
+            offset_x,
+            offset_y,
+            ..Default::default()
+        }
+    }
+}
+
+#[config_default]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigHudLayout {
+    /// Health, armor and ammo of the local character.
+    #[default = ConfigHudElement::new(ConfigHudAnchor::TopLeft, 0.0, 0.0)]
+    pub health_ammo: ConfigHudElement,
+    /// The race or round timer.
+    #[default = ConfigHudElement::new(ConfigHudAnchor::Top, 0.0, 5.0)]
+    pub timer: ConfigHudElement,
+    /// Leading players and team scores of a match.
+    #[default = ConfigHudElement::new(ConfigHudAnchor::Top, 0.0, 45.0)]
+    pub scores: ConfigHudElement,
+    /// Who is currently spectated.
+    #[default = ConfigHudElement::new(ConfigHudAnchor::Bottom, 0.0, -20.0)]
+    pub spectator_info: ConfigHudElement,
+    /// The frames per second, if enabled.
+    #[default = ConfigHudElement::new(ConfigHudAnchor::TopRight, 0.0, 0.0)]
+    pub fps: ConfigHudElement,
+    /// The network & debug statistics, if opened.
+    #[default = ConfigHudElement::new(ConfigHudAnchor::Right, 0.0, 0.0)]
+    pub net_graph: ConfigHudElement,
+}
+
 #[derive(
     Debug,
     Default,
@@ -396,6 +544,8 @@ pub struct ConfigPlayer {
     /// The default eyes to use if the server supports custom eyes.
     #[default = ConfigTeeEye::Normal]
     pub eyes: ConfigTeeEye,
+    /// Position, scale & visibility of the HUD elements.
+    pub hud_layout: ConfigHudLayout,
 }
 
 impl ConfigPlayer {
@@ -488,6 +638,63 @@ pub struct ConfigDyncamMouse {
     pub max_distance: f64,
 }
 
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigGamepad {
+    /// Whether gamepads/controllers can be used.
+    #[default = true]
+    pub enabled: bool,
+    /// The sensitivity of the aim stick in percent.
+    /// At 100 a fully pushed stick aims at the maximal cursor distance.
+    #[default = 100.0]
+    #[conf_valid(range(min = 0.0, max = 1000.0))]
+    pub sensitivity: f64,
+    /// How far (in percent) the aim stick must be pushed
+    /// before it moves the cursor.
+    #[default = 20.0]
+    #[conf_valid(range(min = 0.0, max = 99.0))]
+    pub deadzone: f64,
+    /// How far (in percent) a stick must be pushed into a direction
+    /// to count as pressed bind key.
+    #[default = 50.0]
+    #[conf_valid(range(min = 1.0, max = 100.0))]
+    pub axis_threshold: f64,
+    /// Whether the left stick aims instead of the right stick.
+    pub aim_with_left_stick: bool,
+}
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, ConfigInterface, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub enum ConfigTouchMoveLayout {
+    /// A joystick that moves left/right.
+    Joystick,
+    /// Separate buttons to move left & right.
+    Buttons,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigTouchControls {
+    /// Whether the on-screen touch controls are used.
+    #[default = cfg!(target_os = "android")]
+    pub enabled: bool,
+    /// How the movement controls look like.
+    #[default = ConfigTouchMoveLayout::Joystick]
+    pub move_layout: ConfigTouchMoveLayout,
+    /// The size of the controls in percent.
+    #[default = 100.0]
+    #[conf_valid(range(min = 25.0, max = 300.0))]
+    pub size: f64,
+    /// The opacity of the controls in percent.
+    #[default = 40.0]
+    #[conf_valid(range(min = 0.0, max = 100.0))]
+    pub opacity: f64,
+    /// Whether the movement controls are on the right
+    /// and the aim stick is on the left side of the screen.
+    pub swap_sides: bool,
+}
+
 #[config_default]
 #[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
 pub struct ConfigInput {
@@ -497,6 +704,10 @@ pub struct ConfigInput {
     pub dyncam_mouse: ConfigDyncamMouse,
     /// Whether to use the dynamic camera mouse.
     pub use_dyncam: bool,
+    /// Settings related to gamepads/controllers.
+    pub gamepad: ConfigGamepad,
+    /// Settings related to the on-screen touch controls.
+    pub touch: ConfigTouchControls,
 }
 
 impl ConfigInput {
@@ -584,6 +795,74 @@ pub struct ConfigServerRenderMod {
     pub required: bool,
 }
 
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    ConfigInterface,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ConfigServerAutoDemoMode {
+    /// No automatic recording.
+    #[default]
+    Off,
+    /// Record all the time.
+    Always,
+    /// Record one demo per round of a match,
+    /// as reported by the game mod.
+    PerRound,
+    /// Record while a specific player is on the server.
+    WhilePlayerPresent,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerAutoDemo {
+    /// When server side demos are recorded automatically.
+    #[default = ConfigServerAutoDemoMode::Off]
+    pub mode: ConfigServerAutoDemoMode,
+    /// The name of the player that triggers a recording
+    /// in the `WhilePlayerPresent` mode.
+    #[conf_valid(length(max = MAX_CHARACTER_NAME_LEN))]
+    #[default = ""]
+    pub player: String,
+    /// How many demos are kept in the server demo directory.
+    /// The oldest demos are removed first.
+    /// 0 keeps all demos.
+    #[default = 0]
+    pub max_count: u32,
+    /// How many MiB all demos in the server demo directory
+    /// can use together. The oldest demos are removed first.
+    /// 0 disables the limit.
+    #[default = 0]
+    pub max_size_mib: u64,
+}
+
+#[config_default]
+#[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
+pub struct ConfigServerPractice {
+    /// Enables the practice commands (save states, rewind & slow motion).
+    /// Should only be used for local servers, since every player
+    /// with rcon access can change the state of the whole game.
+    #[default = false]
+    pub enabled: bool,
+    /// How many seconds of the game can be rewound.
+    /// 0 disables rewinding.
+    #[conf_valid(range(min = 0, max = 120))]
+    #[default = 10]
+    pub rewind_secs: u32,
+    /// How many snapshots per second are kept for rewinding.
+    #[conf_valid(range(min = 1, max = 50))]
+    #[default = 10]
+    pub rewind_snapshots_per_second: u32,
+}
+
 pub const MAX_SERVER_NAME_LEN: usize = 64;
 #[config_default]
 #[derive(Debug, Clone, Serialize, Deserialize, ConfigInterface)]
@@ -694,6 +973,10 @@ pub struct ConfigServer {
     /// before being able to join the server
     #[default = ""]
     pub password: String,
+    /// Automatic server side demo recording.
+    pub auto_demo: ConfigServerAutoDemo,
+    /// Practice mode, mostly interesting for the internal server.
+    pub practice: ConfigServerPractice,
 }
 
 /// Sound configs used during rendering sound & graphics.
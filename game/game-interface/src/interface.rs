@@ -368,6 +368,11 @@ pub trait GameStateInterface: GameStateCreate {
     /// It's generally encouraged that the mod can deal with errors, e.g. if the binary interface changed.
     fn build_from_snapshot_by_hotreload(&mut self, snapshot: &MtPoolCow<'static, [u8]>);
 
+    /// Builds the game state out of an opaque snapshot previously build by [`GameStateInterface::snapshot_for_hotreload`].
+    /// Unlike [`GameStateInterface::build_from_snapshot_by_hotreload`] all currently joined players
+    /// stay in the game, which allows to restore save states while playing (e.g. for a practice mode).
+    fn build_from_snapshot_by_savestate(&mut self, snapshot: &MtPoolCow<'static, [u8]>);
+
     /// Builds the game state out of an opaque snapshot previously build by [`GameStateInterface::snapshot_for`].
     /// The difference to [`GameStateInterface::build_from_snapshot`] is that this function is intended to be used
     /// for the previous game state, which is ultimately used for prediction.
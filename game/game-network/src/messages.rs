@@ -17,7 +17,7 @@ use game_interface::{
     types::{
         character_info::NetworkCharacterInfo,
         emoticons::EmoticonType,
-        game::GameTickType,
+        game::{GameTickType, NonZeroGameTickType},
         id_types::PlayerId,
         player_info::PlayerUniqueId,
         render::{character::TeeEye, game::game_match::MatchSide},
@@ -151,6 +151,10 @@ pub enum ServerToClientMessage<'a> {
         entities: HashMap<PlayerId, MsgSvSpatialChatOfEntitity>,
     },
     AddLocalPlayerResponse(MsgSvAddLocalPlayerResponse),
+    /// The amount of ticks the server does in a second.
+    /// Differs from the game's tick speed if the game is slowed down
+    /// (e.g. in practice mode), the client should predict with this rate.
+    TickRate(NonZeroGameTickType),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
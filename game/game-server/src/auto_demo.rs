@@ -0,0 +1,270 @@
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use game_config::config::{ConfigServerAutoDemo, ConfigServerAutoDemoMode};
+
+/// Sub directory of the demo directory for server side demos.
+pub const SERVER_DEMO_DIR: &str = "server_demos";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoDemoAction {
+    /// Start a new demo, stopping a running one.
+    Start,
+    /// Stop the running demo.
+    Stop,
+    None,
+}
+
+/// State of the automatic server side demo recording.
+#[derive(Debug, Default)]
+pub struct AutoDemo {
+    /// The current round, increased whenever a new round starts.
+    pub round: u64,
+    /// Whether the last update saw an active round.
+    round_active: bool,
+    /// Whether the running demo was started automatically.
+    /// Manually started demos are never stopped automatically.
+    recording: bool,
+}
+
+impl AutoDemo {
+    /// Decides whether a demo should be started or stopped.
+    ///
+    /// `round_active` is `None` if the game has no rounds,
+    /// which counts as one endless round.
+    pub fn update(
+        &mut self,
+        config: &ConfigServerAutoDemo,
+        is_recording: bool,
+        round_active: Option<bool>,
+        player_present: bool,
+    ) -> AutoDemoAction {
+        let round_active = round_active.unwrap_or(true);
+        let new_round = round_active && !self.round_active;
+        self.round_active = round_active;
+        if new_round {
+            self.round += 1;
+        }
+        if !is_recording {
+            self.recording = false;
+        }
+
+        let action = match config.mode {
+            ConfigServerAutoDemoMode::Off => AutoDemoAction::None,
+            ConfigServerAutoDemoMode::Always => {
+                if !is_recording {
+                    AutoDemoAction::Start
+                } else {
+                    AutoDemoAction::None
+                }
+            }
+            ConfigServerAutoDemoMode::PerRound => {
+                if round_active && (!is_recording || (new_round && self.recording)) {
+                    AutoDemoAction::Start
+                } else if !round_active && self.recording {
+                    AutoDemoAction::Stop
+                } else {
+                    AutoDemoAction::None
+                }
+            }
+            ConfigServerAutoDemoMode::WhilePlayerPresent => {
+                if player_present && !is_recording {
+                    AutoDemoAction::Start
+                } else if !player_present && self.recording {
+                    AutoDemoAction::Stop
+                } else {
+                    AutoDemoAction::None
+                }
+            }
+        };
+        match action {
+            AutoDemoAction::Start => self.recording = true,
+            AutoDemoAction::Stop => self.recording = false,
+            AutoDemoAction::None => {}
+        }
+        action
+    }
+
+    /// A demo was started manually, which replaced
+    /// the automatic one, if any.
+    pub fn on_manual_demo(&mut self) {
+        self.recording = false;
+    }
+
+    /// The name of the demo file (without extension)
+    /// for the current map, time & round.
+    pub fn demo_name(&self, map: &str) -> String {
+        format!(
+            "{}_{}_round{}",
+            map,
+            chrono::Utc::now().format("%Y_%m_%d_%H_%M_%S"),
+            self.round
+        )
+    }
+}
+
+/// A demo file in the server demo directory.
+#[derive(Debug, Clone)]
+pub struct DemoFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Returns the demos that have to be removed, oldest first,
+/// so that at most `max_count` demos with at most `max_size`
+/// bytes in total remain. A limit of 0 is ignored.
+pub fn demos_to_remove(mut demos: Vec<DemoFile>, max_count: usize, max_size: u64) -> Vec<PathBuf> {
+    demos.sort_by_key(|demo| demo.modified);
+    let mut count = demos.len();
+    let mut size: u64 = demos.iter().map(|demo| demo.size).sum();
+    demos
+        .into_iter()
+        .take_while(|demo| {
+            let remove =
+                (max_count != 0 && count > max_count) || (max_size != 0 && size > max_size);
+            if remove {
+                count -= 1;
+                size -= demo.size;
+            }
+            remove
+        })
+        .map(|demo| demo.path)
+        .collect()
+}
+
+/// Removes the oldest demos in `dir`, until the limits
+/// of the config are satisfied.
+pub fn apply_retention(dir: &Path, config: &ConfigServerAutoDemo) -> anyhow::Result<()> {
+    if config.max_count == 0 && config.max_size_mib == 0 {
+        return Ok(());
+    }
+    let mut demos = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "twdemo") {
+            let metadata = entry.metadata()?;
+            demos.push(DemoFile {
+                path,
+                size: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+    }
+    for path in demos_to_remove(
+        demos,
+        config.max_count as usize,
+        config.max_size_mib.saturating_mul(1024 * 1024),
+    ) {
+        log::info!("removing old server demo {path:?}");
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use game_config::config::{ConfigServerAutoDemo, ConfigServerAutoDemoMode};
+
+    use super::{AutoDemo, AutoDemoAction, DemoFile, demos_to_remove};
+
+    #[test]
+    fn per_round() {
+        let config = ConfigServerAutoDemo {
+            mode: ConfigServerAutoDemoMode::PerRound,
+            ..Default::default()
+        };
+        let mut auto_demo = AutoDemo::default();
+        assert_eq!(
+            auto_demo.update(&config, false, Some(true), false),
+            AutoDemoAction::Start
+        );
+        assert_eq!(auto_demo.round, 1);
+        assert_eq!(
+            auto_demo.update(&config, true, Some(true), false),
+            AutoDemoAction::None
+        );
+        assert_eq!(
+            auto_demo.update(&config, true, Some(false), false),
+            AutoDemoAction::Stop
+        );
+        assert_eq!(
+            auto_demo.update(&config, false, Some(false), false),
+            AutoDemoAction::None
+        );
+        assert_eq!(
+            auto_demo.update(&config, false, Some(true), false),
+            AutoDemoAction::Start
+        );
+        assert_eq!(auto_demo.round, 2);
+    }
+
+    #[test]
+    fn manual_demos_are_not_stopped() {
+        let config = ConfigServerAutoDemo {
+            mode: ConfigServerAutoDemoMode::WhilePlayerPresent,
+            player: "nameless tee".to_string(),
+            ..Default::default()
+        };
+        let mut auto_demo = AutoDemo::default();
+        assert_eq!(
+            auto_demo.update(&config, true, None, false),
+            AutoDemoAction::None
+        );
+        assert_eq!(
+            auto_demo.update(&config, false, None, true),
+            AutoDemoAction::Start
+        );
+        assert_eq!(
+            auto_demo.update(&config, true, None, false),
+            AutoDemoAction::Stop
+        );
+    }
+
+    #[test]
+    fn manual_demos_are_not_stopped_per_round() {
+        let config = ConfigServerAutoDemo {
+            mode: ConfigServerAutoDemoMode::PerRound,
+            ..Default::default()
+        };
+        let mut auto_demo = AutoDemo::default();
+        assert_eq!(
+            auto_demo.update(&config, false, Some(true), false),
+            AutoDemoAction::Start
+        );
+        auto_demo.on_manual_demo();
+        assert_eq!(
+            auto_demo.update(&config, true, Some(false), false),
+            AutoDemoAction::None
+        );
+        assert_eq!(
+            auto_demo.update(&config, true, Some(true), false),
+            AutoDemoAction::None
+        );
+        assert_eq!(auto_demo.round, 2);
+    }
+
+    #[test]
+    fn retention() {
+        let demo = |name: &str, size: u64, secs: u64| DemoFile {
+            path: name.into(),
+            size,
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+        };
+        let demos = vec![demo("c", 10, 3), demo("a", 10, 1), demo("b", 10, 2)];
+        let paths = |names: &[&str]| names.iter().map(PathBuf::from).collect::<Vec<_>>();
+
+        assert!(demos_to_remove(demos.clone(), 0, 0).is_empty());
+        assert_eq!(demos_to_remove(demos.clone(), 2, 0), paths(&["a"]));
+        assert_eq!(demos_to_remove(demos.clone(), 0, 15), paths(&["a", "b"]));
+        assert_eq!(demos_to_remove(demos, 1, 25), paths(&["a", "b"]));
+    }
+}
@@ -1,10 +1,12 @@
 #![allow(clippy::too_many_arguments)]
 
+pub mod auto_demo;
 pub mod auto_map_votes;
 pub mod client;
 pub mod local_server;
 pub mod map_votes;
 pub mod network_plugins;
+pub mod practice;
 pub mod rcon;
 pub mod server;
 pub mod server_game;
@@ -6,13 +6,18 @@ use game_base::local_server_info::{LocalServerInfo, LocalServerState, LocalServe
 use game_config::config::ConfigGame;
 use network::network::utils::create_certifified_keys;
 
-use crate::server::ddnet_server_main;
+use crate::{server::ddnet_server_main, server_game::ServerMap};
 
+/// Starts the internal server.
+///
+/// `map` is used instead of loading the map from the file system,
+/// if it's the map of the config.
 pub fn start_local_server(
     time: &SteadyClock,
     shared_info: Arc<LocalServerInfo>,
     config_engine: ConfigEngine,
     config_game: ConfigGame,
+    map: Option<ServerMap>,
 ) {
     let (cert, private_key) = create_certifified_keys();
     let server_cert_hash = cert
@@ -39,6 +44,7 @@ pub fn start_local_server(
                 shared_info_thread,
                 Default::default(),
                 Some((config_engine, config_game)),
+                map,
             )
         })
         .unwrap();
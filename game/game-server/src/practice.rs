@@ -0,0 +1,170 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use anyhow::anyhow;
+use game_config::config::ConfigServerPractice;
+use game_interface::{
+    interface::GameStateInterface,
+    types::game::{GameTickType, NonZeroGameTickType},
+};
+use pool::mt_datatypes::PoolCow as MtPoolCow;
+
+/// The slowest speed the game can be slowed down to.
+pub const MIN_PRACTICE_SPEED: f64 = 0.05;
+
+/// State of the practice mode: save states,
+/// the rewind history & the game speed.
+#[derive(Debug)]
+pub struct Practice {
+    /// Save states by their slot.
+    slots: BTreeMap<u32, MtPoolCow<'static, [u8]>>,
+    /// The most recent snapshots of the game, oldest first.
+    history: VecDeque<MtPoolCow<'static, [u8]>>,
+    /// The game speed, `1.0` is the normal speed.
+    speed: f64,
+}
+
+impl Default for Practice {
+    fn default() -> Self {
+        Self {
+            slots: Default::default(),
+            history: Default::default(),
+            speed: 1.0,
+        }
+    }
+}
+
+impl Practice {
+    /// Saves the current state of the game to the given slot.
+    pub fn save(&mut self, slot: u32, game: &impl GameStateInterface) -> anyhow::Result<()> {
+        let snapshot = game
+            .snapshot_for_hotreload()
+            .ok_or_else(|| anyhow!("The game does not support save states."))?;
+        self.slots.insert(slot, snapshot);
+        Ok(())
+    }
+
+    /// Loads the state of the game from the given slot.
+    pub fn load(&mut self, slot: u32, game: &mut impl GameStateInterface) -> anyhow::Result<()> {
+        let snapshot = self
+            .slots
+            .get(&slot)
+            .ok_or_else(|| anyhow!("Slot {slot} is empty."))?;
+        game.build_from_snapshot_by_savestate(snapshot);
+        // the history belongs to a different timeline now
+        self.history.clear();
+        Ok(())
+    }
+
+    /// Remembers the current state of the game for rewinding,
+    /// should be called after every game tick.
+    pub fn tick(
+        &mut self,
+        config: &ConfigServerPractice,
+        game: &impl GameStateInterface,
+        monotonic_tick: GameTickType,
+        ticks_in_a_second: NonZeroGameTickType,
+    ) {
+        let max_len = (config.rewind_secs * config.rewind_snapshots_per_second) as usize;
+        if !config.enabled || max_len == 0 {
+            self.history.clear();
+            return;
+        }
+
+        let interval =
+            (ticks_in_a_second.get() / config.rewind_snapshots_per_second.max(1) as u64).max(1);
+        if monotonic_tick % interval == 0
+            && let Some(snapshot) = game.snapshot_for_hotreload()
+        {
+            self.history.push_back(snapshot);
+        }
+        while self.history.len() > max_len {
+            self.history.pop_front();
+        }
+    }
+
+    /// Rewinds the game by about `secs` seconds.
+    ///
+    /// Returns the seconds that were actually rewound.
+    pub fn rewind(
+        &mut self,
+        config: &ConfigServerPractice,
+        secs: f64,
+        game: &mut impl GameStateInterface,
+    ) -> anyhow::Result<f64> {
+        let snapshots_per_second = config.rewind_snapshots_per_second.max(1);
+        let steps = rewind_steps(secs, snapshots_per_second, self.history.len())
+            .ok_or_else(|| anyhow!("There is nothing to rewind."))?;
+        self.history.truncate(self.history.len() - steps);
+        // the restored snapshot stays in the history,
+        // so rewinding again continues from there
+        let snapshot = self.history.back().unwrap();
+        game.build_from_snapshot_by_savestate(snapshot);
+        Ok(steps as f64 / snapshots_per_second as f64)
+    }
+
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    /// Sets the game speed, `1.0` is the normal speed.
+    pub fn set_speed(&mut self, speed: f64) -> anyhow::Result<()> {
+        if !(MIN_PRACTICE_SPEED..=1.0).contains(&speed) {
+            return Err(anyhow!(
+                "The speed must be between {MIN_PRACTICE_SPEED} and 1.0."
+            ));
+        }
+        self.speed = speed;
+        Ok(())
+    }
+
+    /// The amount of ticks the server should do in a second,
+    /// which is lower than the game's tick speed in slow motion.
+    pub fn tick_rate(&self, ticks_in_a_second: NonZeroGameTickType) -> NonZeroGameTickType {
+        tick_rate(self.speed, ticks_in_a_second)
+    }
+
+    /// Forgets all save states and the rewind history,
+    /// e.g. because a different map was loaded.
+    pub fn reset(&mut self) {
+        self.slots.clear();
+        self.history.clear();
+    }
+}
+
+/// How many snapshots have to be dropped from a history of `len` snapshots
+/// to rewind by `secs` seconds. At least the oldest snapshot is always kept.
+///
+/// Returns `None` if the history is empty.
+fn rewind_steps(secs: f64, snapshots_per_second: u32, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let steps = (secs.max(0.0) * snapshots_per_second as f64).round() as usize;
+    Some(steps.min(len - 1))
+}
+
+fn tick_rate(speed: f64, ticks_in_a_second: NonZeroGameTickType) -> NonZeroGameTickType {
+    let ticks = (ticks_in_a_second.get() as f64 * speed).round() as GameTickType;
+    NonZeroGameTickType::new(ticks).unwrap_or(NonZeroGameTickType::MIN)
+}
+
+#[cfg(test)]
+mod test {
+    use game_interface::types::game::NonZeroGameTickType;
+
+    use super::{rewind_steps, tick_rate};
+
+    #[test]
+    fn rewind_and_speed() {
+        assert_eq!(rewind_steps(1.0, 10, 0), None);
+        assert_eq!(rewind_steps(1.0, 10, 1), Some(0));
+        assert_eq!(rewind_steps(1.0, 10, 50), Some(10));
+        assert_eq!(rewind_steps(0.25, 10, 50), Some(3));
+        assert_eq!(rewind_steps(10.0, 10, 50), Some(49));
+
+        let ticks = NonZeroGameTickType::new(50).unwrap();
+        assert_eq!(tick_rate(1.0, ticks).get(), 50);
+        assert_eq!(tick_rate(0.5, ticks).get(), 25);
+        assert_eq!(tick_rate(0.001, ticks).get(), 1);
+    }
+}
@@ -71,4 +71,9 @@ pub enum ServerRconCommand {
     AddMiscVote,
     RemoveMiscVote,
     RecordDemo,
+    StopDemo,
+    PracticeSave,
+    PracticeLoad,
+    PracticeRewind,
+    PracticeSpeed,
 }
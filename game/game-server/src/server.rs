@@ -5,6 +5,7 @@ use std::{
     num::NonZeroUsize,
     path::PathBuf,
     sync::{Arc, Weak, atomic::AtomicBool},
+    thread::JoinHandle,
     time::Duration,
 };
 
@@ -68,6 +69,7 @@ use vanilla::{
 use x509_cert::der::Encode;
 
 use crate::{
+    auto_demo::{AutoDemo, AutoDemoAction, SERVER_DEMO_DIR, apply_retention},
     auto_map_votes::AutoMapVotes,
     client::{
         ClientSnapshotForDiff, ClientSnapshotStorage, Clients, ServerClient, ServerClientPlayer,
@@ -75,10 +77,11 @@ use crate::{
     },
     map_votes::{MapVotes, ServerMapVotes},
     network_plugins::{accounts_only::AccountsOnly, cert_ban::CertBans},
+    practice::Practice,
     rcon::{Rcon, ServerRconCommand},
     server_game::{
         ClientAuth, RESERVED_DDNET_NAMES, RESERVED_VANILLA_NAMES, ServerExtraVoteInfo, ServerGame,
-        ServerVote,
+        ServerMap, ServerVote,
     },
 };
 
@@ -111,7 +114,7 @@ use game_interface::{
     rcon_entries::{AuthLevel, ExecRconInput, RconEntries, RconEntry},
     tick_result::TickEvent,
     types::{
-        game::{GameEntityId, GameTickType},
+        game::{GameEntityId, GameTickType, NonZeroGameTickType},
         id_types::PlayerId,
         input::{CharacterInput, CharacterInputInfo},
         network_stats::PlayerNetworkStats,
@@ -119,6 +122,7 @@ use game_interface::{
             AccountId, PlayerBanReason, PlayerClientInfo, PlayerDropReason, PlayerKickReason,
             PlayerUniqueId,
         },
+        render::game::{GameRenderInfo, MatchRoundTimeType},
         snapshot::SnapshotClientInfo,
     },
     vote_commands::{VoteCommand, VoteCommandResultEvent},
@@ -219,6 +223,16 @@ pub struct Server {
 
     // server side demos
     demo_recorder: Option<DemoRecorder>,
+    auto_demo: AutoDemo,
+
+    /// A map that is not loaded from the file system,
+    /// e.g. the map of an editor playtest.
+    in_memory_map: Option<ServerMap>,
+
+    // practice mode
+    practice: Practice,
+    /// The tick rate the clients were last informed about.
+    sent_tick_rate: Option<NonZeroGameTickType>,
 
     // votes
     map_votes: ServerMapVotes,
@@ -419,6 +433,19 @@ impl Server {
                     cmd: ServerRconCommand::RecordDemo,
                 },
             ),
+            (
+                "stop_demo".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: Default::default(),
+                        description: "Stop the current server side demo recording."
+                            .try_into()
+                            .unwrap(),
+                        usage: "stop_demo".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::StopDemo,
+                },
+            ),
             (
                 "exec".try_into().unwrap(),
                 Command {
@@ -497,6 +524,74 @@ impl Server {
                     cmd: ServerRconCommand::RemoveMiscVote,
                 },
             ),
+            (
+                "practice.save".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Number,
+                            user_ty: None,
+                        }],
+                        description: "Saves the state of the game to the given slot \
+                            (practice mode only)."
+                            .try_into()
+                            .unwrap(),
+                        usage: "practice.save <slot>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::PracticeSave,
+                },
+            ),
+            (
+                "practice.load".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Number,
+                            user_ty: None,
+                        }],
+                        description: "Loads the state of the game from the given slot \
+                            (practice mode only)."
+                            .try_into()
+                            .unwrap(),
+                        usage: "practice.load <slot>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::PracticeLoad,
+                },
+            ),
+            (
+                "practice.rewind".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Float,
+                            user_ty: None,
+                        }],
+                        description: "Rewinds the game by the given amount of seconds \
+                            (practice mode only)."
+                            .try_into()
+                            .unwrap(),
+                        usage: "practice.rewind <seconds>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::PracticeRewind,
+                },
+            ),
+            (
+                "practice.speed".try_into().unwrap(),
+                Command {
+                    rcon: RconEntry {
+                        args: vec![CommandArg {
+                            ty: CommandArgType::Float,
+                            user_ty: None,
+                        }],
+                        description: "Sets the game speed, 1.0 is the normal speed \
+                            (practice mode only)."
+                            .try_into()
+                            .unwrap(),
+                        usage: "practice.speed <factor>".try_into().unwrap(),
+                    },
+                    cmd: ServerRconCommand::PracticeSpeed,
+                },
+            ),
         ];
 
         let mut rcon_vars: Vec<_> = Default::default();
@@ -652,6 +747,7 @@ impl Server {
         rcon_chain: CommandChain<ServerRconCommand>,
         cache: ParserCache,
         raw_rcon_input: &[String],
+        in_memory_map: Option<ServerMap>,
     ) -> anyhow::Result<Self> {
         let config_db = config_game.sv.db.clone();
         let accounts_enabled = !config_db.enable_accounts.is_empty();
@@ -935,6 +1031,9 @@ impl Server {
                 } else {
                     None
                 },
+                in_memory_map
+                    .clone()
+                    .filter(|in_memory_map| in_memory_map.name.as_str() == config_game.sv.map),
             )?,
 
             last_tick_time: time.now(),
@@ -956,6 +1055,12 @@ impl Server {
 
             // server side demo recorder
             demo_recorder: None,
+            auto_demo: Default::default(),
+
+            in_memory_map,
+
+            practice: Default::default(),
+            sent_tick_rate: None,
 
             // votes
             map_votes,
@@ -1221,6 +1326,17 @@ impl Server {
         self.broadcast_in_order_filtered(packet, channel, |_| true);
     }
 
+    /// The amount of ticks the server does in a second,
+    /// in slow motion the game is ticked less often.
+    fn tick_rate(&self) -> NonZeroGameTickType {
+        let ticks_in_a_second = self.game_server.game.game_tick_speed();
+        if self.config_game.sv.practice.enabled {
+            self.practice.tick_rate(ticks_in_a_second)
+        } else {
+            ticks_in_a_second
+        }
+    }
+
     fn send_vote(&self, vote_state: Option<VoteState>, start_time: Duration) {
         self.broadcast_in_order(
             ServerToClientMessage::Vote(vote_state.map(|mut vote_state| {
@@ -1855,6 +1971,24 @@ impl Server {
                 Ok(())
             }
 
+            fn practice_arg<T: std::str::FromStr>(
+                config: &ConfigGame,
+                cmd: &parser::Command,
+            ) -> anyhow::Result<T>
+            where
+                T::Err: std::error::Error + Send + Sync + 'static,
+            {
+                if !config.sv.practice.enabled {
+                    return Err(anyhow!(
+                        "Practice mode is not enabled, see sv.practice.enabled."
+                    ));
+                }
+                let (Syn::Number(arg) | Syn::Float(arg)) = &cmd.args[0].0 else {
+                    panic!("Command parser returned a non requested command arg");
+                };
+                Ok(arg.parse()?)
+            }
+
             match chain_cmd.cmd {
                 ServerRconCommand::BanId => {
                     let mut res = String::new();
@@ -2015,39 +2149,8 @@ impl Server {
                     Ok(res)
                 }
                 ServerRconCommand::RecordDemo => {
-                    let had_demo_recorder = self.demo_recorder.is_some();
-                    self.demo_recorder = Some(DemoRecorder::new(
-                        DemoRecorderCreateProps {
-                            base: DemoRecorderCreatePropsBase {
-                                map: self.game_server.map.name.as_str().try_into().unwrap(),
-                                map_hash: generate_hash_for(&self.game_server.map.map_file),
-                                game_options: GameStateCreateOptions {
-                                    hint_max_characters: Some(
-                                        self.config_game.sv.max_players as usize,
-                                    ),
-                                    account_db: None,
-                                    config: self.game_server.game.info.config.clone(),
-                                    initial_rcon_input: Default::default(),
-                                },
-                                required_resources: self.game_server.required_resources.clone(),
-                                client_local_infos: Default::default(),
-                                physics_module: self.game_server.game_mod.clone(),
-                                render_module: self.game_server.render_mod.clone(),
-                                physics_group_name: self
-                                    .game_server
-                                    .game
-                                    .info
-                                    .options
-                                    .physics_group_name
-                                    .clone(),
-                            },
-                            io: self.io.clone(),
-                            in_memory: None,
-                        },
-                        self.game_server.game.info.ticks_in_a_second,
-                        Some("server_demos".as_ref()),
-                        None,
-                    ));
+                    let had_demo_recorder = self.start_demo_recording(None);
+                    self.auto_demo.on_manual_demo();
                     Ok(format!(
                         "Started demo recording.{}",
                         if had_demo_recorder {
@@ -2057,7 +2160,148 @@ impl Server {
                         }
                     ))
                 }
+                ServerRconCommand::StopDemo => {
+                    if self.stop_demo_recording() {
+                        Ok("Stopped demo recording.".to_string())
+                    } else {
+                        Err(anyhow!("No demo is currently recorded."))
+                    }
+                }
+                ServerRconCommand::PracticeSave => {
+                    let slot: u32 = practice_arg(&self.config_game, &cmd)?;
+                    self.practice.save(slot, &self.game_server.game)?;
+                    Ok(format!("Saved the game to slot {slot}."))
+                }
+                ServerRconCommand::PracticeLoad => {
+                    let slot: u32 = practice_arg(&self.config_game, &cmd)?;
+                    self.practice.load(slot, &mut self.game_server.game)?;
+                    Ok(format!("Loaded the game from slot {slot}."))
+                }
+                ServerRconCommand::PracticeRewind => {
+                    let secs: f64 = practice_arg(&self.config_game, &cmd)?;
+                    let secs = self.practice.rewind(
+                        &self.config_game.sv.practice,
+                        secs,
+                        &mut self.game_server.game,
+                    )?;
+                    Ok(format!("Rewound the game by {secs:.1} seconds."))
+                }
+                ServerRconCommand::PracticeSpeed => {
+                    let speed: f64 = practice_arg(&self.config_game, &cmd)?;
+                    self.practice.set_speed(speed)?;
+                    Ok(format!("Set the game speed to {speed}."))
+                }
+            }
+        }
+    }
+
+    /// Starts a new server side demo, stopping the current one.
+    ///
+    /// Returns `true` if a previous demo was stopped.
+    fn start_demo_recording(&mut self, name: Option<String>) -> bool {
+        let had_demo_recorder = self.stop_demo_recording();
+        self.demo_recorder = Some(DemoRecorder::new(
+            DemoRecorderCreateProps {
+                base: DemoRecorderCreatePropsBase {
+                    map: self.game_server.map.name.as_str().try_into().unwrap(),
+                    map_hash: generate_hash_for(&self.game_server.map.map_file),
+                    game_options: GameStateCreateOptions {
+                        hint_max_characters: Some(self.config_game.sv.max_players as usize),
+                        account_db: None,
+                        config: self.game_server.game.info.config.clone(),
+                        initial_rcon_input: Default::default(),
+                    },
+                    required_resources: self.game_server.required_resources.clone(),
+                    client_local_infos: Default::default(),
+                    physics_module: self.game_server.game_mod.clone(),
+                    render_module: self.game_server.render_mod.clone(),
+                    physics_group_name: self
+                        .game_server
+                        .game
+                        .info
+                        .options
+                        .physics_group_name
+                        .clone(),
+                },
+                io: self.io.clone(),
+                in_memory: None,
+            },
+            self.game_server.game.info.ticks_in_a_second,
+            Some(SERVER_DEMO_DIR.as_ref()),
+            name,
+        ));
+        had_demo_recorder
+    }
+
+    /// Stops the current server side demo.
+    ///
+    /// Returns `true` if a demo was recorded.
+    fn stop_demo_recording(&mut self) -> bool {
+        let Some(demo_recorder) = self.demo_recorder.take() else {
+            return false;
+        };
+        self.apply_demo_retention(demo_recorder.finish());
+        true
+    }
+
+    /// Removes the oldest server side demos, if the configured
+    /// limits are exceeded, once the finished demo is completely written.
+    fn apply_demo_retention(&self, demo_writer: JoinHandle<()>) {
+        let config = self.config_game.sv.auto_demo.clone();
+        let dir = self
+            .io
+            .fs
+            .get_save_path()
+            .join("demos")
+            .join(SERVER_DEMO_DIR);
+        self.io.rt.spawn_without_lifetime(async move {
+            demo_writer
+                .join()
+                .map_err(|_| anyhow!("failed to write the server demo"))?;
+            apply_retention(&dir, &config)
+        });
+    }
+
+    /// Starts or stops automatic demo recordings.
+    fn auto_demo_tick(&mut self) {
+        let round_active = self
+            .game_server
+            .game
+            .all_stages(0.0)
+            .values()
+            .find_map(|stage| match &stage.game {
+                GameRenderInfo::Match {
+                    round_time_type, ..
+                } => Some(!matches!(
+                    round_time_type,
+                    MatchRoundTimeType::GameOver { .. }
+                )),
+                GameRenderInfo::Race {} => None,
+            });
+        let auto_demo_player = &self.config_game.sv.auto_demo.player;
+        let player_present = !auto_demo_player.is_empty()
+            && self
+                .game_server
+                .cached_character_infos
+                .values()
+                .any(|info| {
+                    info.player_info.is_some() && info.info.name.as_str() == auto_demo_player
+                });
+
+        match self.auto_demo.update(
+            &self.config_game.sv.auto_demo,
+            self.demo_recorder.is_some(),
+            round_active,
+            player_present,
+        ) {
+            AutoDemoAction::Start => {
+                let name = self.auto_demo.demo_name(self.game_server.map.name.as_str());
+                self.start_demo_recording(Some(name));
             }
+            AutoDemoAction::Stop => {
+                self.stop_demo_recording();
+            }
+            AutoDemoAction::None => {}
         }
     }
 
@@ -2345,6 +2589,12 @@ impl Server {
                             ),
                             con_id,
                         );
+                        // the game data of the client was reset by the load
+                        self.network.send_in_order_to(
+                            &ServerToClientMessage::TickRate(self.tick_rate()),
+                            con_id,
+                            NetworkInOrderChannel::Global,
+                        );
                     } else {
                         self.network.send_unordered_to(
                             &ServerToClientMessage::ReadyResponse(MsgClReadyResponse::Error {
@@ -3177,6 +3427,15 @@ impl Server {
             }
 
             let ticks_in_a_second = self.game_server.game.game_tick_speed();
+            let tick_rate = self.tick_rate();
+            // the clients have to predict with the same rate
+            if self.sent_tick_rate != Some(tick_rate) {
+                self.sent_tick_rate = Some(tick_rate);
+                self.broadcast_in_order(
+                    ServerToClientMessage::TickRate(tick_rate),
+                    NetworkInOrderChannel::Global,
+                );
+            }
 
             // get time before checking ticks
             cur_time = self.time.now();
@@ -3271,7 +3530,7 @@ impl Server {
                 }
             }
 
-            while is_next_tick(cur_time, &mut self.last_tick_time, ticks_in_a_second) {
+            while is_next_tick(cur_time, &mut self.last_tick_time, tick_rate) {
                 // apply all queued inputs
                 if let Some(mut inputs) = self
                     .game_server
@@ -3358,6 +3617,18 @@ impl Server {
                     );
                 }
 
+                // check about once per second, if demos should be recorded
+                if self.game_server.cur_monotonic_tick % ticks_in_a_second.get() == 0 {
+                    self.auto_demo_tick();
+                }
+
+                self.practice.tick(
+                    &self.config_game.sv.practice,
+                    &self.game_server.game,
+                    self.game_server.cur_monotonic_tick,
+                    ticks_in_a_second,
+                );
+
                 if let Some(recorder) = &mut self.demo_recorder {
                     recorder.add_snapshot(
                         self.game_server.cur_monotonic_tick,
@@ -3563,12 +3834,11 @@ impl Server {
             if is_next_tick(
                 cur_time,
                 &mut self.last_tick_time.clone(), /* <-- dummy */
-                ticks_in_a_second,
+                tick_rate,
             ) {
                 std::thread::yield_now();
             } else {
-                let next_tick_time =
-                    time_until_tick(ticks_in_a_second) - (cur_time - self.last_tick_time);
+                let next_tick_time = time_until_tick(tick_rate) - (cur_time - self.last_tick_time);
 
                 //let mut guard = self.game_event_generator_server.blocking_lock();
                 //guard = guard.ev_cond.wait_timeout(guard.into(), next_tick_time);
@@ -3621,6 +3891,9 @@ impl Server {
             } else {
                 None
             },
+            self.in_memory_map
+                .clone()
+                .filter(|in_memory_map| in_memory_map.name == *map),
         )?;
         if let Some(snapshot) = snapshot {
             self.game_server
@@ -3680,6 +3953,11 @@ impl Server {
     }
 
     fn load_map(&mut self, map: &NetworkReducedAsciiString<MAX_MAP_NAME_LEN>) {
+        // a demo can only contain a single map
+        self.stop_demo_recording();
+        self.auto_demo = Default::default();
+        // save states only work for the map they were made on
+        self.practice.reset();
         self.config_game.sv.map = map.to_string();
         if let Err(err) = self.load_impl(None, map) {
             log::error!("Fatal error during map load: {err}");
@@ -3715,6 +3993,7 @@ pub fn ddnet_server_main<const IS_INTERNAL_SERVER: bool>(
     shared_info: Arc<LocalServerInfo>,
     args: Vec<String>,
     config_overwrite: Option<(ConfigEngine, ConfigGame)>,
+    in_memory_map: Option<ServerMap>,
 ) -> anyhow::Result<()> {
     let thread_pool = Arc::new(
         rayon::ThreadPoolBuilder::new()
@@ -3788,6 +4067,7 @@ pub fn ddnet_server_main<const IS_INTERNAL_SERVER: bool>(
         rcon_chain,
         cache,
         &skipped_lines,
+        in_memory_map,
     )?;
 
     // Handle remaining args after the server started.
@@ -70,6 +70,7 @@ impl ServerPlayer {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct ServerMap {
     pub name: NetworkReducedAsciiString<MAX_MAP_NAME_LEN>,
     pub map_file: Vec<u8>,
@@ -434,6 +435,7 @@ impl ServerGame {
         download_server_port_v4: u16,
         download_server_port_v6: u16,
         server_provided_assets_path: Option<&Path>,
+        in_memory_map: Option<ServerMap>,
     ) -> anyhow::Result<Self> {
         let fs = io.fs.clone();
         let required_resources = io.rt.spawn(async move {
@@ -441,7 +443,10 @@ impl ServerGame {
             Ok(serde_json::from_slice(&file)?)
         });
 
-        let map = ServerMap::new(map_name, io, runtime_thread_pool)?;
+        let map = match in_memory_map {
+            Some(map) => map,
+            None => ServerMap::new(map_name, io, runtime_thread_pool)?,
+        };
         let (game_state_mod, game_mod, game_mod_file, game_mod_name, game_mod_blake3_hash) =
             match game_mod {
                 x if RESERVED_VANILLA_NAMES.contains(&x) => (
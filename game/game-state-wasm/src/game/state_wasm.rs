@@ -206,6 +206,9 @@ pub mod state_wasm {
         #[wasm_func_auto_call]
         fn build_from_snapshot_by_hotreload(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {}
 
+        #[wasm_func_auto_call]
+        fn build_from_snapshot_by_savestate(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {}
+
         #[wasm_func_auto_call]
         fn build_from_snapshot_for_prev(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {}
 
@@ -336,6 +336,13 @@ impl GameStateInterface for GameStateWasmManager {
             .build_from_snapshot_by_hotreload(snapshot)
     }
 
+    #[instrument(level = "trace", skip_all)]
+    fn build_from_snapshot_by_savestate(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {
+        self.state
+            .as_mut()
+            .build_from_snapshot_by_savestate(snapshot)
+    }
+
     #[instrument(level = "trace", skip_all)]
     fn build_from_snapshot_for_prev(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {
         self.state.as_mut().build_from_snapshot_for_prev(snapshot)
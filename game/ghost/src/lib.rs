@@ -1 +1,2 @@
 pub mod recorder;
+pub mod storage;
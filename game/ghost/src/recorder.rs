@@ -1,5 +1,13 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread::JoinHandle,
+    time::Duration,
+};
 
+use base_io::runtime::IoRuntimeTask;
+use base_io_traits::fs_traits::FileSystemInterface;
 use demo::recorder::{DemoRecorder, DemoRecorderCreateProps};
 use game_interface::{
     events::{GameEvents, GameWorldAction, GameWorldEvent, GameWorldNotificationEvent},
@@ -10,29 +18,178 @@ use game_interface::{
 
 use pool::mt_datatypes::PoolCow as MtPoolCow;
 
+use crate::storage::{
+    GHOST_INDEX_FILE, GhostEntry, GhostIndex, ghost_dir, ghost_sub_dir, load_ghost_index,
+    migrate_legacy_ghosts,
+};
+
+#[derive(Debug)]
+struct GhostRecording {
+    demo: DemoRecorder,
+    file_name: String,
+}
+
+#[derive(Debug)]
+enum GhostIndexState {
+    Loading(IoRuntimeTask<GhostIndex>),
+    Loaded(GhostIndex),
+}
+
 #[derive(Debug)]
 pub struct GhostRecorder {
-    players: HashMap<PlayerId, DemoRecorder>,
+    players: HashMap<PlayerId, GhostRecording>,
 
     props: DemoRecorderCreateProps,
     ticks_per_second: NonZeroGameTickType,
-    base_name: String,
+
+    /// Sub directory of the demo directory for this map's ghosts.
+    sub_dir: PathBuf,
+    /// Directory relative to the save path for this map's ghosts.
+    dir: PathBuf,
+    index: Option<GhostIndexState>,
+    index_write_task: Option<IoRuntimeTask<()>>,
+    /// A ghost was kept since the last [`GhostRecorder::take_new_ghost`].
+    new_ghost: bool,
+    /// How many of the best runs are kept per map.
+    max_ghosts: usize,
 }
 
 impl GhostRecorder {
     pub fn new(
         props: DemoRecorderCreateProps,
         ticks_per_second: NonZeroGameTickType,
-        base_name: String,
+        max_ghosts: usize,
     ) -> Self {
+        let map_name = props.base.map.to_string();
+        let map_hash = props.base.map_hash;
+        let sub_dir = ghost_sub_dir(&map_name, &map_hash);
+        let dir = ghost_dir(&map_name, &map_hash);
+
+        let fs = props.io.fs.clone();
+        let index_dir = dir.clone();
+        let index_task = props.io.rt.spawn(async move {
+            if let Err(err) = migrate_legacy_ghosts(fs.as_ref(), &map_name, &map_hash).await {
+                log::warn!("failed to migrate legacy ghosts: {err}");
+            }
+            let (mut index, changed) = load_ghost_index(fs.as_ref(), &index_dir).await?;
+            // the amount of stored ghosts might have been lowered
+            let pruned = index.prune(max_ghosts);
+            if changed || !pruned.is_empty() {
+                Self::store_index(fs, &index_dir, index.to_json()?, pruned).await?;
+            }
+            Ok(index)
+        });
+
         Self {
             players: Default::default(),
             props,
             ticks_per_second,
-            base_name,
+            sub_dir,
+            dir,
+            index: Some(GhostIndexState::Loading(index_task)),
+            index_write_task: None,
+            new_ghost: false,
+            max_ghosts,
         }
     }
 
+    /// The ghost index of the current map.
+    ///
+    /// __Blocks__ if the index is still loading.
+    fn index(&mut self) -> &mut GhostIndex {
+        let index = match self.index.take() {
+            Some(GhostIndexState::Loading(task)) => task.get().unwrap_or_else(|err| {
+                log::warn!("failed to load the ghost index, starting a new one: {err}");
+                Default::default()
+            }),
+            Some(GhostIndexState::Loaded(index)) => index,
+            None => Default::default(),
+        };
+        match self.index.insert(GhostIndexState::Loaded(index)) {
+            GhostIndexState::Loaded(index) => index,
+            GhostIndexState::Loading(_) => unreachable!(),
+        }
+    }
+
+    /// Writes the index & removes the files of the pruned ghosts.
+    async fn store_index(
+        fs: Arc<dyn FileSystemInterface>,
+        dir: &Path,
+        index: Vec<u8>,
+        pruned: Vec<GhostEntry>,
+    ) -> anyhow::Result<()> {
+        fs.create_dir(dir).await?;
+        fs.write_file(&dir.join(GHOST_INDEX_FILE), index).await?;
+        for entry in pruned {
+            let path = dir.join(format!("{}.twdemo", entry.file_name));
+            if let Err(err) = fs.remove_file(&path).await {
+                log::warn!("failed to remove pruned ghost {path:?}: {err}");
+            }
+        }
+        Ok(())
+    }
+
+    fn write_index(&mut self, pruned: Vec<GhostEntry>, demo_writer: JoinHandle<()>) {
+        let index = match self.index().to_json() {
+            Ok(index) => index,
+            Err(err) => {
+                log::error!("failed to serialize the ghost index: {err}");
+                return;
+            }
+        };
+        let fs = self.props.io.fs.clone();
+        let dir = self.dir.clone();
+        // The previous write is implicitly awaited on drop,
+        // which keeps the writes in order.
+        self.index_write_task = Some(self.props.io.rt.spawn(async move {
+            // only list ghosts that are completely written
+            demo_writer
+                .join()
+                .map_err(|_| anyhow::anyhow!("failed to write the ghost demo"))?;
+            Self::store_index(fs, &dir, index, pruned).await
+        }));
+    }
+
+    /// A player finished a race, the ghost is either stored
+    /// as one of the best runs or thrown away.
+    fn on_finish(&mut self, player_id: &PlayerId, finish_time: Duration, player_name: String) {
+        let Some(recording) = self.players.remove(player_id) else {
+            return;
+        };
+        let max_ghosts = self.max_ghosts;
+        if !self.index().would_keep(finish_time, max_ghosts) {
+            recording.demo.cancel();
+            return;
+        }
+
+        let pruned = self.index().insert(
+            GhostEntry {
+                file_name: recording.file_name,
+                time: finish_time,
+                date: chrono::Utc::now(),
+                player: player_name,
+            },
+            max_ghosts,
+        );
+        let demo_writer = recording.demo.finish();
+        self.write_index(pruned, demo_writer);
+        self.new_ghost = true;
+    }
+
+    /// Whether a new ghost was stored since the last call,
+    /// e.g. so the shown ghosts can be reloaded.
+    pub fn take_new_ghost(&mut self) -> bool {
+        let stored = self.new_ghost
+            && self
+                .index_write_task
+                .as_ref()
+                .is_none_or(|task| task.is_finished());
+        if stored {
+            self.new_ghost = false;
+        }
+        stored
+    }
+
     pub fn on_snapshot(
         &mut self,
         monotonic_tick: u64,
@@ -44,33 +201,44 @@ impl GhostRecorder {
         for (player_id, ghost) in ghosts.players.drain() {
             match ghost {
                 GhostResultPlayer::GhostInactive { ghost_snapshot } => {
-                    if let Some(demo_recorder) = self.players.get_mut(&player_id) {
-                        demo_recorder.add_snapshot(monotonic_tick, ghost_snapshot.to_vec());
+                    if let Some(recording) = self.players.get_mut(&player_id) {
+                        recording
+                            .demo
+                            .add_snapshot(monotonic_tick, ghost_snapshot.to_vec());
                     }
                 }
                 GhostResultPlayer::GhostRecordStarted { ghost_snapshot }
                 | GhostResultPlayer::GhostRecordActive { ghost_snapshot } => {
-                    let demo_recorder = self.players.entry(player_id).or_insert_with(|| {
-                        DemoRecorder::new(
-                            self.props.clone(),
-                            self.ticks_per_second,
-                            Some("ghosts".as_ref()),
-                            Some(self.base_name.clone()),
-                        )
+                    let recording = self.players.entry(player_id).or_insert_with(|| {
+                        let file_name = chrono::Utc::now()
+                            .format("%Y_%m_%d_%H_%M_%S_%3f")
+                            .to_string();
+                        GhostRecording {
+                            demo: DemoRecorder::new(
+                                self.props.clone(),
+                                self.ticks_per_second,
+                                Some(&self.sub_dir),
+                                Some(file_name.clone()),
+                            ),
+                            file_name,
+                        }
                     });
 
-                    demo_recorder.add_snapshot(monotonic_tick, ghost_snapshot.to_vec());
+                    recording
+                        .demo
+                        .add_snapshot(monotonic_tick, ghost_snapshot.to_vec());
                 }
             }
         }
     }
 
-    pub fn on_event(&mut self, events: &GameEvents) {
-        fn on_finish(players: &mut HashMap<PlayerId, DemoRecorder>, player_id: &PlayerId) {
-            // finish
-            players.remove(player_id);
-        }
-
+    /// `player_name` is used to store the name of the player
+    /// that finished a run in the ghost's metadata.
+    pub fn on_event(
+        &mut self,
+        events: &GameEvents,
+        player_name: impl Fn(&PlayerId) -> Option<String>,
+    ) {
         for world in events.worlds.values() {
             for event in world.events.values() {
                 match event {
@@ -79,19 +247,32 @@ impl GhostRecorder {
                     }
                     GameWorldEvent::Notification(event) => match event {
                         GameWorldNotificationEvent::Action(ev) => match ev {
-                            GameWorldAction::RaceFinish { character, .. } => {
-                                on_finish(&mut self.players, character)
-                            }
-                            GameWorldAction::RaceTeamFinish { characters, .. } => {
+                            GameWorldAction::RaceFinish {
+                                character,
+                                finish_time,
+                            } => self.on_finish(
+                                character,
+                                *finish_time,
+                                player_name(character).unwrap_or_default(),
+                            ),
+                            GameWorldAction::RaceTeamFinish {
+                                characters,
+                                finish_time,
+                                ..
+                            } => {
                                 for character in characters.iter() {
-                                    on_finish(&mut self.players, character);
+                                    self.on_finish(
+                                        character,
+                                        *finish_time,
+                                        player_name(character).unwrap_or_default(),
+                                    );
                                 }
                             }
                             GameWorldAction::Kill { victims, .. } => {
                                 // reset ghost on kill
                                 for victim in victims.iter() {
-                                    if let Some(demo) = self.players.remove(victim) {
-                                        demo.cancel();
+                                    if let Some(recording) = self.players.remove(victim) {
+                                        recording.demo.cancel();
                                     }
                                 }
                             }
@@ -0,0 +1,300 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use base::hash::{Hash, fmt_hash};
+use base_io_traits::fs_traits::{FileSystemEntryTy, FileSystemInterface};
+use chrono::{DateTime, Utc};
+use demo::{DemoHeader, utils::deser_ex};
+use serde::{Deserialize, Serialize};
+
+/// Name of the index file inside a map's ghost directory.
+pub const GHOST_INDEX_FILE: &str = "index.json";
+
+/// The sub directory (relative to the demo directory) in which
+/// all ghosts of a map are stored.
+pub fn ghost_sub_dir(map_name: &str, map_hash: &Hash) -> PathBuf {
+    format!("ghosts/{}_{}", map_name, fmt_hash(map_hash)).into()
+}
+
+/// The directory (relative to the save path) in which
+/// all ghosts of a map are stored.
+pub fn ghost_dir(map_name: &str, map_hash: &Hash) -> PathBuf {
+    PathBuf::from("demos").join(ghost_sub_dir(map_name, map_hash))
+}
+
+/// The directory (relative to the save path) in which the ghosts
+/// of a map were stored before the ghost index existed.
+fn legacy_ghost_dir(map_name: &str, map_hash: &Hash) -> PathBuf {
+    format!("ghosts/{}_{}", map_name, fmt_hash(map_hash)).into()
+}
+
+/// Metadata of a single stored ghost run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GhostEntry {
+    /// File name of the ghost demo (without extension).
+    pub file_name: String,
+    /// The finish time of the run.
+    pub time: Duration,
+    /// When the run was finished.
+    pub date: DateTime<Utc>,
+    /// The name of the player that did the run.
+    pub player: String,
+}
+
+/// All stored ghosts of a single map.
+///
+/// The entries are always sorted by their finish time,
+/// so the best run comes first.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct GhostIndex {
+    pub entries: Vec<GhostEntry>,
+}
+
+impl GhostIndex {
+    pub fn from_json_slice(json: &[u8]) -> anyhow::Result<Self> {
+        let mut index: Self = serde_json::from_slice(json)?;
+        index.entries.sort_by_key(|entry| entry.time);
+        Ok(index)
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+
+    /// The fastest stored run.
+    pub fn best(&self) -> Option<&GhostEntry> {
+        self.entries.first()
+    }
+
+    /// Whether a run with the given time would be kept,
+    /// if at most `max_entries` runs are stored.
+    pub fn would_keep(&self, time: Duration, max_entries: usize) -> bool {
+        max_entries > 0
+            && (self.entries.len() < max_entries
+                || self
+                    .entries
+                    .get(max_entries - 1)
+                    .is_some_and(|worst| time < worst.time))
+    }
+
+    /// Inserts a new run and prunes all runs that are worse
+    /// than the best `max_entries` runs.
+    ///
+    /// Returns the pruned entries, whose files should be deleted.
+    /// This can include the newly inserted entry.
+    pub fn insert(&mut self, entry: GhostEntry, max_entries: usize) -> Vec<GhostEntry> {
+        let index = self.entries.partition_point(|e| e.time <= entry.time);
+        self.entries.insert(index, entry);
+        self.prune(max_entries)
+    }
+
+    /// Prunes all runs that are worse than the best `max_entries` runs.
+    ///
+    /// Returns the pruned entries, whose files should be deleted.
+    pub fn prune(&mut self, max_entries: usize) -> Vec<GhostEntry> {
+        if self.entries.len() > max_entries {
+            self.entries.split_off(max_entries)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Removes the entry with the given file name.
+    pub fn remove(&mut self, file_name: &str) -> Option<GhostEntry> {
+        self.entries
+            .iter()
+            .position(|entry| entry.file_name == file_name)
+            .map(|index| self.entries.remove(index))
+    }
+
+    /// Adds all ghost demos of `files` (file name & content) that are not
+    /// part of the index yet. Their finish time is the length of the demo.
+    ///
+    /// Returns `true` if any entry was added.
+    fn add_unindexed(&mut self, files: impl IntoIterator<Item = (String, Vec<u8>)>) -> bool {
+        let mut added = false;
+        for (name, demo) in files {
+            let Some(file_name) = name.strip_suffix(".twdemo") else {
+                continue;
+            };
+            if self
+                .entries
+                .iter()
+                .any(|entry| entry.file_name == file_name)
+            {
+                continue;
+            }
+            match deser_ex::<DemoHeader>(&demo, true) {
+                Ok((header, _)) if !header.len.is_zero() => {
+                    self.insert(
+                        GhostEntry {
+                            file_name: file_name.to_string(),
+                            time: header.len,
+                            date: Default::default(),
+                            player: Default::default(),
+                        },
+                        usize::MAX,
+                    );
+                    added = true;
+                }
+                _ => log::warn!("ignoring broken ghost {name}"),
+            }
+        }
+        added
+    }
+}
+
+/// Names of all ghost demos in the given directory.
+async fn ghost_files(fs: &dyn FileSystemInterface, dir: &Path) -> Vec<String> {
+    // a missing directory simply has no ghosts
+    fs.entries_in_dir(dir)
+        .await
+        .map(|entries| {
+            entries
+                .into_iter()
+                .filter(|(name, ty)| {
+                    matches!(ty, FileSystemEntryTy::File { .. }) && name.ends_with(".twdemo")
+                })
+                .map(|(name, _)| name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Loads the ghost index from a map's ghost directory.
+///
+/// Ghost demos in the directory that are missing in the index,
+/// e.g. because they were stored before the index existed,
+/// are added to it.
+///
+/// Returns the index & whether it differs from the stored one.
+pub async fn load_ghost_index(
+    fs: &dyn FileSystemInterface,
+    dir: &Path,
+) -> anyhow::Result<(GhostIndex, bool)> {
+    let index_path = dir.join(GHOST_INDEX_FILE);
+    let mut index = if fs.file_exists(&index_path).await {
+        GhostIndex::from_json_slice(&fs.read_file(&index_path).await?)?
+    } else {
+        GhostIndex::default()
+    };
+
+    let mut files = Vec::new();
+    for name in ghost_files(fs, dir).await {
+        if index
+            .entries
+            .iter()
+            .all(|entry| Some(entry.file_name.as_str()) != name.strip_suffix(".twdemo"))
+        {
+            let demo = fs.read_file(&dir.join(&name)).await?;
+            files.push((name, demo));
+        }
+    }
+    let changed = index.add_unindexed(files);
+    Ok((index, changed))
+}
+
+/// Moves the ghosts of a map from the legacy directory into
+/// the map's ghost directory, where [`load_ghost_index`] picks them up.
+pub async fn migrate_legacy_ghosts(
+    fs: &dyn FileSystemInterface,
+    map_name: &str,
+    map_hash: &Hash,
+) -> anyhow::Result<()> {
+    let legacy_dir = legacy_ghost_dir(map_name, map_hash);
+    let dir = ghost_dir(map_name, map_hash);
+    for name in ghost_files(fs, &legacy_dir).await {
+        let demo = fs.read_file(&legacy_dir.join(&name)).await?;
+        fs.create_dir(&dir).await?;
+        fs.write_file(&dir.join(&name), demo).await?;
+        fs.remove_file(&legacy_dir.join(&name)).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use demo::DemoHeader;
+
+    use super::{GhostEntry, GhostIndex};
+
+    fn entry(name: &str, secs: u64) -> GhostEntry {
+        GhostEntry {
+            file_name: name.to_string(),
+            time: Duration::from_secs(secs),
+            date: Default::default(),
+            player: "nameless tee".to_string(),
+        }
+    }
+
+    #[test]
+    fn keeps_best_runs() {
+        let mut index = GhostIndex::default();
+        assert!(index.insert(entry("a", 30), 2).is_empty());
+        assert!(index.insert(entry("b", 20), 2).is_empty());
+        assert_eq!(index.best().unwrap().file_name, "b");
+
+        assert!(!index.would_keep(Duration::from_secs(40), 2));
+        assert!(index.would_keep(Duration::from_secs(25), 2));
+
+        let pruned = index.insert(entry("c", 25), 2);
+        assert_eq!(pruned, vec![entry("a", 30)]);
+        assert_eq!(
+            index
+                .entries
+                .iter()
+                .map(|e| e.file_name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "c"]
+        );
+
+        let pruned = index.insert(entry("d", 50), 2);
+        assert_eq!(pruned, vec![entry("d", 50)]);
+    }
+
+    #[test]
+    fn adds_unindexed_ghosts() {
+        let header = |secs: u64| {
+            bincode::serde::encode_to_vec(
+                DemoHeader {
+                    len: Duration::from_secs(secs),
+                    size_ext: 1,
+                    size_chunks: 1,
+                },
+                bincode::config::standard().with_fixed_int_encoding(),
+            )
+            .unwrap()
+        };
+
+        let mut index = GhostIndex::default();
+        index.insert(entry("a", 30), 5);
+        assert!(!index.add_unindexed([("a.twdemo".to_string(), header(30))]));
+        assert!(index.add_unindexed([
+            ("b.twdemo".to_string(), header(20)),
+            ("broken.twdemo".to_string(), header(0)),
+            ("c.txt".to_string(), header(10)),
+        ]));
+        assert_eq!(
+            index
+                .entries
+                .iter()
+                .map(|e| (e.file_name.as_str(), e.time.as_secs()))
+                .collect::<Vec<_>>(),
+            vec![("b", 20), ("a", 30)]
+        );
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let mut index = GhostIndex::default();
+        index.insert(entry("a", 30), 5);
+        index.insert(entry("b", 10), 5);
+        let index = GhostIndex::from_json_slice(&index.to_json().unwrap()).unwrap();
+        assert_eq!(index.best().unwrap().file_name, "b");
+        assert_eq!(index.entries.len(), 2);
+    }
+}
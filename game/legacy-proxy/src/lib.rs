@@ -1,4 +1,5 @@
 mod client;
+pub mod ping;
 pub mod projectile;
 mod socket;
 
@@ -0,0 +1,108 @@
+use std::{
+    io,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use arrayvec::ArrayVec;
+use libtw2_gamenet_ddnet::msg::{self, Connless};
+use libtw2_net::{
+    Net, Timestamp,
+    net::{Callback, ChunkOrEvent},
+};
+use libtw2_packer::{Unpacker, with_packer};
+use rand::RngCore;
+use tokio::net::UdpSocket;
+
+use crate::client::WarnPkt;
+
+/// Collects the packets libtw2 wants to send,
+/// so they can be send over an async socket.
+struct Packets {
+    start: Instant,
+    packets: Vec<Vec<u8>>,
+}
+
+impl Callback<SocketAddr> for Packets {
+    type Error = io::Error;
+    fn secure_random(&mut self, buffer: &mut [u8]) {
+        rand::rng().fill_bytes(buffer)
+    }
+    fn send(&mut self, _addr: SocketAddr, data: &[u8]) -> Result<(), io::Error> {
+        self.packets.push(data.to_vec());
+        Ok(())
+    }
+    fn time(&mut self) -> Timestamp {
+        Timestamp::from_secs_since_epoch(0) + self.start.elapsed()
+    }
+}
+
+/// Measures the round trip time to a legacy server
+/// using the server info request of the legacy protocol.
+///
+/// Unlike [`crate::proxy_run`] this never connects to the server.
+pub async fn legacy_server_ping(addr: SocketAddr, timeout: Duration) -> anyhow::Result<Duration> {
+    let socket = UdpSocket::bind(if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    })
+    .await?;
+    let mut packets = Packets {
+        start: Instant::now(),
+        packets: Default::default(),
+    };
+    let mut net = Net::<SocketAddr>::client();
+
+    let token = rand::rng().next_u32() as u8;
+    let mut buf: ArrayVec<[u8; 2048]> = ArrayVec::new();
+    with_packer(&mut buf, |p| {
+        Connless::RequestInfo(msg::connless::RequestInfo { token })
+            .encode(p)
+            .unwrap()
+    });
+    net.send_connless(&mut packets, addr, &buf)?;
+
+    let start = Instant::now();
+    for packet in packets.packets.drain(..) {
+        socket.send_to(&packet, addr).await?;
+    }
+
+    tokio::time::timeout(timeout, async {
+        let mut data = Vec::with_capacity(4096);
+        let mut buf = Vec::with_capacity(4096);
+        loop {
+            data.clear();
+            let (_, from) = socket.recv_buf_from(&mut data).await?;
+            if from != addr {
+                continue;
+            }
+            let elapsed = start.elapsed();
+            buf.clear();
+            let (iter, res) = net.feed(
+                &mut packets,
+                &mut WarnPkt(addr, &data),
+                addr,
+                &data,
+                &mut buf,
+            );
+            res?;
+            let answered = iter.into_iter().any(|chunk| match chunk {
+                ChunkOrEvent::Connless(msg) => matches!(
+                    Connless::decode(&mut WarnPkt(addr, msg.data), &mut Unpacker::new(msg.data)),
+                    Ok(Connless::Info(msg::connless::Info { token: info_token, .. }))
+                    | Ok(Connless::InfoExtended(msg::connless::InfoExtended {
+                        token: info_token, ..
+                    })) if info_token as u8 == token
+                ),
+                _ => false,
+            });
+            if answered {
+                return anyhow::Ok(elapsed);
+            }
+        }
+    })
+    .await
+    .map_err(|_| anyhow!("legacy server {addr} did not answer the info request in time"))?
+}
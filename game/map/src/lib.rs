@@ -3,8 +3,10 @@
 
 pub mod file;
 pub mod header;
+pub mod lint;
 pub mod map;
 pub mod skeleton;
+pub mod tiles;
 pub mod types;
 pub mod utils;
 
@@ -211,4 +213,35 @@ mod test {
         //compression_tests_for_map("ctf1");
         compression_tests_for_map("arctic");
     }
+
+    #[test]
+    fn json_round_trip() {
+        let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../");
+        std::env::set_current_dir(workspace_root).unwrap();
+        let io = IoFileSys::new(|rt| {
+            Arc::new(
+                FileSystem::new(rt, "ddnet-test", "ddnet-test", "ddnet-test", "ddnet-test")
+                    .unwrap(),
+            )
+        });
+
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+
+        let fs = io.fs.clone();
+        let file = io
+            .rt
+            .spawn(async move { fs.read_file("map/maps/arctic.twmap.tar".as_ref()).await })
+            .get()
+            .unwrap();
+        let map = Map::read(&MapFileReader::new(file).unwrap(), &thread_pool).unwrap();
+        let file = map.write(&thread_pool).unwrap();
+
+        let json = map.as_json();
+        let map = Map::from_json(json.as_bytes()).unwrap();
+        assert_eq!(map.as_json(), json);
+        assert!(map.write(&thread_pool).unwrap() == file);
+    }
 }
@@ -0,0 +1,562 @@
+//! Headless validation of maps, that reports common mapping mistakes.
+//!
+//! Unlike the validation during [`Map::read`], which only rejects
+//! maps that cannot be loaded at all, the lints here find maps that
+//! load fine, but likely do not behave as the mapper intended.
+
+use std::{collections::BTreeSet, fmt::Display, num::NonZeroU32};
+
+use image_utils::png::{PngValidatorOptions, is_png_image_valid};
+use num_traits::FromPrimitive;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    map::{
+        Map,
+        groups::{
+            MapGroupPhysics,
+            layers::{
+                design::MapLayer,
+                physics::MapLayerPhysics,
+                tiles::{SwitchTile, TeleTile, Tile, TuneTile},
+            },
+        },
+        resources::MapResourceRef,
+    },
+    tiles::{DdraceTileNum, EntityTiles, TILE_SWITCHTIMEDOPEN},
+};
+
+/// The size in tiles of a 2D array image (16x16 tiles).
+const IMAGE_ARRAY_TILES: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MapLintSeverity {
+    /// Probably unintended, but the map works.
+    Warning,
+    /// The map will not work as expected.
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MapLintIssue {
+    UnusedImage {
+        index: usize,
+        name: String,
+    },
+    UnusedImageArray {
+        index: usize,
+        name: String,
+    },
+    UnusedSound {
+        index: usize,
+        name: String,
+    },
+    UnusedAnimation {
+        /// `pos`, `color` or `sound`
+        ty: String,
+        index: usize,
+        name: String,
+    },
+    /// A physics layer has a different amount of tiles than the physics group.
+    PhysicsLayerSize {
+        layer: String,
+        tiles: usize,
+        expected: usize,
+    },
+    /// A teleporter that has no teleporter out with the same number.
+    TeleWithoutDestination {
+        number: u8,
+        x: usize,
+        y: usize,
+    },
+    /// A switch that triggers a number that is not used by any other tile.
+    SwitchWithoutUser {
+        number: u8,
+        x: usize,
+        y: usize,
+    },
+    /// A tune tile that references a tune zone that is not defined.
+    UndefinedTuneZone {
+        number: u8,
+        x: usize,
+        y: usize,
+    },
+    /// An entity on the border of the map, where players can't reach it.
+    EntityOutsidePlayfield {
+        index: u8,
+        x: usize,
+        y: usize,
+    },
+    MissingSpawn,
+    /// An image used by tile layers that can't be split into 16x16 tiles.
+    InvalidImageArray {
+        index: usize,
+        name: String,
+        reason: String,
+    },
+}
+
+impl MapLintIssue {
+    pub fn severity(&self) -> MapLintSeverity {
+        match self {
+            Self::UnusedImage { .. }
+            | Self::UnusedImageArray { .. }
+            | Self::UnusedSound { .. }
+            | Self::UnusedAnimation { .. }
+            | Self::SwitchWithoutUser { .. }
+            | Self::EntityOutsidePlayfield { .. } => MapLintSeverity::Warning,
+            Self::PhysicsLayerSize { .. }
+            | Self::TeleWithoutDestination { .. }
+            | Self::UndefinedTuneZone { .. }
+            | Self::MissingSpawn
+            | Self::InvalidImageArray { .. } => MapLintSeverity::Error,
+        }
+    }
+}
+
+impl Display for MapLintIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnusedImage { index, name } => write!(f, "image #{index} ({name}) is unused"),
+            Self::UnusedImageArray { index, name } => {
+                write!(f, "tile layer image #{index} ({name}) is unused")
+            }
+            Self::UnusedSound { index, name } => write!(f, "sound #{index} ({name}) is unused"),
+            Self::UnusedAnimation { ty, index, name } => {
+                write!(f, "{ty} animation #{index} ({name}) is unused")
+            }
+            Self::PhysicsLayerSize {
+                layer,
+                tiles,
+                expected,
+            } => write!(
+                f,
+                "{layer} layer has {tiles} tiles, but the game layer has {expected} tiles"
+            ),
+            Self::TeleWithoutDestination { number, x, y } => {
+                write!(f, "teleporter {number} at ({x}, {y}) has no destination")
+            }
+            Self::SwitchWithoutUser { number, x, y } => {
+                write!(f, "switch {number} at ({x}, {y}) is not used by any tile")
+            }
+            Self::UndefinedTuneZone { number, x, y } => {
+                write!(f, "tune zone {number} at ({x}, {y}) is not defined")
+            }
+            Self::EntityOutsidePlayfield { index, x, y } => {
+                write!(
+                    f,
+                    "entity {index} at ({x}, {y}) is outside of the playfield"
+                )
+            }
+            Self::MissingSpawn => write!(f, "the map has no spawn"),
+            Self::InvalidImageArray {
+                index,
+                name,
+                reason,
+            } => write!(
+                f,
+                "tile layer image #{index} ({name}) is not a valid 2D array image: {reason}"
+            ),
+        }
+    }
+}
+
+/// The indices of all teleporters & tele checkpoints that have no
+/// tele out (or tele checkpoint out) with the same number.
+pub fn teles_without_destination(tiles: &[TeleTile]) -> impl Iterator<Item = usize> + '_ {
+    let numbers_of = |ty: DdraceTileNum| {
+        tiles
+            .iter()
+            .filter(|tile| tile.base.index == ty as u8)
+            .map(|tile| tile.number)
+            .collect::<BTreeSet<_>>()
+    };
+    let tele_outs = numbers_of(DdraceTileNum::TeleOut);
+    let tele_check_outs = numbers_of(DdraceTileNum::TeleCheckOut);
+    tiles.iter().enumerate().filter_map(move |(index, tile)| {
+        let destinations = match DdraceTileNum::from_u8(tile.base.index) {
+            Some(
+                DdraceTileNum::TeleIn
+                | DdraceTileNum::TeleInEvil
+                | DdraceTileNum::TeleInWeapon
+                | DdraceTileNum::TeleInHook,
+            ) => &tele_outs,
+            Some(DdraceTileNum::TeleCheck) => &tele_check_outs,
+            _ => return None,
+        };
+        (!destinations.contains(&tile.number)).then_some(index)
+    })
+}
+
+/// All issues found in a map.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct MapLintReport {
+    pub issues: Vec<MapLintIssue>,
+}
+
+impl MapLintReport {
+    /// Lints everything that does not require the resource files.
+    ///
+    /// See [`MapLintReport::lint_image_array`] for the images of tile layers.
+    pub fn new(map: &Map) -> Self {
+        let mut report = Self::default();
+        report.lint_unused_resources(map);
+        report.lint_physics(&map.groups.physics);
+        report
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| issue.severity() == MapLintSeverity::Error)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Checks that an image used by tile layers can be split into 16x16 tiles.
+    pub fn lint_image_array(&mut self, index: usize, image: &MapResourceRef, file: &[u8]) {
+        let tiles = NonZeroU32::new(IMAGE_ARRAY_TILES).unwrap();
+        if let Err(err) = is_png_image_valid(
+            file,
+            PngValidatorOptions {
+                min_width: Some(tiles),
+                min_height: Some(tiles),
+                divisible_width: Some(tiles),
+                divisible_height: Some(tiles),
+                ..Default::default()
+            },
+        ) {
+            self.issues.push(MapLintIssue::InvalidImageArray {
+                index,
+                name: image.name.as_str().to_string(),
+                reason: err.to_string(),
+            });
+        }
+    }
+
+    fn lint_unused_resources(&mut self, map: &Map) {
+        let mut images = BTreeSet::new();
+        let mut image_arrays = BTreeSet::new();
+        let mut sounds = BTreeSet::new();
+        let mut pos_anims = BTreeSet::new();
+        let mut color_anims = BTreeSet::new();
+        let mut sound_anims = BTreeSet::new();
+
+        for layer in map
+            .groups
+            .background
+            .iter()
+            .chain(map.groups.foreground.iter())
+            .flat_map(|group| group.layers.iter())
+        {
+            match layer {
+                MapLayer::Abritrary(_) => {}
+                MapLayer::Tile(layer) => {
+                    image_arrays.extend(layer.attr.image_array);
+                    color_anims.extend(layer.attr.color_anim);
+                }
+                MapLayer::Quad(layer) => {
+                    images.extend(layer.attr.image);
+                    for quad in &layer.quads {
+                        pos_anims.extend(quad.pos_anim);
+                        color_anims.extend(quad.color_anim);
+                    }
+                }
+                MapLayer::Sound(layer) => {
+                    sounds.extend(layer.attr.sound);
+                    for sound in &layer.sounds {
+                        pos_anims.extend(sound.pos_anim);
+                        sound_anims.extend(sound.sound_anim);
+                    }
+                }
+            }
+        }
+
+        let unused = |resources: &[MapResourceRef], used: &BTreeSet<usize>| {
+            resources
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !used.contains(index))
+                .map(|(index, res)| (index, res.name.as_str().to_string()))
+                .collect::<Vec<_>>()
+        };
+        self.issues.extend(
+            unused(&map.resources.images, &images)
+                .into_iter()
+                .map(|(index, name)| MapLintIssue::UnusedImage { index, name }),
+        );
+        self.issues.extend(
+            unused(&map.resources.image_arrays, &image_arrays)
+                .into_iter()
+                .map(|(index, name)| MapLintIssue::UnusedImageArray { index, name }),
+        );
+        self.issues.extend(
+            unused(&map.resources.sounds, &sounds)
+                .into_iter()
+                .map(|(index, name)| MapLintIssue::UnusedSound { index, name }),
+        );
+
+        let mut unused_anims = |ty: &str, names: Vec<&String>, used: &BTreeSet<usize>| {
+            self.issues.extend(
+                names
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(index, _)| !used.contains(index))
+                    .map(|(index, name)| MapLintIssue::UnusedAnimation {
+                        ty: ty.to_string(),
+                        index,
+                        name: name.clone(),
+                    }),
+            );
+        };
+        unused_anims(
+            "pos",
+            map.animations.pos.iter().map(|a| &a.name).collect(),
+            &pos_anims,
+        );
+        unused_anims(
+            "color",
+            map.animations.color.iter().map(|a| &a.name).collect(),
+            &color_anims,
+        );
+        unused_anims(
+            "sound",
+            map.animations.sound.iter().map(|a| &a.name).collect(),
+            &sound_anims,
+        );
+    }
+
+    fn lint_physics(&mut self, physics: &MapGroupPhysics) {
+        let width = physics.attr.width.get() as usize;
+        let height = physics.attr.height.get() as usize;
+        let expected = width * height;
+        let pos = |index: usize| (index % width, index / width);
+
+        let mut game: Option<&Vec<Tile>> = None;
+        let mut front: Option<&Vec<Tile>> = None;
+        let mut tele: Option<&Vec<TeleTile>> = None;
+        let mut switch: Option<&Vec<SwitchTile>> = None;
+        let mut tune = None;
+        for layer in &physics.layers {
+            let (name, tiles) = match layer {
+                MapLayerPhysics::Arbitrary(_) => continue,
+                MapLayerPhysics::Game(layer) => {
+                    game = Some(&layer.tiles);
+                    ("game", layer.tiles.len())
+                }
+                MapLayerPhysics::Front(layer) => {
+                    front = Some(&layer.tiles);
+                    ("front", layer.tiles.len())
+                }
+                MapLayerPhysics::Tele(layer) => {
+                    tele = Some(&layer.base.tiles);
+                    ("tele", layer.base.tiles.len())
+                }
+                MapLayerPhysics::Speedup(layer) => ("speedup", layer.tiles.len()),
+                MapLayerPhysics::Switch(layer) => {
+                    switch = Some(&layer.base.tiles);
+                    ("switch", layer.base.tiles.len())
+                }
+                MapLayerPhysics::Tune(layer) => {
+                    tune = Some(layer);
+                    ("tune", layer.base.tiles.len())
+                }
+            };
+            if tiles != expected {
+                self.issues.push(MapLintIssue::PhysicsLayerSize {
+                    layer: name.to_string(),
+                    tiles,
+                    expected,
+                });
+            }
+        }
+
+        // entities & spawns
+        let entity_layers = || game.into_iter().chain(front).flat_map(|tiles| tiles.iter());
+        if !entity_layers().any(|tile| {
+            [
+                EntityTiles::Spawn as u8,
+                EntityTiles::SpawnRed as u8,
+                EntityTiles::SpawnBlue as u8,
+            ]
+            .contains(&tile.index)
+        }) {
+            self.issues.push(MapLintIssue::MissingSpawn);
+        }
+        for tiles in game.into_iter().chain(front) {
+            for (index, tile) in tiles.iter().enumerate() {
+                let (x, y) = pos(index);
+                if tile.index >= EntityTiles::Spawn as u8
+                    && (x == 0 || y == 0 || x + 1 >= width || y + 1 >= height)
+                {
+                    self.issues.push(MapLintIssue::EntityOutsidePlayfield {
+                        index: tile.index,
+                        x,
+                        y,
+                    });
+                }
+            }
+        }
+
+        // teleporters
+        if let Some(tiles) = tele {
+            for index in teles_without_destination(tiles) {
+                let (x, y) = pos(index);
+                self.issues.push(MapLintIssue::TeleWithoutDestination {
+                    number: tiles[index].number,
+                    x,
+                    y,
+                });
+            }
+        }
+
+        // switches
+        if let Some(tiles) = switch {
+            let is_trigger = |tile: &SwitchTile| {
+                tile.base.index == TILE_SWITCHTIMEDOPEN
+                    || matches!(
+                        DdraceTileNum::from_u8(tile.base.index),
+                        Some(
+                            DdraceTileNum::SwitchTimedClose
+                                | DdraceTileNum::SwitchOpen
+                                | DdraceTileNum::SwitchClose
+                        )
+                    )
+            };
+            let users: BTreeSet<_> = tiles
+                .iter()
+                .filter(|tile| tile.base.index != 0 && !is_trigger(tile))
+                .map(|tile| tile.number)
+                .collect();
+            for (index, tile) in tiles.iter().enumerate() {
+                if is_trigger(tile) && tile.number != 0 && !users.contains(&tile.number) {
+                    let (x, y) = pos(index);
+                    self.issues.push(MapLintIssue::SwitchWithoutUser {
+                        number: tile.number,
+                        x,
+                        y,
+                    });
+                }
+            }
+        }
+
+        // tune zones, zone 0 are the global tunes
+        if let Some(layer) = tune {
+            for (index, tile) in layer.base.tiles.iter().enumerate() {
+                let TuneTile { base, number } = tile;
+                if base.index == DdraceTileNum::Tune as u8
+                    && *number != 0
+                    && !layer.tune_zones.contains_key(number)
+                {
+                    let (x, y) = pos(index);
+                    self.issues.push(MapLintIssue::UndefinedTuneZone {
+                        number: *number,
+                        x,
+                        y,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        map::groups::{
+            MapGroupPhysics, MapGroupPhysicsAttr,
+            layers::{
+                physics::{MapLayerPhysics, MapLayerTilePhysicsBase, MapLayerTilePhysicsTele},
+                tiles::{TeleTile, TileBase},
+            },
+        },
+        tiles::{DdraceTileNum, EntityTiles},
+        types::NonZeroU16MinusOne,
+    };
+
+    use super::{MapLintIssue, MapLintReport};
+
+    const SPAWN: u8 = EntityTiles::Spawn as u8;
+    const TELE_IN: u8 = DdraceTileNum::TeleIn as u8;
+    const TELE_OUT: u8 = DdraceTileNum::TeleOut as u8;
+
+    fn physics(game: Vec<u8>, tele: Vec<(u8, u8)>) -> MapGroupPhysics {
+        let tile = |index| TileBase {
+            index,
+            ..Default::default()
+        };
+        MapGroupPhysics {
+            attr: MapGroupPhysicsAttr {
+                width: NonZeroU16MinusOne::new(3).unwrap(),
+                height: NonZeroU16MinusOne::new(3).unwrap(),
+            },
+            layers: vec![
+                MapLayerPhysics::Game(MapLayerTilePhysicsBase {
+                    tiles: game.into_iter().map(tile).collect(),
+                }),
+                MapLayerPhysics::Tele(MapLayerTilePhysicsTele {
+                    base: MapLayerTilePhysicsBase {
+                        tiles: tele
+                            .into_iter()
+                            .map(|(index, number)| TeleTile {
+                                base: tile(index),
+                                number,
+                            })
+                            .collect(),
+                    },
+                    tele_names: Default::default(),
+                }),
+            ],
+        }
+    }
+
+    fn lint(physics: MapGroupPhysics) -> Vec<MapLintIssue> {
+        let mut report = MapLintReport::default();
+        report.lint_physics(&physics);
+        report.issues
+    }
+
+    #[test]
+    fn physics_lints() {
+        let mut game = vec![0; 9];
+        game[4] = SPAWN;
+        let mut tele = vec![(0, 0); 9];
+        tele[3] = (TELE_IN, 1);
+        tele[5] = (TELE_OUT, 1);
+        assert!(lint(physics(game.clone(), tele.clone())).is_empty());
+
+        game[0] = SPAWN;
+        game[4] = 0;
+        tele[5] = (TELE_OUT, 2);
+        tele.pop();
+        assert_eq!(
+            lint(physics(game, tele)),
+            vec![
+                MapLintIssue::PhysicsLayerSize {
+                    layer: "tele".to_string(),
+                    tiles: 8,
+                    expected: 9,
+                },
+                MapLintIssue::EntityOutsidePlayfield {
+                    index: SPAWN,
+                    x: 0,
+                    y: 0,
+                },
+                MapLintIssue::TeleWithoutDestination {
+                    number: 1,
+                    x: 0,
+                    y: 1,
+                },
+            ]
+        );
+
+        assert_eq!(
+            lint(physics(vec![0; 9], vec![(0, 0); 9])),
+            vec![MapLintIssue::MissingSpawn]
+        );
+    }
+}
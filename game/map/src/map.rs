@@ -30,10 +30,31 @@ use crate::{
 };
 
 use self::{
-    animations::Animations, config::Config, groups::MapGroups, metadata::Metadata,
-    resources::Resources,
+    animations::Animations,
+    config::Config,
+    groups::MapGroups,
+    metadata::Metadata,
+    resources::{MapResourceMetaData, Resources},
 };
 
+#[derive(Debug, Serialize, Deserialize)]
+struct MapGroupAsJson {
+    pub physics: MapGroupPhysics,
+
+    pub background: Vec<MapGroup>,
+    pub foreground: Vec<MapGroup>,
+}
+
+/// The json representation of a map, see [`Map::as_json`] & [`Map::from_json`].
+#[derive(Debug, Serialize, Deserialize)]
+struct MapAsJson {
+    pub resources: Resources,
+    pub groups: MapGroupAsJson,
+    pub animations: Animations,
+    pub config: Config,
+    pub meta: Metadata,
+}
+
 /// A `Map` is mainly a collection of resources, layers & animations.
 ///
 /// Additionally it might contain meta data about author, license etc. aswell as
@@ -68,7 +89,28 @@ impl Map {
         animations: &Animations,
         groups: &MapGroups,
     ) -> anyhow::Result<()> {
-        for group in groups.background.iter() {
+        Self::validate_group_indices(resources, animations, &groups.background)
+    }
+
+    /// Checks that all resource & animation indices used by the design layers exist,
+    /// including the foreground layers.
+    ///
+    /// Used for maps that were not created from a map file, e.g. json imports.
+    pub fn validate_all_resource_and_anim_indices(
+        resources: &Resources,
+        animations: &Animations,
+        groups: &MapGroups,
+    ) -> anyhow::Result<()> {
+        Self::validate_group_indices(resources, animations, &groups.background)?;
+        Self::validate_group_indices(resources, animations, &groups.foreground)
+    }
+
+    fn validate_group_indices(
+        resources: &Resources,
+        animations: &Animations,
+        groups: &[MapGroup],
+    ) -> anyhow::Result<()> {
+        for group in groups.iter() {
             for layer in group.layers.iter() {
                 match layer {
                     MapLayer::Abritrary(_) => Ok(()),
@@ -571,23 +613,31 @@ impl Map {
         name_and_hash(name, file)
     }
 
-    pub fn as_json(&self) -> String {
-        #[derive(Debug, Serialize, Deserialize)]
-        struct MapGroupAsJson {
-            pub physics: MapGroupPhysics,
-
-            pub background: Vec<MapGroup>,
-            pub foreground: Vec<MapGroup>,
-        }
-        #[derive(Debug, Serialize, Deserialize)]
-        struct MapAsJson {
-            pub resources: Resources,
-            pub groups: MapGroupAsJson,
-            pub animations: Animations,
-            pub config: Config,
-            pub meta: Metadata,
+    /// Validates a resource file that is referenced by a map.
+    ///
+    /// Checks that the file matches the hash of the resource
+    /// and that it is a valid file of the resource's type.
+    pub fn validate_resource_file(
+        meta: &MapResourceMetaData,
+        file: &[u8],
+        png_options: PngValidatorOptions,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            generate_hash_for(file) == meta.blake3_hash,
+            "resource file does not match the hash of the resource."
+        );
+        match meta.ty.as_str() {
+            "png" => is_png_image_valid(file, png_options)?,
+            "ogg" => verify_ogg_vorbis(file)?,
+            ty => anyhow::bail!("resource type: {ty} is unknown and cannot be validated."),
         }
+        Ok(())
+    }
 
+    /// Serializes the map as human readable json.
+    ///
+    /// See [`Map::from_json`] for the reverse.
+    pub fn as_json(&self) -> String {
         serde_json::to_string_pretty(&MapAsJson {
             resources: self.resources.clone(),
             groups: MapGroupAsJson {
@@ -601,4 +651,28 @@ impl Map {
         })
         .unwrap()
     }
+
+    /// Deserializes a map from the json created by [`Map::as_json`].
+    ///
+    /// This validates the indices of all design layers,
+    /// but can't check the referenced resource files,
+    /// see [`Map::validate_resource_file`] for that.
+    pub fn from_json(json: &[u8]) -> anyhow::Result<Self> {
+        let map: MapAsJson = serde_json::from_slice(json)?;
+        let groups = MapGroups {
+            physics: map.groups.physics,
+            background: map.groups.background,
+            foreground: map.groups.foreground,
+        };
+
+        Self::validate_all_resource_and_anim_indices(&map.resources, &map.animations, &groups)?;
+
+        Ok(Self {
+            resources: map.resources,
+            groups,
+            animations: map.animations,
+            config: map.config,
+            meta: map.meta,
+        })
+    }
 }
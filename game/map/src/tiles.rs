@@ -0,0 +1,107 @@
+use hiarc::Hiarc;
+use num_derive::FromPrimitive;
+
+#[repr(u8)]
+pub enum EntityTiles {
+    // game layer tiles
+    // TODO define which Layer uses which tiles (needed for mapeditor)
+    Spawn = 192,
+    SpawnRed,
+    SpawnBlue,
+    FlagSpawnRed,
+    FlagSpawnBlue,
+    Armor,
+    Health,
+    WeaponShotgun,
+    WeaponGrenade,
+    PowerupNinja,
+    WeaponLaser,
+}
+
+pub const TILE_SWITCHTIMEDOPEN: u8 = 22;
+
+#[derive(Debug, Hiarc, Clone, Copy, FromPrimitive, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DdraceTileNum {
+    Air = 0,
+    Solid,
+    Death,
+    NoHook,
+    NoLaser,
+    ThroughCut,
+    Through,
+    Jump,
+    Freeze = 9,
+    TeleInEvil,
+    Unfreeze,
+    DFreeze,
+    DUnfreeze,
+    TeleInWeapon,
+    TeleInHook,
+    WallJump = 16,
+    EHookEnable,
+    EHookDisable,
+    HitEnable,
+    HitDisable,
+    SoloEnable,
+    SoloDisable,
+    // Switches
+    SwitchTimedClose,
+    SwitchOpen,
+    SwitchClose,
+    TeleIn,
+    TeleOut,
+    Boost,
+    TeleCheck,
+    TeleCheckOut,
+    TeleCheckIn,
+    RefillJumps = 32,
+    Start,
+    Finish,
+    TimeCheckpointFirst = 35,
+    TimeCheckpointLast = 59,
+    Stop = 60,
+    StopS,
+    StopA,
+    TeleCheckInEvil,
+    CP,
+    CPF,
+    ThroughAll,
+    ThroughDir,
+    Tune,
+    OldLaser = 71,
+    Npc,
+    EHook,
+    NoHit,
+    NPH,
+    UnlockTeam,
+    AddTime = 79,
+    NpcDisable = 88,
+    UnlimitedJumpsDisable,
+    JetpackDisable,
+    NphDisable,
+    SubtractTime = 95,
+    TeleGunEnable = 96,
+    TeleGunDisable = 97,
+    AllowTeleGun = 98,
+    AllowBlueTeleGun = 99,
+    NpcEnable = 104,
+    UnlimitedJumpsEnable,
+    JetpackEnable,
+    NphEnable,
+    TeleGrenadeEnable = 112,
+    TeleGrenadeDisable = 113,
+    TeleLaserEnable = 128,
+    TeleLaserDisable = 129,
+    Credits1 = 140,
+    Credits2 = 141,
+    Credits3 = 142,
+    Credits4 = 143,
+    LFreeze = 144,
+    LUnfreeze = 145,
+    Credits5 = 156,
+    Credits6 = 157,
+    Credits7 = 158,
+    Credits8 = 159,
+    EntitiesOff1 = 190,
+    EntitiesOff2,
+}
@@ -133,6 +133,7 @@ pub mod state {
     pub enum VanillaRconCommandCheat {
         WeaponsAll,
         Tune,
+        TeleportToCursor,
     }
 
     #[derive(Debug, Clone, Copy)]
@@ -370,6 +371,20 @@ pub mod state {
                         cmd: VanillaRconCommand::Cheats(VanillaRconCommandCheat::WeaponsAll),
                     },
                 ),
+                (
+                    "cheats.teleport_to_cursor".try_into().unwrap(),
+                    Command {
+                        rcon: RconEntry {
+                            args: Default::default(),
+                            description:
+                                "Teleports the player to the position of the cursor (cheat)"
+                                    .try_into()
+                                    .unwrap(),
+                            usage: "".try_into().unwrap(),
+                        },
+                        cmd: VanillaRconCommand::Cheats(VanillaRconCommandCheat::TeleportToCursor),
+                    },
+                ),
                 (
                     "cheats.tune".try_into().unwrap(),
                     Command {
@@ -1136,6 +1151,49 @@ pub mod state {
                             Err(anyhow!("The given player was not found in this game"))
                         }
                     }
+                    VanillaRconCommandCheat::TeleportToCursor => {
+                        let Some(player_id) = player_id else {
+                            return Err(anyhow!(
+                                "Teleport cheat command must be executed by an actual player"
+                            ));
+                        };
+                        let Some(character_info) = self.game.players.player(player_id) else {
+                            return Err(anyhow!("The given player was not found in this game"));
+                        };
+                        let Some(character) = self
+                            .game
+                            .stages
+                            .get_mut(&character_info.stage_id())
+                            .and_then(|stage| stage.world.characters.get_mut(player_id))
+                        else {
+                            return Err(anyhow!("The given player was not found in this game"));
+                        };
+
+                        let cursor = character.core.input.cursor.to_vec2();
+                        let pos =
+                            *character.pos.pos() + vec2::new(cursor.x as f32, cursor.y as f32);
+                        let width = (self.collision.get_playfield_width() * 32) as f32;
+                        let height = (self.collision.get_playfield_height() * 32) as f32;
+                        if pos.x < 0.0
+                            || pos.y < 0.0
+                            || pos.x >= width
+                            || pos.y >= height
+                            || self.collision.check_pointf(pos.x, pos.y)
+                        {
+                            return Err(anyhow!(
+                                "Cannot teleport outside of the map or into a solid tile"
+                            ));
+                        }
+
+                        character.pos.move_pos(pos);
+                        character.core.core.vel = vec2::default();
+
+                        Ok(format!(
+                            "Teleported to {:.1}, {:.1}",
+                            pos.x / 32.0,
+                            pos.y / 32.0
+                        ))
+                    }
                     VanillaRconCommandCheat::Tune => {
                         let Some(Syn::Float(val)) = cmd.args.pop().map(|(name, _)| name) else {
                             panic!("Expected a float, this is an implementation bug");
@@ -2877,6 +2935,91 @@ pub mod state {
             }
         }
 
+        fn build_from_snapshot_by_savestate(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {
+            let Ok((snapshot, _)) =
+                bincode::serde::decode_from_slice(snapshot, bincode::config::standard())
+            else {
+                return;
+            };
+
+            // remember all joined players, the snapshot might not know them
+            let mut players = self.player_clone_pool.new();
+            self.game.players.pooled_clone_into(&mut players);
+            let mut joined_players: Vec<_> = players
+                .drain(..)
+                .filter_map(|(id, character_info)| {
+                    let character = self
+                        .game
+                        .stages
+                        .get(&character_info.stage_id())?
+                        .world
+                        .characters
+                        .get(&id)?;
+                    Some((
+                        id,
+                        character.player_info.clone(),
+                        character.core.default_eye,
+                        character.is_player_character().unwrap_or_default(),
+                    ))
+                })
+                .collect();
+            let mut spectator_players = self.spectator_player_clone_pool.new();
+            self.game
+                .spectator_players
+                .pooled_clone_into(&mut spectator_players);
+
+            let _ = SnapshotManager::build_from_snapshot(snapshot, self);
+
+            // characters of players that are not joined anymore are removed
+            let mut players = self.player_clone_pool.new();
+            self.game.players.pooled_clone_into(&mut players);
+            for (id, character_info) in players.drain(..) {
+                if joined_players
+                    .iter()
+                    .any(|(joined_id, ..)| *joined_id == id)
+                    || spectator_players.contains_key(&id)
+                {
+                    continue;
+                }
+                if let Some(stage) = self.game.stages.get_mut(&character_info.stage_id())
+                    && let Some(character) = stage.world.characters.get_mut(&id)
+                {
+                    character.despawn_completely_silent();
+                    stage.world.characters.remove(&id);
+                }
+            }
+
+            // joined players that the snapshot does not know get a new character
+            joined_players.retain(|(id, ..)| {
+                self.game.players.player(id).is_none()
+                    && !self.game.spectator_players.contains_key(id)
+            });
+            for (id, player_info, default_eyes, network_stats) in joined_players {
+                Self::add_char_to_stage(
+                    &mut self.game.stages,
+                    &self.stage_0_id,
+                    &id,
+                    player_info,
+                    Default::default(),
+                    self.game.players.clone(),
+                    self.game.spectator_players.clone(),
+                    network_stats,
+                    None,
+                    0,
+                    default_eyes,
+                    Default::default(),
+                    &self.game_pools,
+                );
+            }
+            for (id, player) in spectator_players.drain() {
+                if self.game.players.player(&id).is_none()
+                    && !self.game.spectator_players.contains_key(&id)
+                {
+                    self.game.spectator_players.insert(id, player);
+                }
+            }
+        }
+
         fn build_from_snapshot_for_prev(&mut self, snapshot: &MtPoolCow<'static, [u8]>) {
             let (snapshot, _): (Snapshot, usize) =
                 bincode::serde::decode_from_slice(snapshot, bincode::config::standard()).unwrap();
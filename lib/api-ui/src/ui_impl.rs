@@ -1,9 +1,11 @@
-use std::{cell::RefCell, time::Duration};
+use std::{cell::RefCell, sync::Arc, time::Duration};
 
 use api::{GRAPHICS, GRAPHICS_BACKEND, read_param_from_host, upload_return_val};
 
 use graphics_types::types::WindowProps;
+use i18n::translations::Translations;
 use ui_base::{
+    translate::set_translations,
     types::{BlurShape, GlassShape, RawInputWrapper, RawOutputWrapper, UiFonts, UiRenderPipe},
     ui::UiContainer,
     ui_render::render_ui,
@@ -37,6 +39,14 @@ pub fn ui_new() {
     });
 }
 
+#[unsafe(no_mangle)]
+pub fn ui_translations() {
+    let translations = read_param_from_host::<Translations>(0);
+    API_UI.with(|g| {
+        set_translations(&g.borrow().context.egui_ctx, Arc::new(translations));
+    });
+}
+
 /// returns platform output and zoom level
 fn ui_run_impl(
     cur_time: Duration,
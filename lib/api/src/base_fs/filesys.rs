@@ -114,6 +114,10 @@ impl FileSystemInterface for FileSystem {
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Unsupported, err))
     }
 
+    async fn remove_file(&self, _file_path: &Path) -> std::io::Result<()> {
+        todo!("not implemented")
+    }
+
     async fn entries_in_dir(
         &self,
         path: &Path,
@@ -716,6 +716,17 @@ impl FileSystemInterface for FileSystem {
         Self::create_dir_in_fs(fs, dir_path).await
     }
 
+    async fn remove_file(&self, file_path: &Path) -> std::io::Result<()> {
+        let _g = self
+            .max_operations_semaphore
+            .acquire()
+            .await
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::BrokenPipe, err.to_string()))?;
+        let fs = self.get_scoped_fs(FileSystemPath::OfType(FileSystemType::ReadWrite));
+        let file_path = fs.get_path(file_path);
+        Ok(virtual_fs::FileSystem::remove_file(&fs.fs, &file_path)?)
+    }
+
     async fn entries_in_dir(
         &self,
         path: &Path,
@@ -56,6 +56,8 @@ pub trait FileSystemInterface: Debug + Sync + Send {
     async fn write_file(&self, file_path: &Path, data: Vec<u8>) -> std::io::Result<()>;
     /// Create a directory recursively to the read-write file system
     async fn create_dir(&self, dir_path: &Path) -> std::io::Result<()>;
+    /// Remove a file from the read-write file system
+    async fn remove_file(&self, file_path: &Path) -> std::io::Result<()>;
 
     /// Get's the name of all entries in a directory, that also includes directories.
     async fn entries_in_dir(
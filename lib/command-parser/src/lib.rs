@@ -1,3 +1,4 @@
 pub mod escape;
 pub mod parser;
 pub mod tokenizer;
+pub mod vars;
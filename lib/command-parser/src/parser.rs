@@ -12,7 +12,10 @@ use logos::Logos;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::escape::{escape, unescape};
+use crate::{
+    escape::{escape, unescape},
+    vars::is_var_ref,
+};
 
 use super::tokenizer::{HumanReadableToken, Token, tokenize};
 
@@ -422,7 +425,7 @@ fn parse_number(tokens: &mut TokenStack) -> anyhow::Result<(String, Range<usize>
     if let Some((token, text, range)) = tokens.peek() {
         if let Token::Text = token {
             anyhow::ensure!(
-                text.parse::<i64>().is_ok() || text.parse::<u64>().is_ok(),
+                text.parse::<i64>().is_ok() || text.parse::<u64>().is_ok() || is_var_ref(text),
                 "Expected a number, found {text}"
             );
             let text = text.clone();
@@ -431,7 +434,7 @@ fn parse_number(tokens: &mut TokenStack) -> anyhow::Result<(String, Range<usize>
         } else if let Token::Quoted = token {
             let text = unescape(text)?;
             anyhow::ensure!(
-                text.parse::<i64>().is_ok() || text.parse::<u64>().is_ok(),
+                text.parse::<i64>().is_ok() || text.parse::<u64>().is_ok() || is_var_ref(&text),
                 "Expected a number, found {text}"
             );
             tokens.next_token();
@@ -449,7 +452,7 @@ fn parse_float(tokens: &mut TokenStack) -> anyhow::Result<(String, Range<usize>)
     if let Some((token, text, range)) = tokens.peek() {
         if let Token::Text = token {
             anyhow::ensure!(
-                text.parse::<f64>().is_ok() || text.parse::<u64>().is_ok(),
+                text.parse::<f64>().is_ok() || text.parse::<u64>().is_ok() || is_var_ref(text),
                 "Expected a float, found {text}"
             );
             let text = text.clone();
@@ -458,7 +461,7 @@ fn parse_float(tokens: &mut TokenStack) -> anyhow::Result<(String, Range<usize>)
         } else if let Token::Quoted = token {
             let text = unescape(text)?;
             anyhow::ensure!(
-                text.parse::<f64>().is_ok() || text.parse::<u64>().is_ok(),
+                text.parse::<f64>().is_ok() || text.parse::<u64>().is_ok() || is_var_ref(&text),
                 "Expected a float, found {text}"
             );
             tokens.next_token();
@@ -1374,6 +1377,30 @@ mod test {
         assert!(lex[0].unwrap_ref_full().args[0].0 == Syn::Text("something".to_string()));
     }
 
+    #[test]
+    fn console_test_vars() {
+        let cache = ParserCache::default();
+        let cmds = vec![(
+            "cl.refresh_rate".try_into().unwrap(),
+            vec![CommandArg {
+                ty: CommandArgType::Number,
+                user_ty: None,
+            }],
+        )]
+        .into_iter()
+        .collect();
+        let lex = parse::<65536>("cl.refresh_rate $rate", &cmds, &cache);
+        dbg!(&lex);
+        assert!(lex[0].unwrap_ref_full().args[0].0 == Syn::Number("$rate".to_string()));
+
+        let lex = parse::<65536>("cl.refresh_rate $", &cmds, &cache);
+        dbg!(&lex);
+        assert!(matches!(
+            lex[0],
+            CommandType::Partial(CommandParseResult::InvalidArg { .. })
+        ));
+    }
+
     #[test]
     fn err_console_tests() {
         let cache = ParserCache::default();
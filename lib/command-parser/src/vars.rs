@@ -0,0 +1,70 @@
+fn is_var_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Whether the given string is a valid name for a user variable.
+pub fn is_var_name(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(is_var_char)
+}
+
+/// Whether the whole text is a reference to a user variable, e.g. `$rate`.
+pub fn is_var_ref(s: &str) -> bool {
+    s.strip_prefix('$').is_some_and(is_var_name)
+}
+
+/// Replaces all references to user variables (`$name`) in the text
+/// by their values.
+///
+/// `$$` is replaced by a single `$`. References to unknown variables
+/// and a `$` that is not followed by a valid variable name are kept as is.
+pub fn substitute_vars(s: &str, var: impl Fn(&str) -> Option<String>) -> String {
+    let mut res = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(index) = rest.find('$') {
+        res.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
+        if let Some(after) = rest.strip_prefix('$') {
+            res.push('$');
+            rest = after;
+            continue;
+        }
+        let name_len = rest.find(|c| !is_var_char(c)).unwrap_or(rest.len());
+        if name_len == 0 {
+            res.push('$');
+            continue;
+        }
+        let name = &rest[..name_len];
+        match var(name) {
+            Some(val) => res.push_str(&val),
+            None => {
+                res.push('$');
+                res.push_str(name);
+            }
+        }
+        rest = &rest[name_len..];
+    }
+    res.push_str(rest);
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_var_ref, substitute_vars};
+
+    #[test]
+    fn substitute() {
+        let var = |name: &str| (name == "rate").then(|| "60".to_string());
+
+        assert!(is_var_ref("$rate"));
+        assert!(!is_var_ref("$"));
+        assert!(!is_var_ref("rate"));
+        assert!(!is_var_ref("$rate-1"));
+
+        assert_eq!(substitute_vars("$rate", var), "60");
+        assert_eq!(
+            substitute_vars("fps: $rate, cost: $$5 $", var),
+            "fps: 60, cost: $5 $"
+        );
+        assert_eq!(substitute_vars("$unknown $rate_", var), "$unknown $rate_");
+    }
+}
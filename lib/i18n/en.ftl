@@ -0,0 +1,42 @@
+# English translations.
+# Also used for all messages that are missing in other languages.
+
+## settings
+settings-general = General
+settings-language = Language
+settings-player = Player
+settings-tee = Tee
+settings-misc = Misc
+settings-assets = Assets
+settings-chat = Chat
+settings-controls = Controls
+settings-graphics = Graphics
+settings-sound = Sound
+settings-spatial-chat = Spatial Chat
+settings-search = 🔍 Settings
+
+## main menu
+menu-internet = Internet
+menu-internet-info = The internet tab shows all servers.
+menu-lan = LAN
+menu-lan-info = The LAN tab shows servers in your local network.
+menu-settings = Settings
+menu-settings-info = Change the settings of your client here.
+menu-profiles = Profiles
+menu-profiles-info = Here you can manage your accounts, and select the current active one.
+menu-favorites = Favorites
+menu-favorites-info = The favorite tab shows servers that you marked with a .
+menu-explore-communities = Explore communities
+menu-explore-communities-info = This tab shows an overview over all existing communities.
+
+## editor
+editor-file = File
+editor-new-map = New map
+editor-open-map = Open map
+editor-save-map = Save map
+editor-import-map = Import from map
+editor-host-map = Host map
+editor-join-map = Join map
+editor-playtest = Playtest
+editor-minimize = Minimize
+editor-close = Close
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+/// A part of a translated message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PatternElement {
+    Text(String),
+    /// `{ $name }`
+    Var(String),
+    /// `{ $name -> [key] pattern *[other] pattern }`
+    Select {
+        var: String,
+        variants: Vec<(String, Pattern)>,
+        default: usize,
+    },
+}
+
+pub type Pattern = Vec<PatternElement>;
+
+/// A catalog of messages, written in a subset of the
+/// [Fluent](https://projectfluent.org/) syntax:
+///
+/// ```text
+/// # comment
+/// hello = Hello { $name }!
+/// multiline = First line
+///     second line
+/// players = { $count ->
+///     [one] One player
+///    *[other] { $count } players
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Catalog {
+    pub messages: HashMap<String, Pattern>,
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Returns the index of the `}` that closes the placeable starting at `start`.
+fn closing_brace(s: &str, start: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in s[start..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_select(var: &str, body: &str) -> anyhow::Result<PatternElement> {
+    let mut variants: Vec<(String, String)> = Vec::new();
+    let mut default = None;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        let (is_default, rest) = match trimmed.strip_prefix('*') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+        if let Some(rest) = rest.strip_prefix('[')
+            && let Some((key, value)) = rest.split_once(']')
+        {
+            if is_default {
+                default = Some(variants.len());
+            }
+            variants.push((key.trim().to_string(), value.trim().to_string()));
+        } else if let Some((_, value)) = variants.last_mut() {
+            if !trimmed.is_empty() {
+                value.push('\n');
+                value.push_str(trimmed);
+            }
+        } else if !trimmed.is_empty() {
+            anyhow::bail!("text before the first variant of ${var}");
+        }
+    }
+    let default = default.ok_or_else(|| anyhow!("${var} has no default variant"))?;
+    Ok(PatternElement::Select {
+        var: var.to_string(),
+        variants: variants
+            .into_iter()
+            .map(|(key, value)| parse_pattern(&value).map(|pattern| (key, pattern)))
+            .collect::<anyhow::Result<_>>()?,
+        default,
+    })
+}
+
+pub fn parse_pattern(s: &str) -> anyhow::Result<Pattern> {
+    let mut res = Vec::new();
+    let mut text = String::new();
+    let mut index = 0;
+    while index < s.len() {
+        let c = s[index..].chars().next().unwrap();
+        if c == '{' {
+            let end = closing_brace(s, index).ok_or_else(|| anyhow!("unclosed {{ in {s}"))?;
+            let inner = s[index + 1..end].trim();
+            if !text.is_empty() {
+                res.push(PatternElement::Text(std::mem::take(&mut text)));
+            }
+            if let Some((var, body)) = inner.split_once("->") {
+                let var = var.trim();
+                let var = var
+                    .strip_prefix('$')
+                    .ok_or_else(|| anyhow!("select on {var} is not a variable"))?;
+                res.push(parse_select(var, body)?);
+            } else if let Some(literal) = inner
+                .strip_prefix('"')
+                .and_then(|inner| inner.strip_suffix('"'))
+            {
+                text.push_str(literal);
+            } else {
+                let var = inner
+                    .strip_prefix('$')
+                    .ok_or_else(|| anyhow!("unsupported placeable {{ {inner} }}"))?;
+                res.push(PatternElement::Var(var.to_string()));
+            }
+            index = end + 1;
+        } else {
+            text.push(c);
+            index += c.len_utf8();
+        }
+    }
+    if !text.is_empty() {
+        res.push(PatternElement::Text(text));
+    }
+    Ok(res)
+}
+
+impl Catalog {
+    /// Parses a catalog.
+    /// Invalid messages are skipped and returned as errors
+    /// next to the catalog.
+    pub fn parse(src: &str) -> (Self, Vec<anyhow::Error>) {
+        let mut entries: Vec<(usize, String, String)> = Vec::new();
+        for (line_index, line) in src.lines().enumerate() {
+            if line.starts_with('#') {
+                continue;
+            }
+            // the closing brace of a select expression may start a line
+            let is_continuation = line.starts_with([' ', '\t', '}']);
+            if is_continuation {
+                if let Some((_, _, value)) = entries.last_mut()
+                    && !line.trim().is_empty()
+                {
+                    if !value.is_empty() {
+                        value.push('\n');
+                    }
+                    value.push_str(line.trim());
+                }
+            } else if let Some((key, value)) = line.split_once('=') {
+                entries.push((
+                    line_index + 1,
+                    key.trim().to_string(),
+                    value.trim().to_string(),
+                ));
+            }
+        }
+
+        let mut errs = Vec::new();
+        let messages = entries
+            .into_iter()
+            .filter_map(|(line, key, value)| {
+                if !is_ident(&key) {
+                    errs.push(anyhow!("line {line}: {key} is not a valid message id"));
+                    return None;
+                }
+                match parse_pattern(&value) {
+                    Ok(pattern) => Some((key, pattern)),
+                    Err(err) => {
+                        errs.push(anyhow!("line {line}: {key}: {err}"));
+                        None
+                    }
+                }
+            })
+            .collect();
+        (Self { messages }, errs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Catalog, PatternElement};
+
+    #[test]
+    fn parse_catalog() {
+        let (catalog, errs) = Catalog::parse(
+            "# comment\n\
+            hello = Hello { $name }!\n\
+            multi = First\n    second\n\
+            players = { $count ->\n    [one] One player\n   *[other] { $count } players\n}\n\
+            broken = { $count ->\n    [one] no default\n}\n",
+        );
+        assert_eq!(errs.len(), 1);
+        assert_eq!(
+            catalog.messages["hello"],
+            vec![
+                PatternElement::Text("Hello ".into()),
+                PatternElement::Var("name".into()),
+                PatternElement::Text("!".into())
+            ]
+        );
+        assert_eq!(
+            catalog.messages["multi"],
+            vec![PatternElement::Text("First\nsecond".into())]
+        );
+        let PatternElement::Select {
+            variants, default, ..
+        } = &catalog.messages["players"][0]
+        else {
+            panic!("expected a select expression");
+        };
+        assert_eq!(*default, 1);
+        assert_eq!(variants[0].0, "one");
+        assert_eq!(variants[1].1[0], PatternElement::Var("count".into()));
+    }
+}
@@ -0,0 +1,54 @@
+use std::collections::BTreeSet;
+
+/// The methods whose first argument is a message id.
+const TR_FUNCTIONS: [&str; 2] = ["tr(", "tr_args("];
+
+/// Extracts all message ids that are passed as string literals
+/// to the `tr` or `tr_args` methods in the given rust source.
+///
+/// Tests (everything after `#[cfg(test)]`) are ignored.
+pub fn extract_message_ids(src: &str) -> BTreeSet<String> {
+    let src = src.split("#[cfg(test)]").next().unwrap_or(src);
+    let mut res = BTreeSet::new();
+    for func in TR_FUNCTIONS {
+        let mut rest = src;
+        while let Some(index) = rest.find(func) {
+            // ignore e.g. `attr(`
+            let is_call = rest[..index].ends_with('.');
+            rest = &rest[index + func.len()..];
+            if !is_call {
+                continue;
+            }
+            if let Some(literal) = rest.trim_start().strip_prefix('"')
+                && let Some((id, _)) = literal.split_once('"')
+            {
+                res.insert(id.to_string());
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::extract_message_ids;
+
+    #[test]
+    fn extract_ids() {
+        let ids = extract_message_ids(
+            r#"
+            ui.label(ui.tr("menu-play"));
+            let s = tr.tr_args(
+                "browser-players",
+                &[("count", 3i64.into())],
+            );
+            let _ = attr("not-a-message");
+            let _ = ui.tr(id);
+            "#,
+        );
+        assert_eq!(
+            ids.into_iter().collect::<Vec<_>>(),
+            vec!["browser-players".to_string(), "menu-play".to_string()]
+        );
+    }
+}
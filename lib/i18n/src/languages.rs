@@ -0,0 +1,24 @@
+/// A language the client offers in its settings.
+#[derive(Debug, Clone, Copy)]
+pub struct Language {
+    /// The code that is stored in the config
+    /// and used as file name of the catalog.
+    pub code: &'static str,
+    /// The name of the language in the language itself.
+    pub name: &'static str,
+    /// The flag that represents the language.
+    pub flag: &'static str,
+}
+
+/// Only languages that ship a catalog in `i18n/` are listed.
+pub const LANGUAGES: &[Language] = &[
+    Language {
+        code: "en",
+        name: "English",
+        flag: "us",
+    },
+];
+
+pub fn language_by_code(code: &str) -> Option<&'static Language> {
+    LANGUAGES.iter().find(|lang| lang.code == code)
+}
@@ -0,0 +1,5 @@
+pub mod catalog;
+pub mod extract;
+pub mod languages;
+pub mod plural;
+pub mod translations;
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// The plural categories of the CLDR plural rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// The base language of a language code, e.g. `pt` for `pt-BR`.
+pub fn base_language(lang: &str) -> &str {
+    lang.split(['-', '_']).next().unwrap_or(lang)
+}
+
+/// The cardinal plural category of `n` in the given language.
+///
+/// This only implements the integer rules of the most common languages,
+/// unknown languages use the english rules.
+pub fn plural_category(lang: &str, n: f64) -> PluralCategory {
+    let is_int = n.fract() == 0.0;
+    let i = n.abs().trunc() as u64;
+    let (mod10, mod100) = (i % 10, i % 100);
+    match base_language(lang).to_ascii_lowercase().as_str() {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" => PluralCategory::Other,
+        "fr" | "pt" => {
+            if i <= 1 {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+        "ru" | "uk" | "be" | "sr" | "hr" | "bs" => {
+            if !is_int {
+                PluralCategory::Other
+            } else if mod10 == 1 && mod100 != 11 {
+                PluralCategory::One
+            } else if (2..=4).contains(&mod10) && !(12..=14).contains(&mod100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "pl" => {
+            if !is_int {
+                PluralCategory::Other
+            } else if i == 1 {
+                PluralCategory::One
+            } else if (2..=4).contains(&mod10) && !(12..=14).contains(&mod100) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Many
+            }
+        }
+        "cs" | "sk" => {
+            if !is_int {
+                PluralCategory::Many
+            } else if i == 1 {
+                PluralCategory::One
+            } else if (2..=4).contains(&i) {
+                PluralCategory::Few
+            } else {
+                PluralCategory::Other
+            }
+        }
+        "ar" => {
+            if !is_int {
+                PluralCategory::Other
+            } else if i == 0 {
+                PluralCategory::Zero
+            } else if i == 1 {
+                PluralCategory::One
+            } else if i == 2 {
+                PluralCategory::Two
+            } else if (3..=10).contains(&mod100) {
+                PluralCategory::Few
+            } else if (11..=99).contains(&mod100) {
+                PluralCategory::Many
+            } else {
+                PluralCategory::Other
+            }
+        }
+        _ => {
+            if is_int && i == 1 {
+                PluralCategory::One
+            } else {
+                PluralCategory::Other
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PluralCategory, plural_category};
+
+    #[test]
+    fn plural_rules() {
+        assert_eq!(plural_category("en", 1.0), PluralCategory::One);
+        assert_eq!(plural_category("en", 0.0), PluralCategory::Other);
+        assert_eq!(plural_category("en-US", 1.5), PluralCategory::Other);
+        assert_eq!(plural_category("fr", 0.0), PluralCategory::One);
+        assert_eq!(plural_category("ru", 21.0), PluralCategory::One);
+        assert_eq!(plural_category("ru", 12.0), PluralCategory::Many);
+        assert_eq!(plural_category("pl", 23.0), PluralCategory::Few);
+        assert_eq!(plural_category("pl", 21.0), PluralCategory::Many);
+        assert_eq!(plural_category("zh_CN", 1.0), PluralCategory::Other);
+    }
+}
@@ -0,0 +1,251 @@
+use std::{path::PathBuf, sync::Arc};
+
+use base_io::{io::IoFileSys, runtime::IoRuntimeTask};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    catalog::{Catalog, Pattern, PatternElement},
+    plural::{base_language, plural_category},
+};
+
+/// The english catalog that is used if a message is missing
+/// in the selected language.
+pub const FALLBACK_CATALOG: &str = include_str!("../en.ftl");
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// An argument of a translated message.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TrArg {
+    Str(String),
+    Num(f64),
+}
+
+impl From<&str> for TrArg {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for TrArg {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<f64> for TrArg {
+    fn from(value: f64) -> Self {
+        Self::Num(value)
+    }
+}
+
+impl From<i64> for TrArg {
+    fn from(value: i64) -> Self {
+        Self::Num(value as f64)
+    }
+}
+
+impl From<usize> for TrArg {
+    fn from(value: usize) -> Self {
+        Self::Num(value as f64)
+    }
+}
+
+impl std::fmt::Display for TrArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrArg::Str(s) => write!(f, "{s}"),
+            TrArg::Num(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+/// All catalogs of a language, ordered by priority.
+///
+/// A message is looked up in the language itself, then in its
+/// base language (`pt` for `pt-BR`) and then in english.
+/// If no catalog knows the message, the message id itself is returned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Translations {
+    pub lang: String,
+    pub catalogs: Vec<Catalog>,
+}
+
+impl Default for Translations {
+    fn default() -> Self {
+        Self::new(FALLBACK_LANGUAGE, Vec::new())
+    }
+}
+
+impl Translations {
+    /// Creates the translations from the catalogs of the language,
+    /// the english fallback is always added.
+    pub fn new(lang: &str, mut catalogs: Vec<Catalog>) -> Self {
+        let (fallback, errs) = Catalog::parse(FALLBACK_CATALOG);
+        debug_assert!(errs.is_empty(), "{errs:?}");
+        catalogs.push(fallback);
+        Self {
+            lang: lang.to_string(),
+            catalogs,
+        }
+    }
+
+    fn find(&self, id: &str) -> Option<&Pattern> {
+        self.catalogs
+            .iter()
+            .find_map(|catalog| catalog.messages.get(id))
+    }
+
+    /// Whether any catalog, including the fallback, contains the message.
+    pub fn contains(&self, id: &str) -> bool {
+        self.find(id).is_some()
+    }
+
+    fn format_pattern(&self, pattern: &Pattern, args: &[(&str, TrArg)], res: &mut String) {
+        let arg = |name: &str| args.iter().find(|(arg, _)| *arg == name).map(|(_, v)| v);
+        for element in pattern {
+            match element {
+                PatternElement::Text(text) => res.push_str(text),
+                PatternElement::Var(var) => match arg(var) {
+                    Some(value) => res.push_str(&value.to_string()),
+                    None => {
+                        res.push('{');
+                        res.push_str(var);
+                        res.push('}');
+                    }
+                },
+                PatternElement::Select {
+                    var,
+                    variants,
+                    default,
+                } => {
+                    let value = arg(var);
+                    // exact matches win over plural categories
+                    let exact = value.and_then(|value| {
+                        variants.iter().position(|(key, _)| match value {
+                            TrArg::Str(s) => key == s,
+                            TrArg::Num(n) => key.parse::<f64>().is_ok_and(|key| key == *n),
+                        })
+                    });
+                    let category = || {
+                        value.and_then(|value| match value {
+                            TrArg::Num(n) => {
+                                let category = plural_category(&self.lang, *n);
+                                variants.iter().position(|(key, _)| key == category.name())
+                            }
+                            TrArg::Str(_) => None,
+                        })
+                    };
+                    let index = exact.or_else(category).unwrap_or(*default);
+                    self.format_pattern(&variants[index].1, args, res);
+                }
+            }
+        }
+    }
+
+    /// Translates a message without arguments.
+    pub fn tr(&self, id: &str) -> String {
+        self.tr_args(id, &[])
+    }
+
+    /// Translates a message, variables in the message are
+    /// replaced by the arguments of the same name.
+    pub fn tr_args(&self, id: &str, args: &[(&str, TrArg)]) -> String {
+        match self.find(id) {
+            Some(pattern) => {
+                let mut res = String::new();
+                self.format_pattern(pattern, args, &mut res);
+                res
+            }
+            None => id.to_string(),
+        }
+    }
+}
+
+/// Loads the translations of a language from `i18n/<lang>.ftl`
+/// in the data directory.
+pub struct TranslationsLoading {
+    lang: String,
+    task: IoRuntimeTask<Translations>,
+}
+
+impl TranslationsLoading {
+    pub fn new(io: &IoFileSys, lang: &str) -> Self {
+        let fs = io.fs.clone();
+        let lang = lang.to_string();
+        let lang_task = lang.clone();
+        Self {
+            lang,
+            task: io.rt.spawn(async move {
+                let lang = lang_task;
+                let mut langs = vec![lang.clone()];
+                let base = base_language(&lang);
+                if base != lang {
+                    langs.push(base.to_string());
+                }
+                let mut catalogs = Vec::new();
+                for lang in langs.into_iter().filter(|l| l != FALLBACK_LANGUAGE) {
+                    let path: PathBuf = format!("i18n/{lang}.ftl").into();
+                    match fs.read_file(&path).await {
+                        Ok(file) => {
+                            let (catalog, errs) = Catalog::parse(&String::from_utf8_lossy(&file));
+                            for err in errs {
+                                log::warn!(target: "i18n", "{}: {err}", path.display());
+                            }
+                            catalogs.push(catalog);
+                        }
+                        Err(err) => {
+                            log::info!(target: "i18n", "no translations at {}: {err}", path.display());
+                        }
+                    }
+                }
+                Ok(Translations::new(&lang, catalogs))
+            }),
+        }
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    pub fn finish(self) -> anyhow::Result<Arc<Translations>> {
+        self.task.get().map(Arc::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::catalog::Catalog;
+
+    use super::Translations;
+
+    #[test]
+    fn lookup_with_fallback() {
+        let (catalog, _) = Catalog::parse(
+            "settings-general = Allgemein\n\
+            mails = { $count ->\n    [0] Keine Nachrichten\n    [one] Eine Nachricht\n   *[other] { $count } Nachrichten\n}\n",
+        );
+        let tr = Translations::new("de", vec![catalog]);
+        assert_eq!(tr.tr("settings-general"), "Allgemein");
+        // missing in german, falls back to english
+        assert_eq!(tr.tr("settings-sound"), "Sound");
+        // missing everywhere
+        assert_eq!(tr.tr("does-not-exist"), "does-not-exist");
+
+        assert_eq!(
+            tr.tr_args("mails", &[("count", 0i64.into())]),
+            "Keine Nachrichten"
+        );
+        assert_eq!(
+            tr.tr_args("mails", &[("count", 1i64.into())]),
+            "Eine Nachricht"
+        );
+        assert_eq!(
+            tr.tr_args("mails", &[("count", 5i64.into())]),
+            "5 Nachrichten"
+        );
+    }
+}
@@ -14,6 +14,48 @@ pub enum MouseExtra {
     WheelUp,
 }
 
+/// Gamepad buttons, named after their position
+/// (e.g. [`GamepadButton::South`] is `A` on an Xbox controller).
+#[derive(
+    Debug, Hiarc, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    Mode,
+    LeftStick,
+    RightStick,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+}
+
+/// A direction of a gamepad stick.
+/// Counts as pressed if the stick is pushed far enough into
+/// that direction.
+#[derive(
+    Debug, Hiarc, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum GamepadAxis {
+    LeftStickLeft,
+    LeftStickRight,
+    LeftStickUp,
+    LeftStickDown,
+    RightStickLeft,
+    RightStickRight,
+    RightStickUp,
+    RightStickDown,
+}
+
 #[derive(
     Debug, Hiarc, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
 )]
@@ -21,6 +63,8 @@ pub enum BindKey {
     Key(PhysicalKey),
     Mouse(MouseButton),
     Extra(MouseExtra),
+    GamepadButton(GamepadButton),
+    GamepadAxis(GamepadAxis),
 }
 
 #[derive(Debug, Clone)]
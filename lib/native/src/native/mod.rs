@@ -17,6 +17,7 @@ pub use winit::dpi::PhysicalSize;
 pub use winit::event::DeviceId;
 pub use winit::event::MouseButton;
 pub use winit::event::MouseScrollDelta;
+pub use winit::event::TouchPhase;
 pub use winit::window::Window;
 pub use winit::{
     event::WindowEvent,
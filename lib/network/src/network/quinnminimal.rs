@@ -8,7 +8,11 @@ use quinn::{
     crypto::rustls::{QuicClientConfig, QuicServerConfig},
 };
 use rustls::{crypto::CryptoProvider, pki_types::PrivateKeyDer};
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use x509_cert::der::{Decode, Encode};
 
 use super::{
@@ -35,6 +39,43 @@ pub fn make_client_endpoint(
     Ok(endpoint)
 }
 
+/// Measures the round trip time to a server with a single QUIC handshake.
+///
+/// The connection is closed right after the handshake, so no game
+/// related data is ever exchanged.
+pub async fn ping_server(
+    addr: SocketAddr,
+    options: &NetworkClientInitOptions<'_>,
+) -> anyhow::Result<Duration> {
+    let bind_addr: SocketAddr = if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    }
+    .parse()?;
+    let endpoint = make_client_endpoint(bind_addr, options)?;
+    let start = Instant::now();
+    let connection = endpoint.connect(addr, "localhost")?.await?;
+    // the handshake also includes the crypto overhead,
+    // so prefer quinn's estimate if it is lower
+    let rtt = connection.rtt().min(start.elapsed());
+    connection.close(VarInt::default(), b"ping");
+    // make sure the server received the close, so it
+    // does not keep the connection until it times out
+    let wait_idle = endpoint.wait_idle();
+    match options
+        .base
+        .timeout
+        .filter(|timeout| *timeout != Duration::ZERO)
+    {
+        Some(timeout) => {
+            let _ = tokio::time::timeout(timeout, wait_idle).await;
+        }
+        None => wait_idle.await,
+    }
+    Ok(rtt)
+}
+
 /// Constructs a QUIC endpoint configured to listen for incoming connections on a certain address
 /// and port.
 ///
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use i18n::translations::{TrArg, Translations};
+
+fn translations_id() -> egui::Id {
+    egui::Id::new("ui-base-translations")
+}
+
+/// Sets the translations that are used by [`UiTranslate`]
+/// for all uis of this context.
+pub fn set_translations(ctx: &egui::Context, translations: Arc<Translations>) {
+    ctx.data_mut(|d| d.insert_temp(translations_id(), translations));
+}
+
+pub fn translations(ctx: &egui::Context) -> Arc<Translations> {
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_insert_with(translations_id(), Default::default)
+            .clone()
+    })
+}
+
+/// Looks up translated messages, see [`Translations`].
+pub trait UiTranslate {
+    fn tr(&self, id: &str) -> String;
+    fn tr_args(&self, id: &str, args: &[(&str, TrArg)]) -> String;
+}
+
+impl UiTranslate for egui::Context {
+    fn tr(&self, id: &str) -> String {
+        translations(self).tr(id)
+    }
+
+    fn tr_args(&self, id: &str, args: &[(&str, TrArg)]) -> String {
+        translations(self).tr_args(id, args)
+    }
+}
+
+impl UiTranslate for egui::Ui {
+    fn tr(&self, id: &str) -> String {
+        self.ctx().tr(id)
+    }
+
+    fn tr_args(&self, id: &str, args: &[(&str, TrArg)]) -> String {
+        self.ctx().tr_args(id, args)
+    }
+}
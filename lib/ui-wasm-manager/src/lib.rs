@@ -12,10 +12,12 @@ use graphics::graphics::graphics::Graphics;
 use graphics_backend::backend::GraphicsBackend;
 
 use hiarc::{Hiarc, hiarc_safer_rc_refcell};
+use i18n::translations::Translations;
 use serde::Serialize;
 use sound::sound::SoundManager;
 use tracing::instrument;
 use ui_base::{
+    translate::set_translations,
     types::{RawInputWrapper, RawOutputWrapper, UiFonts, UiRenderPipe},
     ui::{UiContainer, UiCreator},
 };
@@ -29,6 +31,8 @@ use wasmer::Module;
 
 pub struct UiWasmPageEntry {
     wasm_runtime: WasmManager,
+    /// The generation of the translations the module knows.
+    translations_generation: u64,
 }
 
 impl UiWasmPageEntry {
@@ -37,6 +41,11 @@ impl UiWasmPageEntry {
         self.wasm_runtime.run_by_name::<()>("ui_new")
     }
 
+    fn call_translations(&mut self, translations: &Translations) -> anyhow::Result<()> {
+        self.wasm_runtime.add_param(0, translations);
+        self.wasm_runtime.run_by_name::<()>("ui_translations")
+    }
+
     fn wasm_call_mount(&mut self) -> anyhow::Result<()> {
         self.wasm_runtime.run_by_name::<()>("ui_mount")
     }
@@ -104,6 +113,11 @@ where
 
     fonts: UiFonts,
 
+    translations: Arc<Translations>,
+    /// Increased every time the translations change,
+    /// so wasm modules know when to update theirs.
+    translations_generation: u64,
+
     err: UiWasmManagerErrorPageErr,
 }
 
@@ -161,10 +175,20 @@ where
 
             fonts,
 
+            translations: Default::default(),
+            translations_generation: 0,
+
             err: error_404_err,
         }
     }
 
+    /// Sets the translations for native & wasm pages.
+    pub fn set_translations(&mut self, translations: Arc<Translations>) {
+        set_translations(&self.ui.context.egui_ctx, translations.clone());
+        self.translations = translations;
+        self.translations_generation += 1;
+    }
+
     /// returns Some, if the path was already registered
     /// Re-registers/overwrites the path with the new callback in this case
     pub fn register_path(
@@ -229,6 +253,12 @@ where
                 }
                 match cb {
                     UiPageEntry::Wasm(ui) => {
+                        if ui.translations_generation != self.translations_generation {
+                            if let Err(err) = ui.call_translations(&self.translations) {
+                                return UiPageRunReturn::RuntimeError(err);
+                            }
+                            ui.translations_generation = self.translations_generation;
+                        }
                         if !blur {
                             self.ui.ui_state.blur_shapes.clear();
                         }
@@ -326,7 +356,11 @@ where
                                         MemoryLimit::OneGibiByte,
                                     )
                                     .unwrap();
-                                    let mut entry = UiWasmPageEntry { wasm_runtime };
+                                    let mut entry = UiWasmPageEntry {
+                                        wasm_runtime,
+                                        // forces sending the translations
+                                        translations_generation: u64::MAX,
+                                    };
                                     entry.call_new(&self.fonts).unwrap();
                                     self.ui_paths.insert(
                                         path.to_string(),
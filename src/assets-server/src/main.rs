@@ -294,7 +294,7 @@ async fn main() -> anyhow::Result<()> {
             args.no_cache,
             &upload_password,
             vec![AllowedResources::Tar(vec![
-                default_png,
+                default_png.clone(),
                 AllowedResource::Txt,
                 AllowedResource::Ogg,
             ])],
@@ -325,6 +325,19 @@ async fn main() -> anyhow::Result<()> {
             args.no_cache,
             &upload_password,
             vec![AllowedResources::File(AllowedResource::Txt)],
+            write_lock.clone(),
+        )
+        .await?,
+    )
+    .merge(
+        editor_prefabs(
+            args.no_cache,
+            &upload_password,
+            vec![AllowedResources::Tar(vec![
+                default_png,
+                AllowedResource::Txt,
+                AllowedResource::Ogg,
+            ])],
             write_lock,
         )
         .await?,
@@ -643,6 +656,22 @@ async fn editor_rules(
     .await
 }
 
+async fn editor_prefabs(
+    ignore_cached: bool,
+    upload_password: &Option<Arc<String>>,
+    allowed_resources: Vec<AllowedResources>,
+    write_lock: Arc<Mutex<()>>,
+) -> anyhow::Result<AssetRouter> {
+    assets_generic(
+        "editor/prefabs",
+        ignore_cached,
+        upload_password,
+        allowed_resources,
+        write_lock,
+    )
+    .await
+}
+
 async fn prepare_index_generic(base_path: &str) -> anyhow::Result<AssetsIndex> {
     let mut res: AssetsIndex = Default::default();
 
@@ -0,0 +1,177 @@
+use std::{collections::VecDeque, io::Write, net::SocketAddr, path::PathBuf, sync::mpsc};
+
+use base::join_thread::JoinThread;
+use base_io::{io::Io, runtime::IoRuntimeTask};
+
+/// How many lines of a chat log are kept in memory for searching.
+const MAX_LINES_IN_MEMORY: usize = 10_000;
+/// How many search results are shown at most.
+const MAX_SEARCH_RESULTS: usize = 50;
+
+/// Appends all chat messages of a server to a text file
+/// inside the save directory, one message per line.
+///
+/// The existing log is loaded by the io runtime and the file is written
+/// from a background thread, the most recent lines are additionally
+/// kept in memory so they can be searched.
+pub struct ChatLog {
+    lines: VecDeque<String>,
+    /// Increased for every appended line.
+    revision: u64,
+    last_search: Option<(String, u64, Vec<String>)>,
+
+    /// Loads the existing lines & creates the directory of the log.
+    load_task: Option<IoRuntimeTask<VecDeque<String>>>,
+    /// Lines that were added while the log was still loading.
+    pending: Vec<String>,
+
+    sender: mpsc::Sender<String>,
+    // must be last
+    _thread: JoinThread<()>,
+}
+
+impl ChatLog {
+    /// The chat log file of the server at `addr`, relative to the save directory.
+    pub fn path_for_server(addr: &SocketAddr) -> PathBuf {
+        // `:` is not allowed in file names on all platforms
+        let name = addr.to_string().replace([':', '[', ']'], "_");
+        PathBuf::from("chat_logs").join(format!("{name}.log"))
+    }
+
+    fn last_lines(file: &str) -> VecDeque<String> {
+        let lines: Vec<_> = file.lines().collect();
+        lines[lines.len().saturating_sub(MAX_LINES_IN_MEMORY)..]
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    /// Opens the chat log of the server, existing lines are kept.
+    pub fn new(io: &Io, addr: &SocketAddr) -> anyhow::Result<Self> {
+        let path = Self::path_for_server(addr);
+        let fs = io.fs.clone();
+        let load_path = path.clone();
+        let load_task = io.rt.spawn(async move {
+            if let Some(parent) = load_path.parent() {
+                fs.create_dir(parent).await?;
+            }
+            match fs.read_file(&load_path).await {
+                Ok(file) => Ok(Self::last_lines(&String::from_utf8_lossy(&file))),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Default::default()),
+                Err(err) => Err(err.into()),
+            }
+        });
+
+        let path = io.fs.get_save_path().join(path);
+        let (sender, receiver) = mpsc::channel::<String>();
+        let thread = std::thread::Builder::new()
+            .name("chat-log".into())
+            .spawn(move || {
+                // lines are only sent after the directory was created
+                let mut file = None;
+                while let Ok(line) = receiver.recv() {
+                    let file = file.get_or_insert_with(|| {
+                        std::fs::OpenOptions::new()
+                            .create(true)
+                            .append(true)
+                            .open(&path)
+                    });
+                    let res = match file {
+                        Ok(file) => writeln!(file, "{line}"),
+                        Err(err) => Err(std::io::Error::new(err.kind(), err.to_string())),
+                    };
+                    if let Err(err) = res {
+                        log::error!("writing the chat log {} failed: {err}", path.display());
+                    }
+                }
+            })?;
+
+        Ok(Self {
+            lines: Default::default(),
+            revision: 0,
+            last_search: None,
+            load_task: Some(load_task),
+            pending: Default::default(),
+            sender,
+            _thread: JoinThread::new(thread),
+        })
+    }
+
+    /// Puts the loaded lines in front of the lines added in the meantime
+    /// and writes those to the file.
+    fn finish_loading(&mut self) {
+        if !self
+            .load_task
+            .as_ref()
+            .is_some_and(|task| task.is_finished())
+        {
+            return;
+        }
+        let Some(task) = self.load_task.take() else {
+            return;
+        };
+        match task.get() {
+            Ok(mut lines) => {
+                lines.append(&mut self.lines);
+                let too_many = lines.len().saturating_sub(MAX_LINES_IN_MEMORY);
+                lines.drain(..too_many);
+                self.lines = lines;
+                self.revision += 1;
+            }
+            Err(err) => log::warn!("loading the chat log failed: {err}"),
+        }
+        for line in self.pending.drain(..) {
+            let _ = self.sender.send(line);
+        }
+    }
+
+    /// Adds a message to the log, prefixed by the local time.
+    pub fn append(&mut self, player: &str, msg: &str) {
+        self.finish_loading();
+        let line = format!(
+            "[{}] {player}: {msg}",
+            chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+        );
+        if self.lines.len() >= MAX_LINES_IN_MEMORY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line.clone());
+        self.revision += 1;
+        if self.load_task.is_some() {
+            self.pending.push(line);
+        } else {
+            let _ = self.sender.send(line);
+        }
+    }
+
+    /// The most recent lines that contain `text`, ignoring the case.
+    ///
+    /// The newest line is the last one.
+    /// The result is cached until the text or the log changes.
+    pub fn search(&mut self, text: &str) -> &[String] {
+        self.finish_loading();
+        let is_cached = self
+            .last_search
+            .as_ref()
+            .is_some_and(|(last_text, revision, _)| {
+                last_text == text && *revision == self.revision
+            });
+        if !is_cached {
+            let lower_text = text.to_lowercase();
+            let mut res: Vec<_> = self
+                .lines
+                .iter()
+                .rev()
+                .filter(|line| line.to_lowercase().contains(&lower_text))
+                .take(MAX_SEARCH_RESULTS)
+                .cloned()
+                .collect();
+            res.reverse();
+            self.last_search = Some((text.to_string(), self.revision, res));
+        }
+        self.last_search
+            .as_ref()
+            .map(|(_, _, res)| res.as_slice())
+            .unwrap_or_default()
+    }
+}
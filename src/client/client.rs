@@ -25,6 +25,7 @@ use client_containers::{
     skins::{SKIN_CONTAINER_PATH, SkinContainer},
 };
 use client_demo::{DemoVideoEncodeProperties, DemoViewer, DemoViewerSettings, EncoderSettings};
+use client_ghost::GhostViewer;
 use client_map::client_map::{ClientMapFile, ClientMapLoading, GameMap};
 use client_notifications::overlay::ClientNotifications;
 use client_render_base::{
@@ -48,8 +49,9 @@ use client_ui::{
     chat::user_data::{ChatEvent, ChatMode},
     connect::page::ConnectingUi,
     console::utils::run_commands,
+    demo_player::user_data::DemoViewerCameraMode,
     events::{UiEvent, UiEvents},
-    hud::user_data::RenderDateTime,
+    hud::user_data::{HudEvent, RenderDateTime},
     ingame_menu::{
         account_info::AccountInfo,
         client_info::{ActiveClientInfo, ClientInfo},
@@ -75,7 +77,7 @@ use ddnet_info_proxy::DdnetInfoProxy;
 use demo::recorder::DemoRecorder;
 use editor::editor::{EditorInterface, EditorResult};
 use egui::{CursorIcon, FontDefinitions};
-use game_config::config::{Config, ConfigGame, ConfigMap};
+use game_config::config::{Config, ConfigDemoRecorder, ConfigGame, ConfigMap};
 use graphics::graphics::graphics::Graphics;
 use graphics_backend::{
     backend::{
@@ -111,8 +113,9 @@ use game_interface::{
     },
     votes::{VoteIdentifierType, VoteType, Voted},
 };
-use game_server::{local_server::start_local_server, server::Server};
+use game_server::{local_server::start_local_server, server::Server, server_game::ServerMap};
 use graphics_types::rendering::ColorRgba;
+use i18n::translations::{Translations, TranslationsLoading};
 use input_binds::binds::{BindKey, Binds};
 use legacy_proxy::LegacyProxy;
 use math::math::{
@@ -138,6 +141,7 @@ use steam::{init_steam, traits::SteamRaii};
 use tracing::instrument;
 use ui_base::{
     font_data::{UiFontData, UiFontDataLoading},
+    translate::set_translations,
     types::UiRenderPipe,
     ui::UiCreator,
 };
@@ -168,13 +172,18 @@ use game_network::messages::{ClientToServerMessage, ClientToServerPlayerMessage}
 
 use super::{
     game::{
+        active::ActiveGame,
         data::{ClientConnectedPlayer, GameData},
         types::{DisconnectAutoCleanup, GameBase, GameConnect, GameMsgPipeline},
     },
     game_events::{GameEventPipeline, GameEventsClient},
     input::input_handling::{InputEv, InputHandling, InputHandlingEvent},
     localplayer::ClientPlayerInputPerTick,
-    overlays::client_stats::{ClientStats, ClientStatsRenderPipe, DebugHudRenderPipe},
+    overlays::{
+        client_stats::{ClientStats, ClientStatsRenderPipe, DebugHudRenderPipe},
+        touch_controls::TouchControlsOverlay,
+    },
+    server_pinger::ServerPinger,
     spatial_chat::spatial_chat::{self, SpatialChatGameWorldTy, SpatialChatGameWorldTyRef},
 };
 
@@ -202,12 +211,12 @@ pub fn ddnet_main(
 
     let mut has_startup_errors = false;
     let local_console_builder = if !start_arguments.is_empty() {
-        let local_console_builder = LocalConsoleBuilder::default();
-        let parser_entries = entries_to_parser(&local_console_builder.entries);
+        let mut local_console_builder = LocalConsoleBuilder::default();
+        LocalConsoleBuilder::sync_aliases(&mut local_console_builder.entries, &config_game);
         for line in start_arguments.iter().filter(|l| !l.is_empty()) {
             let cmds = command_parser::parser::parse(
                 line,
-                &parser_entries,
+                &entries_to_parser(&local_console_builder.entries),
                 &local_console_builder.parser_cache,
             );
             let mut res = String::default();
@@ -223,6 +232,7 @@ pub fn ddnet_main(
             if !cur_cmds_succeeded {
                 log::error!("{res}");
             }
+            LocalConsoleBuilder::sync_aliases(&mut local_console_builder.entries, &config_game);
             let mut has_events = true;
             let mut count = 0;
             while has_events {
@@ -235,7 +245,7 @@ pub fn ddnet_main(
                             file_path.clone(),
                             &mut config_engine,
                             &mut config_game,
-                            &local_console_builder.entries,
+                            &mut local_console_builder.entries,
                             &local_console_builder.parser_cache,
                             |err| {
                                 log::error!("{err}");
@@ -277,6 +287,8 @@ pub fn ddnet_main(
 
     let config_wnd = config_engine.wnd.clone();
 
+    let translations_loading = TranslationsLoading::new(&io, &config_game.cl.language);
+
     let client = ClientNativeLoadingImpl {
         time: time.clone(),
         shared_info,
@@ -285,6 +297,7 @@ pub fn ddnet_main(
         config_game,
         graphics_backend_io_loading,
         graphics_backend_loading: None,
+        translations_loading,
         local_console_builder,
         has_startup_errors,
     };
@@ -355,6 +368,7 @@ struct ClientNativeLoadingImpl {
     config_game: ConfigGame,
     graphics_backend_io_loading: GraphicsBackendIoLoading,
     graphics_backend_loading: Option<GraphicsBackendLoading>,
+    translations_loading: TranslationsLoading,
 
     local_console_builder: Option<LocalConsoleBuilder>,
     has_startup_errors: bool,
@@ -370,6 +384,7 @@ struct ClientNativeImpl {
     player_settings_sync: PlayerSettingsSync,
     raw_input_info: RawInputInfo,
     browser_data: ServerBrowserData,
+    server_pinger: ServerPinger,
 
     scene: SceneObject,
 
@@ -379,6 +394,7 @@ struct ClientNativeImpl {
     connecting_log: ConnectingLog,
     demo_player: Option<DemoViewer>,
     client_stats: ClientStats,
+    touch_controls: TouchControlsOverlay,
     notifications: ClientNotifications,
     thread_pool: Arc<ThreadPool>,
     io: Io,
@@ -398,6 +414,8 @@ struct ClientNativeImpl {
     ui_events: UiEvents,
     font_data: FontDefinitions,
     ui_creator: UiCreator,
+    translations: Arc<Translations>,
+    translations_loading: Option<TranslationsLoading>,
 
     _ddnet_info_proxy: DdnetInfoProxy,
 
@@ -463,6 +481,39 @@ impl ClientNativeImpl {
         Ok(())
     }
 
+    fn demo_video_encode_props(
+        recorder: &ConfigDemoRecorder,
+        video_name: &str,
+        camera_mode: DemoViewerCameraMode,
+        camera_pos: Option<vec2>,
+        zoom: f32,
+    ) -> DemoVideoEncodeProperties {
+        DemoVideoEncodeProperties {
+            file_name: format!("videos/{video_name}.mp4").into(),
+            pixels_per_point: recorder.pixels_per_point,
+            encoder_settings: EncoderSettings {
+                fps: recorder.fps,
+                width: recorder.width,
+                height: recorder.height,
+                hw_accel: recorder.hw_accel.clone(),
+                max_threads: std::thread::available_parallelism()
+                    .map(|v| v.get() + 2)
+                    .unwrap_or_default()
+                    .max(2) as u64,
+                sample_rate: recorder.sample_rate,
+                crf: recorder.crf,
+            },
+            settings: DemoViewerSettings {
+                global_sound_volume: recorder.global_sound_volume,
+                render: recorder.render.clone(),
+                snd: recorder.snd.clone(),
+                camera_mode,
+                camera_pos,
+                zoom,
+            },
+        }
+    }
+
     fn connect_internal_server(
         &mut self,
         addresses: Vec<SocketAddr>,
@@ -500,6 +551,7 @@ impl ClientNativeImpl {
                         self.shared_info.clone(),
                         self.config.engine.clone(),
                         self.config.game.clone(),
+                        None,
                     );
                 }
 
@@ -520,6 +572,63 @@ impl ClientNativeImpl {
         }
     }
 
+    /// Executes a rcon command on the server as the active local player.
+    fn send_rcon_exec(
+        game: &ActiveGame,
+        notifications: &mut ClientNotifications,
+        ident_text: &str,
+        args: &str,
+    ) {
+        if let Some((player_id, _)) = game.game_data.local.active_local_player() {
+            if let (Ok(ident_text), Ok(args)) = (ident_text.try_into(), args.try_into()) {
+                game.network.send_in_order_to_server(
+                    &ClientToServerMessage::PlayerMsg((
+                        *player_id,
+                        ClientToServerPlayerMessage::RconExec { ident_text, args },
+                    )),
+                    NetworkInOrderChannel::Custom(
+                        7302, // reads as "rcon"
+                    ),
+                );
+            } else {
+                notifications.add_err("rcon text limit reached.", Duration::from_secs(3));
+            }
+        }
+    }
+
+    /// Restarts the internal server on the given map with the
+    /// practice mode enabled and connects to it.
+    fn start_practice(&mut self, map: String) {
+        let mut config_game = self.config.game.clone();
+        config_game.sv.map = map;
+        config_game.sv.practice.enabled = true;
+        self.restart_local_server(config_game, None);
+    }
+
+    /// Restarts the internal server with the given config and connects to it.
+    fn restart_local_server(&mut self, config_game: ConfigGame, map: Option<ServerMap>) {
+        // a running internal server might still be on another map
+        self.game = Game::None;
+        *self.shared_info.state.lock().unwrap() = LocalServerState::None;
+        // ui events are only handled while the ui is open
+        self.ui_manager.ui.ui_state.is_ui_open = true;
+
+        start_local_server(
+            &self.time,
+            self.shared_info.clone(),
+            self.config.engine.clone(),
+            config_game,
+            map,
+        );
+        self.ui_events.push(UiEvent::Connect {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            cert_hash: Default::default(),
+            rcon_secret: None,
+            can_start_internal_server: false,
+            can_connect_internal_server: true,
+        });
+    }
+
     fn on_window_change(&mut self, native: &mut dyn NativeImpl) {
         let config_wnd = &self.config.engine.wnd;
 
@@ -590,10 +699,12 @@ impl ClientNativeImpl {
                 || self.editor.is_open()
                 || self.demo_player.is_some();
 
-            let intra_tick_ratio = intra_tick_time_to_ratio(
-                game.game_data.intra_tick_time,
-                game_state.game_tick_speed(),
-            );
+            let ticks_per_second = game_state.game_tick_speed();
+            let tick_rate = game.game_data.tick_rate.unwrap_or(ticks_per_second);
+            let intra_tick_ratio =
+                intra_tick_time_to_ratio(game.game_data.intra_tick_time, tick_rate);
+            // rendering works with the game time, which passes slower in slow motion
+            let game_intra_tick_time = time_until_tick(ticks_per_second).mul_f64(intra_tick_ratio);
 
             let active_local_player_id = game
                 .game_data
@@ -627,8 +738,7 @@ impl ClientNativeImpl {
             ) = if self.config.game.cl.anti_ping && !main_local_char_prefer_unpredicted {
                 (game_state, None, intra_tick_ratio, intra_tick_ratio)
             } else {
-                let ticks_per_second = game_state.game_tick_speed();
-                let tick_time = time_until_tick(ticks_per_second);
+                let tick_time = time_until_tick(tick_rate);
                 let sub_ticks = (game
                     .game_data
                     .prediction_timer
@@ -841,9 +951,10 @@ impl ClientNativeImpl {
                 stages,
                 scoreboard_info: None,
                 date_time: None,
+                ghost_split: None,
                 game_time_info: GameTimeInfo {
                     ticks_per_second: main_game.game_tick_speed(),
-                    intra_tick_time: game.game_data.intra_tick_time,
+                    intra_tick_time: game_intra_tick_time,
                 },
                 settings: RenderGameSettings::new(
                     &self.config.game.cl.render,
@@ -852,10 +963,31 @@ impl ClientNativeImpl {
                     1.0,
                     self.config.game.cl.anti_ping,
                     self.config.game.snd.global_volume,
+                    &self
+                        .config
+                        .game
+                        .players
+                        .get(self.config.game.profiles.main as usize)
+                        .map(|p| p.hud_layout)
+                        .unwrap_or_default(),
                 ),
                 ext: main_game.collect_render_ext(),
             };
 
+            let chat_log_search = game
+                .game_data
+                .local
+                .active_local_player()
+                .filter(|(_, client_player)| {
+                    matches!(client_player.chat_input_active, Some(ChatMode::Search))
+                })
+                .map(|(_, client_player)| client_player.chat_msg.clone())
+                .and_then(|text| {
+                    game.chat_log
+                        .as_mut()
+                        .map(|chat_log| chat_log.search(&text).to_vec())
+                });
+
             type CharacterInfos = PoolFxLinkedHashMap<CharacterId, CharacterInfo>;
             type StageRenderInfos = PoolFxLinkedHashMap<StageId, StageRenderInfo>;
             let mut fill_for_player = {
@@ -1029,7 +1161,11 @@ impl ClientNativeImpl {
                                 } else {
                                     None
                                 },
+                                hud_editor_input: (client_player.hud_editor_active
+                                    && !is_menu_open)
+                                    .then(|| self.inp_manager.clone_inp().egui.unwrap_or_default()),
                                 chat_show_all: client_player.show_chat_all,
+                                chat_log_search: chat_log_search.clone(),
                                 scoreboard_active: client_player.show_scoreboard
                                     || force_scoreboard_visible,
 
@@ -1143,6 +1279,92 @@ impl ClientNativeImpl {
                 }
             }
 
+            // ghosts race alongside the active local player
+            if game.ghost_viewer.is_none()
+                && main_game.info.options.ghosts
+                && self.config.game.cl.ghost.max_shown > 0
+            {
+                let props = &game.demo_recorder_props.base;
+                game.ghost_viewer = Some(GhostViewer::new(
+                    &self.io,
+                    &self.thread_pool,
+                    &self.sound,
+                    &self.graphics,
+                    &self.graphics_backend,
+                    &self.sound_backend,
+                    &self.time,
+                    &props.map,
+                    props.map_hash,
+                    self.font_data.clone(),
+                ));
+            }
+            if let Some(ghost_viewer) = &mut game.ghost_viewer {
+                if game
+                    .ghost_recorder
+                    .as_mut()
+                    .is_some_and(|recorder| recorder.take_new_ghost())
+                {
+                    ghost_viewer.reload();
+                }
+                let active_character = render_game_input.players.keys().next().and_then(|id| {
+                    render_game_input
+                        .character_infos
+                        .get(id)
+                        .and_then(|c| c.stage_id)
+                        .and_then(|stage_id| render_game_input.stages.get(&stage_id))
+                        .and_then(|stage| stage.world.characters.get(id))
+                });
+                let player_pos = active_character.map(|c| c.lerped_pos);
+                let race_time = active_character
+                    .map(|c| {
+                        time_until_tick(render_game_input.game_time_info.ticks_per_second)
+                            .mul_f64(c.game_ticks_passed as f64)
+                    })
+                    .unwrap_or_default();
+
+                ghost_viewer.update(
+                    &self.config.engine,
+                    &self.config.game,
+                    &self.ui_creator,
+                    race_time,
+                    &mut render_game_input,
+                );
+                render_game_input.ghost_split =
+                    ghost_viewer.split(&self.config.game, race_time, player_pos);
+            }
+
+            // let the auto director control the free camera of spectators
+            let spectates_freely = render_game_input.players.keys().next().is_some_and(|id| {
+                render_game_input
+                    .character_infos
+                    .get(id)
+                    .and_then(|c| c.player_info.as_ref())
+                    .is_some_and(|p| {
+                        matches!(p.ingame_mode, PlayerIngameMode::Spectator)
+                            && matches!(p.cam_mode, PlayerCameraMode::Free)
+                    })
+            });
+            if self.config.game.cl.render.auto_director && spectates_freely {
+                if let Some(camera) = game.auto_director.update(
+                    &self.cur_time,
+                    render_game_input
+                        .settings
+                        .ingame_aspect
+                        .unwrap_or_else(|| self.graphics.canvas_handle.canvas_aspect()),
+                    &render_game_input.events,
+                    &render_game_input.stages,
+                ) && let Some((_, player)) = render_game_input.players.iter_mut().next()
+                {
+                    player.render_for_player.cam_mode = RenderPlayerCameraMode::AtPos {
+                        pos: camera.pos,
+                        locked_ingame: false,
+                    };
+                    player.render_for_player.zoom = camera.zoom;
+                }
+            } else {
+                game.auto_director.reset();
+            }
+
             // if miniscreens of the dummies should show up, add additional infor for player.
             if self.config.game.cl.dummy.mini_screen
                 && let Some((_, player)) = render_game_input.players.iter_mut().next()
@@ -1227,6 +1449,8 @@ impl ClientNativeImpl {
                                             msg: NetworkString::new(&msg).unwrap(),
                                         })
                                     }
+                                    // searching never sends a message
+                                    ChatMode::Search => None,
                                 } {
                                     game.network.send_in_order_to_server(
                                         &ClientToServerMessage::PlayerMsg((
@@ -1311,6 +1535,18 @@ impl ClientNativeImpl {
                                     !self.config.game.cl.phased_ingame_spectate;
                             }
                         },
+                        PlayerFeedbackEvent::Hud(ev) => match ev {
+                            HudEvent::LayoutChanged(layout) => {
+                                if let Some(p) = self
+                                    .config
+                                    .game
+                                    .players
+                                    .get_mut(self.config.game.profiles.main as usize)
+                                {
+                                    p.hud_layout = (&layout).into();
+                                }
+                            }
+                        },
                     }
                 }
             }
@@ -1334,6 +1570,7 @@ impl ClientNativeImpl {
                 &self.io,
                 &self.thread_pool,
                 &self.font_data,
+                &self.translations,
             );
             self.editor = if is_open {
                 EditorState::Open(editor)
@@ -1366,6 +1603,35 @@ impl ClientNativeImpl {
                         EditorState::None => EditorState::None,
                     };
                 }
+                EditorResult::Playtest(playtest) => {
+                    // keep the editor alive, so tabs & undo history
+                    // are still there when returning
+                    self.editor = match std::mem::take(&mut self.editor) {
+                        EditorState::Open(editor) | EditorState::Minimized(editor) => {
+                            EditorState::Minimized(editor)
+                        }
+                        EditorState::None => EditorState::None,
+                    };
+
+                    match playtest.map_name.as_str().try_into() {
+                        Ok(name) => {
+                            let mut config_game = self.config.game.clone();
+                            config_game.sv.map = playtest.map_name;
+                            config_game.sv.game_mod = playtest.game_mod;
+                            self.restart_local_server(
+                                config_game,
+                                Some(ServerMap {
+                                    name,
+                                    map_file: playtest.map_file,
+                                    resource_files: playtest.resource_files,
+                                }),
+                            );
+                        }
+                        Err(err) => {
+                            log::error!("invalid playtest map name: {err}");
+                        }
+                    }
+                }
                 EditorResult::Close => {
                     self.editor = EditorState::None;
                 }
@@ -1393,6 +1659,19 @@ impl ClientNativeImpl {
                         || demo_viewer.is_closed()
                     {
                         self.demo_player = None;
+                    } else if let Some(request) = demo_viewer.take_encode_request() {
+                        // restart the demo as video encode, with the camera of the viewer
+                        *demo_player = demo_viewer.encode_to_video(
+                            &self.thread_pool,
+                            self.font_data.clone(),
+                            Self::demo_video_encode_props(
+                                &self.config.game.cl.recorder,
+                                &request.video_name,
+                                request.camera_mode,
+                                request.camera_pos,
+                                request.zoom,
+                            ),
+                        );
                     }
                 } else if let Err(err) = demo_player.continue_loading(
                     &self.sound,
@@ -1419,7 +1698,7 @@ impl ClientNativeImpl {
                             .into_iter()
                             .filter_map(|ev| match ev {
                                 InputEv::Key(ev) => ev.is_down.then_some(ev.key),
-                                InputEv::Move(_) => None,
+                                InputEv::Move(_) | InputEv::Aim(_) => None,
                             })
                             .collect(),
                     });
@@ -1464,6 +1743,7 @@ impl ClientNativeImpl {
                                 self.shared_info.clone(),
                                 self.config.engine.clone(),
                                 self.config.game.clone(),
+                                None,
                             );
                         }
                         UiEvent::CheckLocalServer => {
@@ -1481,39 +1761,24 @@ impl ClientNativeImpl {
                                 None,
                             ));
                         }
-                        UiEvent::EncodeDemoToVideo { name, video_name } => {
+                        UiEvent::EncodeDemoToVideo {
+                            name,
+                            video_name,
+                            camera_mode,
+                            zoom,
+                        } => {
                             self.demo_player = Some(DemoViewer::new(
                                 &self.io,
                                 &self.thread_pool,
                                 name.as_ref(),
                                 self.font_data.clone(),
-                                Some(DemoVideoEncodeProperties {
-                                    file_name: format!("videos/{video_name}.mp4").into(),
-                                    pixels_per_point: self.config.game.cl.recorder.pixels_per_point,
-                                    encoder_settings: EncoderSettings {
-                                        fps: self.config.game.cl.recorder.fps,
-                                        width: self.config.game.cl.recorder.width,
-                                        height: self.config.game.cl.recorder.height,
-                                        hw_accel: self.config.game.cl.recorder.hw_accel.clone(),
-                                        max_threads: std::thread::available_parallelism()
-                                            .map(|v| v.get() + 2)
-                                            .unwrap_or_default()
-                                            .max(2)
-                                            as u64,
-                                        sample_rate: self.config.game.cl.recorder.sample_rate,
-                                        crf: self.config.game.cl.recorder.crf,
-                                    },
-                                    settings: DemoViewerSettings {
-                                        global_sound_volume: self
-                                            .config
-                                            .game
-                                            .cl
-                                            .recorder
-                                            .global_sound_volume,
-                                        render: self.config.game.cl.recorder.render.clone(),
-                                        snd: self.config.game.cl.recorder.snd.clone(),
-                                    },
-                                }),
+                                Some(Self::demo_video_encode_props(
+                                    &self.config.game.cl.recorder,
+                                    &video_name,
+                                    camera_mode,
+                                    None,
+                                    zoom,
+                                )),
                             ));
                         }
                         UiEvent::RecordDemo => {
@@ -1556,6 +1821,7 @@ impl ClientNativeImpl {
                                     &self.io,
                                     &self.thread_pool,
                                     &self.font_data,
+                                    &self.translations,
                                 )),
                             };
                         }
@@ -2107,29 +2373,18 @@ impl ClientNativeImpl {
             for event in events {
                 match event {
                     RemoteConsoleEvent::Exec { ident_text, args } => {
-                        if let Some((player_id, _)) = game.game_data.local.active_local_player() {
-                            if let (Ok(ident_text), Ok(args)) =
-                                (ident_text.as_str().try_into(), args.as_str().try_into())
-                            {
-                                game.network.send_in_order_to_server(
-                                    &ClientToServerMessage::PlayerMsg((
-                                        *player_id,
-                                        ClientToServerPlayerMessage::RconExec { ident_text, args },
-                                    )),
-                                    NetworkInOrderChannel::Custom(
-                                        7302, // reads as "rcon"
-                                    ),
-                                );
-                            } else {
-                                self.notifications
-                                    .add_err("rcon text limit reached.", Duration::from_secs(3));
-                            }
-                        }
+                        Self::send_rcon_exec(game, &mut self.notifications, &ident_text, &args);
                     }
                 }
             }
         }
 
+        // on-screen touch controls
+        if let Some(touch_controls) = self.inp_manager.touch_controls() {
+            self.touch_controls
+                .render(touch_controls, &self.config.game.inp.touch);
+        }
+
         // notifications (e.g. error popups)
         self.notifications.render();
 
@@ -2151,6 +2406,15 @@ impl ClientNativeImpl {
             },
             force_bottom: self.ui_manager.ui.ui_state.is_ui_open,
             show_fps: self.config.game.cl.show_fps,
+            hud_layout: if let Game::Active(_) = &self.game {
+                self.config
+                    .game
+                    .players
+                    .get(self.config.game.profiles.main as usize)
+                    .map(|p| (&p.hud_layout).into())
+            } else {
+                None
+            },
             mic_active: if let Game::Active(game) = &self.game {
                 matches!(game.spatial_world, SpatialChatGameWorldTy::World(_))
             } else {
@@ -2208,7 +2472,7 @@ impl ClientNativeImpl {
         config_engine: &mut ConfigEngine,
         config_game: &mut ConfigGame,
 
-        entries: &[ConsoleEntry],
+        entries: &mut Vec<ConsoleEntry>,
         parser_cache: &ParserCache,
         mut on_err: impl FnMut(String),
         mut on_log: impl FnMut(String),
@@ -2244,10 +2508,11 @@ impl ClientNativeImpl {
         };
 
         let mut cmds_succeeded = true;
-        let parser_entries = entries_to_parser(entries);
+        let mut parser_entries = entries_to_parser(entries);
         for line in cmds_file.lines().filter(|l| !l.is_empty()) {
             let cmds = command_parser::parser::parse(line, &parser_entries, parser_cache);
             let mut res = String::default();
+            let aliases_count = config_game.cl.console.aliases.len();
             let cur_cmds_succeeded =
                 run_commands(&cmds, entries, config_engine, config_game, &mut res, true);
             log::debug!("{res}");
@@ -2255,6 +2520,12 @@ impl ClientNativeImpl {
                 on_log(res);
             }
             cmds_succeeded &= cur_cmds_succeeded;
+
+            // aliases defined in this file can be used in the next lines
+            if aliases_count != config_game.cl.console.aliases.len() {
+                LocalConsoleBuilder::sync_aliases(entries, config_game);
+                parser_entries = entries_to_parser(entries);
+            }
         }
         if !cmds_succeeded {
             on_err(
@@ -2388,7 +2659,7 @@ impl ClientNativeImpl {
                     file_path,
                     &mut self.config.engine,
                     &mut self.config.game,
-                    &self.local_console.entries,
+                    &mut self.local_console.entries,
                     &self.local_console.user,
                     |err| {
                         self.notifications.add_err(err, Duration::from_secs(10));
@@ -2484,6 +2755,21 @@ impl ClientNativeImpl {
                         }
                     }
                 }
+                LocalConsoleEvent::Alias => {
+                    LocalConsoleBuilder::sync_aliases(
+                        &mut self.local_console.entries,
+                        &self.config.game,
+                    );
+                }
+                LocalConsoleEvent::Practice { map } => self.start_practice(map),
+                LocalConsoleEvent::Rcon { ident_text, args } => {
+                    if let Game::Active(game) = &self.game {
+                        Self::send_rcon_exec(game, &mut self.notifications, &ident_text, &args);
+                    } else {
+                        self.notifications
+                            .add_err("Not connected to a server.", Duration::from_secs(3));
+                    }
+                }
                 LocalConsoleEvent::Quit => native.quit(),
                 LocalConsoleEvent::ConfigVariable { name } => {
                     // some special cases
@@ -2707,11 +2993,19 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
         ui_creator.load_font(&font_data);
         benchmark.bench("loading font");
 
+        let translations = loading.translations_loading.finish().unwrap_or_else(|err| {
+            log::warn!("Failed to load translations: {err}");
+            Default::default()
+        });
+        set_translations(&ui_creator.context.egui_ctx, translations.clone());
+        benchmark.bench("translations");
+
         let mut local_console = loading
             .local_console_builder
             .take()
             .unwrap_or_default()
             .build(&ui_creator);
+        LocalConsoleBuilder::sync_aliases(&mut local_console.entries, &loading.config_game);
         benchmark.bench("local console");
 
         // then prepare components allocations etc.
@@ -2795,6 +3089,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             graphics_memory_usage.staging_memory_usage,
             &ui_creator,
         );
+        let touch_controls = TouchControlsOverlay::new(&graphics, &loading.time, &ui_creator);
         let mut notifications = ClientNotifications::new(&graphics, &loading.time, &ui_creator);
         if loading.has_startup_errors {
             notifications.add_err(
@@ -2813,6 +3108,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             UiPageLoadingType::ShowLoadingPage(loading_page),
             &ui_creator,
         );
+        ui_manager.set_translations(translations.clone());
         benchmark.bench("ui manager");
 
         let (steam_client, steam_rt) = init_steam(412220)?;
@@ -3000,6 +3296,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             demo_player: None,
 
             client_stats,
+            touch_controls,
             notifications,
 
             thread_pool,
@@ -3015,6 +3312,8 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             ui_events,
             font_data,
             ui_creator,
+            translations,
+            translations_loading: None,
 
             _ddnet_info_proxy: ddnet_info_proxy,
 
@@ -3029,6 +3328,7 @@ impl FromNativeLoadingImpl<ClientNativeLoadingImpl> for GraphicsApp<ClientNative
             raw_input_info,
             spatial_chat: spatial_chat::SpatialChat::new(spatial_chat),
             browser_data,
+            server_pinger: Default::default(),
 
             scene,
 
@@ -3142,9 +3442,8 @@ impl InputEventHandler for ClientNativeImpl {
     }
 
     fn raw_window_event(&mut self, window: &native::native::Window, event: &WindowEvent) -> bool {
-        self.inp_manager.raw_event(window, event);
-        // we never actually consume events
-        false
+        // only the touch controls consume events
+        self.inp_manager.raw_event(window, event)
     }
 }
 
@@ -3158,8 +3457,52 @@ impl AppWithGraphics for ClientNativeImpl {
     }
 
     #[instrument(level = "trace", skip_all)]
+    /// Loads the translations if the language changed
+    /// and hands them to all uis once loaded.
+    fn update_translations(&mut self) {
+        let lang = &self.config.game.cl.language;
+        if self.translations.lang == *lang {
+            self.translations_loading = None;
+        } else if self
+            .translations_loading
+            .as_ref()
+            .is_none_or(|loading| loading.lang() != lang)
+        {
+            self.translations_loading =
+                Some(TranslationsLoading::new(&self.io.clone().into(), lang));
+        }
+
+        if self
+            .translations_loading
+            .as_ref()
+            .is_some_and(|loading| loading.is_finished())
+        {
+            let loading = self.translations_loading.take().unwrap();
+            let lang = loading.lang().to_string();
+            let translations = loading.finish().unwrap_or_else(|err| {
+                log::warn!("Failed to load translations for {lang}: {err}");
+                Arc::new(Translations::new(&lang, Vec::new()))
+            });
+            set_translations(&self.ui_creator.context.egui_ctx, translations.clone());
+            self.ui_manager.set_translations(translations.clone());
+            if let EditorState::Open(editor) | EditorState::Minimized(editor) = &mut self.editor {
+                editor.set_translations((*translations).clone());
+            }
+            self.translations = translations;
+        }
+    }
+
     fn run(&mut self, native: &mut dyn NativeImpl) {
-        self.inp_manager.collect_events();
+        self.update_translations();
+        self.inp_manager
+            .collect_events(&self.config.game.inp.gamepad);
+        self.inp_manager.set_touch_controls(
+            &self.config.game.inp.touch,
+            self.config.game.inp.touch.enabled
+                && matches!(self.game, Game::Active(_))
+                && matches!(self.editor, EditorState::None)
+                && !self.ui_manager.ui.ui_state.is_ui_open,
+        );
 
         let mut open_editor = false;
         self.inp_manager.handle_global_binds(
@@ -3183,6 +3526,7 @@ impl AppWithGraphics for ClientNativeImpl {
                         &self.io,
                         &self.thread_pool,
                         &self.font_data,
+                        &self.translations,
                     );
                     EditorState::Open(editor)
                 }
@@ -3194,6 +3538,12 @@ impl AppWithGraphics for ClientNativeImpl {
         let time = &mut self.time;
         self.cur_time = time.now();
 
+        // pings are only interesting while the server browser can be seen
+        if self.ui_manager.ui.ui_state.is_ui_open {
+            self.server_pinger
+                .update(&self.io, &self.browser_data, self.cur_time);
+        }
+
         if let Some(legacy_proxy) = &self.legacy_proxy_thread
             && !matches!(self.game, Game::Active(_))
             && legacy_proxy.thread.is_finished()
@@ -3353,24 +3703,26 @@ impl AppWithGraphics for ClientNativeImpl {
 
                 let player = game.game_data.local.active_local_player();
                 let needs_abs_cursor = player
-                    .and_then(|(id, client_player)| {
-                        game.game_data
-                            .cached_character_infos
-                            .get(id)
-                            .and_then(|c| c.player_info.as_ref().map(|p| (client_player, p)))
-                    })
-                    .is_some_and(|(client_player, p)| {
-                        client_player.spectator_selection_active
-                            && (game.map.game.info.options.has_ingame_freecam
-                                || match p.cam_mode {
-                                    PlayerCameraMode::Default => false,
-                                    PlayerCameraMode::Free => true,
-                                    PlayerCameraMode::LockedTo { locked_ingame, .. }
-                                    | PlayerCameraMode::LockedOn { locked_ingame, .. } => {
-                                        !locked_ingame
-                                    }
-                                })
-                    });
+                    .is_some_and(|(_, client_player)| client_player.hud_editor_active)
+                    || player
+                        .and_then(|(id, client_player)| {
+                            game.game_data
+                                .cached_character_infos
+                                .get(id)
+                                .and_then(|c| c.player_info.as_ref().map(|p| (client_player, p)))
+                        })
+                        .is_some_and(|(client_player, p)| {
+                            client_player.spectator_selection_active
+                                && (game.map.game.info.options.has_ingame_freecam
+                                    || match p.cam_mode {
+                                        PlayerCameraMode::Default => false,
+                                        PlayerCameraMode::Free => true,
+                                        PlayerCameraMode::LockedTo { locked_ingame, .. }
+                                        | PlayerCameraMode::LockedOn { locked_ingame, .. } => {
+                                            !locked_ingame
+                                        }
+                                    })
+                        });
                 native.relative_mouse(!needs_abs_cursor);
 
                 self.inp_manager.set_last_known_cursor(
@@ -3391,7 +3743,11 @@ impl AppWithGraphics for ClientNativeImpl {
             let game_state = &mut game.map.game;
 
             let tick_of_inp = game_state.predicted_game_monotonic_tick + 1;
-            let ticks_per_second = game_state.game_tick_speed();
+            // in slow motion the server ticks less often than the game's tick speed
+            let ticks_per_second = game
+                .game_data
+                .tick_rate
+                .unwrap_or_else(|| game_state.game_tick_speed());
 
             let mut player_inputs = game.player_inputs_pool.new();
 
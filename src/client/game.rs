@@ -54,6 +54,7 @@ use game_network::{
     game_event_generator::GameEventGenerator,
     messages::{ClientToServerMessage, ServerToClientMessage},
 };
+use ghost::recorder::GhostRecorder;
 use log::info;
 use math::math::vector::vec2;
 use network::network::{
@@ -70,7 +71,7 @@ use types::{DisconnectAutoCleanup, GameBase, GameConnect, GameMsgPipeline, GameN
 use ui_base::ui::UiCreator;
 use url::Url;
 
-use crate::localplayer::LocalPlayers;
+use crate::{chat_log::ChatLog, localplayer::LocalPlayers};
 
 use super::spatial_chat::spatial_chat::SpatialChatGameWorldTy;
 
@@ -582,6 +583,15 @@ impl Game {
                         None,
                     );
 
+                    let ghost_recorder = (server_options.ghosts && config_game.cl.ghost.record)
+                        .then(|| {
+                            GhostRecorder::new(
+                                demo_recorder_props.clone(),
+                                map.game.game_tick_speed(),
+                                config_game.cl.ghost.max_stored as usize,
+                            )
+                        });
+
                     let replay = Replay::new(
                         &demo_recorder_props.io,
                         &base.tp,
@@ -600,6 +610,17 @@ impl Game {
 
                     let events_pool = Pool::with_capacity(4);
 
+                    let chat_log = config_game
+                        .cl
+                        .chat
+                        .logs
+                        .then(|| ChatLog::new(&demo_recorder_props.io, &connect.addr))
+                        .and_then(|chat_log| {
+                            chat_log
+                                .inspect_err(|err| log::warn!("failed to open the chat log: {err}"))
+                                .ok()
+                        });
+
                     connect
                         .log
                         .log("Map fully loaded, waiting for first snapshot from server now.");
@@ -613,10 +634,13 @@ impl Game {
                         manual_demo_recorder: None,
                         race_demo_recorder: None,
 
-                        ghost_recorder: None,
+                        ghost_recorder,
                         ghost_viewer: None,
 
+                        auto_director: Default::default(),
+
                         replay,
+                        chat_log,
 
                         game_data: GameData::new(base.time.now(), prediction_timer, local),
 
@@ -6,9 +6,12 @@ use client_console::console::remote_console::RemoteConsole;
 use client_ghost::GhostViewer;
 use client_map::client_map::GameMap;
 use client_notifications::overlay::ClientNotifications;
-use client_render_game::render_game::{ObservedPlayer, RenderGameForPlayer};
+use client_render_game::{
+    auto_director::AutoDirector,
+    render_game::{ObservedPlayer, RenderChatMsg, RenderGameForPlayer},
+};
 use client_replay::replay::Replay;
-use client_types::console::ConsoleEntry;
+use client_types::{chat::mentions, console::ConsoleEntry};
 use command_parser::parser::ParserCache;
 use demo::{
     DemoEvent,
@@ -52,6 +55,7 @@ use tracing::instrument;
 use url::Url;
 
 use crate::{
+    chat_log::ChatLog,
     game::data::{ClientConnectedPlayer, SnapshotStorageItem},
     localplayer::{ClientPlayer, ClientPlayerZoomMode, ServerInputForDiff},
     spatial_chat::spatial_chat::SpatialChatGameWorldTy,
@@ -76,7 +80,11 @@ pub struct ActiveGame {
     pub ghost_recorder: Option<GhostRecorder>,
     pub ghost_viewer: Option<GhostViewer>,
 
+    pub auto_director: AutoDirector,
+
     pub replay: Replay,
+    /// The chat log of this server, if enabled.
+    pub chat_log: Option<ChatLog>,
 
     pub game_data: GameData,
 
@@ -336,6 +344,9 @@ impl ActiveGame {
                 }
                 self.replay
                     .add_snapshot(game_monotonic_tick, snapshot.as_ref().to_vec());
+                if let Some(ghost_recorder) = &mut self.ghost_recorder {
+                    ghost_recorder.on_snapshot(game_monotonic_tick, &snapshot, &mut self.map.game);
+                }
 
                 let GameMap { game, .. } = &mut self.map;
                 let ticks_per_second = game.game_tick_speed();
@@ -608,6 +619,12 @@ impl ActiveGame {
                 }
                 self.replay
                     .add_event(game_monotonic_tick, DemoEvent::Game(events.clone()));
+                if let Some(ghost_recorder) = &mut self.ghost_recorder {
+                    let character_infos = &self.game_data.cached_character_infos;
+                    ghost_recorder.on_event(&events, |id| {
+                        character_infos.get(id).map(|c| c.info.name.to_string())
+                    });
+                }
 
                 let event_id = events.event_id;
                 self.events.insert((game_monotonic_tick, false), events);
@@ -643,7 +660,58 @@ impl ActiveGame {
                     DemoEvent::Chat(Box::new(chat_msg.msg.clone())),
                 );
 
-                self.game_data.chat_msgs.push_back(chat_msg.msg);
+                let chat_config = &pipe.config_game.cl.chat;
+                let msg = chat_msg.msg;
+                let sender = &msg.sender;
+                let is_ignored = chat_config
+                    .ignored_names
+                    .iter()
+                    .any(|name| name.as_str() == sender.name.as_str())
+                    || self
+                        .game_data
+                        .cached_character_infos
+                        .get(&sender.id)
+                        .and_then(|c| c.account_name.as_ref())
+                        .is_some_and(|account_name| {
+                            chat_config
+                                .ignored_accounts
+                                .iter()
+                                .any(|name| name.as_str() == account_name.as_str())
+                        });
+                if is_ignored {
+                    return;
+                }
+
+                if let Some(chat_log) = &mut self.chat_log {
+                    chat_log.append(&sender.name, &msg.msg);
+                }
+
+                let local_players = &self.game_data.local.local_players;
+                let highlighted = !local_players.contains_key(&sender.id)
+                    && chat_config
+                        .highlight_own_name
+                        .then(|| {
+                            local_players.keys().filter_map(|id| {
+                                self.game_data
+                                    .cached_character_infos
+                                    .get(id)
+                                    .map(|c| c.info.name.as_str())
+                            })
+                        })
+                        .into_iter()
+                        .flatten()
+                        .chain(chat_config.highlight_words.iter().map(|word| word.as_str()))
+                        .any(|word| mentions(&msg.msg, word));
+                if highlighted && chat_config.highlight_notification {
+                    pipe.notifications.add_info(
+                        format!("{}: {}", sender.name, msg.msg),
+                        Duration::from_secs(5),
+                    );
+                }
+
+                self.game_data
+                    .chat_msgs
+                    .push_back(RenderChatMsg { msg, highlighted });
             }
             ServerToClientMessage::StartVoteRes(res) => {
                 if let Some(msg) = match res {
@@ -859,6 +927,10 @@ impl ActiveGame {
                     .client_info
                     .set_local_player_count(self.game_data.local.expected_local_players.len());
             }
+            ServerToClientMessage::TickRate(tick_rate) => {
+                self.game_data.tick_rate =
+                    (tick_rate != self.map.game.game_tick_speed()).then_some(tick_rate);
+            }
         }
     }
 
@@ -11,20 +11,18 @@ use binds::binds::{
     BindAction, BindActionsCharacter, BindActionsLocalPlayer, bind_to_str,
     gen_local_player_action_hash_map, gen_local_player_action_hash_map_rev, syn_to_bind,
 };
+use client_render_game::render_game::RenderChatMsg;
 use client_types::console::{ConsoleEntry, entries_to_parser};
 use command_parser::parser::{self, Command, CommandType, ParserCache, Syn};
 use game_base::{
-    network::{
-        messages::{MsgClSnapshotAck, PlayerInputChainable},
-        types::chat::NetChatMsg,
-    },
+    network::messages::{MsgClSnapshotAck, PlayerInputChainable},
     player_input::PlayerInput,
 };
 use game_config::config::ConfigGame;
 use game_interface::{
     interface::GameStateServerOptions,
     types::{
-        game::GameTickType,
+        game::{GameTickType, NonZeroGameTickType},
         id_types::{CharacterId, PlayerId},
         input::{CharacterInputInfo, CharacterInputMethodFlags, cursor::CharacterInputCursor},
         render::character::CharacterInfo,
@@ -33,7 +31,7 @@ use game_interface::{
     },
     votes::{MAX_CATEGORY_NAME_LEN, MapVote, MapVoteKey, MiscVote, MiscVoteKey, VoteState, Voted},
 };
-use input_binds::binds::{BindKey, Binds, MouseExtra};
+use input_binds::binds::{BindKey, Binds, GamepadAxis, GamepadButton, MouseExtra};
 use math::math::vector::{dvec2, luffixed};
 use native::native::{KeyCode, MouseButton, PhysicalKey};
 use pool::{
@@ -180,9 +178,12 @@ pub struct GameData {
     pub last_game_tick: Duration,
     pub last_frame_time: Duration,
     pub intra_tick_time: Duration,
+    /// The tick rate of the server, if it differs from the game's tick speed
+    /// (e.g. slow motion in practice mode).
+    pub tick_rate: Option<NonZeroGameTickType>,
 
-    pub chat_msgs_pool: Pool<VecDeque<NetChatMsg>>,
-    pub chat_msgs: PoolVecDeque<NetChatMsg>,
+    pub chat_msgs_pool: Pool<VecDeque<RenderChatMsg>>,
+    pub chat_msgs: PoolVecDeque<RenderChatMsg>,
     pub player_inp_pool: Pool<FxLinkedHashMap<PlayerId, PlayerInput>>,
     pub player_snap_pool: Pool<Vec<u8>>,
     pub player_inputs_state_pool: Pool<FxLinkedHashMap<PlayerId, CharacterInputInfo>>,
@@ -231,6 +232,7 @@ impl GameData {
 
             last_game_tick: cur_time,
             intra_tick_time: Duration::ZERO,
+            tick_rate: None,
             last_frame_time: cur_time,
 
             chat_msgs: chat_and_system_msgs_pool.new(),
@@ -441,6 +443,58 @@ impl GameData {
                 &[BindKey::Key(PhysicalKey::Code(KeyCode::NumpadMultiply))],
                 vec![BindAction::LocalPlayer(BindActionsLocalPlayer::ZoomReset)],
             ),
+            (
+                &[BindKey::GamepadAxis(GamepadAxis::LeftStickLeft)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::Character(
+                    BindActionsCharacter::MoveLeft,
+                ))],
+            ),
+            (
+                &[BindKey::GamepadAxis(GamepadAxis::LeftStickRight)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::Character(
+                    BindActionsCharacter::MoveRight,
+                ))],
+            ),
+            (
+                &[BindKey::GamepadButton(GamepadButton::South)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::Character(
+                    BindActionsCharacter::Jump,
+                ))],
+            ),
+            (
+                &[BindKey::GamepadButton(GamepadButton::RightTrigger)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::Character(
+                    BindActionsCharacter::Fire,
+                ))],
+            ),
+            (
+                &[BindKey::GamepadButton(GamepadButton::LeftTrigger)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::Character(
+                    BindActionsCharacter::Hook,
+                ))],
+            ),
+            (
+                &[BindKey::GamepadButton(GamepadButton::LeftBumper)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::Character(
+                    BindActionsCharacter::PrevWeapon,
+                ))],
+            ),
+            (
+                &[BindKey::GamepadButton(GamepadButton::RightBumper)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::Character(
+                    BindActionsCharacter::NextWeapon,
+                ))],
+            ),
+            (
+                &[BindKey::GamepadButton(GamepadButton::Start)],
+                vec![BindAction::LocalPlayer(BindActionsLocalPlayer::OpenMenu)],
+            ),
+            (
+                &[BindKey::GamepadButton(GamepadButton::Select)],
+                vec![BindAction::LocalPlayer(
+                    BindActionsLocalPlayer::ShowScoreboard,
+                )],
+            ),
             (
                 &[BindKey::Key(PhysicalKey::Code(KeyCode::PageUp))],
                 vec![BindAction::Command(Command {
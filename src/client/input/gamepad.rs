@@ -0,0 +1,179 @@
+use std::collections::{BTreeSet, HashMap};
+
+use gilrs::{Axis, Button, EventType, Gilrs};
+use input_binds::binds::{GamepadAxis, GamepadButton};
+
+#[derive(Debug, Clone, Copy)]
+pub enum GamepadEvent {
+    Button {
+        id: usize,
+        button: GamepadButton,
+        is_down: bool,
+    },
+    Axis {
+        id: usize,
+        axis: GamepadAxis,
+        is_down: bool,
+    },
+}
+
+/// The position of both sticks of a gamepad.
+/// Values are in `[-1, 1]`, y points downwards (like screen coordinates).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GamepadSticks {
+    pub left: [f32; 2],
+    pub right: [f32; 2],
+}
+
+#[derive(Debug, Default)]
+struct GamepadState {
+    sticks: GamepadSticks,
+    buttons_down: BTreeSet<GamepadButton>,
+    axes_down: BTreeSet<GamepadAxis>,
+}
+
+/// Polls connected gamepads and translates them to
+/// bind keys & stick positions.
+pub struct Gamepads {
+    gilrs: Option<Gilrs>,
+    states: HashMap<usize, GamepadState>,
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(err) => {
+                log::info!(target: "gamepad", "gamepads are not supported: {err}");
+                None
+            }
+        };
+        Self {
+            gilrs,
+            states: Default::default(),
+        }
+    }
+
+    fn button(button: Button) -> Option<GamepadButton> {
+        Some(match button {
+            Button::South => GamepadButton::South,
+            Button::East => GamepadButton::East,
+            Button::North => GamepadButton::North,
+            Button::West => GamepadButton::West,
+            Button::LeftTrigger => GamepadButton::LeftBumper,
+            Button::RightTrigger => GamepadButton::RightBumper,
+            Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+            Button::RightTrigger2 => GamepadButton::RightTrigger,
+            Button::Select => GamepadButton::Select,
+            Button::Start => GamepadButton::Start,
+            Button::Mode => GamepadButton::Mode,
+            Button::LeftThumb => GamepadButton::LeftStick,
+            Button::RightThumb => GamepadButton::RightStick,
+            Button::DPadUp => GamepadButton::DpadUp,
+            Button::DPadDown => GamepadButton::DpadDown,
+            Button::DPadLeft => GamepadButton::DpadLeft,
+            Button::DPadRight => GamepadButton::DpadRight,
+            _ => return None,
+        })
+    }
+
+    fn update_axes(
+        id: usize,
+        state: &mut GamepadState,
+        axis_threshold: f32,
+        evs: &mut Vec<GamepadEvent>,
+    ) {
+        let [lx, ly] = state.sticks.left;
+        let [rx, ry] = state.sticks.right;
+        for (axis, value) in [
+            (GamepadAxis::LeftStickLeft, -lx),
+            (GamepadAxis::LeftStickRight, lx),
+            (GamepadAxis::LeftStickUp, -ly),
+            (GamepadAxis::LeftStickDown, ly),
+            (GamepadAxis::RightStickLeft, -rx),
+            (GamepadAxis::RightStickRight, rx),
+            (GamepadAxis::RightStickUp, -ry),
+            (GamepadAxis::RightStickDown, ry),
+        ] {
+            let is_down = value >= axis_threshold;
+            let changed = if is_down {
+                state.axes_down.insert(axis)
+            } else {
+                state.axes_down.remove(&axis)
+            };
+            if changed {
+                evs.push(GamepadEvent::Axis { id, axis, is_down });
+            }
+        }
+    }
+
+    /// Collects all events since the last call.
+    /// `axis_threshold` is the stick deflection in `(0, 1]`
+    /// from which a stick direction counts as pressed.
+    pub fn poll(&mut self, axis_threshold: f32) -> Vec<GamepadEvent> {
+        let mut evs = Vec::new();
+        let Some(gilrs) = &mut self.gilrs else {
+            return evs;
+        };
+        while let Some(ev) = gilrs.next_event() {
+            let id: usize = ev.id.into();
+            match ev.event {
+                EventType::ButtonPressed(button, _) | EventType::ButtonReleased(button, _) => {
+                    let Some(button) = Self::button(button) else {
+                        continue;
+                    };
+                    let is_down = matches!(ev.event, EventType::ButtonPressed(..));
+                    let state = self.states.entry(id).or_default();
+                    let changed = if is_down {
+                        state.buttons_down.insert(button)
+                    } else {
+                        state.buttons_down.remove(&button)
+                    };
+                    if changed {
+                        evs.push(GamepadEvent::Button {
+                            id,
+                            button,
+                            is_down,
+                        });
+                    }
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    let state = self.states.entry(id).or_default();
+                    // gilrs' y axes point upwards
+                    match axis {
+                        Axis::LeftStickX => state.sticks.left[0] = value,
+                        Axis::LeftStickY => state.sticks.left[1] = -value,
+                        Axis::RightStickX => state.sticks.right[0] = value,
+                        Axis::RightStickY => state.sticks.right[1] = -value,
+                        _ => continue,
+                    }
+                    Self::update_axes(id, state, axis_threshold, &mut evs);
+                }
+                EventType::Disconnected => {
+                    // release everything that is still pressed
+                    if let Some(state) = self.states.remove(&id) {
+                        evs.extend(state.buttons_down.into_iter().map(|button| {
+                            GamepadEvent::Button {
+                                id,
+                                button,
+                                is_down: false,
+                            }
+                        }));
+                        evs.extend(state.axes_down.into_iter().map(|axis| GamepadEvent::Axis {
+                            id,
+                            axis,
+                            is_down: false,
+                        }));
+                    }
+                }
+                _ => {}
+            }
+        }
+        evs
+    }
+
+    /// The current stick positions of all gamepads that sent input.
+    pub fn sticks(&self) -> impl Iterator<Item = (usize, &GamepadSticks)> {
+        self.states.iter().map(|(id, state)| (*id, &state.sticks))
+    }
+}
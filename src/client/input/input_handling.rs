@@ -13,7 +13,7 @@ use client_ui::emote_wheel::user_data::EmoteWheelEvent;
 use command_parser::parser::CommandTypeRef;
 use config::config::ConfigEngine;
 use egui::{Context, CursorIcon};
-use game_config::config::ConfigGame;
+use game_config::config::{ConfigGame, ConfigGamepad, ConfigTouchControls};
 use game_interface::types::emoticons::EmoticonType;
 use game_interface::types::id_types::PlayerId;
 use game_interface::types::input::cursor::CharacterInputCursor;
@@ -28,43 +28,63 @@ use graphics::graphics::graphics::{Graphics, ScreenshotCb};
 use graphics_types::rendering::State;
 use math::math::{length, normalize_pre_length, vector::dvec2};
 
-use input_binds::binds::{BindKey, Binds, MouseExtra};
+use input_binds::binds::{BindKey, Binds, GamepadButton, MouseExtra};
 use native::native::NativeImpl;
 use native::native::{DeviceId, MouseButton, MouseScrollDelta, PhysicalKey, Window};
 use tracing::instrument;
 use ui_base::{types::UiState, ui::UiContainer};
 
 use crate::game::data::{GameData, LocalPlayerGameData};
+use crate::input::gamepad::{GamepadEvent, Gamepads};
+use crate::input::touch::TouchControls;
 use crate::localplayer::dummy_control::{DummyControlState, DummyHammerState};
 use crate::localplayer::{ClientPlayer, ClientPlayerZoomMode, ClientPlayerZoomState};
 
-pub type DeviceToLocalPlayerIndex = HashMap<DeviceId, usize>;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDeviceId {
+    Native(DeviceId),
+    /// A gamepad, identified by the id of the gamepad library.
+    Gamepad(usize),
+}
+
+pub type DeviceToLocalPlayerIndex = HashMap<InputDeviceId, usize>;
 
 #[derive(Debug, Clone)]
 pub struct InputKeyEv {
     pub key: BindKey,
     pub is_down: bool,
-    pub device: DeviceId,
+    pub device: InputDeviceId,
 }
 
 #[derive(Debug, Clone)]
 pub struct InputAxisMoveEv {
-    pub device: DeviceId,
+    pub device: InputDeviceId,
     pub xrel: f64,
     pub yrel: f64,
 }
 
+/// The position of an aim stick, outside of its deadzone.
+#[derive(Debug, Clone)]
+pub struct InputAxisAimEv {
+    pub device: InputDeviceId,
+    /// In `[-1, 1]`, y points downwards.
+    pub x: f64,
+    pub y: f64,
+}
+
 #[derive(Debug, Clone)]
 pub enum InputEv {
     Key(InputKeyEv),
     Move(InputAxisMoveEv),
+    Aim(InputAxisAimEv),
 }
 
 impl InputEv {
-    pub fn device(&self) -> &DeviceId {
+    pub fn device(&self) -> &InputDeviceId {
         match self {
             InputEv::Key(ev) => &ev.device,
             InputEv::Move(ev) => &ev.device,
+            InputEv::Aim(ev) => &ev.device,
         }
     }
 }
@@ -128,6 +148,9 @@ pub struct InputHandling {
     last_known_cursor: Option<CursorIcon>,
 
     inp: Input,
+    gamepads: Gamepads,
+    touch: TouchControls,
+    touch_controls_active: bool,
 
     bind_cmds: HashMap<&'static str, BindActionsLocalPlayer>,
 }
@@ -151,6 +174,9 @@ impl InputHandling {
             ),
             last_known_cursor: None,
             inp: Input::new(),
+            gamepads: Gamepads::new(),
+            touch: Default::default(),
+            touch_controls_active: false,
             bind_cmds,
         }
     }
@@ -173,8 +199,99 @@ impl InputHandling {
     }
 
     #[instrument(level = "trace", skip_all)]
-    pub fn collect_events(&mut self) {
-        self.inp.egui = Some(self.state.egui_input_mut().take());
+    pub fn collect_events(&mut self, config: &ConfigGamepad) {
+        let mut egui = self.state.egui_input_mut().take();
+        self.collect_gamepad_events(config, &mut egui);
+        self.inp.egui = Some(egui);
+    }
+
+    /// Translates buttons used for menu navigation to the key events
+    /// egui uses for keyboard navigation.
+    fn gamepad_egui_key(button: GamepadButton) -> Option<(egui::Key, egui::Modifiers)> {
+        Some(match button {
+            GamepadButton::DpadUp => (egui::Key::ArrowUp, egui::Modifiers::NONE),
+            GamepadButton::DpadDown => (egui::Key::ArrowDown, egui::Modifiers::NONE),
+            GamepadButton::DpadLeft => (egui::Key::ArrowLeft, egui::Modifiers::NONE),
+            GamepadButton::DpadRight => (egui::Key::ArrowRight, egui::Modifiers::NONE),
+            GamepadButton::South => (egui::Key::Enter, egui::Modifiers::NONE),
+            GamepadButton::East => (egui::Key::Escape, egui::Modifiers::NONE),
+            GamepadButton::RightBumper => (egui::Key::Tab, egui::Modifiers::NONE),
+            GamepadButton::LeftBumper => (egui::Key::Tab, egui::Modifiers::SHIFT),
+            _ => return None,
+        })
+    }
+
+    fn collect_gamepad_events(&mut self, config: &ConfigGamepad, egui: &mut egui::RawInput) {
+        if !config.enabled {
+            // still drain the events, so old input is not applied once enabled
+            self.gamepads.poll(1.0);
+            return;
+        }
+        for ev in self
+            .gamepads
+            .poll((config.axis_threshold / 100.0).clamp(0.01, 1.0) as f32)
+        {
+            match ev {
+                GamepadEvent::Button {
+                    id,
+                    button,
+                    is_down,
+                } => {
+                    if let Some((key, modifiers)) = Self::gamepad_egui_key(button) {
+                        egui.events.push(egui::Event::Key {
+                            key,
+                            physical_key: None,
+                            pressed: is_down,
+                            repeat: false,
+                            modifiers,
+                        });
+                    }
+                    self.inp.evs.push(InputEv::Key(InputKeyEv {
+                        key: BindKey::GamepadButton(button),
+                        is_down,
+                        device: InputDeviceId::Gamepad(id),
+                    }));
+                }
+                GamepadEvent::Axis { id, axis, is_down } => {
+                    self.inp.evs.push(InputEv::Key(InputKeyEv {
+                        key: BindKey::GamepadAxis(axis),
+                        is_down,
+                        device: InputDeviceId::Gamepad(id),
+                    }));
+                }
+            }
+        }
+
+        let deadzone = config.deadzone / 100.0;
+        for (id, sticks) in self.gamepads.sticks() {
+            let [x, y] = if config.aim_with_left_stick {
+                sticks.left
+            } else {
+                sticks.right
+            };
+            let (x, y) = (x as f64, y as f64);
+            if length(&dvec2::new(x, y)) > deadzone {
+                self.inp.evs.push(InputEv::Aim(InputAxisAimEv {
+                    device: InputDeviceId::Gamepad(id),
+                    x,
+                    y,
+                }));
+            }
+        }
+    }
+
+    /// The touch controls only handle touches while they are active,
+    /// e.g. not while a menu is open.
+    pub fn set_touch_controls(&mut self, config: &ConfigTouchControls, active: bool) {
+        self.touch.set_config(config);
+        if !active {
+            self.touch.reset();
+        }
+        self.touch_controls_active = active;
+    }
+
+    pub fn touch_controls(&self) -> Option<&TouchControls> {
+        self.touch_controls_active.then_some(&self.touch)
     }
 
     #[instrument(level = "trace", skip_all)]
@@ -211,6 +328,7 @@ impl InputHandling {
         config_game: &mut ConfigGame,
         bind_cmds: &HashMap<&'static str, BindActionsLocalPlayer>,
         entries: &[ConsoleEntry],
+        input_method: CharacterInputMethodFlags,
     ) {
         let Some((local_player_id, local_player)) = local.active_local_player_mut() else {
             return;
@@ -310,6 +428,12 @@ impl InputHandling {
                     BindActionsLocalPlayer::ActivateWhisperChatInput => {
                         // only listen for click
                     }
+                    BindActionsLocalPlayer::ActivateChatSearchInput => {
+                        // only listen for click
+                    }
+                    BindActionsLocalPlayer::ToggleHudEditor => {
+                        // only listen for click
+                    }
                     BindActionsLocalPlayer::Kill => {
                         // only listen for click
                     }
@@ -487,6 +611,8 @@ impl InputHandling {
                     BindActionsLocalPlayer::OpenMenu => {
                         if local_player.chat_input_active.is_some() {
                             local_player.chat_input_active = None;
+                        } else if local_player.hud_editor_active {
+                            local_player.hud_editor_active = false;
                         } else {
                             ui.ui_state.is_ui_open = true;
                         }
@@ -502,6 +628,12 @@ impl InputHandling {
                             local_player.chat_input_active = Some(ChatMode::Whisper(None));
                         }
                     }
+                    BindActionsLocalPlayer::ActivateChatSearchInput => {
+                        local_player.chat_input_active = Some(ChatMode::Search);
+                    }
+                    BindActionsLocalPlayer::ToggleHudEditor => {
+                        local_player.hud_editor_active = !local_player.hud_editor_active;
+                    }
                     BindActionsLocalPlayer::Kill => evs.push(InputHandlingEvent::Kill {
                         local_player_id: *local_player_id,
                     }),
@@ -571,7 +703,7 @@ impl InputHandling {
             character.changes_by_reset();
             local_player.binds.reset_cur_keys();
         }
-        if !next_show_spectator_selection {
+        if !next_show_spectator_selection && !local_player.hud_editor_active {
             set(input, character);
         }
 
@@ -582,10 +714,7 @@ impl InputHandling {
             local_player.zoom_state = None;
         }
 
-        input
-            .state
-            .input_method_flags
-            .set(CharacterInputMethodFlags::MOUSE_KEYBOARD);
+        input.state.input_method_flags.set(input_method);
 
         // generate emoticon/tee-eye event if needed
         if local_player.emote_wheel_active
@@ -806,7 +935,10 @@ impl InputHandling {
             match ev {
                 InputEv::Key(key_ev) => {
                     match &key_ev.key {
-                        BindKey::Key(_) | BindKey::Mouse(_) => {
+                        BindKey::Key(_)
+                        | BindKey::Mouse(_)
+                        | BindKey::GamepadButton(_)
+                        | BindKey::GamepadAxis(_) => {
                             if key_ev.is_down {
                                 global_binds.handle_key_down(&key_ev.key);
                             } else {
@@ -837,7 +969,7 @@ impl InputHandling {
                         io,
                     );
                 }
-                InputEv::Move(_) => {}
+                InputEv::Move(_) | InputEv::Aim(_) => {}
             }
         }
     }
@@ -887,7 +1019,10 @@ impl InputHandling {
                     let vp_height = br_y as f64 - tl_y as f64;
                     match ev {
                         InputEv::Key(key_ev) => match &key_ev.key {
-                            BindKey::Key(_) | BindKey::Mouse(_) => {
+                            BindKey::Key(_)
+                            | BindKey::Mouse(_)
+                            | BindKey::GamepadButton(_)
+                            | BindKey::GamepadAxis(_) => {
                                 if key_ev.is_down {
                                     local_player.binds.handle_key_down(&key_ev.key);
                                 } else {
@@ -902,6 +1037,7 @@ impl InputHandling {
                                     config_game,
                                     &self.bind_cmds,
                                     entries,
+                                    Self::input_method(key_ev),
                                 );
                             }
                             BindKey::Extra(_) => {
@@ -915,6 +1051,7 @@ impl InputHandling {
                                     config_game,
                                     &self.bind_cmds,
                                     entries,
+                                    Self::input_method(key_ev),
                                 );
                                 let Some((_, local_player)) =
                                     game_data.local.active_local_player_mut()
@@ -926,7 +1063,8 @@ impl InputHandling {
                         },
                         InputEv::Move(move_ev)
                             if !local_player.emote_wheel_active
-                                && !local_player.spectator_selection_active =>
+                                && !local_player.spectator_selection_active
+                                && !local_player.hud_editor_active =>
                         {
                             let factor = config_game.inp.sensitivity() / 100.0;
 
@@ -980,7 +1118,52 @@ impl InputHandling {
                                 local_player.cursor_last_cam_mode = Some(cam_mode.clone());
                             }
                         }
-                        InputEv::Move(_) => {
+                        InputEv::Aim(aim_ev)
+                            if !local_player.emote_wheel_active
+                                && !local_player.spectator_selection_active
+                                && !local_player.hud_editor_active =>
+                        {
+                            // only the default camera follows the aim stick
+                            if let Some(PlayerCameraMode::Default) = game_data
+                                .cached_character_infos
+                                .get(local_player_id)
+                                .and_then(|c| c.player_info.as_ref())
+                                .map(|s| &s.cam_mode)
+                            {
+                                let config = &config_game.inp.gamepad;
+                                let dir = dvec2::new(aim_ev.x, aim_ev.y);
+                                let len = length(&dir);
+                                let deadzone = config.deadzone / 100.0;
+                                let strength = (((len - deadzone) / (1.0 - deadzone))
+                                    * config.sensitivity
+                                    / 100.0)
+                                    .clamp(0.0, 1.0);
+
+                                let min_distance = config_game.inp.min_distance();
+                                let max_distance =
+                                    Self::get_max_mouse_distance(config_game).max(min_distance);
+                                let distance =
+                                    min_distance + (max_distance - min_distance) * strength;
+                                local_player
+                                    .input
+                                    .inp
+                                    .cursor
+                                    .set(CharacterInputCursor::from_vec2(
+                                        &(normalize_pre_length(&dir, len) * distance / 32.0),
+                                    ));
+                                Self::clamp_cursor(config_game, local_player);
+                                local_player.cursor_pos = local_player.input.inp.cursor.to_vec2();
+                                local_player.player_cursor_pos = local_player.cursor_pos;
+                                local_player
+                                    .input
+                                    .inp
+                                    .state
+                                    .input_method_flags
+                                    .set(CharacterInputMethodFlags::CONTROLLER);
+                                local_player.cursor_last_cam_mode = Some(PlayerCameraMode::Default);
+                            }
+                        }
+                        InputEv::Move(_) | InputEv::Aim(_) => {
                             // else ignore mouse movement
                         }
                     }
@@ -1003,7 +1186,9 @@ impl InputHandling {
                         )),
                     );
 
-                    local_player.emote_wheel_active || local_player.spectator_selection_active
+                    local_player.emote_wheel_active
+                        || local_player.spectator_selection_active
+                        || local_player.hud_editor_active
                 } else {
                     true
                 }
@@ -1012,9 +1197,33 @@ impl InputHandling {
             }
         });
 
+        // touch controls
+        if let Some((_, local_player)) = game_data.local.active_local_player_mut()
+            && local_player.chat_input_active.is_none()
+            && self.touch.apply(
+                &mut local_player.input.inp,
+                Self::get_max_mouse_distance(config_game),
+            )
+        {
+            Self::clamp_cursor(config_game, local_player);
+            local_player.cursor_pos = local_player.input.inp.cursor.to_vec2();
+            local_player.player_cursor_pos = local_player.cursor_pos;
+        }
+
         res
     }
 
+    fn input_method(key_ev: &InputKeyEv) -> CharacterInputMethodFlags {
+        match key_ev.key {
+            BindKey::Key(_) | BindKey::Mouse(_) | BindKey::Extra(_) => {
+                CharacterInputMethodFlags::MOUSE_KEYBOARD
+            }
+            BindKey::GamepadButton(_) | BindKey::GamepadAxis(_) => {
+                CharacterInputMethodFlags::CONTROLLER
+            }
+        }
+    }
+
     pub fn key_down(
         &mut self,
         _window: &native::native::Window,
@@ -1024,7 +1233,7 @@ impl InputHandling {
         self.inp.evs.push(InputEv::Key(InputKeyEv {
             key: BindKey::Key(*key),
             is_down: true,
-            device: *device,
+            device: InputDeviceId::Native(*device),
         }));
     }
 
@@ -1037,7 +1246,7 @@ impl InputHandling {
         self.inp.evs.push(InputEv::Key(InputKeyEv {
             key: BindKey::Key(*key),
             is_down: false,
-            device: *device,
+            device: InputDeviceId::Native(*device),
         }));
     }
 
@@ -1052,7 +1261,7 @@ impl InputHandling {
         self.inp.evs.push(InputEv::Key(InputKeyEv {
             key: BindKey::Mouse(*btn),
             is_down: true,
-            device: *device,
+            device: InputDeviceId::Native(*device),
         }));
     }
 
@@ -1067,7 +1276,7 @@ impl InputHandling {
         self.inp.evs.push(InputEv::Key(InputKeyEv {
             key: BindKey::Mouse(*btn),
             is_down: false,
-            device: *device,
+            device: InputDeviceId::Native(*device),
         }));
     }
 
@@ -1081,7 +1290,7 @@ impl InputHandling {
         yrel: f64,
     ) {
         self.inp.evs.push(InputEv::Move(InputAxisMoveEv {
-            device: *device,
+            device: InputDeviceId::Native(*device),
             xrel,
             yrel,
         }))
@@ -1116,7 +1325,7 @@ impl InputHandling {
         self.inp.evs.push(InputEv::Key(InputKeyEv {
             key: BindKey::Extra(wheel_dir),
             is_down: false,
-            device: *device,
+            device: InputDeviceId::Native(*device),
         }));
     }
 
@@ -1154,11 +1363,28 @@ impl InputHandling {
         }
     }
 
-    pub fn raw_event(&mut self, window: &Window, event: &native::native::WindowEvent) {
+    /// Returns `true` if the event was consumed by the touch controls.
+    pub fn raw_event(&mut self, window: &Window, event: &native::native::WindowEvent) -> bool {
         if !Self::consumable_event(event) {
-            return;
+            return false;
+        }
+
+        if self.touch_controls_active
+            && let native::native::WindowEvent::Touch(touch) = event
+        {
+            let size = window.inner_size();
+            let height = size.height.max(1) as f64;
+            if self.touch.handle_touch(
+                touch.id,
+                touch.phase,
+                dvec2::new(touch.location.x / height, touch.location.y / height),
+                size.width as f64 / height,
+            ) {
+                return true;
+            }
         }
 
         let _ = self.state.on_window_event(window, event);
+        false
     }
 }
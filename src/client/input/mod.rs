@@ -1 +1,3 @@
+pub mod gamepad;
 pub mod input_handling;
+pub mod touch;
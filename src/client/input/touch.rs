@@ -0,0 +1,400 @@
+use std::collections::HashMap;
+
+use game_config::config::{ConfigTouchControls, ConfigTouchMoveLayout};
+use game_interface::types::input::{
+    CharacterInput, CharacterInputMethodFlags, cursor::CharacterInputCursor,
+};
+use math::math::{length, normalize_pre_length, vector::dvec2};
+use native::native::TouchPhase;
+
+/// Relative joystick deflection from which the character moves.
+const MOVE_DEADZONE: f64 = 0.3;
+/// Relative aim stick deflection from which the character aims & fires.
+const AIM_DEADZONE: f64 = 0.2;
+/// Touches slightly outside of a control still hit it.
+const HIT_SLACK: f64 = 1.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchControl {
+    MoveJoystick,
+    MoveLeft,
+    MoveRight,
+    AimStick,
+    Jump,
+    Hook,
+    PrevWeapon,
+    NextWeapon,
+}
+
+impl TouchControl {
+    pub fn is_stick(&self) -> bool {
+        matches!(self, Self::MoveJoystick | Self::AimStick)
+    }
+}
+
+/// A round on-screen control.
+/// Positions & sizes are in units of the screen height.
+#[derive(Debug, Clone, Copy)]
+pub struct TouchControlShape {
+    pub control: TouchControl,
+    pub center: dvec2,
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveTouch {
+    pub control: TouchControl,
+    pub center: dvec2,
+    pub radius: f64,
+    pub pos: dvec2,
+}
+
+impl ActiveTouch {
+    /// The deflection of a stick, the length is at most 1.
+    pub fn deflection(&self) -> dvec2 {
+        let rel = (self.pos - self.center) / self.radius;
+        let len = length(&rel);
+        if len > 1.0 {
+            normalize_pre_length(&rel, len)
+        } else {
+            rel
+        }
+    }
+}
+
+/// The input the touch controls currently represent.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TouchControlsState {
+    pub dir: i32,
+    pub jump: bool,
+    pub hook: bool,
+    pub fire: bool,
+    /// Aim direction, the length is the deflection of the aim stick.
+    pub aim: Option<dvec2>,
+}
+
+/// Translates touches on the on-screen controls to character input.
+#[derive(Debug, Default)]
+pub struct TouchControls {
+    config: ConfigTouchControls,
+    touches: HashMap<u64, ActiveTouch>,
+    weapon_diff: i64,
+    changed: bool,
+}
+
+impl TouchControls {
+    pub fn set_config(&mut self, config: &ConfigTouchControls) {
+        self.config = config.clone();
+    }
+
+    /// `aspect` is the screen width divided by the screen height.
+    pub fn layout(&self, aspect: f64) -> Vec<TouchControlShape> {
+        let size = self.config.size / 100.0;
+        let margin = 0.05;
+        let stick_radius = 0.15 * size;
+        let button_radius = 0.08 * size;
+
+        let stick_y = 1.0 - margin - stick_radius;
+        let move_x = margin + stick_radius;
+        let aim_x = aspect - margin - stick_radius;
+
+        let mut shapes = Vec::new();
+        let mut add = |control: TouchControl, x: f64, y: f64, radius: f64| {
+            // mirror the whole layout if the sides are swapped
+            let x = if self.config.swap_sides {
+                aspect - x
+            } else {
+                x
+            };
+            shapes.push(TouchControlShape {
+                control,
+                center: dvec2::new(x, y),
+                radius,
+            });
+        };
+
+        match self.config.move_layout {
+            ConfigTouchMoveLayout::Joystick => {
+                add(TouchControl::MoveJoystick, move_x, stick_y, stick_radius);
+            }
+            ConfigTouchMoveLayout::Buttons => {
+                let y = 1.0 - margin - button_radius;
+                add(
+                    TouchControl::MoveLeft,
+                    margin + button_radius,
+                    y,
+                    button_radius,
+                );
+                add(
+                    TouchControl::MoveRight,
+                    margin + button_radius * 3.5,
+                    y,
+                    button_radius,
+                );
+            }
+        }
+        let weapon_y = stick_y - stick_radius - button_radius * 2.5;
+        add(
+            TouchControl::PrevWeapon,
+            margin + button_radius,
+            weapon_y,
+            button_radius,
+        );
+        add(
+            TouchControl::NextWeapon,
+            margin + button_radius * 3.5,
+            weapon_y,
+            button_radius,
+        );
+
+        add(TouchControl::AimStick, aim_x, stick_y, stick_radius);
+        add(
+            TouchControl::Jump,
+            aim_x - stick_radius - button_radius * 1.5,
+            1.0 - margin - button_radius,
+            button_radius,
+        );
+        add(
+            TouchControl::Hook,
+            aim_x,
+            stick_y - stick_radius - button_radius * 1.5,
+            button_radius,
+        );
+        shapes
+    }
+
+    /// Handles a touch, `pos` is in units of the screen height.
+    ///
+    /// Returns `true` if the touch belongs to the touch controls.
+    pub fn handle_touch(&mut self, id: u64, phase: TouchPhase, pos: dvec2, aspect: f64) -> bool {
+        match phase {
+            TouchPhase::Started => {
+                let hit = self
+                    .layout(aspect)
+                    .into_iter()
+                    .filter(|shape| {
+                        // a control can only be used by one finger at a time
+                        !self.touches.values().any(|t| t.control == shape.control)
+                    })
+                    .map(|shape| (length(&(pos - shape.center)) / shape.radius, shape))
+                    .filter(|(dist, _)| *dist <= HIT_SLACK)
+                    .min_by(|(d1, _), (d2, _)| d1.total_cmp(d2));
+                let Some((_, shape)) = hit else {
+                    return false;
+                };
+                match shape.control {
+                    TouchControl::PrevWeapon => self.weapon_diff -= 1,
+                    TouchControl::NextWeapon => self.weapon_diff += 1,
+                    _ => {}
+                }
+                self.touches.insert(
+                    id,
+                    ActiveTouch {
+                        control: shape.control,
+                        center: shape.center,
+                        radius: shape.radius,
+                        pos,
+                    },
+                );
+                self.changed = true;
+                true
+            }
+            TouchPhase::Moved => {
+                let Some(touch) = self.touches.get_mut(&id) else {
+                    return false;
+                };
+                touch.pos = pos;
+                self.changed |= touch.control.is_stick();
+                true
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                if self.touches.remove(&id).is_some() {
+                    self.changed = true;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Releases all touches, e.g. if the controls are hidden.
+    pub fn reset(&mut self) {
+        if !self.touches.is_empty() {
+            self.touches.clear();
+            self.changed = true;
+        }
+    }
+
+    pub fn active_touches(&self) -> impl Iterator<Item = &ActiveTouch> {
+        self.touches.values()
+    }
+
+    pub fn state(&self) -> TouchControlsState {
+        let mut state = TouchControlsState::default();
+        for touch in self.touches.values() {
+            match touch.control {
+                TouchControl::MoveJoystick => {
+                    let x = touch.deflection().x;
+                    if x < -MOVE_DEADZONE {
+                        state.dir = -1;
+                    } else if x > MOVE_DEADZONE {
+                        state.dir = 1;
+                    }
+                }
+                TouchControl::MoveLeft => state.dir = -1,
+                TouchControl::MoveRight => state.dir = 1,
+                TouchControl::AimStick => {
+                    let aim = touch.deflection();
+                    if length(&aim) > AIM_DEADZONE {
+                        state.aim = Some(aim);
+                        state.fire = true;
+                    }
+                }
+                TouchControl::Jump => state.jump = true,
+                TouchControl::Hook => state.hook = true,
+                TouchControl::PrevWeapon | TouchControl::NextWeapon => {}
+            }
+        }
+        state
+    }
+
+    /// Applies the touch controls to the character input,
+    /// if anything changed since the last call.
+    ///
+    /// `max_distance` is the maximal cursor distance for a fully pushed aim stick.
+    ///
+    /// Returns `true` if the input was changed.
+    pub fn apply(&mut self, input: &mut CharacterInput, max_distance: f64) -> bool {
+        if !std::mem::take(&mut self.changed) {
+            return false;
+        }
+        let state = self.state();
+
+        if let Some(aim) = state.aim {
+            input.cursor.set(CharacterInputCursor::from_vec2(
+                &(aim * max_distance / 32.0),
+            ));
+        }
+        if self.weapon_diff != 0 {
+            input
+                .consumable
+                .weapon_diff
+                .add(std::mem::take(&mut self.weapon_diff));
+        }
+        if !*input.state.jump && state.jump {
+            input.consumable.jump.add(1);
+        }
+        if !*input.state.fire && state.fire {
+            input.consumable.fire.add(1, *input.cursor);
+        }
+        if !*input.state.hook && state.hook {
+            input.consumable.hook.add(1, *input.cursor);
+        }
+        input.state.jump.set(state.jump);
+        input.state.fire.set(state.fire);
+        input.state.hook.set(state.hook);
+        input.state.dir.set(state.dir);
+        input
+            .state
+            .input_method_flags
+            .set(CharacterInputMethodFlags::TOUCHSCREEN);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use game_config::config::ConfigTouchControls;
+    use game_interface::types::input::CharacterInput;
+    use math::math::vector::dvec2;
+    use native::native::TouchPhase;
+
+    use super::{TouchControl, TouchControls};
+
+    const ASPECT: f64 = 16.0 / 9.0;
+
+    fn center_of(controls: &TouchControls, control: TouchControl) -> (dvec2, f64) {
+        let shape = controls
+            .layout(ASPECT)
+            .into_iter()
+            .find(|shape| shape.control == control)
+            .unwrap();
+        (shape.center, shape.radius)
+    }
+
+    #[test]
+    fn touches_to_character_input() {
+        let mut controls = TouchControls::default();
+        controls.set_config(&ConfigTouchControls::default());
+        let mut input = CharacterInput::default();
+
+        // touches outside of the controls are ignored
+        assert!(!controls.handle_touch(0, TouchPhase::Started, dvec2::new(0.9, 0.1), ASPECT));
+        assert!(!controls.apply(&mut input, 400.0));
+
+        // push the joystick to the right
+        let (center, radius) = center_of(&controls, TouchControl::MoveJoystick);
+        assert!(controls.handle_touch(1, TouchPhase::Started, center, ASPECT));
+        assert!(controls.apply(&mut input, 400.0));
+        assert_eq!(*input.state.dir, 0);
+        controls.handle_touch(
+            1,
+            TouchPhase::Moved,
+            center + dvec2::new(radius * 0.8, 0.0),
+            ASPECT,
+        );
+        controls.apply(&mut input, 400.0);
+        assert_eq!(*input.state.dir, 1);
+
+        // jump while moving
+        let prev = input;
+        let (center, _) = center_of(&controls, TouchControl::Jump);
+        controls.handle_touch(2, TouchPhase::Started, center, ASPECT);
+        controls.apply(&mut input, 400.0);
+        assert!(*input.state.jump);
+        assert!(input.consumable.diff(&prev.consumable).jump.is_some());
+        controls.handle_touch(2, TouchPhase::Ended, center, ASPECT);
+        controls.apply(&mut input, 400.0);
+        assert!(!*input.state.jump);
+        assert_eq!(*input.state.dir, 1);
+
+        // aim up & fire
+        let prev = input;
+        let (center, radius) = center_of(&controls, TouchControl::AimStick);
+        controls.handle_touch(3, TouchPhase::Started, center, ASPECT);
+        controls.handle_touch(
+            3,
+            TouchPhase::Moved,
+            center - dvec2::new(0.0, radius * 2.0),
+            ASPECT,
+        );
+        controls.apply(&mut input, 400.0);
+        assert!(*input.state.fire);
+        let cursor = input.cursor.to_vec2();
+        assert!(cursor.x.abs() < 0.001 && (cursor.y + 400.0 / 32.0).abs() < 0.001);
+        let diff = input.consumable.diff(&prev.consumable);
+        assert!(diff.fire.is_some());
+
+        // switch weapon
+        let prev = input;
+        let (center, _) = center_of(&controls, TouchControl::NextWeapon);
+        controls.handle_touch(4, TouchPhase::Started, center, ASPECT);
+        controls.handle_touch(4, TouchPhase::Ended, center, ASPECT);
+        controls.apply(&mut input, 400.0);
+        assert_eq!(
+            input
+                .consumable
+                .diff(&prev.consumable)
+                .weapon_diff
+                .map(|diff| diff.get()),
+            Some(1)
+        );
+
+        // releasing everything stops all actions
+        controls.reset();
+        controls.apply(&mut input, 400.0);
+        assert_eq!(*input.state.dir, 0);
+        assert!(!*input.state.fire);
+    }
+}
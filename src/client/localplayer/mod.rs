@@ -55,6 +55,8 @@ pub struct ClientPlayer {
     pub last_emote_wheel_selection: Option<EmoteWheelEvent>,
 
     pub spectator_selection_active: bool,
+    /// The HUD editor is shown, which captures the mouse.
+    pub hud_editor_active: bool,
 
     /// For updating the player info on the server.
     pub player_info_version: u64,
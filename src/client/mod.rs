@@ -1,9 +1,11 @@
+mod chat_log;
 pub mod client;
 pub mod game;
 mod game_events;
 mod input;
 pub mod localplayer;
 mod overlays;
+mod server_pinger;
 pub mod spatial_chat;
 pub mod ui;
 
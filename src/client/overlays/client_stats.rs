@@ -26,6 +26,7 @@ use ui_base::{
 use math::math::{blend, vector::luffixed};
 
 use base::steady_clock::SteadyClock;
+use client_ui::hud::layout::{FPS_SIZE, HudElementLayout, HudLayout, NET_GRAPH_SIZE};
 
 use crate::game::data::NetworkByteStats;
 
@@ -51,158 +52,191 @@ impl DebugHudData {
         }
     }
 
+    /// Renders the network & graphics statistics, if `layout` is `None`
+    /// as strip on the right side of the screen.
     pub fn render_stats(
         &mut self,
         ui: &mut egui::Ui,
         pipe: &mut UiRenderPipe<Option<DebugHudRenderPipe<'_>>>,
+        layout: Option<&HudElementLayout>,
     ) {
-        StripBuilder::new(ui)
-            .size(egui_extras::Size::remainder())
-            .size(egui_extras::Size::exact(100.0))
-            .horizontal(|mut strip| {
-                strip.cell(|_| {});
-                strip.cell(|ui| {
-                    ui.style_mut().wrap_mode = None;
-                    ui.painter()
-                        .rect_filled(ui.available_rect_before_wrap(), 5.0, Color32::BLACK);
-
-                    if let Some(dbg) = pipe.user_data {
-                        let timing = dbg.prediction_timer.snapshot();
-                        ui.label("Network");
-                        ui.label("Ping (ms):");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!(
-                                "{:.2}",
-                                dbg.prediction_timer.ping_average().as_micros() as f64 / 1000.0
-                            ),
-                        );
-                        ui.label("Min-Ping (ms):");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!(
-                                "{:.2}",
-                                dbg.prediction_timer.ping_min().as_micros() as f64 / 1000.0
-                            ),
-                        );
-                        ui.label("Max-Ping (ms):");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!(
-                                "{:.2}",
-                                dbg.prediction_timer.ping_max().as_micros() as f64 / 1000.0
-                            ),
-                        );
-                        ui.label("Ping jitter (ms):");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!(
-                                "{:.2}",
-                                dbg.prediction_timer.jitter_range.as_micros() as f64 / 1000.0
-                            ),
-                        );
-                        ui.label("Max frame time (ms):");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!(
-                                "{:.2}",
-                                dbg.prediction_timer.max_frametime().as_micros() as f64 / 1000.0
-                            ),
-                        );
-                        ui.label("Prediction adjustment smooth (ms):");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!("{:.4}", timing.smooth_adjustment_time * 1000.0),
-                        );
-                        ui.label("Ingame time (ms):");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!("{:.2}", dbg.ingame_timer.as_micros() as f64 / 1000.0),
-                        );
-                        ui.label("Packet loss (sending) %:");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!("{:.2}", dbg.prediction_timer.packet_loss() * 100.0),
+        let screen = ui.ctx().screen_rect();
+        match layout {
+            Some(layout) => {
+                let res = egui::Area::new(egui::Id::new("client_stats_net_graph"))
+                    .pivot(layout.anchor.align2())
+                    .fixed_pos(layout.anchor_pos(screen))
+                    .show(ui.ctx(), |ui| {
+                        ui.set_width(NET_GRAPH_SIZE.x);
+                        ui.set_max_width(NET_GRAPH_SIZE.x);
+                        ui.set_min_height(NET_GRAPH_SIZE.y);
+                        let bg = ui.painter().add(egui::Shape::Noop);
+                        self.render_stats_content(ui, pipe);
+                        ui.painter().set(
+                            bg,
+                            egui::Shape::rect_filled(ui.min_rect(), 5.0, Color32::BLACK),
                         );
+                    });
+                ui.ctx()
+                    .set_transform_layer(res.response.layer_id, layout.transform(screen));
+            }
+            None => {
+                StripBuilder::new(ui)
+                    .size(egui_extras::Size::remainder())
+                    .size(egui_extras::Size::exact(NET_GRAPH_SIZE.x))
+                    .horizontal(|mut strip| {
+                        strip.cell(|_| {});
+                        strip.cell(|ui| {
+                            ui.painter().rect_filled(
+                                ui.available_rect_before_wrap(),
+                                5.0,
+                                Color32::BLACK,
+                            );
+                            self.render_stats_content(ui, pipe);
+                        })
+                    });
+            }
+        }
+    }
 
-                        ui.label("Sent Kibit/s:");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!(
-                                "{:.2}",
-                                (dbg.byte_stats.bytes_per_sec_sent * luffixed::from_num(8))
-                                    / luffixed::from_num(1024)
-                            ),
-                        );
-                        ui.label("Recv Kibit/s:");
-                        ui.colored_label(
-                            Color32::from_rgb(255, 0, 255),
-                            format!(
-                                "{:.2}",
-                                (dbg.byte_stats.bytes_per_sec_recv * luffixed::from_num(8))
-                                    / luffixed::from_num(1024)
-                            ),
-                        );
-                    }
-
-                    ui.label("Graphics");
-                    ui.label("Texture usage MiB:");
-                    ui.colored_label(
-                        Color32::from_rgb(255, 0, 255),
-                        format!(
-                            "{:.6}",
-                            (self
-                                .texture_memory_usage
-                                .load(std::sync::atomic::Ordering::Relaxed)
-                                as f64
-                                / 1024.0
-                                / 1024.0)
-                        ),
-                    );
+    fn render_stats_content(
+        &mut self,
+        ui: &mut egui::Ui,
+        pipe: &mut UiRenderPipe<Option<DebugHudRenderPipe<'_>>>,
+    ) {
+        ui.style_mut().wrap_mode = None;
+
+        if let Some(dbg) = pipe.user_data {
+            let timing = dbg.prediction_timer.snapshot();
+            ui.label("Network");
+            ui.label("Ping (ms):");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!(
+                    "{:.2}",
+                    dbg.prediction_timer.ping_average().as_micros() as f64 / 1000.0
+                ),
+            );
+            ui.label("Min-Ping (ms):");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!(
+                    "{:.2}",
+                    dbg.prediction_timer.ping_min().as_micros() as f64 / 1000.0
+                ),
+            );
+            ui.label("Max-Ping (ms):");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!(
+                    "{:.2}",
+                    dbg.prediction_timer.ping_max().as_micros() as f64 / 1000.0
+                ),
+            );
+            ui.label("Ping jitter (ms):");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!(
+                    "{:.2}",
+                    dbg.prediction_timer.jitter_range.as_micros() as f64 / 1000.0
+                ),
+            );
+            ui.label("Max frame time (ms):");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!(
+                    "{:.2}",
+                    dbg.prediction_timer.max_frametime().as_micros() as f64 / 1000.0
+                ),
+            );
+            ui.label("Prediction adjustment smooth (ms):");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!("{:.4}", timing.smooth_adjustment_time * 1000.0),
+            );
+            ui.label("Ingame time (ms):");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!("{:.2}", dbg.ingame_timer.as_micros() as f64 / 1000.0),
+            );
+            ui.label("Packet loss (sending) %:");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!("{:.2}", dbg.prediction_timer.packet_loss() * 100.0),
+            );
+
+            ui.label("Sent Kibit/s:");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!(
+                    "{:.2}",
+                    (dbg.byte_stats.bytes_per_sec_sent * luffixed::from_num(8))
+                        / luffixed::from_num(1024)
+                ),
+            );
+            ui.label("Recv Kibit/s:");
+            ui.colored_label(
+                Color32::from_rgb(255, 0, 255),
+                format!(
+                    "{:.2}",
+                    (dbg.byte_stats.bytes_per_sec_recv * luffixed::from_num(8))
+                        / luffixed::from_num(1024)
+                ),
+            );
+        }
 
-                    ui.label("Buffer usage MiB:");
-                    ui.colored_label(
-                        Color32::from_rgb(255, 0, 255),
-                        format!(
-                            "{:.6}",
-                            (self
-                                .buffer_memory_usage
-                                .load(std::sync::atomic::Ordering::Relaxed)
-                                as f64
-                                / 1024.0
-                                / 1024.0)
-                        ),
-                    );
+        ui.label("Graphics");
+        ui.label("Texture usage MiB:");
+        ui.colored_label(
+            Color32::from_rgb(255, 0, 255),
+            format!(
+                "{:.6}",
+                (self
+                    .texture_memory_usage
+                    .load(std::sync::atomic::Ordering::Relaxed) as f64
+                    / 1024.0
+                    / 1024.0)
+            ),
+        );
 
-                    ui.label("Stream usage MiB:");
-                    ui.colored_label(
-                        Color32::from_rgb(255, 0, 255),
-                        format!(
-                            "{:.6}",
-                            (self
-                                .stream_memory_usage
-                                .load(std::sync::atomic::Ordering::Relaxed)
-                                as f64
-                                / 1024.0
-                                / 1024.0)
-                        ),
-                    );
+        ui.label("Buffer usage MiB:");
+        ui.colored_label(
+            Color32::from_rgb(255, 0, 255),
+            format!(
+                "{:.6}",
+                (self
+                    .buffer_memory_usage
+                    .load(std::sync::atomic::Ordering::Relaxed) as f64
+                    / 1024.0
+                    / 1024.0)
+            ),
+        );
 
-                    ui.label("Staging usage MiB:");
-                    ui.colored_label(
-                        Color32::from_rgb(255, 0, 255),
-                        format!(
-                            "{:.6}",
-                            (self
-                                .staging_memory_usage
-                                .load(std::sync::atomic::Ordering::Relaxed)
-                                as f64
-                                / 1024.0
-                                / 1024.0)
-                        ),
-                    );
-                })
-            });
+        ui.label("Stream usage MiB:");
+        ui.colored_label(
+            Color32::from_rgb(255, 0, 255),
+            format!(
+                "{:.6}",
+                (self
+                    .stream_memory_usage
+                    .load(std::sync::atomic::Ordering::Relaxed) as f64
+                    / 1024.0
+                    / 1024.0)
+            ),
+        );
+
+        ui.label("Staging usage MiB:");
+        ui.colored_label(
+            Color32::from_rgb(255, 0, 255),
+            format!(
+                "{:.6}",
+                (self
+                    .staging_memory_usage
+                    .load(std::sync::atomic::Ordering::Relaxed) as f64
+                    / 1024.0
+                    / 1024.0)
+            ),
+        );
     }
 }
 
@@ -225,7 +259,14 @@ impl ClientStatsData {
         }
     }
 
-    pub fn render_fps(&mut self, ui: &mut egui::Ui, pipe: &mut UiRenderPipe<()>, bottom: bool) {
+    /// If `layout` is `None`, the FPS are rendered in the right corner.
+    pub fn render_fps(
+        &mut self,
+        ui: &mut egui::Ui,
+        pipe: &mut UiRenderPipe<()>,
+        bottom: bool,
+        layout: Option<&HudElementLayout>,
+    ) {
         let cur_time: Duration = pipe.cur_time;
         let time_diff = cur_time - self.last_frame_time;
         self.last_frame_time = cur_time;
@@ -238,20 +279,32 @@ impl ClientStatsData {
             FixedI64::from_num(0.1),
         );
 
-        let (pos, anchor) = if bottom {
+        let (pos, anchor, scale) = if let Some(layout) = layout {
+            let anchor = layout.anchor.align2();
+            (
+                anchor.pos_in_rect(&layout.rect(ui.ctx().screen_rect(), FPS_SIZE)),
+                anchor,
+                layout.scale,
+            )
+        } else if bottom {
             (
                 ui.ctx().screen_rect().right_bottom(),
                 egui::Align2::RIGHT_BOTTOM,
+                1.0,
             )
         } else {
-            (ui.ctx().screen_rect().right_top(), egui::Align2::RIGHT_TOP)
+            (
+                ui.ctx().screen_rect().right_top(),
+                egui::Align2::RIGHT_TOP,
+                1.0,
+            )
         };
 
         ui.painter().text(
             pos,
             anchor,
             format!("FPS: {}", self.fps.floor()),
-            FontId::proportional(12.0),
+            FontId::proportional(12.0 * scale),
             Color32::from_rgb(255, 0, 255),
         );
     }
@@ -263,6 +316,8 @@ pub struct ClientStatsRenderPipe<'a> {
     pub force_bottom: bool,
     pub show_fps: bool,
     pub mic_active: bool,
+    /// The HUD layout of the main player, if a game is active.
+    pub hud_layout: Option<HudLayout>,
 }
 
 /// This component collects various client statistics and displays them optionally
@@ -357,18 +412,28 @@ impl ClientStats {
             pixels_per_point,
             |ui, inner_pipe, _| {
                 let game_active = pipe.debug_hud.is_some();
-                if dbg_hud_open {
+                // while the menu is open, the stats stay out of the way
+                let hud_layout = pipe
+                    .hud_layout
+                    .filter(|_| game_active && !pipe.force_bottom);
+                if dbg_hud_open && hud_layout.is_none_or(|layout| layout.net_graph.visible) {
                     self.dbg.render_stats(
                         ui,
                         &mut UiRenderPipe {
                             cur_time: inner_pipe.cur_time,
                             user_data: &mut pipe.debug_hud,
                         },
+                        hud_layout.as_ref().map(|layout| &layout.net_graph),
                     );
                 }
                 let bottom = dbg_hud_open || !game_active || pipe.force_bottom;
-                if pipe.show_fps {
-                    self.stats.render_fps(ui, inner_pipe, bottom);
+                if pipe.show_fps && hud_layout.is_none_or(|layout| layout.fps.visible) {
+                    self.stats.render_fps(
+                        ui,
+                        inner_pipe,
+                        bottom,
+                        hud_layout.as_ref().map(|layout| &layout.fps),
+                    );
                 }
                 if pipe.mic_active {
                     Self::render_mic(ui, bottom);
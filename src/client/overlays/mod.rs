@@ -1 +1,2 @@
 pub mod client_stats;
+pub mod touch_controls;
@@ -0,0 +1,139 @@
+use egui::{Color32, FontId, Stroke, pos2};
+use game_config::config::ConfigTouchControls;
+use graphics::{
+    graphics::graphics::Graphics,
+    handles::{
+        backend::backend::GraphicsBackendHandle, canvas::canvas::GraphicsCanvasHandle,
+        stream::stream::GraphicsStreamHandle, texture::texture::GraphicsTextureHandle,
+    },
+};
+use math::math::vector::dvec2;
+use tracing::instrument;
+use ui_base::{
+    types::UiRenderPipe,
+    ui::{UiContainer, UiCreator},
+    ui_render::render_ui,
+};
+
+use base::steady_clock::SteadyClock;
+
+use crate::input::touch::{TouchControl, TouchControls};
+
+/// Renders the on-screen touch controls.
+pub struct TouchControlsOverlay {
+    ui: UiContainer,
+
+    time: SteadyClock,
+
+    backend_handle: GraphicsBackendHandle,
+    canvas_handle: GraphicsCanvasHandle,
+    stream_handle: GraphicsStreamHandle,
+    texture_handle: GraphicsTextureHandle,
+}
+
+impl TouchControlsOverlay {
+    pub fn new(graphics: &Graphics, time: &SteadyClock, creator: &UiCreator) -> Self {
+        let mut ui = UiContainer::new(creator);
+        ui.set_main_panel_color(&Color32::TRANSPARENT);
+        ui.ui_state.is_ui_open = false;
+        Self {
+            ui,
+            time: time.clone(),
+            backend_handle: graphics.backend_handle.clone(),
+            canvas_handle: graphics.canvas_handle.clone(),
+            stream_handle: graphics.stream_handle.clone(),
+            texture_handle: graphics.texture_handle.clone(),
+        }
+    }
+
+    fn label(control: TouchControl) -> &'static str {
+        match control {
+            TouchControl::MoveJoystick => "",
+            TouchControl::MoveLeft => "\u{f060}",
+            TouchControl::MoveRight => "\u{f061}",
+            TouchControl::AimStick => "\u{f05b}",
+            TouchControl::Jump => "\u{f062}",
+            TouchControl::Hook => "\u{f0c1}",
+            TouchControl::PrevWeapon => "\u{f053}",
+            TouchControl::NextWeapon => "\u{f054}",
+        }
+    }
+
+    fn render_controls(ui: &mut egui::Ui, controls: &TouchControls, config: &ConfigTouchControls) {
+        let rect = ui.ctx().screen_rect();
+        let scale = rect.height();
+        let aspect = (rect.width() / rect.height().max(1.0)) as f64;
+        let to_screen = |pos: dvec2| pos2(pos.x as f32 * scale, pos.y as f32 * scale);
+
+        let alpha = (config.opacity / 100.0 * 255.0).clamp(0.0, 255.0) as u8;
+        let color = Color32::from_white_alpha(alpha);
+        let pressed_color = Color32::from_white_alpha(alpha.saturating_add(alpha / 2));
+
+        for shape in controls.layout(aspect) {
+            let touch = controls
+                .active_touches()
+                .find(|touch| touch.control == shape.control);
+            let center = to_screen(shape.center);
+            let radius = shape.radius as f32 * scale;
+            if shape.control.is_stick() {
+                ui.painter()
+                    .circle_stroke(center, radius, Stroke::new(radius * 0.05, color));
+                let knob = touch
+                    .map(|touch| to_screen(shape.center + touch.deflection() * shape.radius))
+                    .unwrap_or(center);
+                ui.painter().circle_filled(
+                    knob,
+                    radius * 0.4,
+                    if touch.is_some() {
+                        pressed_color
+                    } else {
+                        color
+                    },
+                );
+            } else {
+                ui.painter().circle_filled(
+                    center,
+                    radius,
+                    if touch.is_some() {
+                        pressed_color
+                    } else {
+                        color
+                    },
+                );
+            }
+            ui.painter().text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                Self::label(shape.control),
+                FontId::proportional(radius * 0.6),
+                Color32::from_black_alpha(alpha),
+            );
+        }
+    }
+
+    #[instrument(level = "trace", skip_all)]
+    pub fn render(&mut self, controls: &TouchControls, config: &ConfigTouchControls) {
+        let canvas_width = self.canvas_handle.canvas_width();
+        let canvas_height = self.canvas_handle.canvas_height();
+        let pixels_per_point = self.canvas_handle.pixels_per_point();
+        let (screen_rect, full_output, zoom_level) = self.ui.render(
+            canvas_width,
+            canvas_height,
+            pixels_per_point,
+            |ui, _, _| Self::render_controls(ui, controls, config),
+            &mut UiRenderPipe::new(self.time.now(), &mut ()),
+            Default::default(),
+            false,
+        );
+        render_ui(
+            &mut self.ui,
+            full_output,
+            &screen_rect,
+            zoom_level,
+            &self.backend_handle,
+            &self.texture_handle,
+            &self.stream_handle,
+            false,
+        );
+    }
+}
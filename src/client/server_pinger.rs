@@ -0,0 +1,103 @@
+use std::{borrow::Cow, collections::HashMap, net::SocketAddr, time::Duration};
+
+use base::hash::Hash;
+use base_io::{io::Io, runtime::IoRuntimeTask};
+use game_base::server_browser::ServerBrowserData;
+use legacy_proxy::ping::legacy_server_ping;
+use network::network::{
+    quinnminimal::ping_server,
+    types::{NetworkClientCertCheckMode, NetworkClientCertMode, NetworkClientInitOptions},
+    utils::create_certifified_keys,
+};
+
+/// How many servers are probed at the same time.
+const MAX_CONCURRENT_PROBES: usize = 12;
+/// How long a probe result is used before the server is probed again.
+const PROBE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Measures the ping to all servers of the server browser
+/// in the background.
+///
+/// Servers are probed with a QUIC handshake, legacy servers
+/// with the info request of their protocol.
+#[derive(Default)]
+pub struct ServerPinger {
+    /// When an address was probed the last time,
+    /// failed probes are not retried earlier either.
+    probed: HashMap<SocketAddr, Duration>,
+    probes: Vec<(SocketAddr, IoRuntimeTask<Duration>)>,
+}
+
+impl ServerPinger {
+    fn probe(io: &Io, addr: SocketAddr, legacy: bool, cert_hash: Hash) -> IoRuntimeTask<Duration> {
+        io.rt.spawn(async move {
+            if legacy {
+                legacy_server_ping(addr, PROBE_TIMEOUT).await
+            } else {
+                // a throw away certificate, the ping should not
+                // identify the account
+                let (cert, private_key) = create_certifified_keys();
+                ping_server(
+                    addr,
+                    &NetworkClientInitOptions::new(
+                        NetworkClientCertCheckMode::CheckByPubKeyHash {
+                            hash: Cow::Owned(cert_hash),
+                        },
+                        NetworkClientCertMode::FromCertAndPrivateKey { cert, private_key },
+                    )
+                    .with_timeout(PROBE_TIMEOUT),
+                )
+                .await
+            }
+        })
+    }
+
+    /// Collects finished probes and starts new ones
+    /// for servers that were not probed recently.
+    pub fn update(&mut self, io: &Io, browser_data: &ServerBrowserData, cur_time: Duration) {
+        let (finished, probes): (Vec<_>, Vec<_>) = std::mem::take(&mut self.probes)
+            .into_iter()
+            .partition(|(_, task)| task.is_finished());
+        self.probes = probes;
+        for (addr, task) in finished {
+            match task.get() {
+                Ok(ping) => browser_data.set_ping(addr, ping),
+                Err(err) => log::debug!("ping to {addr} failed: {err}"),
+            }
+        }
+
+        if self.probes.len() >= MAX_CONCURRENT_PROBES {
+            return;
+        }
+
+        let list = browser_data.list();
+        for server in list.servers.iter() {
+            if self.probes.len() >= MAX_CONCURRENT_PROBES {
+                break;
+            }
+            // all addresses belong to the same server,
+            // probing one of them is enough
+            let Some(addr) = server.addresses.first().copied() else {
+                continue;
+            };
+            if self
+                .probed
+                .get(&addr)
+                .is_some_and(|probe_time| cur_time.saturating_sub(*probe_time) < PROBE_INTERVAL)
+            {
+                continue;
+            }
+            self.probed.insert(addr, cur_time);
+            self.probes.push((
+                addr,
+                Self::probe(
+                    io,
+                    addr,
+                    server.legacy_server,
+                    server.info.cert_sha256_fingerprint,
+                ),
+            ));
+        }
+    }
+}
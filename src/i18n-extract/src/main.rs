@@ -0,0 +1,96 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use i18n::{catalog::Catalog, extract::extract_message_ids, translations::FALLBACK_CATALOG};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// directories that are searched for rust sources that use `tr`/`tr_args`
+    #[arg(default_values_t = ["game".to_string(), "lib".to_string(), "src".to_string()])]
+    dirs: Vec<String>,
+    /// a catalog (e.g. `data/i18n/de.ftl`) that is checked for untranslated messages
+    #[arg(short, long)]
+    catalog: Option<String>,
+}
+
+fn collect_ids(path: &Path, ids: &mut BTreeSet<String>) -> anyhow::Result<()> {
+    if path.is_dir() {
+        for entry in std::fs::read_dir(path)? {
+            let path = entry?.path();
+            // skip build artifacts
+            if path.file_name().is_some_and(|name| name == "target") {
+                continue;
+            }
+            collect_ids(&path, ids)?;
+        }
+    } else if path.extension().is_some_and(|ext| ext == "rs") {
+        ids.extend(extract_message_ids(&std::fs::read_to_string(path)?));
+    }
+    Ok(())
+}
+
+fn load_catalog(name: &str, src: &str) -> Catalog {
+    let (catalog, errs) = Catalog::parse(src);
+    for err in errs {
+        log::warn!("{name}: {err}");
+    }
+    catalog
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if std::env::var("RUST_LOG").is_err() {
+        unsafe { std::env::set_var("RUST_LOG", "info") };
+    }
+    env_logger::init();
+
+    let mut ids = BTreeSet::new();
+    for dir in &args.dirs {
+        let dir: PathBuf = dir.into();
+        if let Err(err) = collect_ids(&dir, &mut ids) {
+            log::error!("failed to read sources in {dir:?}: {err}");
+            std::process::exit(2);
+        }
+    }
+    log::info!("found {} message ids", ids.len());
+
+    let mut has_missing = false;
+    let english = load_catalog("en.ftl", FALLBACK_CATALOG);
+    for id in ids.iter().filter(|id| !english.messages.contains_key(*id)) {
+        println!("missing in en.ftl: {id}");
+        has_missing = true;
+    }
+
+    if let Some(path) = &args.catalog {
+        let src = match std::fs::read_to_string(path) {
+            Ok(src) => src,
+            Err(err) => {
+                log::error!("failed to read {path}: {err}");
+                std::process::exit(2);
+            }
+        };
+        let catalog = load_catalog(path, &src);
+        for id in ids.iter().filter(|id| !catalog.messages.contains_key(*id)) {
+            println!("untranslated in {path}: {id}");
+            has_missing = true;
+        }
+        let mut unused: Vec<_> = catalog
+            .messages
+            .keys()
+            .filter(|id| !ids.contains(*id))
+            .collect();
+        unused.sort();
+        for id in unused {
+            println!("unused in {path}: {id}");
+        }
+    }
+
+    if has_missing {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+use anyhow::anyhow;
+use base::hash::generate_hash_for;
+use base_fs::filesys::FileSystem;
+use base_io::io::IoFileSys;
+use clap::Parser;
+use editor::{
+    fs::{read_file_editor, write_file_editor},
+    tools::tile_layer::{
+        auto_mapper::{
+            TileLayerAutoMapperEditorRule, TileLayerAutoMapperRuleType, TileLayerAutoMapperWasm,
+        },
+        legacy_rules::{LegacyRule, LegacyRulesLoading},
+    },
+};
+use editor_auto_mapper_wasm::manager::AutoMapperWasmManager;
+use map::{
+    file::MapFileReader,
+    map::{Map, groups::layers::design::MapLayer},
+};
+
+/// A design tile layer in the form `bg:<group>:<layer>` or `fg:<group>:<layer>`.
+#[derive(Debug, Clone, Copy)]
+struct LayerSelector {
+    is_background: bool,
+    group_index: usize,
+    layer_index: usize,
+}
+
+impl FromStr for LayerSelector {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':');
+        let (Some(ty), Some(group_index), Some(layer_index), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            anyhow::bail!("expected <bg|fg>:<group>:<layer>, found {s}");
+        };
+        let is_background = match ty {
+            "bg" => true,
+            "fg" => false,
+            _ => anyhow::bail!("layer type must be bg or fg, found {ty}"),
+        };
+        Ok(Self {
+            is_background,
+            group_index: group_index.parse()?,
+            layer_index: layer_index.parse()?,
+        })
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// the .twmap.tar map
+    map: String,
+    /// the rule file (.editorrulejson, .rules or .wasm)
+    rule: String,
+    /// the name of the configuration inside a .rules file
+    #[arg(short, long)]
+    config: Option<String>,
+    /// the seed the rule is run with, same as in the editor
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+    /// design tile layers the rule is run on, e.g. `fg:0:1`
+    #[arg(short, long)]
+    layer: Vec<LayerSelector>,
+    /// run the rule on all design tile layers that use this image array
+    #[arg(short, long)]
+    image: Option<String>,
+    /// run the rule on the physics layer with this index instead,
+    /// the selected design layers are used as design input (if any)
+    #[arg(short, long)]
+    physics: Option<usize>,
+    /// where the map is written to, defaults to overwriting the input map
+    #[arg(short, long)]
+    output: Option<String>,
+}
+
+fn load_rule(
+    io: &IoFileSys,
+    path: PathBuf,
+    config: Option<String>,
+) -> anyhow::Result<TileLayerAutoMapperRuleType> {
+    let fs = io.fs.clone();
+    io.rt
+        .spawn(async move {
+            let file = read_file_editor(&fs, &path)
+                .await
+                .map_err(|err| anyhow!("loading rule file {} failed: {err}", path.display()))?;
+            match path.extension().and_then(|e| e.to_str()) {
+                Some("editorrulejson") => Ok(TileLayerAutoMapperRuleType::EditorRule(
+                    serde_json::from_slice::<TileLayerAutoMapperEditorRule>(&file)?,
+                )),
+                Some("wasm") => {
+                    let compiled_wasm = AutoMapperWasmManager::load_module(&fs, file.clone())
+                        .await
+                        .map_err(|err| anyhow!("compiling the wasm module failed: {err}"))?;
+                    let hash = generate_hash_for(&file);
+                    Ok(TileLayerAutoMapperRuleType::Wasm(Box::new(
+                        TileLayerAutoMapperWasm::new(compiled_wasm, file, hash)?,
+                    )))
+                }
+                Some("rules") => {
+                    let mut rules = LegacyRulesLoading::new(&file)?;
+                    let config = match config {
+                        Some(name) => rules.configs.remove(&name).ok_or_else(|| {
+                            anyhow!("configuration {name} was not found in the rules file")
+                        })?,
+                        None if rules.configs.len() == 1 => {
+                            rules.configs.into_values().next().unwrap()
+                        }
+                        None => {
+                            let mut names: Vec<_> = rules.configs.keys().cloned().collect();
+                            names.sort();
+                            anyhow::bail!(
+                                "the rules file contains multiple configurations, \
+                                select one with --config: {}",
+                                names.join(", ")
+                            );
+                        }
+                    };
+                    Ok(TileLayerAutoMapperRuleType::LegacyRules {
+                        rule: LegacyRule { config },
+                        loading_data: Arc::new(rules.file),
+                    })
+                }
+                _ => Err(anyhow!(
+                    "unknown rule type, expected .editorrulejson, .rules or .wasm"
+                )),
+            }
+        })
+        .get()
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if std::env::var("RUST_LOG").is_err() {
+        unsafe { std::env::set_var("RUST_LOG", "info") };
+    }
+    env_logger::init();
+
+    let io = IoFileSys::new(|rt| {
+        Arc::new(
+            FileSystem::new(rt, "org", "", "DDNet-Rs-Alpha", "DDNet-Accounts")
+                .expect("map-auto-mapper needs the data directory."),
+        )
+    });
+
+    let thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(
+                std::thread::available_parallelism()
+                    .unwrap_or(NonZeroUsize::new(2).unwrap())
+                    .get(),
+            )
+            .build()
+            .unwrap(),
+    );
+
+    let fs = io.fs.clone();
+    let tp = thread_pool.clone();
+    let map_path = args.map.clone();
+    let mut map = io
+        .rt
+        .spawn(async move {
+            let file = read_file_editor(&fs, Path::new(&map_path))
+                .await
+                .map_err(|err| anyhow!("loading map file {map_path} failed: {err}"))?;
+            Map::read(&MapFileReader::new(file)?, &tp)
+                .map_err(|err| anyhow!("loading map {map_path} from file failed: {err}"))
+        })
+        .get()
+        .unwrap_or_else(|err| {
+            log::error!("{err}");
+            std::process::exit(2);
+        });
+
+    let mut rule =
+        load_rule(&io, args.rule.clone().into(), args.config.clone()).unwrap_or_else(|err| {
+            log::error!("{err}");
+            std::process::exit(2);
+        });
+
+    let mut layers = args.layer.clone();
+    if let Some(image) = &args.image {
+        let image_index = map
+            .resources
+            .image_arrays
+            .iter()
+            .position(|res| res.name.as_str() == image.as_str())
+            .unwrap_or_else(|| {
+                log::error!("the map has no image array named {image}");
+                std::process::exit(2);
+            });
+        for (is_background, groups) in [
+            (true, &map.groups.background),
+            (false, &map.groups.foreground),
+        ] {
+            for (group_index, group) in groups.iter().enumerate() {
+                for (layer_index, layer) in group.layers.iter().enumerate() {
+                    if let MapLayer::Tile(layer) = layer
+                        && layer.attr.image_array == Some(image_index)
+                    {
+                        layers.push(LayerSelector {
+                            is_background,
+                            group_index,
+                            layer_index,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    let mut failed = false;
+    match args.physics {
+        Some(physics_layer_index) => {
+            // the design layers are only inputs, which not every rule needs
+            let design_layers: Vec<_> = if layers.is_empty() {
+                vec![None]
+            } else {
+                layers
+                    .iter()
+                    .map(|sel| Some((sel.is_background, sel.group_index, sel.layer_index)))
+                    .collect()
+            };
+            for design_layer in design_layers {
+                match rule.run_on_map_physics_layer(
+                    &mut map,
+                    args.seed,
+                    design_layer,
+                    physics_layer_index,
+                ) {
+                    Ok(()) => log::info!("auto mapped physics layer {physics_layer_index}"),
+                    Err(err) => {
+                        log::error!(
+                            "auto mapping physics layer {physics_layer_index} failed: {err}"
+                        );
+                        failed = true;
+                    }
+                }
+            }
+        }
+        None => {
+            if layers.is_empty() {
+                log::error!("no layers selected, use --layer or --image");
+                std::process::exit(2);
+            }
+            for sel in layers {
+                let ty = if sel.is_background { "bg" } else { "fg" };
+                match rule.run_on_map_layer(
+                    &mut map,
+                    args.seed,
+                    sel.is_background,
+                    sel.group_index,
+                    sel.layer_index,
+                ) {
+                    Ok(()) => log::info!(
+                        "auto mapped layer {ty}:{}:{}",
+                        sel.group_index,
+                        sel.layer_index
+                    ),
+                    Err(err) => {
+                        log::error!(
+                            "auto mapping layer {ty}:{}:{} failed: {err}",
+                            sel.group_index,
+                            sel.layer_index
+                        );
+                        failed = true;
+                    }
+                }
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+
+    let fs = io.fs.clone();
+    let tp = thread_pool.clone();
+    let output = args.output.unwrap_or(args.map);
+    let write = io
+        .rt
+        .spawn(async move { write_file_editor(&fs, Path::new(&output), map.write(&tp)?).await });
+    if let Err(err) = write.get() {
+        log::error!("writing the map failed: {err}");
+        std::process::exit(2);
+    }
+}
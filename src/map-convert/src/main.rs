@@ -9,7 +9,14 @@ use base::{benchmark::Benchmark, hash::fmt_hash};
 use base_fs::filesys::FileSystem;
 use base_io::io::IoFileSys;
 use clap::Parser;
-use map::{file::MapFileReader, map::Map, utils::file_ext_or_twmap_tar};
+use map::{
+    file::MapFileReader,
+    map::{
+        Map, PngValidatorOptions,
+        resources::{MapResourceMetaData, MapResourceRef},
+    },
+    utils::file_ext_or_twmap_tar,
+};
 use map_convert_lib::{legacy_to_new::legacy_to_new, new_to_legacy::new_to_legacy};
 
 #[derive(Parser, Debug)]
@@ -25,6 +32,57 @@ struct Args {
     /// export as json (only works for .twmap.tar maps)
     #[arg(short, long, default_value_t = false)]
     json: bool,
+    /// resource directory (containing `images/` & `sounds/`) for json maps.
+    /// When exporting to json, the resources of the map are read from here
+    /// & written to `<output>/map/resources`, which can be passed back here
+    /// to convert the json map to a map file again
+    #[arg(short, long, default_value = "map/resources")]
+    resources: String,
+}
+
+/// The paths of all resource files used by the map relative to the
+/// resource directory, with their meta data & whether they are high quality.
+fn resource_files(map: &Map) -> Vec<(String, MapResourceMetaData, bool)> {
+    let resource_paths = |dir: &str, res: &MapResourceRef| {
+        [
+            Some((&res.meta, false)),
+            res.hq_meta.as_ref().map(|m| (m, true)),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|(meta, is_hq)| {
+            (
+                format!(
+                    "{dir}/{}_{}.{}",
+                    res.name.as_str(),
+                    fmt_hash(&meta.blake3_hash),
+                    meta.ty.as_str()
+                ),
+                meta.clone(),
+                is_hq,
+            )
+        })
+        .collect::<Vec<_>>()
+    };
+    map.resources
+        .images
+        .iter()
+        .chain(map.resources.image_arrays.iter())
+        .flat_map(|res| resource_paths("images", res))
+        .chain(
+            map.resources
+                .sounds
+                .iter()
+                .flat_map(|res| resource_paths("sounds", res)),
+        )
+        .collect()
+}
+
+/// The name of the map without its file extensions,
+/// e.g. `x` for `x.map`, `x.twmap.tar` and `x.twmap.json`.
+fn map_name(path: &Path) -> String {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    stem.strip_suffix(".twmap").unwrap_or(&stem).to_string()
 }
 
 fn main() {
@@ -70,10 +128,7 @@ fn main() {
             // write map
             let map_path = PathBuf::from(&output_dir).join("map/maps/");
             fs.create_dir(&map_path).await?;
-            let map_path = map_path.join(format!(
-                "{}.twmap.tar",
-                file_path.file_stem().unwrap().to_str().unwrap(),
-            ));
+            let map_path = map_path.join(format!("{}.twmap.tar", map_name(&file_path)));
             fs.write_file(&map_path, file).await?;
 
             log::info!("map file written to {output_dir}/map/maps/");
@@ -131,15 +186,13 @@ fn main() {
             let map = map.get().unwrap();
             let fs = io.fs.clone();
             let output_dir = args.output.clone();
+            let resources_dir = PathBuf::from(&args.resources);
             io.rt.spawn(async move {
                 fs.create_dir(output_dir.as_ref()).await?;
                 // write map
                 let map_path = PathBuf::from(&output_dir).join("json/");
                 fs.create_dir(&map_path).await?;
-                let map_path = map_path.join(format!(
-                    "{}.json",
-                    file_path.file_stem().unwrap().to_str().unwrap(),
-                ));
+                let map_path = map_path.join(format!("{}.twmap.json", map_name(&file_path)));
                 fs.write_file(&map_path, map.as_json().as_bytes().to_vec())
                     .await?;
 
@@ -148,6 +201,21 @@ fn main() {
                     map_path.to_string_lossy()
                 );
 
+                // write resources, so the json map can be converted back
+                for (path, _, _) in resource_files(&map) {
+                    let file = fs
+                        .read_file(&resources_dir.join(&path))
+                        .await
+                        .map_err(|err| anyhow!("loading resource {path} failed: {err}"))?;
+                    let res_path = PathBuf::from(&output_dir).join("map/resources/").join(path);
+                    if let Some(dir) = res_path.parent() {
+                        fs.create_dir(dir).await?;
+                    }
+                    fs.write_file(&res_path, file).await?;
+                }
+
+                log::info!("map resources written to {output_dir}/map/resources");
+
                 Ok(())
             })
         } else {
@@ -159,10 +227,7 @@ fn main() {
                 // write map
                 let map_path = PathBuf::from(&output_dir).join("legacy/maps/");
                 fs.create_dir(&map_path).await?;
-                let map_path = map_path.join(format!(
-                    "{}.map",
-                    file_path.file_stem().unwrap().to_str().unwrap(),
-                ));
+                let map_path = map_path.join(format!("{}.map", map_name(&file_path)));
                 fs.write_file(&map_path, output.map).await?;
 
                 log::info!(
@@ -173,10 +238,98 @@ fn main() {
                 Ok(())
             })
         }
+    }
+    // json to new
+    else if file_path.extension().is_some_and(|e| e == "json") {
+        let fs = io.fs.clone();
+        let tp = thread_pool.clone();
+        let output_dir = args.output.clone();
+        let resources_dir = PathBuf::from(&args.resources);
+        io.rt.spawn(async move {
+            let json = fs
+                .read_file(&file_path)
+                .await
+                .map_err(|err| anyhow!("loading json map file failed: {err}"))?;
+            let map = Map::from_json(&json)
+                .map_err(|err| anyhow!("loading map from json failed: {err}"))?;
+
+            // load & validate all resources
+            let mut files = Vec::new();
+            for (path, meta, is_hq) in resource_files(&map) {
+                let file = fs
+                    .read_file(&resources_dir.join(&path))
+                    .await
+                    .map_err(|err| anyhow!("loading resource {path} failed: {err}"))?;
+                Map::validate_resource_file(
+                    &meta,
+                    &file,
+                    if is_hq {
+                        PngValidatorOptions {
+                            max_width: 4096.try_into().unwrap(),
+                            max_height: 4096.try_into().unwrap(),
+                            ..Default::default()
+                        }
+                    } else {
+                        Default::default()
+                    },
+                )
+                .map_err(|err| anyhow!("resource {path} is invalid: {err}"))?;
+                files.push((path, file));
+            }
+
+            let file = map.write(&tp)?;
+
+            // make sure the written map is valid & contains the same map
+            let reader = MapFileReader::new(file.clone())?;
+            Map::validate_downloaded_map_file(&reader, Default::default())?;
+            let written_map = Map::read(&reader, &tp)?;
+            anyhow::ensure!(
+                written_map.as_json() == map.as_json(),
+                "the written map differs from the json map."
+            );
+
+            fs.create_dir(output_dir.as_ref()).await?;
+            // write map
+            let map_path = PathBuf::from(&output_dir).join("map/maps/");
+            fs.create_dir(&map_path).await?;
+            let map_path = map_path.join(format!("{}.twmap.tar", map_name(&file_path)));
+            fs.write_file(&map_path, file).await?;
+
+            log::info!("map file written to {}", map_path.to_string_lossy());
+
+            // write resources
+            for (path, file) in files {
+                let res_path = PathBuf::from(&output_dir).join("map/resources/").join(path);
+                if let Some(dir) = res_path.parent() {
+                    fs.create_dir(dir).await?;
+                }
+                fs.write_file(&res_path, file).await?;
+            }
+
+            log::info!("map resources written to {output_dir}/map/resources");
+
+            Ok(())
+        })
     } else {
-        panic!("Given file was neither a legacy map nor new map.");
+        panic!("Given file was neither a legacy map, new map nor json map.");
     };
     if let Err(err) = task.get() {
         log::error!("{err}");
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::map_name;
+
+    #[test]
+    fn map_names() {
+        assert_eq!(map_name(Path::new("maps/x.map")), "x");
+        assert_eq!(map_name(Path::new("maps/x.twmap.tar")), "x");
+        assert_eq!(map_name(Path::new("json/x.twmap.json")), "x");
+        assert_eq!(map_name(Path::new("x.json")), "x");
+        assert_eq!(map_name(Path::new("my.map.name.map")), "my.map.name");
+    }
+}
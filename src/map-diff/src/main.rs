@@ -0,0 +1,193 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::anyhow;
+use base::hash::{Hash, fmt_hash};
+use base_fs::filesys::FileSystem;
+use base_io::io::IoFileSys;
+use clap::{Parser, Subcommand};
+use editor::map_diff::{MapDiff, MapMerge};
+use map::{file::MapFileReader, map::Map};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    cmd: Cmd,
+    /// resource directory (containing `images/` & `sounds/`)
+    #[arg(short, long, default_value = "map/resources")]
+    resources: String,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cmd {
+    /// Print the editor actions that turn the old map into the new map
+    Diff {
+        /// the old .twmap.tar map
+        old: String,
+        /// the new .twmap.tar map
+        new: String,
+        /// print the actions as json action group
+        #[arg(short, long, default_value_t = false)]
+        json: bool,
+    },
+    /// Merge the changes of two maps that have a common ancestor
+    Merge {
+        /// the common ancestor of both maps
+        base: String,
+        /// our version of the map, wins on conflicts
+        ours: String,
+        /// their version of the map
+        theirs: String,
+        /// where the merged .twmap.tar is written to
+        output: String,
+    },
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if std::env::var("RUST_LOG").is_err() {
+        unsafe { std::env::set_var("RUST_LOG", "info") };
+    }
+    env_logger::init();
+
+    let io = IoFileSys::new(|rt| {
+        Arc::new(
+            FileSystem::new(rt, "org", "", "DDNet-Rs-Alpha", "DDNet-Accounts")
+                .expect("map-diff needs the data directory."),
+        )
+    });
+
+    let thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(
+                std::thread::available_parallelism()
+                    .unwrap_or(NonZeroUsize::new(2).unwrap())
+                    .get(),
+            )
+            .build()
+            .unwrap(),
+    );
+
+    let load = |paths: Vec<String>, with_resources: bool| {
+        let fs = io.fs.clone();
+        let tp = thread_pool.clone();
+        let resources_dir = PathBuf::from(&args.resources);
+        io.rt
+            .spawn(async move {
+                let mut maps = Vec::new();
+                let mut files: HashMap<Hash, Vec<u8>> = Default::default();
+                for path in paths {
+                    let file = fs
+                        .read_file(Path::new(&path))
+                        .await
+                        .map_err(|err| anyhow!("loading map file {path} failed: {err}"))?;
+                    let map = Map::read(&MapFileReader::new(file)?, &tp)
+                        .map_err(|err| anyhow!("loading map {path} from file failed: {err}"))?;
+
+                    if with_resources {
+                        let resources = map
+                            .resources
+                            .images
+                            .iter()
+                            .chain(map.resources.image_arrays.iter())
+                            .map(|res| ("images", res))
+                            .chain(map.resources.sounds.iter().map(|res| ("sounds", res)));
+                        for (dir, res) in resources {
+                            if files.contains_key(&res.meta.blake3_hash) {
+                                continue;
+                            }
+                            let path = resources_dir.join(format!(
+                                "{dir}/{}_{}.{}",
+                                res.name.as_str(),
+                                fmt_hash(&res.meta.blake3_hash),
+                                res.meta.ty.as_str()
+                            ));
+                            match fs.read_file(&path).await {
+                                Ok(file) => {
+                                    files.insert(res.meta.blake3_hash, file);
+                                }
+                                Err(err) => {
+                                    log::warn!(
+                                        "resource {} could not be loaded: {err}",
+                                        path.display()
+                                    );
+                                }
+                            }
+                        }
+                    }
+
+                    maps.push(map);
+                }
+                Ok((maps, files))
+            })
+            .get()
+    };
+
+    match args.cmd {
+        Cmd::Diff { old, new, json } => {
+            let (maps, files) = load(vec![old, new], true).unwrap_or_else(|err| {
+                log::error!("{err}");
+                std::process::exit(2);
+            });
+            let diff = MapDiff::new(&maps[0], &maps[1], &files).unwrap_or_else(|err| {
+                log::error!("creating the diff failed: {err}");
+                std::process::exit(2);
+            });
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&diff.into_action_group()).unwrap()
+                );
+            } else {
+                for action in diff.summary() {
+                    println!("{action}");
+                }
+            }
+        }
+        Cmd::Merge {
+            base,
+            ours,
+            theirs,
+            output,
+        } => {
+            let (maps, _) = load(vec![base, ours, theirs], false).unwrap_or_else(|err| {
+                log::error!("{err}");
+                std::process::exit(2);
+            });
+            let merge = MapMerge::new(&maps[0], &maps[1], &maps[2]).unwrap_or_else(|err| {
+                log::error!("merging the maps failed: {err}");
+                std::process::exit(2);
+            });
+
+            let fs = io.fs.clone();
+            let tp = thread_pool.clone();
+            let map = merge.map;
+            let write = io.rt.spawn(async move {
+                fs.write_file(Path::new(&output), map.write(&tp)?).await?;
+                Ok(())
+            });
+            if let Err(err) = write.get() {
+                log::error!("writing the merged map failed: {err}");
+                std::process::exit(2);
+            }
+
+            for conflict in &merge.conflicts {
+                println!("conflict: {conflict}");
+            }
+            if !merge.conflicts.is_empty() {
+                println!(
+                    "{} conflict(s), our version was kept for them",
+                    merge.conflicts.len()
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+}
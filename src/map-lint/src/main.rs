@@ -0,0 +1,158 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::anyhow;
+use base::hash::{Hash, fmt_hash};
+use base_fs::filesys::FileSystem;
+use base_io::io::IoFileSys;
+use clap::Parser;
+use map::{
+    file::MapFileReader,
+    lint::{MapLintReport, MapLintSeverity},
+    map::Map,
+    utils::file_ext_or_twmap_tar,
+};
+use map_convert_lib::legacy_to_new::legacy_to_new;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// file name of the map to lint. (Legacy maps expect mapres to be in the io path)
+    file: String,
+    /// resource directory (containing `images/`) for .twmap.tar maps
+    #[arg(short, long, default_value = "map/resources")]
+    resources: String,
+    /// print the report as json
+    #[arg(short, long, default_value_t = false)]
+    json: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if std::env::var("RUST_LOG").is_err() {
+        unsafe { std::env::set_var("RUST_LOG", "info") };
+    }
+    env_logger::init();
+
+    let io = IoFileSys::new(|rt| {
+        Arc::new(
+            FileSystem::new(rt, "org", "", "DDNet-Rs-Alpha", "DDNet-Accounts")
+                .expect("map-lint needs the data directory for the legacy map resources."),
+        )
+    });
+
+    let thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(
+                std::thread::available_parallelism()
+                    .unwrap_or(NonZeroUsize::new(2).unwrap())
+                    .get(),
+            )
+            .build()
+            .unwrap(),
+    );
+
+    let file_path: PathBuf = args.file.clone().into();
+    let (map, images) = if file_path.extension().is_some_and(|e| e == "map") {
+        match legacy_to_new(&file_path, &io, &thread_pool, false) {
+            Ok(output) => {
+                let images: HashMap<Hash, Vec<u8>> = output
+                    .resources
+                    .images
+                    .into_iter()
+                    .map(|(hash, image)| (hash, image.buf))
+                    .collect();
+                (output.map, images)
+            }
+            Err(err) => {
+                log::error!("loading legacy map failed: {err}");
+                std::process::exit(2);
+            }
+        }
+    } else if file_ext_or_twmap_tar(&file_path).is_some_and(|e| e == "twmap.tar") {
+        let fs = io.fs.clone();
+        let tp = thread_pool.clone();
+        let resources_dir = PathBuf::from(&args.resources);
+        let task = io.rt.spawn(async move {
+            let file = fs
+                .read_file(&file_path)
+                .await
+                .map_err(|err| anyhow!("loading map file failed: {err}"))?;
+            let map = Map::read(&MapFileReader::new(file)?, &tp)
+                .map_err(|err| anyhow!("loading map from file failed: {err}"))?;
+
+            // images of tile layers are linted, if they are available
+            let mut images: HashMap<Hash, Vec<u8>> = Default::default();
+            for image in &map.resources.image_arrays {
+                let path = resources_dir.join(format!(
+                    "images/{}_{}.{}",
+                    image.name.as_str(),
+                    fmt_hash(&image.meta.blake3_hash),
+                    image.meta.ty.as_str()
+                ));
+                match fs.read_file(&path).await {
+                    Ok(file) => {
+                        images.insert(image.meta.blake3_hash, file);
+                    }
+                    Err(err) => {
+                        log::warn!("image {} could not be loaded: {err}", path.display());
+                    }
+                }
+            }
+
+            Ok((map, images))
+        });
+        match task.get() {
+            Ok(res) => res,
+            Err(err) => {
+                log::error!("{err}");
+                std::process::exit(2);
+            }
+        }
+    } else {
+        panic!("Given file was neither a legacy map nor new map.");
+    };
+
+    let mut report = MapLintReport::new(&map);
+    for (index, image) in map.resources.image_arrays.iter().enumerate() {
+        if let Some(file) = images.get(&image.meta.blake3_hash) {
+            report.lint_image_array(index, image, file);
+        }
+    }
+
+    if args.json {
+        println!("{}", report.to_json());
+    } else {
+        print_report(Path::new(&args.file), &report);
+    }
+
+    if report.has_errors() {
+        std::process::exit(1);
+    }
+}
+
+fn print_report(path: &Path, report: &MapLintReport) {
+    for issue in &report.issues {
+        let severity = match issue.severity() {
+            MapLintSeverity::Warning => "warning",
+            MapLintSeverity::Error => "error",
+        };
+        println!("{}: {severity}: {issue}", path.display());
+    }
+    let errors = report
+        .issues
+        .iter()
+        .filter(|issue| issue.severity() == MapLintSeverity::Error)
+        .count();
+    println!(
+        "{}: {} error(s), {} warning(s)",
+        path.display(),
+        errors,
+        report.issues.len() - errors
+    );
+}
@@ -33,6 +33,7 @@ fn main() {
         shared_info,
         args,
         None,
+        None,
     )
     .unwrap();
     server_is_open.store(false, std::sync::atomic::Ordering::Relaxed);
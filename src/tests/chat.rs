@@ -38,6 +38,7 @@ pub fn test_chat(
                 skin_container: &mut containers.skin_container,
                 tee_render: render_tee,
                 mode: ChatMode::Global,
+                log_search: None,
                 character_infos: &Default::default(),
                 local_character_ids: &Default::default(),
             });
@@ -66,6 +67,7 @@ pub fn test_chat(
                 channel: NetChatMsgPlayerChannel::Global,
             }),
             add_time: Duration::MAX,
+            highlighted: false,
         },
         MsgInChat {
             msg: ServerMsg::Chat(ChatMsg {
@@ -83,6 +85,7 @@ pub fn test_chat(
                 channel: NetChatMsgPlayerChannel::Global,
             }),
             add_time: Duration::MAX,
+            highlighted: false,
         },
     ]
     .into();
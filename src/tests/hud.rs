@@ -66,6 +66,7 @@ pub fn test_hud(
                     time: PoolString::new_str_without_pool("22:14:14"),
                     date: PoolString::new_str_without_pool("Saturday, 27. September 2025"),
                 }),
+                ghost_split: &None,
             })
         };
         render_helper(